@@ -0,0 +1,208 @@
+//! Clip and animator storage.
+//! 片段和动画器存储。
+
+use std::collections::HashMap;
+
+use crate::core::error::{EngineError, Result};
+use super::animator::Animator;
+use super::clip::{AnimationClip, AnimationFrame};
+
+/// Event ID raised when a `Once` clip reaches its end.
+/// `Once`片段播放结束时触发的事件ID。
+pub const ANIMATION_EVENT_FINISHED: u32 = u32::MAX;
+
+/// A clip event raised by an animator.
+/// 由动画器触发的片段事件。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationEvent {
+    /// Animator that raised the event.
+    /// 触发事件的动画器。
+    pub animator_id: u32,
+    /// Event ID from the clip, or `ANIMATION_EVENT_FINISHED`.
+    /// 片段中的事件ID，或`ANIMATION_EVENT_FINISHED`。
+    pub event_id: u32,
+    /// Frame that was entered.
+    /// 进入的帧。
+    pub frame: u32,
+}
+
+/// Owns clips and animators and advances them each frame.
+/// 持有片段和动画器，并每帧推进。
+pub struct AnimationManager {
+    clips: HashMap<u32, AnimationClip>,
+    next_clip_id: u32,
+
+    animators: HashMap<u32, Animator>,
+    next_animator_id: u32,
+
+    /// Events raised since the last drain.
+    /// 自上次取出以来触发的事件。
+    events: Vec<AnimationEvent>,
+
+    /// Current frame of each animator in a sprite submission.
+    /// 精灵提交中每个动画器的当前帧。
+    frame_textures: Vec<u32>,
+    frame_uvs: Vec<f32>,
+}
+
+impl Default for AnimationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationManager {
+    /// Create an empty animation manager.
+    /// 创建空的动画管理器。
+    pub fn new() -> Self {
+        Self {
+            clips: HashMap::new(),
+            next_clip_id: 1,
+            animators: HashMap::new(),
+            next_animator_id: 1,
+            events: Vec::new(),
+            frame_textures: Vec::new(),
+            frame_uvs: Vec::new(),
+        }
+    }
+
+    /// Add a clip, returning its ID.
+    /// 添加片段，返回其ID。
+    pub fn add_clip(&mut self, clip: AnimationClip) -> u32 {
+        let id = self.next_clip_id;
+        self.next_clip_id += 1;
+        self.clips.insert(id, clip);
+        id
+    }
+
+    /// Get a clip.
+    /// 获取片段。
+    #[inline]
+    pub fn clip(&self, id: u32) -> Option<&AnimationClip> {
+        self.clips.get(&id)
+    }
+
+    /// Get a mutable clip.
+    /// 获取可变片段。
+    #[inline]
+    pub fn clip_mut(&mut self, id: u32) -> Option<&mut AnimationClip> {
+        self.clips.get_mut(&id)
+    }
+
+    /// Remove a clip. Animators playing it stop updating until given another clip.
+    /// 移除片段。播放该片段的动画器在获得其他片段前停止更新。
+    pub fn remove_clip(&mut self, id: u32) -> bool {
+        self.clips.remove(&id).is_some()
+    }
+
+    /// Create an animator playing a clip, returning its ID.
+    /// 创建播放片段的动画器，返回其ID。
+    pub fn create_animator(&mut self, clip_id: u32) -> Result<u32> {
+        self.require_clip(clip_id)?;
+        let id = self.next_animator_id;
+        self.next_animator_id += 1;
+        self.animators.insert(id, Animator::new(clip_id));
+        Ok(id)
+    }
+
+    /// Get an animator.
+    /// 获取动画器。
+    #[inline]
+    pub fn animator(&self, id: u32) -> Option<&Animator> {
+        self.animators.get(&id)
+    }
+
+    /// Get a mutable animator.
+    /// 获取可变动画器。
+    #[inline]
+    pub fn animator_mut(&mut self, id: u32) -> Option<&mut Animator> {
+        self.animators.get_mut(&id)
+    }
+
+    /// Remove an animator.
+    /// 移除动画器。
+    pub fn remove_animator(&mut self, id: u32) -> bool {
+        self.animators.remove(&id).is_some()
+    }
+
+    /// Restart an animator with a clip.
+    /// 使用片段重新启动动画器。
+    pub fn play(&mut self, animator_id: u32, clip_id: u32) -> Result<()> {
+        self.require_clip(clip_id)?;
+        let animator = self
+            .animators
+            .get_mut(&animator_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Animator not found: {}", animator_id)))?;
+        animator.play(clip_id);
+        Ok(())
+    }
+
+    fn require_clip(&self, clip_id: u32) -> Result<&AnimationClip> {
+        self.clips
+            .get(&clip_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Animation clip not found: {}", clip_id)))
+    }
+
+    /// Advance all animators and queue the events they raise.
+    /// 推进所有动画器，并将其触发的事件加入队列。
+    pub fn update(&mut self, delta_seconds: f32) {
+        for (&animator_id, animator) in &mut self.animators {
+            let Some(clip) = self.clips.get(&animator.clip_id()) else {
+                continue;
+            };
+            let was_finished = animator.is_finished();
+            let events = &mut self.events;
+            animator.advance(clip, delta_seconds, |frame| {
+                events.extend(clip.events_at(frame).map(|event_id| AnimationEvent {
+                    animator_id,
+                    event_id,
+                    frame: frame as u32,
+                }));
+            });
+            if animator.is_finished() && !was_finished {
+                self.events.push(AnimationEvent {
+                    animator_id,
+                    event_id: ANIMATION_EVENT_FINISHED,
+                    frame: animator.frame() as u32,
+                });
+            }
+        }
+    }
+
+    /// Take the events raised since the last call.
+    /// 取出自上次调用以来触发的事件。
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, AnimationEvent> {
+        self.events.drain(..)
+    }
+
+    /// Frame shown by an animator.
+    /// 动画器显示的帧。
+    pub fn current_frame(&self, animator_id: u32) -> Result<&AnimationFrame> {
+        let animator = self
+            .animators
+            .get(&animator_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Animator not found: {}", animator_id)))?;
+        let clip = self.require_clip(animator.clip_id())?;
+        Ok(&clip.frames()[animator.frame().min(clip.frames().len() - 1)])
+    }
+
+    /// Texture IDs and UVs of the frames shown by several animators, for a sprite batch.
+    /// 多个动画器当前帧的纹理ID和UV，用于精灵批处理。
+    pub fn sprite_frames(&mut self, animator_ids: &[u32]) -> Result<(&[u32], &[f32])> {
+        let mut textures = std::mem::take(&mut self.frame_textures);
+        let mut uvs = std::mem::take(&mut self.frame_uvs);
+        textures.clear();
+        uvs.clear();
+
+        let result = animator_ids.iter().try_for_each(|&id| {
+            let frame = self.current_frame(id)?;
+            textures.push(frame.texture_id);
+            uvs.extend_from_slice(&frame.uv);
+            Ok(())
+        });
+
+        self.frame_textures = textures;
+        self.frame_uvs = uvs;
+        result.map(|()| (self.frame_textures.as_slice(), self.frame_uvs.as_slice()))
+    }
+}
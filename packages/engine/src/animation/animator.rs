@@ -0,0 +1,181 @@
+//! Clip playback state.
+//! 片段播放状态。
+
+use super::clip::{AnimationClip, PlayMode};
+
+/// Plays one clip at a time.
+/// 每次播放一个片段。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::animation::{AnimationClip, Animator, PlayMode};
+/// let clip = AnimationClip::from_data(&[1, 2, 3], &[], &[0.1, 0.1, 0.1], PlayMode::PingPong).unwrap();
+/// let mut animator = Animator::new(1);
+/// let mut entered = Vec::new();
+/// animator.advance(&clip, 0.35, |frame| entered.push(frame));
+/// // 0 on start, then 1, 2, 1 | 开始时为0，然后1、2、1
+/// assert_eq!(entered, [0, 1, 2, 1]);
+/// assert_eq!(animator.frame(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animator {
+    clip_id: u32,
+    frame: usize,
+
+    /// Seconds spent on the current frame.
+    /// 当前帧已显示的秒数。
+    elapsed: f32,
+
+    /// Playback rate multiplier.
+    /// 播放速率倍数。
+    speed: f32,
+
+    paused: bool,
+
+    /// Ping-pong direction.
+    /// 往返方向。
+    forward: bool,
+
+    /// Whether the first frame has been reported.
+    /// 是否已报告第一帧。
+    started: bool,
+
+    finished: bool,
+}
+
+impl Animator {
+    /// Create an animator playing a clip from its first frame.
+    /// 创建从第一帧开始播放片段的动画器。
+    pub fn new(clip_id: u32) -> Self {
+        Self {
+            clip_id,
+            frame: 0,
+            elapsed: 0.0,
+            speed: 1.0,
+            paused: false,
+            forward: true,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Restart playback, optionally switching clips.
+    /// 重新开始播放，可同时切换片段。
+    pub fn play(&mut self, clip_id: u32) {
+        *self = Self { speed: self.speed, ..Self::new(clip_id) };
+    }
+
+    /// ID of the playing clip.
+    /// 正在播放的片段ID。
+    #[inline]
+    pub fn clip_id(&self) -> u32 {
+        self.clip_id
+    }
+
+    /// Index of the shown frame.
+    /// 当前显示帧的索引。
+    #[inline]
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Playback rate multiplier.
+    /// 播放速率倍数。
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set the playback rate multiplier (negative values are clamped to 0).
+    /// 设置播放速率倍数（负值被限制为0）。
+    #[inline]
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Whether playback is paused.
+    /// 播放是否已暂停。
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause or resume playback.
+    /// 暂停或恢复播放。
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Whether a `Once` clip has reached its end.
+    /// `Once`片段是否已播放结束。
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advance playback, calling `on_frame` for each frame entered.
+    /// 推进播放，每进入一帧调用一次`on_frame`。
+    ///
+    /// Whole loop cycles within one step are skipped, so a long step reports
+    /// each frame at most about once.
+    /// 单步内完整的循环周期会被跳过，因此较长的步长每帧最多报告约一次。
+    pub fn advance(&mut self, clip: &AnimationClip, delta_seconds: f32, mut on_frame: impl FnMut(usize)) {
+        let frames = clip.frames();
+        if self.frame >= frames.len() {
+            self.play(self.clip_id);
+        }
+        if !self.started {
+            self.started = true;
+            on_frame(self.frame);
+        }
+        if self.paused || self.finished {
+            return;
+        }
+
+        let mut step = delta_seconds.max(0.0) * self.speed;
+        let cycle = clip.cycle_duration();
+        if clip.mode() != PlayMode::Once && step > cycle {
+            step %= cycle;
+        }
+        self.elapsed += step;
+
+        loop {
+            let duration = frames[self.frame].duration;
+            if self.elapsed < duration {
+                break;
+            }
+            match self.next_frame(clip) {
+                Some(next) => {
+                    self.elapsed -= duration;
+                    self.frame = next;
+                    on_frame(next);
+                }
+                None => {
+                    self.elapsed = duration;
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Frame after the current one, or `None` at the end of a `Once` clip.
+    /// 当前帧的下一帧，`Once`片段结束时为`None`。
+    fn next_frame(&mut self, clip: &AnimationClip) -> Option<usize> {
+        let count = clip.frames().len();
+        match clip.mode() {
+            PlayMode::Loop => Some((self.frame + 1) % count),
+            PlayMode::Once => (self.frame + 1 < count).then_some(self.frame + 1),
+            PlayMode::PingPong if count == 1 => Some(0),
+            PlayMode::PingPong => {
+                if self.forward && self.frame + 1 >= count {
+                    self.forward = false;
+                } else if !self.forward && self.frame == 0 {
+                    self.forward = true;
+                }
+                Some(if self.forward { self.frame + 1 } else { self.frame - 1 })
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Animation clips.
+//! 动画片段。
+
+use crate::core::error::{EngineError, Result};
+
+/// How a clip continues after its last frame.
+/// 片段在最后一帧之后如何继续。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Restart from the first frame.
+    /// 从第一帧重新开始。
+    #[default]
+    Loop,
+    /// Play backwards to the first frame, then forwards again.
+    /// 倒放回第一帧，然后再正放。
+    PingPong,
+    /// Stop on the last frame.
+    /// 停在最后一帧。
+    Once,
+}
+
+impl PlayMode {
+    /// Mode from its code (0 loop, 1 ping-pong, 2 once).
+    /// 从代码获取模式（0 循环，1 往返，2 单次）。
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Loop),
+            1 => Some(Self::PingPong),
+            2 => Some(Self::Once),
+            _ => None,
+        }
+    }
+}
+
+/// One frame of a clip.
+/// 片段的一帧。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationFrame {
+    /// Texture or atlas region ID.
+    /// 纹理或图集区域ID。
+    pub texture_id: u32,
+    /// [u0, v0, u1, v1] within the texture region.
+    /// 纹理区域内的UV坐标。
+    pub uv: [f32; 4],
+    /// Display time in seconds.
+    /// 显示时间（秒）。
+    pub duration: f32,
+}
+
+/// A sequence of frames with a play mode and frame events.
+/// 带播放模式和帧事件的帧序列。
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationClip {
+    frames: Vec<AnimationFrame>,
+    mode: PlayMode,
+
+    /// (frame, event ID) pairs sorted by frame.
+    /// 按帧排序的（帧，事件ID）对。
+    events: Vec<(usize, u32)>,
+}
+
+impl AnimationClip {
+    /// Create a clip.
+    /// 创建片段。
+    ///
+    /// Fails if there are no frames or a duration is not positive.
+    /// 没有帧或持续时间不为正时失败。
+    pub fn new(frames: Vec<AnimationFrame>, mode: PlayMode) -> Result<Self> {
+        if frames.is_empty() {
+            return Err(EngineError::InvalidBatchData("Animation clip has no frames".into()));
+        }
+        if let Some(frame) = frames.iter().find(|f| !(f.duration > 0.0 && f.duration.is_finite())) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Animation frame duration must be positive, got {}",
+                frame.duration
+            )));
+        }
+        Ok(Self { frames, mode, events: Vec::new() })
+    }
+
+    /// Create a clip from packed data.
+    /// 从打包数据创建片段。
+    ///
+    /// # Arguments | 参数
+    /// * `texture_ids` - Texture or atlas region per frame | 每帧的纹理或图集区域
+    /// * `uvs` - [u0, v0, u1, v1] per frame, or empty for whole regions | 每帧的UV，为空表示整个区域
+    /// * `durations` - Seconds per frame | 每帧秒数
+    /// * `mode` - Play mode | 播放模式
+    pub fn from_data(texture_ids: &[u32], uvs: &[f32], durations: &[f32], mode: PlayMode) -> Result<Self> {
+        let count = texture_ids.len();
+        if durations.len() != count || (!uvs.is_empty() && uvs.len() != count * 4) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Animation clip data mismatch: {} frames, {} UV floats, {} durations",
+                count,
+                uvs.len(),
+                durations.len()
+            )));
+        }
+
+        let frames = (0..count)
+            .map(|i| AnimationFrame {
+                texture_id: texture_ids[i],
+                uv: uvs.get(i * 4..i * 4 + 4).map_or([0.0, 0.0, 1.0, 1.0], |uv| [uv[0], uv[1], uv[2], uv[3]]),
+                duration: durations[i],
+            })
+            .collect();
+        Self::new(frames, mode)
+    }
+
+    /// Frames in order.
+    /// 按顺序排列的帧。
+    #[inline]
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// Play mode.
+    /// 播放模式。
+    #[inline]
+    pub fn mode(&self) -> PlayMode {
+        self.mode
+    }
+
+    /// Set the play mode.
+    /// 设置播放模式。
+    #[inline]
+    pub fn set_mode(&mut self, mode: PlayMode) {
+        self.mode = mode;
+    }
+
+    /// Raise an event whenever a frame is entered.
+    /// 每次进入某帧时触发事件。
+    pub fn add_event(&mut self, frame: usize, event_id: u32) -> Result<()> {
+        if frame >= self.frames.len() {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Animation frame {} out of range for {} frames",
+                frame,
+                self.frames.len()
+            )));
+        }
+        let at = self.events.partition_point(|&(f, _)| f <= frame);
+        self.events.insert(at, (frame, event_id));
+        Ok(())
+    }
+
+    /// Remove all events.
+    /// 移除所有事件。
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    /// Event IDs raised when entering a frame.
+    /// 进入某帧时触发的事件ID。
+    pub fn events_at(&self, frame: usize) -> impl Iterator<Item = u32> + '_ {
+        let start = self.events.partition_point(|&(f, _)| f < frame);
+        self.events[start..].iter().take_while(move |&&(f, _)| f == frame).map(|&(_, id)| id)
+    }
+
+    /// Time after which playback repeats, in seconds.
+    /// 播放重复的时间间隔（秒）。
+    ///
+    /// For ping-pong clips the first and last frames are shown once per cycle.
+    /// 往返片段中，首帧和末帧每个周期只显示一次。
+    pub fn cycle_duration(&self) -> f32 {
+        let total: f32 = self.frames.iter().map(|f| f.duration).sum();
+        match (self.mode, self.frames.as_slice()) {
+            (PlayMode::PingPong, [first, .., last]) => total * 2.0 - first.duration - last.duration,
+            _ => total,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Sprite frame animation.
+//! 精灵帧动画。
+//!
+//! Clips hold texture regions with per-frame durations; animators play clips
+//! and are advanced by the engine, so sprites can be submitted by animator ID.
+//! 动画片段保存带每帧持续时间的纹理区域；动画器播放片段并由引擎推进，因此精灵可按动画器ID提交。
+
+mod clip;
+mod animator;
+mod animation_manager;
+
+pub use clip::{AnimationClip, AnimationFrame, PlayMode};
+pub use animator::Animator;
+pub use animation_manager::{AnimationEvent, AnimationManager, ANIMATION_EVENT_FINISHED};
@@ -6,6 +6,7 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 
 use super::error::{EngineError, Result};
+use crate::input::{DomInputListeners, InputQueue};
 
 /// WebGL2 rendering context wrapper.
 /// WebGL2渲染上下文包装器。
@@ -20,6 +21,17 @@ pub struct WebGLContext {
     /// The canvas element.
     /// Canvas元素。
     canvas: HtmlCanvasElement,
+
+    /// Input events from canvas listeners or injected by the host.
+    /// 来自canvas监听器或由宿主注入的输入事件。
+    input_queue: InputQueue,
+
+    /// Canvas input listeners, removed when the context is dropped.
+    /// canvas输入监听器，在上下文销毁时移除。
+    ///
+    /// `None` for external contexts, whose host injects events instead.
+    /// 外部上下文为`None`，由其宿主注入事件。
+    input_listeners: Option<DomInputListeners>,
 }
 
 impl WebGLContext {
@@ -50,13 +62,17 @@ impl WebGLContext {
             .dyn_into::<WebGl2RenderingContext>()
             .map_err(|_| EngineError::ContextCreationFailed)?;
 
+        // Feed keyboard, mouse, wheel, touch and pointer events | 接入键盘、鼠标、滚轮、触摸和指针事件
+        let input_queue = InputQueue::new();
+        let input_listeners = DomInputListeners::attach(&canvas, &input_queue)?;
+
         log::info!(
             "WebGL2 context created | WebGL2上下文已创建: {}x{}",
             canvas.width(),
             canvas.height()
         );
 
-        Ok(Self { gl, canvas })
+        Ok(Self { gl, canvas, input_queue, input_listeners: Some(input_listeners) })
     }
 
     /// Create a new WebGL context from external JavaScript objects.
@@ -94,7 +110,7 @@ impl WebGLContext {
             canvas_height
         );
 
-        Ok(Self { gl, canvas })
+        Ok(Self { gl, canvas, input_queue: InputQueue::new(), input_listeners: None })
     }
 
     /// Get a reference to the WebGL2 context.
@@ -111,6 +127,20 @@ impl WebGLContext {
         &self.canvas
     }
 
+    /// Get the queue input events are delivered to.
+    /// 获取输入事件投递到的队列。
+    #[inline]
+    pub fn input_queue(&self) -> &InputQueue {
+        &self.input_queue
+    }
+
+    /// Check whether canvas input listeners are attached.
+    /// 检查是否已附加canvas输入监听器。
+    #[inline]
+    pub fn has_input_listeners(&self) -> bool {
+        self.input_listeners.is_some()
+    }
+
     /// Get canvas width.
     /// 获取canvas宽度。
     #[inline]
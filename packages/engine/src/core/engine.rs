@@ -1,20 +1,33 @@
 //! Main engine implementation.
 //! 主引擎实现。
 
+use std::collections::HashMap;
+
 use wasm_bindgen::prelude::*;
+use web_sys::WebGl2RenderingContext;
 
 use super::context::WebGLContext;
-use super::error::Result;
-use crate::input::InputManager;
-use crate::renderer::{Renderer2D, GridRenderer, GizmoRenderer, TransformMode, ViewportManager};
+use super::error::{EngineError, Result};
+use crate::animation::{AnimationClip, AnimationEvent, AnimationManager, Animator};
+use crate::input::{ActionMap, GestureRecognizer, InputEvent, InputManager};
+use crate::renderer::{
+    Renderer2D, GridRenderer, GizmoRenderer, TransformMode, ViewportManager,
+    Camera2D, FrameGraph, RenderPass, RenderPassKind, ViewportConfig,
+    PostEffect, PostProcessStack, UniformValue, SpriteBatchData, NineSlice, RenderStats, GpuTimer,
+    Tileset, TileAnimation, TilemapLayer, ParticleEmitter,
+    Font, FontKind, TextLayout, TextStyle, SortKey, PickMode, TextureLoadCallback, TextureLoadState,
+    TextureLoadOptions, TextureSampler,
+};
+use crate::renderer::shapes::{Paint, Path, StrokeStyle};
+use crate::renderer::mesh::{self, Mesh2D, MeshDraw};
 use crate::resource::TextureManager;
 
 /// Engine configuration options.
 /// 引擎配置选项。
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
-    /// Maximum sprites per batch.
-    /// 每批次最大精灵数。
+    /// Initial sprite batch capacity; the batch grows beyond it as needed.
+    /// 精灵批处理的初始容量；超出时按需增长。
     pub max_sprites: usize,
 
     /// Enable debug mode.
@@ -61,6 +74,22 @@ pub struct Engine {
     /// 输入管理器。
     input_manager: InputManager,
 
+    /// Named input actions.
+    /// 命名输入动作。
+    actions: ActionMap,
+
+    /// Touch gesture recognizer.
+    /// 触摸手势识别器。
+    gestures: GestureRecognizer,
+
+    /// Whether two-finger pans move the camera.
+    /// 双指平移是否移动相机。
+    gesture_pan_camera: bool,
+
+    /// Whether pinches zoom the camera.
+    /// 捏合是否缩放相机。
+    gesture_pinch_camera: bool,
+
     /// Engine configuration.
     /// 引擎配置。
     #[allow(dead_code)]
@@ -85,6 +114,38 @@ pub struct Engine {
     /// and axis indicator are automatically hidden.
     /// 当为 false（运行时模式）时，编辑器专用 UI（如网格、gizmos、坐标轴指示器）会自动隐藏。
     is_editor: bool,
+
+    /// Ordered render passes executed by `render` and `render_to_viewport`.
+    /// 由`render`和`render_to_viewport`执行的有序渲染通道。
+    frame_graph: FrameGraph,
+
+    /// Post-processing effects for the main canvas.
+    /// 主画布的后处理效果。
+    post_process: PostProcessStack,
+
+    /// Whether the lighting pass applies submitted lights on the main canvas.
+    /// 主画布的光照通道是否应用已提交的光源。
+    lighting_enabled: bool,
+
+    /// Animation clips and animators.
+    /// 动画片段和动画器。
+    animations: AnimationManager,
+
+    /// Statistics of the last frame rendered to the main canvas.
+    /// 上一次渲染到主画布的帧的统计。
+    frame_stats: RenderStats,
+
+    /// Statistics of the last frame rendered to each viewport.
+    /// 上一次渲染到各视口的帧的统计。
+    viewport_stats: HashMap<String, RenderStats>,
+
+    /// GPU timer for the main canvas, when enabled and supported.
+    /// 主画布的GPU计时器（启用且支持时）。
+    gpu_timer: Option<GpuTimer>,
+
+    /// Picking IDs for the next sprite submission.
+    /// 下一次精灵提交的拾取ID。
+    pick_ids: Vec<u32>,
 }
 
 impl Engine {
@@ -109,7 +170,7 @@ impl Engine {
         let grid_renderer = GridRenderer::new(context.gl())?;
         let gizmo_renderer = GizmoRenderer::new(context.gl())?;
         let texture_manager = TextureManager::new(context.gl().clone());
-        let input_manager = InputManager::new();
+        let input_manager = InputManager::with_queue(context.input_queue().clone());
 
         log::info!("Engine created successfully | 引擎创建成功");
 
@@ -120,11 +181,23 @@ impl Engine {
             gizmo_renderer,
             texture_manager,
             input_manager,
+            actions: ActionMap::default(),
+            gestures: GestureRecognizer::default(),
+            gesture_pan_camera: false,
+            gesture_pinch_camera: false,
             config,
             show_grid: true,
             viewport_manager: ViewportManager::new(),
             show_gizmos: true,
             is_editor: true, // 默认为编辑器模式 | Default to editor mode
+            frame_graph: FrameGraph::default(),
+            post_process: PostProcessStack::default(),
+            lighting_enabled: false,
+            animations: AnimationManager::new(),
+            frame_stats: RenderStats::default(),
+            viewport_stats: HashMap::new(),
+            gpu_timer: None,
+            pick_ids: Vec::new(),
         })
     }
 
@@ -148,7 +221,7 @@ impl Engine {
         let grid_renderer = GridRenderer::new(context.gl())?;
         let gizmo_renderer = GizmoRenderer::new(context.gl())?;
         let texture_manager = TextureManager::new(context.gl().clone());
-        let input_manager = InputManager::new();
+        let input_manager = InputManager::with_queue(context.input_queue().clone());
 
         log::info!("Engine created from external context | 从外部上下文创建引擎");
 
@@ -159,11 +232,23 @@ impl Engine {
             gizmo_renderer,
             texture_manager,
             input_manager,
+            actions: ActionMap::default(),
+            gestures: GestureRecognizer::default(),
+            gesture_pan_camera: false,
+            gesture_pinch_camera: false,
             config,
             show_grid: true,
             viewport_manager: ViewportManager::new(),
             show_gizmos: true,
             is_editor: true, // 默认为编辑器模式 | Default to editor mode
+            frame_graph: FrameGraph::default(),
+            post_process: PostProcessStack::default(),
+            lighting_enabled: false,
+            animations: AnimationManager::new(),
+            frame_stats: RenderStats::default(),
+            viewport_stats: HashMap::new(),
+            gpu_timer: None,
+            pick_ids: Vec::new(),
         })
     }
 
@@ -189,6 +274,12 @@ impl Engine {
 
     /// Submit sprite batch data for rendering.
     /// 提交精灵批次数据进行渲染。
+    ///
+    /// `sort_keys` holds `[layer, orderInLayer, ySort]` per sprite (see
+    /// `SortKey`); when empty, sprites draw in submission order. Picking IDs
+    /// staged with `set_sprite_pick_ids` apply to this submission.
+    /// `sort_keys`为每个精灵的`[layer, orderInLayer, ySort]`（参见`SortKey`）；
+    /// 为空时按提交顺序绘制。通过`set_sprite_pick_ids`暂存的拾取ID应用于本次提交。
     pub fn submit_sprite_batch(
         &mut self,
         transforms: &[f32],
@@ -196,57 +287,301 @@ impl Engine {
         uvs: &[f32],
         colors: &[u32],
         material_ids: &[u32],
+        sort_keys: &[f32],
     ) -> Result<()> {
-        // Debug: log once
-        use std::sync::atomic::{AtomicBool, Ordering};
-        static LOGGED: AtomicBool = AtomicBool::new(false);
-        if !LOGGED.swap(true, Ordering::Relaxed) {
-            let sprite_count = texture_ids.len();
-            log::info!("Engine submit_sprite_batch: {} sprites, texture_ids: {:?}", sprite_count, texture_ids);
-        }
-
-        self.renderer.submit_batch(
+        let pick_ids = std::mem::take(&mut self.pick_ids);
+        let data = SpriteBatchData {
             transforms,
             texture_ids,
             uvs,
             colors,
             material_ids,
-            &self.texture_manager,
-        )
+            sort_keys,
+            pick_ids: &pick_ids,
+        };
+        self.renderer.submit_batch(&data, &self.texture_manager)
+    }
+
+    /// Enable or disable culling of sprites outside the camera view.
+    /// 启用或禁用相机视图外精灵的剔除。
+    pub fn set_sprite_culling(&mut self, enabled: bool) {
+        self.renderer.set_sprite_culling(enabled);
+    }
+
+    /// Sprites drawn and culled by the last rendered view.
+    /// 上次渲染视图中绘制和剔除的精灵数。
+    pub fn sprite_cull_stats(&self) -> (usize, usize) {
+        self.renderer.sprite_cull_stats()
+    }
+
+    /// Stage picking IDs (0 = not pickable) for the sprites of the next submission.
+    /// 为下一次提交的精灵暂存拾取ID（0 = 不可拾取）。
+    ///
+    /// Consumed by the next `submit_sprite_batch` or `submit_animated_sprites`
+    /// call, which fails if the count does not match its sprites.
+    /// 由下一次`submit_sprite_batch`或`submit_animated_sprites`调用消耗，
+    /// 数量与其精灵不符时该调用失败。
+    pub fn set_sprite_pick_ids(&mut self, pick_ids: &[u32]) {
+        self.pick_ids.clear();
+        self.pick_ids.extend_from_slice(pick_ids);
+    }
+
+    /// Set how screen-space picks are resolved.
+    /// 设置屏幕空间拾取的解析方式。
+    pub fn set_pick_mode(&mut self, mode: PickMode, alpha_threshold: f32) {
+        self.renderer.set_pick_mode(mode, alpha_threshold);
+    }
+
+    /// IDs of pickable sprites under a screen position, topmost last.
+    /// 屏幕位置下可拾取精灵的ID，最上层在最后。
+    pub fn pick(&mut self, screen_x: f32, screen_y: f32) -> Result<Vec<u32>> {
+        let screen = crate::math::Vec2::new(screen_x, screen_y);
+        self.renderer.pick(self.context.gl(), &self.texture_manager, screen)
+    }
+
+    /// IDs of pickable sprites overlapping a screen rectangle, topmost last.
+    /// 与屏幕矩形重叠的可拾取精灵ID，最上层在最后。
+    pub fn pick_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) -> Result<Vec<u32>> {
+        let (a, b) = (crate::math::Vec2::new(x0, y0), crate::math::Vec2::new(x1, y1));
+        self.renderer.pick_rect(self.context.gl(), &self.texture_manager, a, b)
+    }
+
+    /// IDs of pickable sprites containing a world position, topmost last.
+    /// 包含世界位置的可拾取精灵ID，最上层在最后。
+    pub fn pick_world(&self, x: f32, y: f32) -> Vec<u32> {
+        self.renderer.pick_world(crate::math::Vec2::new(x, y))
+    }
+
+    /// IDs of pickable sprites overlapping a world rectangle, topmost last.
+    /// 与世界矩形重叠的可拾取精灵ID，最上层在最后。
+    pub fn pick_world_rect(&self, x: f32, y: f32, width: f32, height: f32) -> Vec<u32> {
+        self.renderer.pick_world_rect(&crate::math::Rect::new(x, y, width, height))
+    }
+
+    /// Draw sprites using a texture as nine-slices, or stop with `None`.
+    /// 将使用该纹理的精灵绘制为九宫格，`None`表示停止。
+    pub fn set_nine_slice(&mut self, texture_id: u32, slice: Option<NineSlice>) {
+        self.renderer.set_nine_slice(texture_id, slice);
     }
 
     /// Render the current frame.
     /// 渲染当前帧。
     pub fn render(&mut self) -> Result<()> {
+        self.texture_manager.sync_loaded_sizes();
+        let gl = self.context.gl().clone();
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.begin(&gl);
+        }
+
         // Clear background with clear color
         let [r, g, b, a] = self.renderer.get_clear_color();
         self.context.clear(r, g, b, a);
 
-        // Render grid first (background) - only in editor mode
-        // 首先渲染网格（背景）- 仅在编辑器模式下
-        if self.is_editor && self.show_grid {
-            self.grid_renderer.render(self.context.gl(), self.renderer.camera());
-            self.grid_renderer.render_axes(self.context.gl(), self.renderer.camera());
+        let camera = self.renderer.camera().clone();
+        let surface = (self.context.width(), self.context.height());
+        let config = ViewportConfig {
+            show_grid: self.show_grid,
+            show_gizmos: self.show_gizmos,
+            clear_color: [r, g, b, a],
+            post_process: self.post_process.clone(),
+            lighting: self.lighting_enabled,
+        };
+        let result = self.run_frame_graph(&gl, &camera, surface, &config).map(|stats| {
+            self.frame_stats = RenderStats { gpu_time_ms: self.frame_stats.gpu_time_ms, ..stats };
+        });
+
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.end(&gl);
+            self.frame_stats.gpu_time_ms = timer.poll(&gl);
         }
+        result
+    }
 
-        // Render sprites
-        self.renderer.render(self.context.gl(), &self.texture_manager)?;
+    /// Statistics of the last frame rendered to the main canvas.
+    /// 上一次渲染到主画布的帧的统计。
+    #[inline]
+    pub fn frame_stats(&self) -> &RenderStats {
+        &self.frame_stats
+    }
+
+    /// Statistics of the last frame rendered to a viewport.
+    /// 上一次渲染到视口的帧的统计。
+    #[inline]
+    pub fn viewport_stats(&self, viewport_id: &str) -> Option<&RenderStats> {
+        self.viewport_stats.get(viewport_id)
+    }
+
+    /// Enable or disable GPU frame timing, returning whether it is active.
+    /// 启用或禁用GPU帧计时，返回是否生效。
+    ///
+    /// Requires `EXT_disjoint_timer_query_webgl2`, which many browsers only
+    /// expose behind a flag.
+    /// 需要`EXT_disjoint_timer_query_webgl2`，许多浏览器仅在开启标志后提供。
+    pub fn set_gpu_timing(&mut self, enabled: bool) -> bool {
+        if !enabled {
+            if let Some(mut timer) = self.gpu_timer.take() {
+                timer.destroy(self.context.gl());
+            }
+            self.frame_stats.gpu_time_ms = None;
+            return false;
+        }
+        if self.gpu_timer.is_none() {
+            self.gpu_timer = GpuTimer::new(self.context.gl());
+        }
+        self.gpu_timer.is_some()
+    }
+
+    /// Execute the frame graph passes in order, then finish the frame and return its statistics.
+    /// 按顺序执行帧图通道，然后结束当前帧并返回其统计。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - Context of the surface being rendered | 被渲染表面的上下文
+    /// * `camera` - Frame camera for screen passes | 屏幕通道使用的帧相机
+    /// * `surface` - Surface size in pixels | 表面尺寸（像素）
+    /// * `config` - Grid/gizmo visibility, clear color, post effects and lighting of the surface | 表面的网格/Gizmo可见性、清除颜色、后处理效果和光照
+    fn run_frame_graph(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        camera: &Camera2D,
+        surface: (u32, u32),
+        config: &ViewportConfig,
+    ) -> Result<RenderStats> {
+        let passes = self.frame_graph.passes().to_vec();
+        RenderStats::take_frame();
+
+        // Capture screen passes offscreen until the post-process pass runs
+        // 在后处理通道执行前，将屏幕通道离屏捕获
+        let mut capturing = config.post_process.has_enabled()
+            && passes.iter().any(|p| p.enabled && p.kind == RenderPassKind::PostProcess);
+        if capturing {
+            self.renderer.begin_post_process(gl, surface, config.clear_color)?;
+        }
 
-        // Render gizmos on top - only in editor mode
-        // 在顶部渲染 gizmos - 仅在编辑器模式下
-        if self.is_editor && self.show_gizmos {
-            self.gizmo_renderer.render(self.context.gl(), self.renderer.camera());
-            // Render axis indicator in corner
-            // 在角落渲染坐标轴指示器
-            self.gizmo_renderer.render_axis_indicator(
-                self.context.gl(),
-                self.context.width() as f32,
-                self.context.height() as f32,
-            );
+        for pass in passes.iter().filter(|p| p.enabled) {
+            // Editor-only passes are skipped in runtime mode
+            // 运行时模式下跳过编辑器专用通道
+            match pass.kind {
+                RenderPassKind::Grid if !(self.is_editor && config.show_grid) => continue,
+                RenderPassKind::Gizmos if !(self.is_editor && config.show_gizmos) => continue,
+                RenderPassKind::Lighting if !config.lighting => continue,
+                RenderPassKind::PostProcess => {
+                    if capturing {
+                        self.renderer.apply_post_process(gl, &self.texture_manager, &config.post_process);
+                        capturing = false;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Bind the pass target | 绑定通道目标
+            let (pass_camera, target_size) = match pass.target {
+                Some(target_id) => {
+                    let render_texture = match self.renderer.render_texture(target_id) {
+                        Some(rt) => rt,
+                        None => {
+                            log::warn!(
+                                "Render pass '{}' targets missing render texture {} | 渲染通道目标渲染纹理不存在",
+                                pass.name, target_id
+                            );
+                            continue;
+                        }
+                    };
+                    render_texture.bind(gl);
+                    if pass.clear {
+                        render_texture.clear(gl);
+                    }
+                    (render_texture.resolve_camera(camera), render_texture.dimensions())
+                }
+                None => {
+                    if capturing {
+                        self.renderer.bind_post_process_target(gl);
+                    } else {
+                        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+                        gl.viewport(0, 0, surface.0 as i32, surface.1 as i32);
+                    }
+                    if pass.clear {
+                        let [r, g, b, a] = config.clear_color;
+                        gl.clear_color(r, g, b, a);
+                        gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+                    }
+                    (camera.clone(), surface)
+                }
+            };
+
+            match pass.kind {
+                RenderPassKind::Grid => {
+                    // Editor passes stay out of the stats | 编辑器通道不计入统计
+                    RenderStats::uncounted(|| {
+                        self.grid_renderer.render(gl, &pass_camera);
+                        self.grid_renderer.render_axes(gl, &pass_camera);
+                    });
+                }
+                RenderPassKind::World => {
+                    self.renderer.draw_sprites(gl, &self.texture_manager, &pass_camera, pass.target, pass.layers.clone())?;
+                }
+                RenderPassKind::Gizmos => {
+                    RenderStats::uncounted(|| {
+                        self.gizmo_renderer.render(gl, &pass_camera);
+                        // Render axis indicator in corner
+                        // 在角落渲染坐标轴指示器
+                        self.gizmo_renderer.render_axis_indicator(
+                            gl,
+                            target_size.0 as f32,
+                            target_size.1 as f32,
+                        );
+                    });
+                }
+                RenderPassKind::Blit { source } => {
+                    // A texture cannot be sampled while it is being rendered to
+                    // 纹理不能在渲染到自身时被采样
+                    if pass.target == Some(source) {
+                        log::warn!(
+                            "Render pass '{}' blits texture {} onto itself | 渲染通道将纹理拷贝到自身",
+                            pass.name, source
+                        );
+                        continue;
+                    }
+                    self.renderer.blit(gl, &self.texture_manager, source);
+                }
+                RenderPassKind::Lighting => {
+                    self.renderer.render_lighting(gl, &self.texture_manager, &pass_camera, target_size)?;
+                    // Return to the lit target | 返回被照亮的目标
+                    self.rebind_pass_target(gl, pass.target, capturing, surface);
+                    self.renderer.composite_lighting(gl);
+                }
+                RenderPassKind::PostProcess => {}
+            }
         }
+
+        // Restore the default framebuffer | 恢复默认帧缓冲区
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+        gl.viewport(0, 0, surface.0 as i32, surface.1 as i32);
+
+        let stats = RenderStats::take_frame();
+        self.renderer.end_frame();
         self.gizmo_renderer.clear();
 
-        Ok(())
+        Ok(stats)
+    }
+
+    /// Bind a pass target again without clearing it.
+    /// 重新绑定通道目标，不清除。
+    fn rebind_pass_target(
+        &self,
+        gl: &WebGl2RenderingContext,
+        target: Option<u32>,
+        capturing: bool,
+        surface: (u32, u32),
+    ) {
+        match target.and_then(|id| self.renderer.render_texture(id)) {
+            Some(render_texture) => render_texture.bind(gl),
+            None if capturing => self.renderer.bind_post_process_target(gl),
+            None => {
+                gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+                gl.viewport(0, 0, surface.0 as i32, surface.1 as i32);
+            }
+        }
     }
 
     /// Render sprites only without clearing the screen.
@@ -345,12 +680,62 @@ impl Engine {
         self.texture_manager.load_texture(id, url)
     }
 
+    /// Load a texture from URL with sampling and upload options.
+    /// 使用采样和上传选项从URL加载纹理。
+    pub fn load_texture_with(&mut self, id: u32, url: &str, options: TextureLoadOptions) -> Result<()> {
+        self.texture_manager.load_texture_with(id, url, options)
+    }
+
+    /// Change how a texture is sampled.
+    /// 更改纹理的采样方式。
+    pub fn set_texture_sampler(&mut self, id: u32, sampler: TextureSampler) -> Result<()> {
+        self.texture_manager.set_texture_sampler(id, sampler)
+    }
+
+    /// Check if a texture's colors were multiplied by alpha on upload.
+    /// 检查纹理颜色是否在上传时已乘以alpha。
+    pub fn is_texture_premultiplied(&self, id: u32) -> bool {
+        self.texture_manager.get_texture(id).is_some_and(|t| t.premultiplied_alpha())
+    }
+
+    /// Get the load state of a texture loaded from a URL.
+    /// 获取从URL加载的纹理的加载状态。
+    pub fn texture_load_state(&self, id: u32) -> Option<TextureLoadState> {
+        self.texture_manager.load_state(id)
+    }
+
+    /// Call back with the image size once a texture has loaded, or with the error.
+    /// 纹理加载完成后以图像尺寸回调，失败时以错误回调。
+    pub fn when_texture_loaded(&self, id: u32, callback: TextureLoadCallback) {
+        self.texture_manager.when_loaded(id, callback);
+    }
+
     /// Load texture by path, returning texture ID.
     /// 按路径加载纹理，返回纹理ID。
     pub fn load_texture_by_path(&mut self, path: &str) -> Result<u32> {
         self.texture_manager.load_texture_by_path(path)
     }
 
+    /// Load an image into a shared atlas page, returning its region ID.
+    /// 将图像加载到共享图集页面，返回其区域ID。
+    pub fn load_texture_to_atlas(&mut self, path: &str, width: u32, height: u32) -> Result<u32> {
+        self.texture_manager.load_texture_to_atlas(path, width, height)
+    }
+
+    /// Get an atlas region as (page texture ID, [u0, v0, u1, v1], width, height).
+    /// 获取图集区域：(页面纹理ID, [u0, v0, u1, v1], 宽度, 高度)。
+    pub fn get_atlas_region(&self, id: u32) -> Option<(u32, [f32; 4], u32, u32)> {
+        self.texture_manager
+            .get_atlas_region(id)
+            .map(|r| (r.page_texture_id, r.uv, r.rect.width, r.rect.height))
+    }
+
+    /// Set the size of newly created atlas pages.
+    /// 设置新建图集页面的尺寸。
+    pub fn set_atlas_page_size(&mut self, size: u32) {
+        self.texture_manager.set_atlas_page_size(size);
+    }
+
     /// Get texture ID by path.
     /// 按路径获取纹理ID。
     pub fn get_texture_id_by_path(&self, path: &str) -> Option<u32> {
@@ -379,21 +764,681 @@ impl Engine {
     /// This removes all loaded textures from GPU memory and resets the ID counter.
     /// 这会从GPU内存中移除所有已加载的纹理并重置ID计数器。
     pub fn clear_all_textures(&mut self) {
+        self.renderer.clear_render_textures(self.context.gl());
         self.texture_manager.clear_all();
     }
 
+    // ===== Render Textures =====
+    // ===== 渲染纹理 =====
+
+    /// Create a render texture, returning its texture ID.
+    /// 创建渲染纹理，返回其纹理ID。
+    pub fn create_render_texture(&mut self, width: u32, height: u32) -> Result<u32> {
+        self.renderer.create_render_texture(self.context.gl(), &mut self.texture_manager, width, height)
+    }
+
+    /// Resize a render texture.
+    /// 调整渲染纹理大小。
+    pub fn resize_render_texture(&mut self, id: u32, width: u32, height: u32) -> Result<bool> {
+        self.renderer.resize_render_texture(self.context.gl(), &mut self.texture_manager, id, width, height)
+    }
+
+    /// Remove a render texture and any passes targeting it.
+    /// 移除渲染纹理及所有以其为目标的通道。
+    pub fn remove_render_texture(&mut self, id: u32) -> bool {
+        let names: Vec<String> = self.frame_graph.passes()
+            .iter()
+            .filter(|p| p.target == Some(id))
+            .map(|p| p.name.clone())
+            .collect();
+        for name in names {
+            self.frame_graph.remove_pass(&name);
+        }
+        self.renderer.remove_render_texture(self.context.gl(), &mut self.texture_manager, id)
+    }
+
+    /// Give a render texture its own camera.
+    /// 为渲染纹理设置独立相机。
+    pub fn set_render_texture_camera(&mut self, id: u32, x: f32, y: f32, zoom: f32, rotation: f32) -> bool {
+        match self.renderer.render_texture_mut(id) {
+            Some(render_texture) => {
+                let camera = render_texture.camera_mut();
+                camera.position.x = x;
+                camera.position.y = y;
+                camera.set_zoom(zoom);
+                camera.rotation = rotation;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Make a render texture follow the frame camera again.
+    /// 使渲染纹理重新跟随帧相机。
+    pub fn clear_render_texture_camera(&mut self, id: u32) -> bool {
+        match self.renderer.render_texture_mut(id) {
+            Some(render_texture) => {
+                render_texture.camera = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set a render texture's clear color.
+    /// 设置渲染纹理的清除颜色。
+    pub fn set_render_texture_clear_color(&mut self, id: u32, r: f32, g: f32, b: f32, a: f32) -> bool {
+        match self.renderer.render_texture_mut(id) {
+            Some(render_texture) => {
+                render_texture.clear_color = [r, g, b, a];
+                true
+            }
+            None => false,
+        }
+    }
+
+    // ===== Frame Graph =====
+    // ===== 帧图 =====
+
+    /// Add a render pass, replacing any pass with the same name.
+    /// 添加渲染通道，替换同名通道。
+    ///
+    /// # Arguments | 参数
+    /// * `pass` - Pass to add | 要添加的通道
+    /// * `before` - Insert before this pass, or append if `None` | 插入到此通道之前，`None`表示追加
+    pub fn add_render_pass(&mut self, pass: RenderPass, before: Option<&str>) {
+        match before {
+            Some(before) => self.frame_graph.insert_before(before, pass),
+            None => self.frame_graph.add_pass(pass),
+        }
+    }
+
+    /// Remove a render pass by name.
+    /// 按名称移除渲染通道。
+    pub fn remove_render_pass(&mut self, name: &str) -> bool {
+        self.frame_graph.remove_pass(name)
+    }
+
+    /// Enable or disable a render pass.
+    /// 启用或禁用渲染通道。
+    pub fn set_render_pass_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.frame_graph.get_mut(name) {
+            Some(pass) => {
+                pass.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Limit a World pass to a range of sorting layers.
+    /// 将World通道限制在排序层范围内。
+    pub fn set_render_pass_layers(&mut self, name: &str, min_layer: i32, max_layer: i32) -> bool {
+        match self.frame_graph.get_mut(name) {
+            Some(pass) => {
+                pass.layers = min_layer..=max_layer;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restore the default grid → world → lighting → post → ui → gizmos passes.
+    /// 恢复默认的 网格 → 世界 → 光照 → 后处理 → UI → Gizmo 通道。
+    pub fn reset_render_passes(&mut self) {
+        self.frame_graph.reset();
+    }
+
+    /// Get the frame graph.
+    /// 获取帧图。
+    pub fn frame_graph(&self) -> &FrameGraph {
+        &self.frame_graph
+    }
+
+    /// Get the mutable frame graph.
+    /// 获取可变帧图。
+    pub fn frame_graph_mut(&mut self) -> &mut FrameGraph {
+        &mut self.frame_graph
+    }
+
+    // ===== Post Processing =====
+    // ===== 后处理 =====
+
+    /// Get the post-processing stack of a viewport, or of the main canvas for `None`.
+    /// 获取视口的后处理栈，`None`表示主画布。
+    pub fn post_process(&self, viewport_id: Option<&str>) -> Option<&PostProcessStack> {
+        match viewport_id {
+            Some(id) => self.viewport_manager.get(id).map(|v| &v.config.post_process),
+            None => Some(&self.post_process),
+        }
+    }
+
+    /// Get the mutable post-processing stack of a viewport, or of the main canvas for `None`.
+    /// 获取视口的可变后处理栈，`None`表示主画布。
+    pub fn post_process_mut(&mut self, viewport_id: Option<&str>) -> Option<&mut PostProcessStack> {
+        match viewport_id {
+            Some(id) => self.viewport_manager.get_mut(id).map(|v| &mut v.config.post_process),
+            None => Some(&mut self.post_process),
+        }
+    }
+
+    /// Add a post effect, replacing an effect with the same name.
+    /// 添加后处理效果，替换同名效果。
+    pub fn add_post_effect(&mut self, viewport_id: Option<&str>, effect: PostEffect) -> bool {
+        match self.post_process_mut(viewport_id) {
+            Some(stack) => {
+                stack.add(effect);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a post effect by name.
+    /// 按名称移除后处理效果。
+    pub fn remove_post_effect(&mut self, viewport_id: Option<&str>, name: &str) -> bool {
+        self.post_process_mut(viewport_id)
+            .is_some_and(|stack| stack.remove(name))
+    }
+
+    /// Enable or disable a post effect.
+    /// 启用或禁用后处理效果。
+    pub fn set_post_effect_enabled(&mut self, viewport_id: Option<&str>, name: &str, enabled: bool) -> bool {
+        match self.post_process_mut(viewport_id).and_then(|stack| stack.get_mut(name)) {
+            Some(effect) => {
+                effect.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set a uniform of a post effect.
+    /// 设置后处理效果的uniform。
+    pub fn set_post_effect_uniform(
+        &mut self,
+        viewport_id: Option<&str>,
+        name: &str,
+        uniform: &str,
+        value: UniformValue,
+    ) -> bool {
+        match self.post_process_mut(viewport_id).and_then(|stack| stack.get_mut(name)) {
+            Some(effect) => {
+                effect.uniforms.set(uniform, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all post effects.
+    /// 移除所有后处理效果。
+    pub fn clear_post_effects(&mut self, viewport_id: Option<&str>) -> bool {
+        match self.post_process_mut(viewport_id) {
+            Some(stack) => {
+                stack.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get post effect names in execution order.
+    /// 按执行顺序获取后处理效果名称。
+    pub fn post_effect_names(&self, viewport_id: Option<&str>) -> Vec<String> {
+        self.post_process(viewport_id)
+            .map(|stack| stack.effects().iter().map(|e| e.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    // ===== Lighting =====
+    // ===== 光照 =====
+
+    /// Submit lights for this frame (see `LIGHT_STRIDE` for the layout).
+    /// 提交本帧的光源（布局参见`LIGHT_STRIDE`）。
+    pub fn submit_lights(&mut self, data: &[f32]) -> Result<()> {
+        self.renderer.lighting_mut().submit_lights(data)
+    }
+
+    /// Submit shadow-casting polygons for this frame.
+    /// 提交本帧投射阴影的多边形。
+    ///
+    /// # Arguments | 参数
+    /// * `points` - Flat points of all polygons | 所有多边形的扁平点
+    /// * `counts` - Point count of each polygon | 每个多边形的点数
+    pub fn submit_occluders(&mut self, points: &[f32], counts: &[u32]) -> Result<()> {
+        self.renderer.lighting_mut().submit_occluders(points, counts)
+    }
+
+    /// Assign a normal map to a texture, or remove it with `None`.
+    /// 为纹理指定法线贴图，`None`表示移除。
+    pub fn set_normal_map(&mut self, texture_id: u32, normal_texture_id: Option<u32>) {
+        self.renderer.lighting_mut().set_normal_map(texture_id, normal_texture_id);
+    }
+
+    /// Enable lighting for a viewport, or for the main canvas with `None`.
+    /// 为视口启用光照，`None`表示主画布。
+    ///
+    /// While enabled, areas reached by no light are black; submit a global
+    /// light for ambient lighting.
+    /// 启用后，没有光照到达的区域为黑色；提交全局光作为环境光。
+    pub fn set_lighting_enabled(&mut self, viewport_id: Option<&str>, enabled: bool) -> bool {
+        match viewport_id {
+            Some(id) => match self.viewport_manager.get_mut(id) {
+                Some(viewport) => {
+                    viewport.config.lighting = enabled;
+                    true
+                }
+                None => false,
+            },
+            None => {
+                self.lighting_enabled = enabled;
+                true
+            }
+        }
+    }
+
+    /// Set the light source size used for soft shadow penumbrae.
+    /// 设置用于软阴影半影的光源尺寸。
+    pub fn set_shadow_softness(&mut self, softness: f32) {
+        self.renderer.lighting_mut().shadow_softness = softness.max(0.0);
+    }
+
+    /// Register a tileset, returning its ID.
+    /// 注册瓦片集，返回其ID。
+    pub fn add_tileset(&mut self, tileset: Tileset) -> u32 {
+        self.renderer.tilemaps_mut().add_tileset(tileset)
+    }
+
+    /// Animate a tile of a tileset, or stop with `None`.
+    /// 为瓦片集的瓦片设置动画，`None`表示停止。
+    pub fn set_tile_animation(&mut self, tileset_id: u32, tile: u32, animation: Option<TileAnimation>) -> bool {
+        match self.renderer.tilemaps_mut().tileset_mut(tileset_id) {
+            Some(tileset) => {
+                tileset.set_animation(tile, animation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Create an empty tilemap layer, returning its ID.
+    /// 创建空瓦片地图层，返回其ID。
+    pub fn create_tilemap_layer(&mut self, layer: TilemapLayer) -> u32 {
+        self.renderer.tilemaps_mut().add_layer(layer)
+    }
+
+    /// Remove a tilemap layer.
+    /// 移除瓦片地图层。
+    pub fn remove_tilemap_layer(&mut self, layer_id: u32) -> bool {
+        self.renderer.tilemaps_mut().remove_layer(self.context.gl(), layer_id)
+    }
+
+    /// Get a mutable tilemap layer.
+    /// 获取可变瓦片地图层。
+    pub fn tilemap_layer_mut(&mut self, layer_id: u32) -> Result<&mut TilemapLayer> {
+        self.renderer
+            .tilemaps_mut()
+            .layer_mut(layer_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Tilemap layer not found: {}", layer_id)))
+    }
+
+    /// Advance tile animations.
+    /// 推进瓦片动画。
+    pub fn update_tilemaps(&mut self, delta_seconds: f32) {
+        self.renderer.tilemaps_mut().update(delta_seconds);
+    }
+
+    /// Add a particle emitter, returning its ID.
+    /// 添加粒子发射器，返回其ID。
+    pub fn create_particle_emitter(&mut self, emitter: ParticleEmitter) -> Result<u32> {
+        self.renderer.particles_mut().add_emitter(self.context.gl(), emitter)
+    }
+
+    /// Remove a particle emitter.
+    /// 移除粒子发射器。
+    pub fn remove_particle_emitter(&mut self, emitter_id: u32) -> bool {
+        self.renderer.particles_mut().remove_emitter(self.context.gl(), emitter_id)
+    }
+
+    /// Get a mutable particle emitter.
+    /// 获取可变粒子发射器。
+    pub fn particle_emitter_mut(&mut self, emitter_id: u32) -> Result<&mut ParticleEmitter> {
+        self.renderer
+            .particles_mut()
+            .emitter_mut(emitter_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Particle emitter not found: {}", emitter_id)))
+    }
+
+    /// Number of live particles of an emitter (0 if unknown).
+    /// 发射器的存活粒子数（未知时为0）。
+    pub fn particle_count(&self, emitter_id: u32) -> u32 {
+        self.renderer
+            .particles()
+            .emitter(emitter_id)
+            .map_or(0, |emitter| emitter.particles().len() as u32)
+    }
+
+    /// Advance all particle emitters.
+    /// 推进所有粒子发射器。
+    pub fn update_particles(&mut self, delta_seconds: f32) {
+        self.renderer.particles_mut().update(delta_seconds);
+    }
+
+    /// Parse a BMFont file and load it, returning the font ID.
+    /// 解析BMFont文件并加载，返回字体ID。
+    ///
+    /// # Arguments | 参数
+    /// * `source` - Text-format .fnt contents | 文本格式的.fnt内容
+    /// * `page_textures` - Texture ID for each font page | 每个字体页面的纹理ID
+    /// * `kind` - Override the glyph storage read from the file | 覆盖从文件读取的字形存储方式
+    pub fn load_font(&mut self, source: &str, page_textures: Vec<u32>, kind: Option<FontKind>) -> Result<u32> {
+        let mut font = Font::parse_fnt(source)?;
+        if let Some(kind) = kind {
+            font.kind = kind;
+        }
+        self.renderer.load_font(font, page_textures)
+    }
+
+    /// Remove a font.
+    /// 移除字体。
+    pub fn remove_font(&mut self, font_id: u32) -> bool {
+        self.renderer.remove_font(font_id)
+    }
+
+    /// Submit a string for this frame.
+    /// 提交本帧的字符串。
+    pub fn submit_text(&mut self, font_id: u32, text: &str, position: glam::Vec2, style: &TextStyle) -> Result<()> {
+        self.renderer.submit_text(font_id, text, position, style, &self.texture_manager)
+    }
+
+    /// Lay out a string without drawing it.
+    /// 布局字符串但不绘制。
+    pub fn measure_text(&self, font_id: u32, text: &str, style: &TextStyle) -> Result<TextLayout> {
+        self.renderer.measure_text(font_id, text, style)
+    }
+
+    /// Fill a path for this frame.
+    /// 为本帧填充路径。
+    ///
+    /// # Arguments | 参数
+    /// * `commands` - Packed path commands (see `shapes::PATH_MOVE_TO`) | 打包的路径命令
+    /// * `paint` - Packed paint (see `shapes::PAINT_SOLID`) | 打包的填充
+    /// * `sort` - Draw order among sprites | 在精灵之间的绘制顺序
+    pub fn fill_path(&mut self, commands: &[f32], paint: &[f32], sort: SortKey) -> Result<()> {
+        let paint = Paint::from_data(paint)?;
+        let path = Path::from_commands(commands, self.shape_tolerance())?;
+        self.renderer.fill_shape(&path, &paint, sort);
+        Ok(())
+    }
+
+    /// Stroke a path for this frame.
+    /// 为本帧描边路径。
+    ///
+    /// # Arguments | 参数
+    /// * `commands` - Packed path commands | 打包的路径命令
+    /// * `paint` - Packed paint | 打包的填充
+    /// * `stroke` - Packed stroke style (see `shapes::STROKE_STYLE_STRIDE`) | 打包的描边样式
+    /// * `sort` - Draw order among sprites | 在精灵之间的绘制顺序
+    pub fn stroke_path(&mut self, commands: &[f32], paint: &[f32], stroke: &[f32], sort: SortKey) -> Result<()> {
+        let paint = Paint::from_data(paint)?;
+        let style = StrokeStyle::from_data(stroke)?;
+        let path = Path::from_commands(commands, self.shape_tolerance())?;
+        self.renderer.stroke_shape(&path, &style, &paint, sort);
+        Ok(())
+    }
+
+    /// Curve flattening tolerance: a quarter of a screen pixel.
+    /// 曲线展平容差：四分之一屏幕像素。
+    fn shape_tolerance(&self) -> f32 {
+        self.renderer.pixel_size() * 0.25
+    }
+
+    /// Add a mesh, returning its ID.
+    /// 添加网格，返回其ID。
+    pub fn create_mesh(&mut self, mesh: Mesh2D) -> u32 {
+        self.renderer.meshes_mut().add_mesh(mesh)
+    }
+
+    /// Get a mutable mesh.
+    /// 获取可变网格。
+    pub fn mesh_mut(&mut self, mesh_id: u32) -> Result<&mut Mesh2D> {
+        self.renderer
+            .meshes_mut()
+            .mesh_mut(mesh_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Mesh not found: {}", mesh_id)))
+    }
+
+    /// Remove a mesh.
+    /// 移除网格。
+    pub fn remove_mesh(&mut self, mesh_id: u32) -> bool {
+        self.renderer.meshes_mut().remove_mesh(mesh_id)
+    }
+
+    /// Draw a mesh this frame.
+    /// 在本帧绘制网格。
+    ///
+    /// # Arguments | 参数
+    /// * `mesh_id` - Mesh ID | 网格ID
+    /// * `draw` - Transform, texture, material, tint and sort key | 变换、纹理、材质、色调和排序键
+    /// * `bones` - Packed bone palette (see `mesh::BONE_STRIDE`), empty for unskinned | 打包的骨骼调色板，非蒙皮时为空
+    pub fn submit_mesh(&mut self, mesh_id: u32, draw: &MeshDraw, bones: &[f32]) -> Result<()> {
+        let mut palette = Vec::with_capacity(bones.len() / mesh::BONE_STRIDE);
+        mesh::parse_palette(bones, &mut palette)?;
+        self.renderer.submit_mesh(mesh_id, draw, &palette, &self.texture_manager)
+    }
+
+    /// Add an animation clip, returning its ID.
+    /// 添加动画片段，返回其ID。
+    pub fn add_animation_clip(&mut self, clip: AnimationClip) -> u32 {
+        self.animations.add_clip(clip)
+    }
+
+    /// Remove an animation clip.
+    /// 移除动画片段。
+    pub fn remove_animation_clip(&mut self, clip_id: u32) -> bool {
+        self.animations.remove_clip(clip_id)
+    }
+
+    /// Raise an event whenever an animator enters a clip frame.
+    /// 每当动画器进入片段的某帧时触发事件。
+    pub fn add_animation_event(&mut self, clip_id: u32, frame: usize, event_id: u32) -> Result<()> {
+        self.animations
+            .clip_mut(clip_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Animation clip not found: {}", clip_id)))?
+            .add_event(frame, event_id)
+    }
+
+    /// Create an animator playing a clip, returning its ID.
+    /// 创建播放片段的动画器，返回其ID。
+    pub fn create_animator(&mut self, clip_id: u32) -> Result<u32> {
+        self.animations.create_animator(clip_id)
+    }
+
+    /// Remove an animator.
+    /// 移除动画器。
+    pub fn remove_animator(&mut self, animator_id: u32) -> bool {
+        self.animations.remove_animator(animator_id)
+    }
+
+    /// Get a mutable animator.
+    /// 获取可变动画器。
+    pub fn animator_mut(&mut self, animator_id: u32) -> Result<&mut Animator> {
+        self.animations
+            .animator_mut(animator_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Animator not found: {}", animator_id)))
+    }
+
+    /// Restart an animator with a clip.
+    /// 使用片段重新启动动画器。
+    pub fn play_animation(&mut self, animator_id: u32, clip_id: u32) -> Result<()> {
+        self.animations.play(animator_id, clip_id)
+    }
+
+    /// Frame index shown by an animator.
+    /// 动画器显示的帧索引。
+    pub fn animator_frame(&self, animator_id: u32) -> Option<usize> {
+        self.animations.animator(animator_id).map(Animator::frame)
+    }
+
+    /// Check whether an animator's `Once` clip has ended (false if unknown).
+    /// 检查动画器的`Once`片段是否已结束（未知时为false）。
+    pub fn is_animation_finished(&self, animator_id: u32) -> bool {
+        self.animations.animator(animator_id).is_some_and(Animator::is_finished)
+    }
+
+    /// Advance all animators.
+    /// 推进所有动画器。
+    pub fn update_animations(&mut self, delta_seconds: f32) {
+        self.animations.update(delta_seconds);
+    }
+
+    /// Take the animation events raised since the last call.
+    /// 取出自上次调用以来触发的动画事件。
+    pub fn drain_animation_events(&mut self) -> impl Iterator<Item = AnimationEvent> + '_ {
+        self.animations.drain_events()
+    }
+
+    /// Submit sprites showing the current frame of their animators.
+    /// 提交显示其动画器当前帧的精灵。
+    ///
+    /// Takes the same data as `submit_sprite_batch` with one animator per
+    /// sprite in place of texture IDs and UVs.
+    /// 数据与`submit_sprite_batch`相同，但以每个精灵一个动画器代替纹理ID和UV。
+    pub fn submit_animated_sprites(
+        &mut self,
+        animator_ids: &[u32],
+        transforms: &[f32],
+        colors: &[u32],
+        material_ids: &[u32],
+        sort_keys: &[f32],
+    ) -> Result<()> {
+        let (texture_ids, uvs) = self.animations.sprite_frames(animator_ids)?;
+        let pick_ids = std::mem::take(&mut self.pick_ids);
+        let data = SpriteBatchData {
+            transforms,
+            texture_ids,
+            uvs,
+            colors,
+            material_ids,
+            sort_keys,
+            pick_ids: &pick_ids,
+        };
+        self.renderer.submit_batch(&data, &self.texture_manager)
+    }
+
     /// Check if a key is currently pressed.
     /// 检查某个键是否当前被按下。
     pub fn is_key_down(&self, key_code: &str) -> bool {
         self.input_manager.is_key_down(key_code)
     }
 
-    /// Update input state.
-    /// 更新输入状态。
+    /// Check if a key was pressed since the last input update.
+    /// 检查某个键是否在上次输入更新后被按下。
+    pub fn is_key_just_pressed(&self, key_code: &str) -> bool {
+        self.input_manager.is_key_just_pressed(key_code)
+    }
+
+    /// Check if a key was released since the last input update.
+    /// 检查某个键是否在上次输入更新后被释放。
+    pub fn is_key_just_released(&self, key_code: &str) -> bool {
+        self.input_manager.is_key_just_released(key_code)
+    }
+
+    /// Get the input state.
+    /// 获取输入状态。
+    #[inline]
+    pub fn input(&self) -> &InputManager {
+        &self.input_manager
+    }
+
+    /// Get the mutable input state.
+    /// 获取可变输入状态。
+    #[inline]
+    pub fn input_mut(&mut self) -> &mut InputManager {
+        &mut self.input_manager
+    }
+
+    /// Queue an input event for the next input update.
+    /// 为下一次输入更新排队输入事件。
+    ///
+    /// Used by hosts without canvas listeners, such as external contexts.
+    /// 供没有canvas监听器的宿主使用，例如外部上下文。
+    pub fn push_input_event(&self, event: InputEvent) {
+        self.input_manager.push_event(event);
+    }
+
+    /// Mouse position in world coordinates.
+    /// 世界坐标下的鼠标位置。
+    pub fn mouse_world_position(&self) -> (f32, f32) {
+        let position = self.input_manager.mouse.position;
+        self.screen_to_world(position.x, position.y)
+    }
+
+    /// Update input state, applying events received since the last update.
+    /// 更新输入状态，应用自上次更新以来收到的事件。
     pub fn update_input(&mut self) {
         self.input_manager.update();
     }
 
+    /// Get the input actions.
+    /// 获取输入动作。
+    #[inline]
+    pub fn actions(&self) -> &ActionMap {
+        &self.actions
+    }
+
+    /// Get the mutable input actions.
+    /// 获取可变输入动作。
+    #[inline]
+    pub fn actions_mut(&mut self) -> &mut ActionMap {
+        &mut self.actions
+    }
+
+    /// Evaluate input actions against the current input state.
+    /// 根据当前输入状态计算输入动作。
+    ///
+    /// # Arguments | 参数
+    /// * `dt` - Seconds since the last call, for hold and tap timing | 距上次调用的秒数，用于长按和轻击计时
+    pub fn update_actions(&mut self, dt: f32) {
+        let viewport = (self.width() as f32, self.height() as f32);
+        self.actions.update(&self.input_manager, dt, viewport);
+    }
+
+    /// Get the gesture recognizer.
+    /// 获取手势识别器。
+    #[inline]
+    pub fn gestures(&self) -> &GestureRecognizer {
+        &self.gestures
+    }
+
+    /// Get the mutable gesture recognizer.
+    /// 获取可变手势识别器。
+    #[inline]
+    pub fn gestures_mut(&mut self) -> &mut GestureRecognizer {
+        &mut self.gestures
+    }
+
+    /// Choose which two-finger gestures drive the camera.
+    /// 选择哪些双指手势驱动相机。
+    pub fn set_gesture_camera(&mut self, pan: bool, pinch: bool) {
+        self.gesture_pan_camera = pan;
+        self.gesture_pinch_camera = pinch;
+    }
+
+    /// Recognize gestures from the current touch state and apply camera gestures.
+    /// 根据当前触摸状态识别手势并应用相机手势。
+    ///
+    /// # Arguments | 参数
+    /// * `dt` - Seconds since the last call | 距上次调用的秒数
+    pub fn update_gestures(&mut self, dt: f32) {
+        self.gestures.update_from(&self.input_manager.touch, dt);
+        if self.gesture_pan_camera || self.gesture_pinch_camera {
+            let camera = self.renderer.camera_mut();
+            self.gestures.apply_to_camera(camera, self.gesture_pan_camera, self.gesture_pinch_camera);
+        }
+    }
+
     /// Resize viewport.
     /// 调整视口大小。
     pub fn resize(&mut self, width: f32, height: f32) {
@@ -411,12 +1456,26 @@ impl Engine {
     /// * `rotation` - Rotation in radians | 旋转角度（弧度）
     pub fn set_camera(&mut self, x: f32, y: f32, zoom: f32, rotation: f32) {
         let camera = self.renderer.camera_mut();
+        camera.stop_zoom();
         camera.position.x = x;
         camera.position.y = y;
         camera.set_zoom(zoom);
         camera.rotation = rotation;
     }
 
+    /// Get the mutable frame camera.
+    /// 获取可变的帧相机。
+    #[inline]
+    pub fn camera_mut(&mut self) -> &mut Camera2D {
+        self.renderer.camera_mut()
+    }
+
+    /// Apply camera behaviors (follow, bounds, shake, smooth zoom) for one frame.
+    /// 应用一帧的相机行为（跟随、边界、震动、平滑缩放）。
+    pub fn update_camera(&mut self, delta_seconds: f32) {
+        self.renderer.camera_mut().update(delta_seconds);
+    }
+
     /// Get camera position.
     /// 获取相机位置。
     pub fn get_camera(&self) -> (f32, f32, f32, f32) {
@@ -489,6 +1548,12 @@ impl Engine {
         self.is_editor
     }
 
+    /// Enable or disable instanced sprite rendering.
+    /// 启用或禁用实例化精灵渲染。
+    pub fn set_instancing(&mut self, enabled: bool) {
+        self.renderer.set_instancing(enabled);
+    }
+
     /// Set clear color for the active viewport.
     /// 设置活动视口的清除颜色。
     pub fn set_clear_color(&mut self, r: f32, g: f32, b: f32, a: f32) {
@@ -513,6 +1578,7 @@ impl Engine {
     /// 注销视口。
     pub fn unregister_viewport(&mut self, id: &str) {
         self.viewport_manager.unregister(id);
+        self.viewport_stats.remove(id);
     }
 
     /// Set the active viewport.
@@ -567,8 +1633,7 @@ impl Engine {
         };
 
         // Get viewport settings
-        let show_grid = viewport.config.show_grid;
-        let show_gizmos = viewport.config.show_gizmos;
+        let config = viewport.config.clone();
         let camera = viewport.camera.clone();
 
         // Bind viewport and clear
@@ -582,31 +1647,10 @@ impl Engine {
         renderer_camera.rotation = camera.rotation;
         renderer_camera.set_viewport(camera.viewport_width(), camera.viewport_height());
 
-        // Render grid if enabled - only in editor mode
-        // 渲染网格（如果启用）- 仅在编辑器模式下
-        if self.is_editor && show_grid {
-            self.grid_renderer.render(viewport.gl(), &camera);
-            self.grid_renderer.render_axes(viewport.gl(), &camera);
-        }
-
-        // Render sprites
-        self.renderer.render(viewport.gl(), &self.texture_manager)?;
-
-        // Render gizmos if enabled - only in editor mode
-        // 渲染 gizmos（如果启用）- 仅在编辑器模式下
-        if self.is_editor && show_gizmos {
-            self.gizmo_renderer.render(viewport.gl(), &camera);
-            // Render axis indicator in corner
-            // 在角落渲染坐标轴指示器
-            let (vp_width, vp_height) = viewport.dimensions();
-            self.gizmo_renderer.render_axis_indicator(
-                viewport.gl(),
-                vp_width as f32,
-                vp_height as f32,
-            );
-        }
-        self.gizmo_renderer.clear();
-
+        let gl = viewport.gl().clone();
+        let surface = viewport.dimensions();
+        let stats = self.run_frame_graph(&gl, &camera, surface, &config)?;
+        self.viewport_stats.insert(viewport_id.to_string(), stats);
         Ok(())
     }
 
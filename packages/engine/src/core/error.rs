@@ -42,6 +42,11 @@ pub enum EngineError {
     #[error("Invalid batch data: {0} | 无效的批处理数据: {0}")]
     InvalidBatchData(String),
 
+    /// Framebuffer creation failed or is incomplete.
+    /// 帧缓冲区创建失败或不完整。
+    #[error("Framebuffer creation failed: {0} | 帧缓冲区创建失败: {0}")]
+    FramebufferCreationFailed(String),
+
     /// Buffer creation failed.
     /// 缓冲区创建失败。
     #[error("Buffer creation failed | 缓冲区创建失败")]
@@ -51,6 +56,21 @@ pub enum EngineError {
     /// WebGL操作失败。
     #[error("WebGL operation failed: {0} | WebGL操作失败: {0}")]
     WebGLError(String),
+
+    /// Malformed font definition.
+    /// 格式错误的字体定义。
+    #[error("Invalid font: {0} | 无效的字体: {0}")]
+    InvalidFont(String),
+
+    /// Invalid input action bindings.
+    /// 无效的输入动作绑定。
+    #[error("Invalid input bindings: {0} | 无效的输入绑定: {0}")]
+    InvalidInputBindings(String),
+
+    /// Invalid or corrupt input recording.
+    /// 无效或损坏的输入录制。
+    #[error("Invalid input recording: {0} | 无效的输入录制: {0}")]
+    InvalidInputRecording(String),
 }
 
 /// Result type alias for engine operations.
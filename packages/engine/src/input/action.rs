@@ -0,0 +1,516 @@
+//! Serializable action and binding definitions.
+//! 可序列化的动作与绑定定义。
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Vec2;
+use super::{InputManager, MouseButton, StandardStick};
+
+/// Physical input that can drive an action.
+/// 可驱动动作的物理输入。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum InputSource {
+    /// Keyboard key by `KeyboardEvent.code`, e.g. "KeyW".
+    /// 按`KeyboardEvent.code`指定的键盘按键，例如"KeyW"。
+    Key {
+        /// Key code.
+        /// 键码。
+        code: String,
+    },
+
+    /// Mouse button (0 = left, 1 = middle, 2 = right).
+    /// 鼠标按钮（0 = 左，1 = 中，2 = 右）。
+    MouseButton {
+        /// Button index.
+        /// 按钮索引。
+        button: i16,
+    },
+
+    /// Any touch inside a region in normalized canvas coordinates (0..1, y down).
+    /// 归一化canvas坐标（0..1，y向下）区域内的任意触摸。
+    TouchRegion {
+        /// Left edge.
+        /// 左边缘。
+        x: f32,
+        /// Top edge.
+        /// 上边缘。
+        y: f32,
+        /// Region width.
+        /// 区域宽度。
+        width: f32,
+        /// Region height.
+        /// 区域高度。
+        height: f32,
+    },
+
+    /// Gamepad button by standard-mapping index; triggers report analog values.
+    /// 按标准映射索引指定的手柄按钮；扳机报告模拟值。
+    GamepadButton {
+        /// Button index.
+        /// 按钮索引。
+        button: usize,
+    },
+
+    /// Signed gamepad axis in [-1, 1].
+    /// 有符号的手柄轴，范围[-1, 1]。
+    GamepadAxis {
+        /// Axis index.
+        /// 轴索引。
+        axis: usize,
+    },
+
+    /// Gamepad stick as a vector with y up (0 = left, 1 = right).
+    /// 手柄摇杆向量，y向上（0 = 左，1 = 右）。
+    GamepadStick {
+        /// Stick index.
+        /// 摇杆索引。
+        stick: u8,
+    },
+}
+
+impl InputSource {
+    /// Key source shorthand.
+    /// 按键输入源的简写。
+    pub fn key(code: &str) -> Self {
+        InputSource::Key { code: code.to_string() }
+    }
+
+    /// Serialize the source to JSON.
+    /// 将输入源序列化为JSON。
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| EngineError::InvalidInputBindings(e.to_string()))
+    }
+
+    /// Read the source as a vector and its actuation in [0, 1].
+    /// 以向量形式读取输入源及其驱动量，范围[0, 1]。
+    ///
+    /// Buttons, keys and touch regions read along x. Axes keep their sign in
+    /// the value but only actuate in the positive direction, so a half-axis can
+    /// drive a button action through a negative binding scale.
+    /// 按钮、按键和触摸区域沿x读取。轴的值保留符号，但只在正方向产生驱动量，
+    /// 因此可通过负绑定缩放让半轴驱动按钮动作。
+    pub fn read(&self, input: &InputManager, devices: &InputDevices, scale: f32) -> (Vec2, f32) {
+        match self {
+            InputSource::Key { code } => {
+                let value = if devices.keyboard_mouse && input.is_key_down(code) { 1.0 } else { 0.0 };
+                (Vec2::new(value * scale, 0.0), value)
+            }
+            InputSource::MouseButton { button } => {
+                let down = devices.keyboard_mouse
+                    && MouseButton::from_index(*button).is_some_and(|b| input.mouse.is_button_down(b));
+                let value = if down { 1.0 } else { 0.0 };
+                (Vec2::new(value * scale, 0.0), value)
+            }
+            InputSource::TouchRegion { x, y, width, height } => {
+                let (vw, vh) = devices.viewport;
+                let inside = devices.touch
+                    && vw > 0.0
+                    && vh > 0.0
+                    && input.touch.get_touches().any(|t| {
+                        let (u, v) = (t.position.x / vw, t.position.y / vh);
+                        u >= *x && u <= x + width && v >= *y && v <= y + height
+                    });
+                let value = if inside { 1.0 } else { 0.0 };
+                (Vec2::new(value * scale, 0.0), value)
+            }
+            InputSource::GamepadButton { button } => {
+                let value = devices.pad(input).map_or(0.0, |pad| pad.button_value(*button));
+                (Vec2::new(value * scale, 0.0), value)
+            }
+            InputSource::GamepadAxis { axis } => {
+                let value = devices.pad(input).map_or(0.0, |pad| pad.axis(*axis)) * scale;
+                (Vec2::new(value, 0.0), value.clamp(0.0, 1.0))
+            }
+            InputSource::GamepadStick { stick } => {
+                let stick = if *stick == 0 { StandardStick::Left } else { StandardStick::Right };
+                let value = devices.pad(input).map_or(Vec2::ZERO, |pad| pad.stick(stick));
+                // Standard mapping has y down | 标准映射的y向下
+                let value = Vec2::new(value.x, -value.y) * scale;
+                (value, value.dot(&value).sqrt().min(1.0))
+            }
+        }
+    }
+
+    /// First source pressed this frame, for "press a key" rebinding prompts.
+    /// 本帧第一个被按下的输入源，用于"请按键"重绑定提示。
+    ///
+    /// Axes and sticks are reported once pushed past half their range.
+    /// 轴和摇杆推过一半行程时才会报告。
+    pub fn capture(input: &InputManager, devices: &InputDevices) -> Option<Self> {
+        if devices.keyboard_mouse {
+            if let Some(code) = input.keyboard.just_pressed_keys().next() {
+                return Some(InputSource::key(code));
+            }
+            for (button, index) in [(MouseButton::Left, 0), (MouseButton::Middle, 1), (MouseButton::Right, 2)] {
+                if input.mouse.is_button_just_pressed(button) {
+                    return Some(InputSource::MouseButton { button: index });
+                }
+            }
+        }
+        let pad = devices.pad(input)?;
+        if let Some(button) = (0..pad.button_count()).find(|&b| pad.is_button_just_pressed(b)) {
+            return Some(InputSource::GamepadButton { button });
+        }
+        (0..pad.axis_count())
+            .find(|&axis| pad.axis(axis).abs() > 0.5)
+            .map(|axis| InputSource::GamepadAxis { axis })
+    }
+}
+
+/// Devices an action map reads for one player.
+/// 动作映射为某个玩家读取的设备。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputDevices {
+    /// Gamepad index, if the player uses one.
+    /// 玩家使用的手柄索引（如有）。
+    pub gamepad: Option<u32>,
+
+    /// Whether keyboard and mouse drive this player.
+    /// 键盘和鼠标是否驱动该玩家。
+    pub keyboard_mouse: bool,
+
+    /// Whether touches drive this player.
+    /// 触摸是否驱动该玩家。
+    pub touch: bool,
+
+    /// Canvas size in pixels, used to normalize touch regions.
+    /// canvas像素尺寸，用于归一化触摸区域。
+    pub viewport: (f32, f32),
+}
+
+impl InputDevices {
+    /// The player's gamepad, if connected.
+    /// 玩家的手柄（如已连接）。
+    fn pad<'a>(&self, input: &'a InputManager) -> Option<&'a super::GamepadPad> {
+        input.gamepads.pad(self.gamepad?)
+    }
+}
+
+impl Default for InputDevices {
+    fn default() -> Self {
+        Self { gamepad: Some(0), keyboard_mouse: true, touch: true, viewport: (0.0, 0.0) }
+    }
+}
+
+/// How a binding turns sources into a value.
+/// 绑定如何将输入源转换为值。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum BindingKind {
+    /// A single source.
+    /// 单个输入源。
+    Single {
+        /// Source.
+        /// 输入源。
+        source: InputSource,
+    },
+
+    /// Two sources forming an axis along x, e.g. A/D.
+    /// 组成x轴的两个输入源，例如A/D。
+    Axis {
+        /// Source for -1.
+        /// 对应-1的输入源。
+        negative: InputSource,
+        /// Source for +1.
+        /// 对应+1的输入源。
+        positive: InputSource,
+    },
+
+    /// Four sources forming a vector with y up, e.g. WASD.
+    /// 组成向量（y向上）的四个输入源，例如WASD。
+    Vector {
+        /// Source for +y.
+        /// 对应+y的输入源。
+        up: InputSource,
+        /// Source for -y.
+        /// 对应-y的输入源。
+        down: InputSource,
+        /// Source for -x.
+        /// 对应-x的输入源。
+        left: InputSource,
+        /// Source for +x.
+        /// 对应+x的输入源。
+        right: InputSource,
+    },
+}
+
+/// Binding of an action to sources.
+/// 动作到输入源的绑定。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+    /// Sources and how they combine.
+    /// 输入源及其组合方式。
+    #[serde(flatten)]
+    pub kind: BindingKind,
+
+    /// Sources that must all be held, e.g. "ControlLeft" for Ctrl+S.
+    /// 必须全部按住的输入源，例如Ctrl+S中的"ControlLeft"。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<InputSource>,
+
+    /// Multiplier applied to the value.
+    /// 应用于值的乘数。
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl Binding {
+    /// Bind a single source.
+    /// 绑定单个输入源。
+    pub fn single(source: InputSource) -> Self {
+        Self { kind: BindingKind::Single { source }, modifiers: Vec::new(), scale: 1.0 }
+    }
+
+    /// Bind two sources as an axis.
+    /// 将两个输入源绑定为轴。
+    pub fn axis(negative: InputSource, positive: InputSource) -> Self {
+        Self { kind: BindingKind::Axis { negative, positive }, modifiers: Vec::new(), scale: 1.0 }
+    }
+
+    /// Bind four sources as a vector.
+    /// 将四个输入源绑定为向量。
+    ///
+    /// # Arguments | 参数
+    /// * `up`, `down`, `left`, `right` - Directional sources | 各方向的输入源
+    pub fn vector(up: InputSource, down: InputSource, left: InputSource, right: InputSource) -> Self {
+        Self { kind: BindingKind::Vector { up, down, left, right }, modifiers: Vec::new(), scale: 1.0 }
+    }
+
+    /// Parse a binding from JSON.
+    /// 从JSON解析绑定。
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| EngineError::InvalidInputBindings(e.to_string()))
+    }
+
+    /// Add a modifier that must be held.
+    /// 添加必须按住的修饰输入源。
+    pub fn with_modifier(mut self, modifier: InputSource) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Set the value multiplier.
+    /// 设置值乘数。
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Read the binding as a vector and its actuation in [0, 1].
+    /// 以向量形式读取绑定及其驱动量，范围[0, 1]。
+    pub fn read(&self, input: &InputManager, devices: &InputDevices) -> (Vec2, f32) {
+        let held = |source: &InputSource| source.read(input, devices, 1.0).1 >= 0.5;
+        if !self.modifiers.iter().all(held) {
+            return (Vec2::ZERO, 0.0);
+        }
+
+        let read = |source: &InputSource| source.read(input, devices, 1.0).1;
+        match &self.kind {
+            BindingKind::Single { source } => source.read(input, devices, self.scale),
+            BindingKind::Axis { negative, positive } => {
+                let (n, p) = (read(negative), read(positive));
+                (Vec2::new((p - n) * self.scale, 0.0), n.max(p))
+            }
+            BindingKind::Vector { up, down, left, right } => {
+                let mut value = Vec2::new(read(right) - read(left), read(up) - read(down));
+                let length = value.dot(&value).sqrt();
+                // Keep diagonals at unit length | 保持对角线为单位长度
+                if length > 1.0 {
+                    value = value * (1.0 / length);
+                }
+                (value * self.scale, length.min(1.0))
+            }
+        }
+    }
+}
+
+/// When an actuated action fires.
+/// 被驱动的动作何时触发。
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Interaction {
+    /// Active while actuated.
+    /// 被驱动时激活。
+    #[default]
+    Press,
+
+    /// Active once held for a duration.
+    /// 按住一段时间后激活。
+    Hold {
+        /// Seconds to hold.
+        /// 需按住的秒数。
+        duration: f32,
+    },
+
+    /// Fires on release if released quickly.
+    /// 快速释放时在释放时触发。
+    Tap {
+        /// Longest press in seconds.
+        /// 最长按下秒数。
+        max_duration: f32,
+    },
+
+    /// Fires on the second of two quick presses.
+    /// 在两次快速按下的第二次触发。
+    DoubleTap {
+        /// Longest gap between presses in seconds.
+        /// 两次按下之间的最长间隔秒数。
+        max_gap: f32,
+    },
+}
+
+/// Named action with its bindings.
+/// 带绑定的命名动作。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionDef {
+    /// Action name, e.g. "jump".
+    /// 动作名称，例如"jump"。
+    pub name: String,
+
+    /// Bindings; the most actuated one wins.
+    /// 绑定；驱动量最大的绑定生效。
+    #[serde(default)]
+    pub bindings: Vec<Binding>,
+
+    /// When the action fires.
+    /// 动作何时触发。
+    #[serde(default)]
+    pub interaction: Interaction,
+
+    /// Actuation at which the action counts as pressed.
+    /// 动作视为按下的驱动量阈值。
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+}
+
+fn default_threshold() -> f32 {
+    0.5
+}
+
+impl ActionDef {
+    /// Create an action with press interaction and no bindings.
+    /// 创建按下交互且无绑定的动作。
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            bindings: Vec::new(),
+            interaction: Interaction::Press,
+            threshold: default_threshold(),
+        }
+    }
+
+    /// Add a binding.
+    /// 添加绑定。
+    pub fn with_binding(mut self, binding: Binding) -> Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// Set the interaction.
+    /// 设置交互方式。
+    pub fn with_interaction(mut self, interaction: Interaction) -> Self {
+        self.interaction = interaction;
+        self
+    }
+}
+
+/// Group of actions enabled together, e.g. "gameplay" or "menu".
+/// 一起启用的动作组，例如"gameplay"或"menu"。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionContext {
+    /// Context name.
+    /// 上下文名称。
+    pub name: String,
+
+    /// Whether new players start with this context enabled.
+    /// 新玩家是否默认启用此上下文。
+    #[serde(default = "default_enabled")]
+    pub enabled_by_default: bool,
+
+    /// Actions in this context.
+    /// 此上下文中的动作。
+    #[serde(default)]
+    pub actions: Vec<ActionDef>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ActionContext {
+    /// Create an empty context enabled by default.
+    /// 创建默认启用的空上下文。
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), enabled_by_default: true, actions: Vec::new() }
+    }
+
+    /// Add an action.
+    /// 添加动作。
+    pub fn with_action(mut self, action: ActionDef) -> Self {
+        self.actions.push(action);
+        self
+    }
+}
+
+/// Complete binding set, as shipped by the editor or saved by a player.
+/// 完整的绑定集，由编辑器提供或由玩家保存。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::input::{ActionBindings, ActionContext, ActionDef, Binding, InputSource};
+/// let bindings = ActionBindings {
+///     contexts: vec![ActionContext::new("gameplay").with_action(
+///         ActionDef::new("save").with_binding(
+///             Binding::single(InputSource::key("KeyS")).with_modifier(InputSource::key("ControlLeft")),
+///         ),
+///     )],
+/// };
+/// let json = bindings.to_json().unwrap();
+/// assert!(json.contains("\"code\":\"ControlLeft\""));
+/// assert_eq!(ActionBindings::from_json(&json).unwrap(), bindings);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ActionBindings {
+    /// Contexts in priority order.
+    /// 按优先级排序的上下文。
+    #[serde(default)]
+    pub contexts: Vec<ActionContext>,
+}
+
+impl ActionBindings {
+    /// Parse bindings from JSON.
+    /// 从JSON解析绑定。
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| EngineError::InvalidInputBindings(e.to_string()))
+    }
+
+    /// Serialize bindings to JSON.
+    /// 将绑定序列化为JSON。
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| EngineError::InvalidInputBindings(e.to_string()))
+    }
+
+    /// Find a context by name.
+    /// 按名称查找上下文。
+    pub fn context(&self, name: &str) -> Option<&ActionContext> {
+        self.contexts.iter().find(|c| c.name == name)
+    }
+
+    /// Find an action by context and name.
+    /// 按上下文和名称查找动作。
+    pub fn action_mut(&mut self, context: &str, action: &str) -> Option<&mut ActionDef> {
+        self.contexts
+            .iter_mut()
+            .find(|c| c.name == context)?
+            .actions
+            .iter_mut()
+            .find(|a| a.name == action)
+    }
+}
@@ -0,0 +1,358 @@
+//! Per-player action state evaluated from bindings.
+//! 根据绑定计算的每玩家动作状态。
+
+use std::collections::HashMap;
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Vec2;
+use super::{ActionBindings, ActionDef, Binding, InputDevices, InputManager, InputSource, Interaction};
+
+/// State of one action for one player.
+/// 某个玩家某个动作的状态。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ActionState {
+    /// Value of the most actuated binding.
+    /// 驱动量最大的绑定的值。
+    pub value: Vec2,
+
+    /// Whether the action is active (held for press and hold, one frame for taps).
+    /// 动作是否激活（按下和长按时持续，轻击时为一帧）。
+    pub active: bool,
+
+    /// Whether the action became active this update.
+    /// 动作是否在本次更新中变为激活。
+    pub triggered: bool,
+
+    /// Whether the action stopped being active this update.
+    /// 动作是否在本次更新中停止激活。
+    pub released: bool,
+
+    /// Whether the bindings are actuated past the threshold.
+    /// 绑定的驱动量是否超过阈值。
+    down: bool,
+
+    /// Seconds the bindings have been actuated.
+    /// 绑定被驱动的秒数。
+    held: f32,
+
+    /// Seconds since the last press, for double taps.
+    /// 距上次按下的秒数，用于双击。
+    since_press: f32,
+
+    /// Whether a first tap is waiting for its second.
+    /// 第一次轻击是否在等待第二次。
+    armed: bool,
+}
+
+impl ActionState {
+    /// Advance the state with this update's reading.
+    /// 用本次更新的读数推进状态。
+    fn step(&mut self, value: Vec2, actuation: f32, def: Option<&ActionDef>, dt: f32) {
+        let (interaction, threshold) = def.map_or((Interaction::Press, 0.5), |d| (d.interaction, d.threshold));
+        let was_down = self.down;
+        let was_active = self.active;
+        let pressed = actuation >= threshold && actuation > 0.0;
+
+        self.value = value;
+        self.down = pressed;
+        self.since_press += dt;
+        if pressed && !was_down {
+            self.held = 0.0;
+        } else if was_down {
+            self.held += dt;
+        }
+
+        self.active = match interaction {
+            Interaction::Press => pressed,
+            Interaction::Hold { duration } => pressed && self.held >= duration,
+            Interaction::Tap { max_duration } => !pressed && was_down && self.held <= max_duration,
+            Interaction::DoubleTap { max_gap } => {
+                let mut fired = false;
+                if pressed && !was_down {
+                    fired = self.armed && self.since_press <= max_gap;
+                    self.armed = !fired;
+                    self.since_press = 0.0;
+                }
+                fired
+            }
+        };
+        self.triggered = self.active && !was_active;
+        self.released = was_active && !self.active;
+    }
+}
+
+/// Input state of one player.
+/// 单个玩家的输入状态。
+#[derive(Debug, Clone, Default)]
+pub struct PlayerActions {
+    /// Devices driving this player.
+    /// 驱动该玩家的设备。
+    pub devices: InputDevices,
+
+    /// Enabled context names.
+    /// 已启用的上下文名称。
+    contexts: Vec<String>,
+
+    /// Action states by name.
+    /// 按名称存储的动作状态。
+    states: HashMap<String, ActionState>,
+}
+
+impl PlayerActions {
+    /// State of an action, if it has been evaluated.
+    /// 动作的状态（如已计算）。
+    pub fn action(&self, name: &str) -> Option<&ActionState> {
+        self.states.get(name)
+    }
+
+    /// Check if a context is enabled.
+    /// 检查上下文是否已启用。
+    pub fn is_context_enabled(&self, name: &str) -> bool {
+        self.contexts.iter().any(|c| c == name)
+    }
+}
+
+/// Named actions evaluated per player from serializable bindings.
+/// 根据可序列化绑定为每个玩家计算的命名动作。
+///
+/// Each player has its own devices and enabled contexts. When several enabled
+/// contexts or bindings drive the same action, the most actuated one wins.
+/// Rebinding edits `bindings_mut` or goes through `rebind`; state carries over.
+/// 每个玩家拥有各自的设备和已启用的上下文。多个已启用的上下文或绑定驱动同一动作时，
+/// 驱动量最大者生效。重绑定可编辑`bindings_mut`或调用`rebind`；状态会保留。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::input::{ActionBindings, ActionContext, ActionDef, ActionMap, Binding, InputEvent, InputManager, InputSource};
+/// let key = InputSource::key;
+/// let mut actions = ActionMap::new(ActionBindings {
+///     contexts: vec![ActionContext::new("gameplay")
+///         .with_action(ActionDef::new("move").with_binding(Binding::vector(key("KeyW"), key("KeyS"), key("KeyA"), key("KeyD"))))],
+/// });
+/// let mut input = InputManager::new();
+/// input.apply(InputEvent::KeyDown("KeyW".into()));
+/// input.apply(InputEvent::KeyDown("KeyD".into()));
+/// actions.update(&input, 1.0 / 60.0, (800.0, 600.0));
+/// let v = actions.vector(0, "move");
+/// assert!((v.x - v.y).abs() < 1e-6 && (v.x * v.x + v.y * v.y - 1.0).abs() < 1e-5);
+/// assert!(actions.is_triggered(0, "move"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ActionMap {
+    /// Binding definitions.
+    /// 绑定定义。
+    bindings: ActionBindings,
+
+    /// Players by index.
+    /// 按索引存储的玩家。
+    players: Vec<PlayerActions>,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        Self::new(ActionBindings::default())
+    }
+}
+
+impl ActionMap {
+    /// Create an action map with one player using keyboard, mouse, touch and gamepad 0.
+    /// 创建带一个玩家的动作映射，该玩家使用键盘、鼠标、触摸和0号手柄。
+    pub fn new(bindings: ActionBindings) -> Self {
+        let mut map = Self { bindings, players: Vec::new() };
+        map.add_player(InputDevices::default());
+        map
+    }
+
+    /// Binding definitions.
+    /// 绑定定义。
+    #[inline]
+    pub fn bindings(&self) -> &ActionBindings {
+        &self.bindings
+    }
+
+    /// Mutable binding definitions.
+    /// 可变的绑定定义。
+    #[inline]
+    pub fn bindings_mut(&mut self) -> &mut ActionBindings {
+        &mut self.bindings
+    }
+
+    /// Replace the bindings, resetting every player's contexts and states.
+    /// 替换绑定，并重置每个玩家的上下文和状态。
+    pub fn set_bindings(&mut self, bindings: ActionBindings) {
+        self.bindings = bindings;
+        let contexts = self.default_contexts();
+        for player in &mut self.players {
+            player.contexts = contexts.clone();
+            player.states.clear();
+        }
+    }
+
+    /// Replace the bindings from JSON.
+    /// 从JSON替换绑定。
+    pub fn load_json(&mut self, json: &str) -> Result<()> {
+        self.set_bindings(ActionBindings::from_json(json)?);
+        Ok(())
+    }
+
+    /// Replace one binding of an action.
+    /// 替换动作的一个绑定。
+    ///
+    /// # Arguments | 参数
+    /// * `context` - Context name | 上下文名称
+    /// * `action` - Action name | 动作名称
+    /// * `index` - Binding index; the binding is appended when equal to the count | 绑定索引；等于数量时追加
+    /// * `binding` - New binding | 新绑定
+    pub fn rebind(&mut self, context: &str, action: &str, index: usize, binding: Binding) -> Result<()> {
+        let def = self
+            .bindings
+            .action_mut(context, action)
+            .ok_or_else(|| EngineError::InvalidInputBindings(format!("Unknown action {}/{}", context, action)))?;
+        match index.cmp(&def.bindings.len()) {
+            std::cmp::Ordering::Less => def.bindings[index] = binding,
+            std::cmp::Ordering::Equal => def.bindings.push(binding),
+            std::cmp::Ordering::Greater => {
+                return Err(EngineError::InvalidInputBindings(format!(
+                    "Binding index {} out of range for {}/{}",
+                    index, context, action
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a player and return its index.
+    /// 添加玩家并返回其索引。
+    pub fn add_player(&mut self, devices: InputDevices) -> usize {
+        self.players.push(PlayerActions {
+            devices,
+            contexts: self.default_contexts(),
+            states: HashMap::new(),
+        });
+        self.players.len() - 1
+    }
+
+    /// Player by index.
+    /// 按索引获取玩家。
+    #[inline]
+    pub fn player(&self, player: usize) -> Option<&PlayerActions> {
+        self.players.get(player)
+    }
+
+    /// Mutable player by index.
+    /// 按索引获取可变玩家。
+    #[inline]
+    pub fn player_mut(&mut self, player: usize) -> Option<&mut PlayerActions> {
+        self.players.get_mut(player)
+    }
+
+    /// Number of players.
+    /// 玩家数量。
+    #[inline]
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Enable or disable a context for a player.
+    /// 为玩家启用或禁用上下文。
+    pub fn set_context_enabled(&mut self, player: usize, context: &str, enabled: bool) {
+        let Some(player) = self.players.get_mut(player) else {
+            return;
+        };
+        player.contexts.retain(|c| c != context);
+        if enabled {
+            player.contexts.push(context.to_string());
+        }
+    }
+
+    /// First source the player pressed this frame, for rebinding prompts.
+    /// 玩家本帧第一个按下的输入源，用于重绑定提示。
+    pub fn capture_source(&self, player: usize, input: &InputManager) -> Option<InputSource> {
+        InputSource::capture(input, &self.players.get(player)?.devices)
+    }
+
+    /// Evaluate all actions for all players.
+    /// 为所有玩家计算所有动作。
+    ///
+    /// # Arguments | 参数
+    /// * `input` - Input state after its update | 更新后的输入状态
+    /// * `dt` - Seconds since the last call | 距上次调用的秒数
+    /// * `viewport` - Canvas size in pixels | canvas像素尺寸
+    pub fn update(&mut self, input: &InputManager, dt: f32, viewport: (f32, f32)) {
+        for player in &mut self.players {
+            player.devices.viewport = viewport;
+
+            // Strongest reading per action across enabled contexts | 每个动作在已启用上下文中的最强读数
+            let mut readings: HashMap<&str, (Vec2, f32, &ActionDef)> = HashMap::new();
+            let contexts = self.bindings.contexts.iter().filter(|c| player.is_context_enabled(&c.name));
+            for def in contexts.flat_map(|c| &c.actions) {
+                let (value, actuation) = def
+                    .bindings
+                    .iter()
+                    .map(|b| b.read(input, &player.devices))
+                    .fold((Vec2::ZERO, 0.0), |best, r| if r.1 > best.1 { r } else { best });
+                let entry = readings.entry(def.name.as_str()).or_insert((value, actuation, def));
+                if actuation > entry.1 {
+                    *entry = (value, actuation, def);
+                }
+            }
+
+            for (name, (value, actuation, def)) in &readings {
+                player
+                    .states
+                    .entry(name.to_string())
+                    .or_default()
+                    .step(*value, *actuation, Some(def), dt);
+            }
+            // Actions in disabled contexts release | 已禁用上下文中的动作被释放
+            for (name, state) in &mut player.states {
+                if !readings.contains_key(name.as_str()) {
+                    state.step(Vec2::ZERO, 0.0, None, dt);
+                }
+            }
+        }
+    }
+
+    /// Check if an action is active.
+    /// 检查动作是否激活。
+    pub fn is_active(&self, player: usize, action: &str) -> bool {
+        self.state(player, action).is_some_and(|s| s.active)
+    }
+
+    /// Check if an action became active this update.
+    /// 检查动作是否在本次更新中变为激活。
+    pub fn is_triggered(&self, player: usize, action: &str) -> bool {
+        self.state(player, action).is_some_and(|s| s.triggered)
+    }
+
+    /// Check if an action stopped being active this update.
+    /// 检查动作是否在本次更新中停止激活。
+    pub fn is_released(&self, player: usize, action: &str) -> bool {
+        self.state(player, action).is_some_and(|s| s.released)
+    }
+
+    /// Axis value of an action (x of its vector).
+    /// 动作的轴值（向量的x分量）。
+    pub fn value(&self, player: usize, action: &str) -> f32 {
+        self.vector(player, action).x
+    }
+
+    /// Vector value of an action.
+    /// 动作的向量值。
+    pub fn vector(&self, player: usize, action: &str) -> Vec2 {
+        self.state(player, action).map_or(Vec2::ZERO, |s| s.value)
+    }
+
+    fn state(&self, player: usize, action: &str) -> Option<&ActionState> {
+        self.players.get(player)?.action(action)
+    }
+
+    fn default_contexts(&self) -> Vec<String> {
+        self.bindings
+            .contexts
+            .iter()
+            .filter(|c| c.enabled_by_default)
+            .map(|c| c.name.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,194 @@
+//! DOM event listeners that feed an input queue.
+//! 向输入队列提供数据的DOM事件监听器。
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    AddEventListenerOptions, Event, HtmlCanvasElement, KeyboardEvent, MouseEvent, PointerEvent,
+    TouchEvent, WheelEvent,
+};
+
+use crate::core::error::{EngineError, Result};
+use super::event::{InputEvent, InputQueue};
+
+/// Wheel distance of one line in pixels.
+/// 一行的滚轮距离（像素）。
+const WHEEL_LINE_HEIGHT: f32 = 16.0;
+
+/// DOM event callback.
+/// DOM事件回调。
+type Listener = Closure<dyn FnMut(Event)>;
+
+/// Keyboard, mouse, wheel, touch and pointer listeners on a canvas.
+/// canvas上的键盘、鼠标、滚轮、触摸和指针监听器。
+///
+/// Positions are converted to canvas pixels, matching `Camera2D::screen_to_world`.
+/// The canvas is made focusable and takes focus on pointer down so it receives
+/// key events. Touch and wheel default actions (scrolling, emulated mouse
+/// events) are prevented. Listeners are removed on drop.
+/// 位置会转换为canvas像素，与`Camera2D::screen_to_world`一致。canvas会被设为可聚焦，
+/// 并在指针按下时获得焦点以接收键盘事件。触摸和滚轮的默认行为（滚动、模拟鼠标事件）
+/// 会被阻止。监听器在销毁时移除。
+pub struct DomInputListeners {
+    /// Element the listeners are attached to.
+    /// 监听器所附加的元素。
+    canvas: HtmlCanvasElement,
+
+    /// Event names with their callbacks.
+    /// 事件名称及其回调。
+    listeners: Vec<(&'static str, Listener)>,
+}
+
+impl DomInputListeners {
+    /// Attach listeners to a canvas.
+    /// 将监听器附加到canvas。
+    ///
+    /// # Arguments | 参数
+    /// * `canvas` - Canvas receiving input | 接收输入的canvas
+    /// * `queue` - Queue the events are pushed to | 事件推入的队列
+    pub fn attach(canvas: &HtmlCanvasElement, queue: &InputQueue) -> Result<Self> {
+        if !canvas.has_attribute("tabindex") {
+            canvas
+                .set_attribute("tabindex", "0")
+                .map_err(|e| EngineError::WebGLError(format!("{:?}", e)))?;
+        }
+
+        let mut dom = Self { canvas: canvas.clone(), listeners: Vec::new() };
+
+        // Keyboard | 键盘
+        dom.listen(queue, "keydown", |_, event, push| {
+            if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+                if !event.repeat() {
+                    push(InputEvent::KeyDown(event.code()));
+                }
+            }
+        })?;
+        dom.listen(queue, "keyup", |_, event, push| {
+            if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+                push(InputEvent::KeyUp(event.code()));
+            }
+        })?;
+        dom.listen(queue, "blur", |_, _, push| push(InputEvent::Blur))?;
+
+        // Mouse | 鼠标
+        dom.listen(queue, "mousemove", |canvas, event, push| {
+            if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                let (x, y) = canvas_position(canvas, event.client_x(), event.client_y());
+                push(InputEvent::MouseMove { x, y });
+            }
+        })?;
+        dom.listen(queue, "mousedown", |canvas, event, push| {
+            if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                let (x, y) = canvas_position(canvas, event.client_x(), event.client_y());
+                push(InputEvent::MouseMove { x, y });
+                push(InputEvent::MouseDown(event.button()));
+            }
+        })?;
+        dom.listen(queue, "mouseup", |canvas, event, push| {
+            if let Some(event) = event.dyn_ref::<MouseEvent>() {
+                let (x, y) = canvas_position(canvas, event.client_x(), event.client_y());
+                push(InputEvent::MouseMove { x, y });
+                push(InputEvent::MouseUp(event.button()));
+            }
+        })?;
+        dom.listen(queue, "wheel", |canvas, event, push| {
+            if let Some(event) = event.dyn_ref::<WheelEvent>() {
+                event.prevent_default();
+                let scale = match event.delta_mode() {
+                    WheelEvent::DOM_DELTA_LINE => WHEEL_LINE_HEIGHT,
+                    WheelEvent::DOM_DELTA_PAGE => canvas.height() as f32,
+                    _ => 1.0,
+                };
+                push(InputEvent::Wheel(event.delta_y() as f32 * scale));
+            }
+        })?;
+
+        // Touch | 触摸
+        for name in ["touchstart", "touchmove", "touchend", "touchcancel"] {
+            dom.listen(queue, name, move |canvas, event, push| {
+                let Some(event) = event.dyn_ref::<TouchEvent>() else {
+                    return;
+                };
+                // Stops scrolling and emulated mouse events | 阻止滚动和模拟鼠标事件
+                event.prevent_default();
+                let touches = event.changed_touches();
+                for touch in (0..touches.length()).filter_map(|i| touches.get(i)) {
+                    let id = touch.identifier();
+                    let (x, y) = canvas_position(canvas, touch.client_x(), touch.client_y());
+                    push(match name {
+                        "touchstart" => InputEvent::TouchStart { id, x, y },
+                        "touchmove" => InputEvent::TouchMove { id, x, y },
+                        _ => InputEvent::TouchEnd(id),
+                    });
+                }
+            })?;
+        }
+
+        // Pointer: focus and capture so drags continue outside the canvas | 指针：聚焦并捕获，使拖动在canvas外继续
+        dom.listen(queue, "pointerdown", |canvas, event, _| {
+            if let Some(event) = event.dyn_ref::<PointerEvent>() {
+                let _ = canvas.focus();
+                let _ = canvas.set_pointer_capture(event.pointer_id());
+            }
+        })?;
+        for name in ["pointerup", "pointercancel"] {
+            dom.listen(queue, name, |canvas, event, _| {
+                if let Some(event) = event.dyn_ref::<PointerEvent>() {
+                    let _ = canvas.release_pointer_capture(event.pointer_id());
+                }
+            })?;
+        }
+
+        Ok(dom)
+    }
+
+    /// Register a listener whose handler receives the canvas, the event and a push function.
+    /// 注册监听器，其处理函数接收canvas、事件和推送函数。
+    fn listen(
+        &mut self,
+        queue: &InputQueue,
+        name: &'static str,
+        handler: impl Fn(&HtmlCanvasElement, &Event, &dyn Fn(InputEvent)) + 'static,
+    ) -> Result<()> {
+        let canvas = self.canvas.clone();
+        let queue = queue.clone();
+        let closure = Listener::new(move |event: Event| {
+            handler(&canvas, &event, &|input| queue.push(input));
+        });
+
+        // Non-passive so touch and wheel handlers can prevent scrolling | 非被动，以便触摸和滚轮处理函数阻止滚动
+        let options = AddEventListenerOptions::new();
+        options.set_passive(false);
+        self.canvas
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                name,
+                closure.as_ref().unchecked_ref(),
+                &options,
+            )
+            .map_err(|e| EngineError::WebGLError(format!("Failed to add {} listener: {:?}", name, e)))?;
+        self.listeners.push((name, closure));
+        Ok(())
+    }
+}
+
+impl Drop for DomInputListeners {
+    fn drop(&mut self) {
+        for (name, closure) in &self.listeners {
+            let _ = self
+                .canvas
+                .remove_event_listener_with_callback(name, closure.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// Convert client coordinates to canvas pixels.
+/// 将客户端坐标转换为canvas像素。
+fn canvas_position(canvas: &HtmlCanvasElement, client_x: i32, client_y: i32) -> (f32, f32) {
+    let rect = canvas.get_bounding_client_rect();
+    let scale_x = if rect.width() > 0.0 { canvas.width() as f64 / rect.width() } else { 1.0 };
+    let scale_y = if rect.height() > 0.0 { canvas.height() as f64 / rect.height() } else { 1.0 };
+    (
+        ((client_x as f64 - rect.left()) * scale_x) as f32,
+        ((client_y as f64 - rect.top()) * scale_y) as f32,
+    )
+}
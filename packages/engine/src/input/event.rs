@@ -0,0 +1,91 @@
+//! Raw input events and the queue that feeds them to the input manager.
+//! 原始输入事件及将其传递给输入管理器的队列。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+/// A raw input event in canvas pixel coordinates (Y down).
+/// canvas像素坐标（Y向下）下的原始输入事件。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputEvent {
+    /// Key pressed, by `KeyboardEvent.code` (e.g. "KeyW").
+    /// 按键按下，使用`KeyboardEvent.code`（如"KeyW"）。
+    KeyDown(String),
+    /// Key released.
+    /// 按键释放。
+    KeyUp(String),
+    /// Mouse moved.
+    /// 鼠标移动。
+    MouseMove {
+        /// X position | X位置
+        x: f32,
+        /// Y position | Y位置
+        y: f32,
+    },
+    /// Mouse button pressed (0 = left, 1 = middle, 2 = right).
+    /// 鼠标按钮按下（0 = 左，1 = 中，2 = 右）。
+    MouseDown(i16),
+    /// Mouse button released.
+    /// 鼠标按钮释放。
+    MouseUp(i16),
+    /// Wheel scrolled by a pixel amount (positive = down).
+    /// 滚轮滚动的像素量（正值 = 向下）。
+    Wheel(f32),
+    /// Touch started.
+    /// 触摸开始。
+    TouchStart {
+        /// Touch identifier | 触摸标识符
+        id: i32,
+        /// X position | X位置
+        x: f32,
+        /// Y position | Y位置
+        y: f32,
+    },
+    /// Touch moved.
+    /// 触摸移动。
+    TouchMove {
+        /// Touch identifier | 触摸标识符
+        id: i32,
+        /// X position | X位置
+        x: f32,
+        /// Y position | Y位置
+        y: f32,
+    },
+    /// Touch ended or was cancelled.
+    /// 触摸结束或被取消。
+    TouchEnd(i32),
+    /// Input focus lost; held keys and buttons are released.
+    /// 失去输入焦点；释放所有按住的键和按钮。
+    Blur,
+}
+
+/// Shared queue of input events waiting for the next input update.
+/// 等待下一次输入更新的共享输入事件队列。
+///
+/// Clones share the same queue, so DOM listeners and injected events feed
+/// the same input manager.
+/// 克隆共享同一个队列，因此DOM监听器和注入的事件输入到同一个输入管理器。
+#[derive(Debug, Clone, Default)]
+pub struct InputQueue(Rc<RefCell<Vec<InputEvent>>>);
+
+impl InputQueue {
+    /// Create an empty queue.
+    /// 创建空队列。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event.
+    /// 追加事件。
+    pub fn push(&self, event: InputEvent) {
+        self.0.borrow_mut().push(event);
+    }
+
+    /// Take all queued events in arrival order.
+    /// 按到达顺序取出所有排队的事件。
+    pub fn take(&self) -> Vec<InputEvent> {
+        std::mem::take(&mut *self.0.borrow_mut())
+    }
+}
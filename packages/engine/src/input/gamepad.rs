@@ -0,0 +1,446 @@
+//! Gamepad input handling.
+//! 游戏手柄输入处理。
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::math::Vec2;
+
+/// Buttons of the standard gamepad mapping.
+/// 标准手柄映射的按钮。
+///
+/// Values are button indices of `Gamepad.buttons` when `mapping` is "standard".
+/// 当`mapping`为"standard"时，值为`Gamepad.buttons`中的按钮索引。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardButton {
+    /// Bottom face button (A / Cross).
+    /// 下方功能键（A / 叉）。
+    South = 0,
+    /// Right face button (B / Circle).
+    /// 右方功能键（B / 圆）。
+    East = 1,
+    /// Left face button (X / Square).
+    /// 左方功能键（X / 方）。
+    West = 2,
+    /// Top face button (Y / Triangle).
+    /// 上方功能键（Y / 三角）。
+    North = 3,
+    /// Left shoulder button.
+    /// 左肩键。
+    LeftBumper = 4,
+    /// Right shoulder button.
+    /// 右肩键。
+    RightBumper = 5,
+    /// Left trigger (analog value in `button_value`).
+    /// 左扳机（模拟值见`button_value`）。
+    LeftTrigger = 6,
+    /// Right trigger (analog value in `button_value`).
+    /// 右扳机（模拟值见`button_value`）。
+    RightTrigger = 7,
+    /// Select / Back / Share.
+    /// 选择 / 返回 / 分享。
+    Select = 8,
+    /// Start / Options.
+    /// 开始 / 选项。
+    Start = 9,
+    /// Left stick press.
+    /// 左摇杆按下。
+    LeftStick = 10,
+    /// Right stick press.
+    /// 右摇杆按下。
+    RightStick = 11,
+    /// D-pad up.
+    /// 方向键上。
+    DPadUp = 12,
+    /// D-pad down.
+    /// 方向键下。
+    DPadDown = 13,
+    /// D-pad left.
+    /// 方向键左。
+    DPadLeft = 14,
+    /// D-pad right.
+    /// 方向键右。
+    DPadRight = 15,
+    /// Home / Guide.
+    /// 主页 / 向导。
+    Home = 16,
+}
+
+/// Analog sticks of the standard gamepad mapping.
+/// 标准手柄映射的模拟摇杆。
+///
+/// Each stick uses two consecutive axes (X then Y, Y positive down).
+/// 每个摇杆使用两个连续的轴（先X后Y，Y向下为正）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardStick {
+    /// Axes 0 and 1.
+    /// 轴0和1。
+    Left = 0,
+    /// Axes 2 and 3.
+    /// 轴2和3。
+    Right = 1,
+}
+
+/// One connected gamepad.
+/// 一个已连接的游戏手柄。
+#[derive(Debug, Clone, Default)]
+pub struct GamepadPad {
+    /// Device description reported by the browser.
+    /// 浏览器报告的设备描述。
+    pub id: String,
+
+    /// Whether the browser maps the device to the standard layout.
+    /// 浏览器是否将设备映射为标准布局。
+    pub standard: bool,
+
+    /// Button values in [0, 1].
+    /// 按钮值，范围[0, 1]。
+    values: Vec<f32>,
+
+    /// Button pressed states.
+    /// 按钮按下状态。
+    pressed: Vec<bool>,
+
+    /// Pressed states of the previous poll.
+    /// 上一次轮询的按下状态。
+    prev_pressed: Vec<bool>,
+
+    /// Axis values after the dead zone.
+    /// 应用死区后的轴值。
+    axes: Vec<f32>,
+}
+
+impl GamepadPad {
+    /// Check if a button is held.
+    /// 检查按钮是否按住。
+    #[inline]
+    pub fn is_button_down(&self, button: usize) -> bool {
+        self.pressed.get(button).copied().unwrap_or(false)
+    }
+
+    /// Check if a button was pressed this frame.
+    /// 检查按钮是否在本帧刚被按下。
+    #[inline]
+    pub fn is_button_just_pressed(&self, button: usize) -> bool {
+        self.is_button_down(button) && !self.prev_pressed.get(button).copied().unwrap_or(false)
+    }
+
+    /// Check if a button was released this frame.
+    /// 检查按钮是否在本帧刚被释放。
+    #[inline]
+    pub fn is_button_just_released(&self, button: usize) -> bool {
+        !self.is_button_down(button) && self.prev_pressed.get(button).copied().unwrap_or(false)
+    }
+
+    /// Analog button value in [0, 1].
+    /// 模拟按钮值，范围[0, 1]。
+    #[inline]
+    pub fn button_value(&self, button: usize) -> f32 {
+        self.values.get(button).copied().unwrap_or(0.0)
+    }
+
+    /// Axis value in [-1, 1] after the dead zone.
+    /// 应用死区后的轴值，范围[-1, 1]。
+    #[inline]
+    pub fn axis(&self, axis: usize) -> f32 {
+        self.axes.get(axis).copied().unwrap_or(0.0)
+    }
+
+    /// Stick position after the dead zone (standard mapping).
+    /// 应用死区后的摇杆位置（标准映射）。
+    #[inline]
+    pub fn stick(&self, stick: StandardStick) -> Vec2 {
+        let x = stick as usize * 2;
+        Vec2::new(self.axis(x), self.axis(x + 1))
+    }
+
+    /// Number of buttons.
+    /// 按钮数量。
+    #[inline]
+    pub fn button_count(&self) -> usize {
+        self.pressed.len()
+    }
+
+    /// Number of axes.
+    /// 轴数量。
+    #[inline]
+    pub fn axis_count(&self) -> usize {
+        self.axes.len()
+    }
+}
+
+/// Gamepad input state.
+/// 游戏手柄输入状态。
+///
+/// Polled from `navigator.getGamepads()`; hosts without the Gamepad API can
+/// feed pads through `set_pad`. Axes are grouped into sticks of two and
+/// filtered by a radial dead zone: inside it the stick reads zero, outside
+/// the remaining range is rescaled to [0, 1] so movement starts smoothly.
+/// 从`navigator.getGamepads()`轮询；没有Gamepad API的宿主可以通过`set_pad`提供手柄数据。
+/// 轴每两个组成一个摇杆并经过径向死区过滤：死区内读数为零，死区外的剩余范围重新映射到[0, 1]，
+/// 使移动平滑开始。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::input::{GamepadState, StandardButton, StandardStick};
+/// let mut gamepads = GamepadState::new();
+/// gamepads.set_dead_zone(0.2);
+/// gamepads.begin_frame();
+/// gamepads.set_pad(0, "pad", true, &[(true, 1.0)], &[0.1, 0.1, 0.6, 0.0]);
+/// let pad = gamepads.pad(0).unwrap();
+/// assert!(pad.is_button_just_pressed(StandardButton::South as usize));
+/// assert_eq!(pad.stick(StandardStick::Left).x, 0.0);
+/// assert!((pad.stick(StandardStick::Right).x - 0.5).abs() < 1e-6);
+/// assert_eq!(gamepads.just_connected(), &[0]);
+///
+/// // Without the Gamepad API rumble does nothing | 没有Gamepad API时震动不执行任何操作
+/// assert!(!gamepads.rumble(0, 100.0, 1.0, 1.0));
+/// ```
+#[derive(Debug)]
+pub struct GamepadState {
+    /// Pads by gamepad index.
+    /// 按手柄索引存储的手柄。
+    pads: Vec<Option<GamepadPad>>,
+
+    /// Radial dead zone in [0, 1).
+    /// 径向死区，范围[0, 1)。
+    dead_zone: f32,
+
+    /// Pads seen this frame.
+    /// 本帧出现的手柄。
+    seen: Vec<bool>,
+
+    /// Pads connected this frame.
+    /// 本帧连接的手柄。
+    just_connected: Vec<u32>,
+
+    /// Pads disconnected this frame.
+    /// 本帧断开的手柄。
+    just_disconnected: Vec<u32>,
+}
+
+impl Default for GamepadState {
+    fn default() -> Self {
+        Self {
+            pads: Vec::new(),
+            dead_zone: 0.15,
+            seen: Vec::new(),
+            just_connected: Vec::new(),
+            just_disconnected: Vec::new(),
+        }
+    }
+}
+
+impl GamepadState {
+    /// Create new gamepad state.
+    /// 创建新的手柄状态。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the radial dead zone.
+    /// 获取径向死区。
+    #[inline]
+    pub fn dead_zone(&self) -> f32 {
+        self.dead_zone
+    }
+
+    /// Set the radial dead zone (0 disables it).
+    /// 设置径向死区（0表示禁用）。
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        self.dead_zone = dead_zone.clamp(0.0, 0.99);
+    }
+
+    /// Get a connected pad.
+    /// 获取已连接的手柄。
+    #[inline]
+    pub fn pad(&self, index: u32) -> Option<&GamepadPad> {
+        self.pads.get(index as usize).and_then(Option::as_ref)
+    }
+
+    /// Indices of connected pads.
+    /// 已连接手柄的索引。
+    pub fn connected(&self) -> impl Iterator<Item = u32> + '_ {
+        self.pads
+            .iter()
+            .enumerate()
+            .filter(|(_, pad)| pad.is_some())
+            .map(|(i, _)| i as u32)
+    }
+
+    /// Pads connected this frame.
+    /// 本帧连接的手柄。
+    #[inline]
+    pub fn just_connected(&self) -> &[u32] {
+        &self.just_connected
+    }
+
+    /// Pads disconnected this frame.
+    /// 本帧断开的手柄。
+    #[inline]
+    pub fn just_disconnected(&self) -> &[u32] {
+        &self.just_disconnected
+    }
+
+    /// Start a poll: pads not set before `end_frame` count as disconnected.
+    /// 开始轮询：在`end_frame`之前未设置的手柄视为已断开。
+    pub fn begin_frame(&mut self) {
+        self.just_connected.clear();
+        self.just_disconnected.clear();
+        self.seen.clear();
+        self.seen.resize(self.pads.len(), false);
+    }
+
+    /// Update a pad from raw data.
+    /// 从原始数据更新手柄。
+    ///
+    /// # Arguments | 参数
+    /// * `index` - Gamepad index | 手柄索引
+    /// * `id` - Device description | 设备描述
+    /// * `standard` - Standard mapping | 是否为标准映射
+    /// * `buttons` - (pressed, value) per button | 每个按钮的（按下，值）
+    /// * `axes` - Raw axis values | 原始轴值
+    pub fn set_pad(&mut self, index: u32, id: &str, standard: bool, buttons: &[(bool, f32)], axes: &[f32]) {
+        let i = index as usize;
+        if self.pads.len() <= i {
+            self.pads.resize_with(i + 1, || None);
+        }
+        if self.seen.len() <= i {
+            self.seen.resize(i + 1, false);
+        }
+        self.seen[i] = true;
+
+        let pad = self.pads[i].get_or_insert_with(|| {
+            self.just_connected.push(index);
+            GamepadPad::default()
+        });
+        if pad.id != id {
+            pad.id = id.to_string();
+        }
+        pad.standard = standard;
+        std::mem::swap(&mut pad.prev_pressed, &mut pad.pressed);
+        pad.pressed.clear();
+        pad.pressed.extend(buttons.iter().map(|&(pressed, _)| pressed));
+        pad.values.clear();
+        pad.values.extend(buttons.iter().map(|&(_, value)| value.clamp(0.0, 1.0)));
+
+        pad.axes.clear();
+        for stick in axes.chunks(2) {
+            let x = stick[0];
+            let y = stick.get(1).copied().unwrap_or(0.0);
+            let (x, y) = apply_dead_zone(x, y, self.dead_zone);
+            pad.axes.push(x);
+            if stick.len() > 1 {
+                pad.axes.push(y);
+            }
+        }
+    }
+
+    /// Finish a poll, disconnecting pads that were not set.
+    /// 结束轮询，断开未设置的手柄。
+    pub fn end_frame(&mut self) {
+        for (i, pad) in self.pads.iter_mut().enumerate() {
+            if pad.is_some() && !self.seen.get(i).copied().unwrap_or(false) {
+                *pad = None;
+                self.just_disconnected.push(i as u32);
+            }
+        }
+    }
+
+    /// Poll `navigator.getGamepads()`.
+    /// 轮询`navigator.getGamepads()`。
+    ///
+    /// Does nothing when the Gamepad API is unavailable, including outside wasm.
+    /// Gamepad API不可用时（包括非wasm环境）不执行任何操作。
+    pub fn poll(&mut self) {
+        if cfg!(not(target_arch = "wasm32")) {
+            return;
+        }
+        let Some(gamepads) = web_sys::window().and_then(|w| w.navigator().get_gamepads().ok()) else {
+            return;
+        };
+
+        self.begin_frame();
+        let mut buttons = Vec::new();
+        let mut axes = Vec::new();
+        for gamepad in gamepads.iter().filter_map(|g| g.dyn_into::<web_sys::Gamepad>().ok()) {
+            if !gamepad.connected() {
+                continue;
+            }
+            buttons.clear();
+            buttons.extend(gamepad.buttons().iter().filter_map(|b| {
+                b.dyn_into::<web_sys::GamepadButton>()
+                    .ok()
+                    .map(|b| (b.pressed(), b.value() as f32))
+            }));
+            axes.clear();
+            axes.extend(gamepad.axes().iter().map(|a| a.as_f64().unwrap_or(0.0) as f32));
+            let standard = gamepad.mapping() == web_sys::GamepadMappingType::Standard;
+            self.set_pad(gamepad.index(), &gamepad.id(), standard, &buttons, &axes);
+        }
+        self.end_frame();
+    }
+
+    /// Play a rumble effect where the browser supports it.
+    /// 在浏览器支持时播放震动效果。
+    ///
+    /// Does nothing when the Gamepad API is unavailable, including outside wasm.
+    /// Gamepad API不可用时（包括非wasm环境）不执行任何操作。
+    ///
+    /// # Arguments | 参数
+    /// * `index` - Gamepad index | 手柄索引
+    /// * `duration_ms` - Duration in milliseconds | 持续时间（毫秒）
+    /// * `strong` - Low-frequency motor magnitude in [0, 1] | 低频马达强度
+    /// * `weak` - High-frequency motor magnitude in [0, 1] | 高频马达强度
+    ///
+    /// # Returns | 返回
+    /// Whether a rumble effect was started | 是否开始了震动效果
+    pub fn rumble(&self, index: u32, duration_ms: f32, strong: f32, weak: f32) -> bool {
+        if cfg!(not(target_arch = "wasm32")) {
+            return false;
+        }
+        let Some(gamepad) = web_sys::window()
+            .and_then(|w| w.navigator().get_gamepads().ok())
+            .and_then(|pads| pads.get(index).dyn_into::<web_sys::Gamepad>().ok())
+        else {
+            return false;
+        };
+
+        // `vibrationActuator.playEffect` is not in every browser | 并非所有浏览器都支持`vibrationActuator.playEffect`
+        let actuator = js_sys::Reflect::get(&gamepad, &JsValue::from_str("vibrationActuator"))
+            .ok()
+            .filter(|a| a.is_object());
+        let Some(actuator) = actuator else {
+            return false;
+        };
+        let Some(play_effect) = js_sys::Reflect::get(&actuator, &JsValue::from_str("playEffect"))
+            .ok()
+            .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+        else {
+            return false;
+        };
+
+        let params = js_sys::Object::new();
+        for (key, value) in [
+            ("duration", duration_ms.max(0.0)),
+            ("strongMagnitude", strong.clamp(0.0, 1.0)),
+            ("weakMagnitude", weak.clamp(0.0, 1.0)),
+        ] {
+            let _ = js_sys::Reflect::set(&params, &JsValue::from_str(key), &JsValue::from_f64(value as f64));
+        }
+        play_effect
+            .call2(&actuator, &JsValue::from_str("dual-rumble"), &params)
+            .is_ok()
+    }
+}
+
+/// Radial dead zone with the outer range rescaled to [0, 1].
+/// 径向死区，外部范围重新映射到[0, 1]。
+fn apply_dead_zone(x: f32, y: f32, dead_zone: f32) -> (f32, f32) {
+    let magnitude = (x * x + y * y).sqrt();
+    if magnitude <= dead_zone || magnitude == 0.0 {
+        return (0.0, 0.0);
+    }
+    let scaled = ((magnitude - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    (x / magnitude * scaled, y / magnitude * scaled)
+}
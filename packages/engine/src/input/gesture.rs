@@ -0,0 +1,481 @@
+//! Touch gesture recognition.
+//! 触摸手势识别。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::math::Vec2;
+use crate::renderer::Camera2D;
+use super::{TouchPoint, TouchState};
+
+/// Gesture thresholds. Distances are in canvas pixels, times in seconds.
+/// 手势阈值。距离单位为canvas像素，时间单位为秒。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GestureConfig {
+    /// Longest press that counts as a tap.
+    /// 视为轻击的最长按压时间。
+    pub tap_max_duration: f32,
+
+    /// Farthest a tap may move.
+    /// 轻击允许的最大移动距离。
+    pub tap_max_distance: f32,
+
+    /// Longest gap between the taps of a double tap.
+    /// 双击两次轻击之间的最长间隔。
+    pub double_tap_max_gap: f32,
+
+    /// Farthest apart the taps of a double tap may be.
+    /// 双击两次轻击之间的最大距离。
+    pub double_tap_max_distance: f32,
+
+    /// Time before a still touch becomes a long press.
+    /// 静止触摸成为长按所需的时间。
+    pub long_press_duration: f32,
+
+    /// Shortest swipe.
+    /// 最短滑动距离。
+    pub swipe_min_distance: f32,
+
+    /// Slowest swipe in pixels per second.
+    /// 最慢滑动速度（像素/秒）。
+    pub swipe_min_velocity: f32,
+
+    /// Change in finger spread before a pinch starts.
+    /// 捏合开始前手指间距的变化量。
+    pub pinch_threshold: f32,
+
+    /// Rotation in radians before a rotate starts.
+    /// 旋转开始前的旋转弧度。
+    pub rotate_threshold: f32,
+
+    /// Centroid movement before a two-finger pan starts.
+    /// 双指平移开始前中心点的移动距离。
+    pub pan_threshold: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: 0.25,
+            tap_max_distance: 10.0,
+            double_tap_max_gap: 0.3,
+            double_tap_max_distance: 30.0,
+            long_press_duration: 0.5,
+            swipe_min_distance: 50.0,
+            swipe_min_velocity: 300.0,
+            pinch_threshold: 10.0,
+            rotate_threshold: 0.1,
+            pan_threshold: 10.0,
+        }
+    }
+}
+
+/// Dominant direction of a swipe in screen space.
+/// 屏幕空间中滑动的主方向。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    /// Toward negative x.
+    /// 朝x负方向。
+    Left = 0,
+    /// Toward positive x.
+    /// 朝x正方向。
+    Right = 1,
+    /// Toward the top of the screen.
+    /// 朝屏幕顶部。
+    Up = 2,
+    /// Toward the bottom of the screen.
+    /// 朝屏幕底部。
+    Down = 3,
+}
+
+impl SwipeDirection {
+    /// Dominant direction of a screen-space vector (y down).
+    /// 屏幕空间向量（y向下）的主方向。
+    pub fn from_vector(v: Vec2) -> Self {
+        if v.x.abs() >= v.y.abs() {
+            if v.x < 0.0 { SwipeDirection::Left } else { SwipeDirection::Right }
+        } else if v.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        }
+    }
+}
+
+/// Recognized gesture. Positions are in canvas pixels (y down).
+/// 识别出的手势。位置单位为canvas像素（y向下）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureEvent {
+    /// Short press without movement.
+    /// 无移动的短按。
+    Tap {
+        /// Touch position.
+        /// 触摸位置。
+        position: Vec2,
+    },
+
+    /// Second tap close to the first; follows its `Tap`.
+    /// 靠近第一次的第二次轻击；紧随其`Tap`之后。
+    DoubleTap {
+        /// Touch position.
+        /// 触摸位置。
+        position: Vec2,
+    },
+
+    /// Touch held still past the long-press duration.
+    /// 静止按住超过长按时间的触摸。
+    LongPress {
+        /// Touch position.
+        /// 触摸位置。
+        position: Vec2,
+    },
+
+    /// Fast single-finger stroke.
+    /// 快速单指划动。
+    Swipe {
+        /// Dominant direction.
+        /// 主方向。
+        direction: SwipeDirection,
+        /// Average velocity in pixels per second.
+        /// 平均速度（像素/秒）。
+        velocity: Vec2,
+        /// Release position.
+        /// 松开位置。
+        position: Vec2,
+    },
+
+    /// Two-finger spread change since the last update.
+    /// 自上次更新以来双指间距的变化。
+    Pinch {
+        /// Midpoint of the fingers.
+        /// 手指中点。
+        center: Vec2,
+        /// Spread ratio to the last update (>1 = apart).
+        /// 与上次更新的间距比值（>1 = 张开）。
+        scale: f32,
+    },
+
+    /// Two-finger rotation since the last update.
+    /// 自上次更新以来的双指旋转。
+    Rotate {
+        /// Midpoint of the fingers.
+        /// 手指中点。
+        center: Vec2,
+        /// Angle change in radians (clockwise on screen).
+        /// 角度变化弧度（屏幕上顺时针）。
+        angle: f32,
+    },
+
+    /// Two-finger midpoint movement since the last update.
+    /// 自上次更新以来双指中点的移动。
+    Pan {
+        /// Midpoint of the fingers.
+        /// 手指中点。
+        center: Vec2,
+        /// Midpoint movement.
+        /// 中点移动量。
+        delta: Vec2,
+    },
+}
+
+/// Single touch being followed.
+/// 正在跟踪的单个触摸。
+#[derive(Debug, Clone, Copy)]
+struct Tracked {
+    start: Vec2,
+    position: Vec2,
+    start_time: f64,
+    /// Part of a multi-finger gesture | 属于多指手势
+    multi: bool,
+    long_pressed: bool,
+}
+
+/// Two-finger gesture in progress.
+/// 进行中的双指手势。
+#[derive(Debug, Clone, Copy)]
+struct TwoFinger {
+    ids: (i32, i32),
+    start_distance: f32,
+    start_angle: f32,
+    start_center: Vec2,
+    distance: f32,
+    angle: f32,
+    center: Vec2,
+    pinching: bool,
+    rotating: bool,
+    panning: bool,
+}
+
+/// Recognizes gestures from touch points.
+/// 从触摸点识别手势。
+///
+/// Pure state machine over `TouchPoint`s: feed it the active and ended touches
+/// each frame and read `events`. Taps, long presses and swipes come from lone
+/// touches; once a second finger lands both become a two-finger gesture that
+/// reports pinch, rotate and pan after their thresholds are crossed.
+/// 基于`TouchPoint`的纯状态机：每帧传入活动和已结束的触摸并读取`events`。
+/// 轻击、长按和滑动来自单独的触摸；第二根手指落下后两者组成双指手势，
+/// 超过阈值后报告捏合、旋转和平移。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::input::{GestureEvent, GestureRecognizer, TouchPoint};
+/// let mut gestures = GestureRecognizer::default();
+/// let mut touch = TouchPoint::new(1, 100.0, 100.0);
+/// gestures.update(0.016, &[touch], &[]);
+/// gestures.update(0.1, &[], &[touch]);
+/// assert!(matches!(gestures.events()[0], GestureEvent::Tap { .. }));
+///
+/// // Two fingers spreading apart | 两指张开
+/// let (mut a, mut b) = (TouchPoint::new(1, 100.0, 100.0), TouchPoint::new(2, 200.0, 100.0));
+/// gestures.update(0.016, &[a, b], &[]);
+/// a.update_position(50.0, 100.0);
+/// b.update_position(250.0, 100.0);
+/// gestures.update(0.016, &[a, b], &[]);
+/// assert!(gestures.events().iter().any(|e| matches!(e, GestureEvent::Pinch { scale, .. } if (*scale - 2.0).abs() < 1e-5)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    /// Thresholds.
+    /// 阈值。
+    pub config: GestureConfig,
+
+    /// Seconds since creation.
+    /// 自创建以来的秒数。
+    time: f64,
+
+    /// Followed touches by ID.
+    /// 按ID跟踪的触摸。
+    tracked: HashMap<i32, Tracked>,
+
+    /// Two-finger gesture in progress.
+    /// 进行中的双指手势。
+    two_finger: Option<TwoFinger>,
+
+    /// Time and position of the last tap, for double taps.
+    /// 上次轻击的时间和位置，用于双击。
+    last_tap: Option<(f64, Vec2)>,
+
+    /// Gestures recognized in the last update.
+    /// 上次更新中识别出的手势。
+    events: Vec<GestureEvent>,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the given thresholds.
+    /// 使用给定阈值创建识别器。
+    pub fn new(config: GestureConfig) -> Self {
+        Self { config, ..Self::default() }
+    }
+
+    /// Gestures recognized in the last update.
+    /// 上次更新中识别出的手势。
+    #[inline]
+    pub fn events(&self) -> &[GestureEvent] {
+        &self.events
+    }
+
+    /// Update from a touch state after its frame update.
+    /// 在触摸状态完成帧更新后据其更新。
+    pub fn update_from(&mut self, touch: &TouchState, dt: f32) {
+        let active: Vec<TouchPoint> = touch.get_touches().copied().collect();
+        self.update(dt, &active, touch.ended_touches());
+    }
+
+    /// Advance time and recognize gestures.
+    /// 推进时间并识别手势。
+    ///
+    /// # Arguments | 参数
+    /// * `dt` - Seconds since the last update | 距上次更新的秒数
+    /// * `active` - Touches currently down | 当前按下的触摸
+    /// * `ended` - Touches lifted since the last update | 自上次更新以来抬起的触摸
+    pub fn update(&mut self, dt: f32, active: &[TouchPoint], ended: &[TouchPoint]) {
+        self.time += dt as f64;
+        self.events.clear();
+
+        for touch in active {
+            let time = self.time;
+            let tracked = self.tracked.entry(touch.id).or_insert(Tracked {
+                start: touch.start_position,
+                position: touch.position,
+                start_time: time,
+                multi: false,
+                long_pressed: false,
+            });
+            tracked.position = touch.position;
+        }
+
+        if active.len() >= 2 {
+            for tracked in self.tracked.values_mut() {
+                tracked.multi = true;
+            }
+            self.update_two_finger(active);
+        } else {
+            self.two_finger = None;
+        }
+
+        self.recognize_long_presses();
+        for touch in ended {
+            self.recognize_release(touch);
+        }
+        // Forget touches lost without an end event | 忘记未收到结束事件而丢失的触摸
+        self.tracked.retain(|id, _| active.iter().any(|t| t.id == *id));
+    }
+
+    /// Reset all tracking.
+    /// 重置所有跟踪。
+    pub fn clear(&mut self) {
+        self.tracked.clear();
+        self.two_finger = None;
+        self.last_tap = None;
+        self.events.clear();
+    }
+
+    /// Pan and zoom a camera by the last update's two-finger gestures.
+    /// 根据上次更新的双指手势平移和缩放相机。
+    ///
+    /// The world points under the fingers stay under them.
+    /// 手指下方的世界点保持在手指下方。
+    ///
+    /// # Arguments | 参数
+    /// * `camera` - Camera to move | 要移动的相机
+    /// * `pan` - Apply two-finger pans | 应用双指平移
+    /// * `pinch` - Apply pinches as zoom | 将捏合应用为缩放
+    pub fn apply_to_camera(&self, camera: &mut Camera2D, pan: bool, pinch: bool) {
+        for event in &self.events {
+            match *event {
+                GestureEvent::Pan { center, delta } if pan => {
+                    let before = camera.screen_to_world(center - delta);
+                    let after = camera.screen_to_world(center);
+                    camera.translate(before - after);
+                }
+                GestureEvent::Pinch { center, scale } if pinch => {
+                    let anchor = camera.screen_to_world(center);
+                    camera.zoom_at(camera.zoom * scale, anchor);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Track the two lowest-ID fingers and emit pinch, rotate and pan.
+    /// 跟踪ID最小的两根手指并发出捏合、旋转和平移。
+    fn update_two_finger(&mut self, active: &[TouchPoint]) {
+        let mut fingers: Vec<&TouchPoint> = active.iter().collect();
+        fingers.sort_by_key(|t| t.id);
+        let (a, b) = (fingers[0], fingers[1]);
+        let offset = b.position - a.position;
+        let distance = offset.length();
+        let angle = offset.y.atan2(offset.x);
+        let center = (a.position + b.position) * 0.5;
+
+        let current = match self.two_finger {
+            Some(current) if current.ids == (a.id, b.id) => current,
+            _ => {
+                self.two_finger = Some(TwoFinger {
+                    ids: (a.id, b.id),
+                    start_distance: distance,
+                    start_angle: angle,
+                    start_center: center,
+                    distance,
+                    angle,
+                    center,
+                    pinching: false,
+                    rotating: false,
+                    panning: false,
+                });
+                return;
+            }
+        };
+
+        let config = &self.config;
+        let pinching = current.pinching || (distance - current.start_distance).abs() > config.pinch_threshold;
+        let rotating = current.rotating || wrap_angle(angle - current.start_angle).abs() > config.rotate_threshold;
+        let panning = current.panning || (center - current.start_center).length() > config.pan_threshold;
+
+        if pinching && current.distance > 0.0 && distance != current.distance {
+            self.events.push(GestureEvent::Pinch { center, scale: distance / current.distance });
+        }
+        let turn = wrap_angle(angle - current.angle);
+        if rotating && turn != 0.0 {
+            self.events.push(GestureEvent::Rotate { center, angle: turn });
+        }
+        let delta = center - current.center;
+        if panning && delta.length_squared() > 0.0 {
+            self.events.push(GestureEvent::Pan { center, delta });
+        }
+
+        self.two_finger = Some(TwoFinger { distance, angle, center, pinching, rotating, panning, ..current });
+    }
+
+    /// Emit long presses for lone touches held still long enough.
+    /// 为静止按住足够久的单独触摸发出长按。
+    fn recognize_long_presses(&mut self) {
+        let config = self.config;
+        for tracked in self.tracked.values_mut() {
+            if tracked.multi || tracked.long_pressed {
+                continue;
+            }
+            let held = (self.time - tracked.start_time) as f32;
+            if held >= config.long_press_duration
+                && tracked.position.distance(&tracked.start) <= config.tap_max_distance
+            {
+                tracked.long_pressed = true;
+                self.events.push(GestureEvent::LongPress { position: tracked.position });
+            }
+        }
+    }
+
+    /// Emit taps, double taps and swipes for a lifted lone touch.
+    /// 为抬起的单独触摸发出轻击、双击和滑动。
+    fn recognize_release(&mut self, touch: &TouchPoint) {
+        let tracked = self.tracked.remove(&touch.id).unwrap_or(Tracked {
+            start: touch.start_position,
+            position: touch.position,
+            start_time: self.time,
+            multi: false,
+            long_pressed: false,
+        });
+        if tracked.multi || tracked.long_pressed {
+            return;
+        }
+
+        let config = &self.config;
+        let duration = (self.time - tracked.start_time) as f32;
+        let movement = touch.position - tracked.start;
+        let distance = movement.length();
+
+        if distance <= config.tap_max_distance && duration <= config.tap_max_duration {
+            let position = touch.position;
+            self.events.push(GestureEvent::Tap { position });
+            let double = self.last_tap.is_some_and(|(time, last)| {
+                (self.time - time) as f32 <= config.double_tap_max_gap
+                    && last.distance(&position) <= config.double_tap_max_distance
+            });
+            if double {
+                self.events.push(GestureEvent::DoubleTap { position });
+                self.last_tap = None;
+            } else {
+                self.last_tap = Some((self.time, position));
+            }
+        } else if distance >= config.swipe_min_distance {
+            let velocity = movement * (1.0 / duration.max(f32::EPSILON));
+            if velocity.length() >= config.swipe_min_velocity {
+                self.events.push(GestureEvent::Swipe {
+                    direction: SwipeDirection::from_vector(movement),
+                    velocity,
+                    position: touch.position,
+                });
+            }
+        }
+    }
+}
+
+/// Wrap an angle to [-π, π].
+/// 将角度限制在[-π, π]。
+fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
@@ -1,13 +1,23 @@
 //! Unified input manager.
 //! 统一输入管理器。
 
-use super::{KeyboardState, MouseState, TouchState};
+use super::{
+    GamepadState, InputEvent, InputQueue, InputRecording, InputReplay, KeyboardState, MouseButton, MouseState,
+    RecordedEvent, TouchState,
+};
 
-/// Unified input manager handling keyboard, mouse, and touch.
-/// 处理键盘、鼠标和触摸的统一输入管理器。
+/// Unified input manager handling keyboard, mouse, touch and gamepads.
+/// 处理键盘、鼠标、触摸和游戏手柄的统一输入管理器。
 ///
-/// Provides a single interface for all input types.
-/// 为所有输入类型提供单一接口。
+/// Provides a single interface for all input types. Events arrive through an
+/// `InputQueue`, filled by DOM listeners or injected by the host, and are
+/// applied on `update`, which also polls gamepads.
+/// 为所有输入类型提供单一接口。事件通过`InputQueue`到达（由DOM监听器填充或由宿主注入），
+/// 并在`update`时应用；`update`同时轮询游戏手柄。
+///
+/// Applied events can be recorded and later replayed in place of live input;
+/// gamepads are not recorded and are left unpolled during replay.
+/// 应用的事件可以被录制，之后代替实时输入回放；游戏手柄不会被录制，回放期间也不会轮询。
 #[derive(Debug, Default)]
 pub struct InputManager {
     /// Keyboard state.
@@ -21,6 +31,22 @@ pub struct InputManager {
     /// Touch state.
     /// 触摸状态。
     pub touch: TouchState,
+
+    /// Gamepad state.
+    /// 游戏手柄状态。
+    pub gamepads: GamepadState,
+
+    /// Events waiting for the next update.
+    /// 等待下一次更新的事件。
+    events: InputQueue,
+
+    /// Recording in progress.
+    /// 进行中的录制。
+    recording: Option<InputRecording>,
+
+    /// Replay in progress.
+    /// 进行中的回放。
+    replay: Option<InputReplay>,
 }
 
 impl InputManager {
@@ -30,12 +56,155 @@ impl InputManager {
         Self::default()
     }
 
-    /// Update all input states for a new frame.
-    /// 为新帧更新所有输入状态。
+    /// Create an input manager reading events from a shared queue.
+    /// 创建从共享队列读取事件的输入管理器。
+    pub fn with_queue(events: InputQueue) -> Self {
+        Self { events, ..Self::default() }
+    }
+
+    /// Queue an event for the next update.
+    /// 为下一次更新排队事件。
+    #[inline]
+    pub fn push_event(&self, event: InputEvent) {
+        self.events.push(event);
+    }
+
+    /// Start a new frame: reset per-frame state, apply queued events and poll gamepads.
+    /// 开始新帧：重置每帧状态，应用排队的事件并轮询游戏手柄。
     pub fn update(&mut self) {
         self.keyboard.update();
         self.mouse.update();
         self.touch.update();
+
+        let live = self.events.take();
+        if let Some(replay) = &mut self.replay {
+            // Live input is dropped while replaying | 回放期间丢弃实时输入
+            let events = replay.next_frame();
+            if replay.is_finished() {
+                self.replay = None;
+            }
+            for event in events {
+                self.apply(event);
+            }
+            return;
+        }
+
+        if let Some(recording) = &mut self.recording {
+            let frame = recording.frame_count;
+            recording.events.extend(live.iter().map(|event| RecordedEvent { frame, event: event.clone() }));
+            recording.frame_count += 1;
+        }
+        for event in live {
+            self.apply(event);
+        }
+        self.gamepads.poll();
+    }
+
+    /// Start recording applied events, replacing any recording in progress.
+    /// 开始录制应用的事件，替换进行中的录制。
+    ///
+    /// The first frame starts with events recreating the keys, buttons, mouse
+    /// position and touches held now, so replay begins from the same state.
+    /// 第一帧以重建当前按住的键、按钮、鼠标位置和触摸的事件开头，使回放从相同状态开始。
+    pub fn start_recording(&mut self) {
+        let mut events = Vec::new();
+        let mut keys: Vec<&str> = self.keyboard.pressed_keys().collect();
+        keys.sort_unstable();
+        events.extend(keys.into_iter().map(|key| InputEvent::KeyDown(key.to_string())));
+
+        let position = self.mouse.position;
+        events.push(InputEvent::MouseMove { x: position.x, y: position.y });
+        for index in 0..3 {
+            if MouseButton::from_index(index).is_some_and(|b| self.mouse.is_button_down(b)) {
+                events.push(InputEvent::MouseDown(index));
+            }
+        }
+
+        let mut touches: Vec<_> = self.touch.get_touches().collect();
+        touches.sort_by_key(|t| t.id);
+        events.extend(touches.into_iter().map(|t| InputEvent::TouchStart { id: t.id, x: t.position.x, y: t.position.y }));
+
+        self.recording = Some(InputRecording {
+            frame_count: 0,
+            events: events.into_iter().map(|event| RecordedEvent { frame: 0, event }).collect(),
+        });
+    }
+
+    /// Stop recording and return the recording.
+    /// 停止录制并返回录制内容。
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    /// Check if a recording is in progress.
+    /// 检查是否正在录制。
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Replay a recording in place of live input, one frame per update.
+    /// 代替实时输入回放录制，每次更新一帧。
+    ///
+    /// Current input state is released first. Live input resumes once every
+    /// recorded frame has been replayed.
+    /// 首先释放当前输入状态。所有录制帧回放完毕后恢复实时输入。
+    pub fn start_replay(&mut self, recording: InputRecording) {
+        self.clear();
+        self.mouse.release_all();
+        self.mouse.update();
+        let dead_zone = self.gamepads.dead_zone();
+        self.gamepads = GamepadState::new();
+        self.gamepads.set_dead_zone(dead_zone);
+        self.replay = Some(InputReplay::new(recording));
+    }
+
+    /// Stop replaying and return to live input.
+    /// 停止回放并恢复实时输入。
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+    }
+
+    /// Check if a replay is in progress.
+    /// 检查是否正在回放。
+    #[inline]
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Replay in progress.
+    /// 进行中的回放。
+    #[inline]
+    pub fn replay(&self) -> Option<&InputReplay> {
+        self.replay.as_ref()
+    }
+
+    /// Apply one event immediately.
+    /// 立即应用一个事件。
+    pub fn apply(&mut self, event: InputEvent) {
+        match event {
+            InputEvent::KeyDown(code) => self.keyboard.key_down(code),
+            InputEvent::KeyUp(code) => self.keyboard.key_up(code),
+            InputEvent::MouseMove { x, y } => self.mouse.mouse_move(x, y),
+            InputEvent::MouseDown(button) => {
+                if let Some(button) = MouseButton::from_index(button) {
+                    self.mouse.button_down(button);
+                }
+            }
+            InputEvent::MouseUp(button) => {
+                if let Some(button) = MouseButton::from_index(button) {
+                    self.mouse.button_up(button);
+                }
+            }
+            InputEvent::Wheel(delta) => self.mouse.scroll(delta),
+            InputEvent::TouchStart { id, x, y } => self.touch.touch_start(id, x, y),
+            InputEvent::TouchMove { id, x, y } => self.touch.touch_move(id, x, y),
+            InputEvent::TouchEnd(id) => self.touch.touch_end(id),
+            InputEvent::Blur => {
+                self.keyboard.release_all();
+                self.mouse.release_all();
+            }
+        }
     }
 
     /// Check if a key is currently pressed.
@@ -52,10 +221,18 @@ impl InputManager {
         self.keyboard.is_key_just_pressed(key)
     }
 
+    /// Check if a key was just released this frame.
+    /// 检查某个键是否在本帧刚被释放。
+    #[inline]
+    pub fn is_key_just_released(&self, key: &str) -> bool {
+        self.keyboard.is_key_just_released(key)
+    }
+
     /// Clear all input states.
     /// 清除所有输入状态。
     pub fn clear(&mut self) {
         self.keyboard.clear();
         self.touch.clear();
+        self.events.take();
     }
 }
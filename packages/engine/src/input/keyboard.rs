@@ -65,6 +65,24 @@ impl KeyboardState {
         self.just_released.contains(key)
     }
 
+    /// Keys currently held.
+    /// 当前按住的键。
+    pub fn pressed_keys(&self) -> impl Iterator<Item = &str> {
+        self.pressed.iter().map(String::as_str)
+    }
+
+    /// Keys pressed this frame.
+    /// 本帧按下的键。
+    pub fn just_pressed_keys(&self) -> impl Iterator<Item = &str> {
+        self.just_pressed.iter().map(String::as_str)
+    }
+
+    /// Release all held keys, reporting them as just released.
+    /// 释放所有按住的键，并报告为刚释放。
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+    }
+
     /// Update state for new frame.
     /// 为新帧更新状态。
     pub fn update(&mut self) {
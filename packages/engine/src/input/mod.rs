@@ -4,9 +4,23 @@
 mod keyboard;
 mod mouse;
 mod touch;
+mod gamepad;
 mod input_manager;
+mod event;
+mod dom;
+mod action;
+mod action_map;
+mod gesture;
+mod recording;
 
 pub use input_manager::InputManager;
 pub use keyboard::KeyboardState;
 pub use mouse::{MouseState, MouseButton};
 pub use touch::{TouchState, TouchPoint};
+pub use gamepad::{GamepadPad, GamepadState, StandardButton, StandardStick};
+pub use event::{InputEvent, InputQueue};
+pub use dom::DomInputListeners;
+pub use action::{ActionBindings, ActionContext, ActionDef, Binding, BindingKind, InputDevices, InputSource, Interaction};
+pub use action_map::{ActionMap, ActionState, PlayerActions};
+pub use gesture::{GestureConfig, GestureEvent, GestureRecognizer, SwipeDirection};
+pub use recording::{InputRecording, InputReplay, RecordedEvent};
@@ -97,10 +97,18 @@ impl MouseState {
         self.buttons[index] = false;
     }
 
-    /// Handle scroll wheel event.
-    /// 处理滚轮事件。
+    /// Handle scroll wheel event; deltas within a frame accumulate.
+    /// 处理滚轮事件；同一帧内的增量会累加。
     pub fn scroll(&mut self, delta: f32) {
-        self.scroll_delta = delta;
+        self.scroll_delta += delta;
+    }
+
+    /// Release all held buttons.
+    /// 释放所有按住的按钮。
+    pub fn release_all(&mut self) {
+        for button in [MouseButton::Left, MouseButton::Middle, MouseButton::Right] {
+            self.button_up(button);
+        }
     }
 
     /// Check if a button is currently pressed.
@@ -0,0 +1,258 @@
+//! Input recording and replay.
+//! 输入录制与回放。
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{EngineError, Result};
+use super::InputEvent;
+
+/// Binary format magic.
+/// 二进制格式标识。
+const MAGIC: &[u8; 4] = b"ESIR";
+
+/// Binary format version.
+/// 二进制格式版本。
+const VERSION: u8 = 1;
+
+/// Input event applied on a given frame.
+/// 在指定帧应用的输入事件。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Input update index, counted from the start of the recording.
+    /// 输入更新索引，从录制开始计数。
+    pub frame: u32,
+
+    /// Event.
+    /// 事件。
+    pub event: InputEvent,
+}
+
+/// Recorded input session.
+/// 录制的输入会话。
+///
+/// Holds every event the input manager applied, tagged with the input update it
+/// was applied in. Frame 0 starts with events recreating the state held when
+/// recording began. Serializes to JSON for inspection or to a compact binary
+/// stream for attaching to bug reports.
+/// 保存输入管理器应用的每个事件，并标记其所在的输入更新。第0帧以重建录制开始时
+/// 按住状态的事件开头。可序列化为便于查看的JSON，或用于附加到缺陷报告的紧凑二进制流。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::input::{InputEvent, InputManager, InputRecording};
+/// let mut input = InputManager::new();
+/// input.start_recording();
+/// input.push_event(InputEvent::KeyDown("Space".into()));
+/// input.update();
+/// input.update();
+/// let recording = input.stop_recording().unwrap();
+/// assert_eq!(recording.frame_count, 2);
+/// assert_eq!(InputRecording::from_bytes(&recording.to_bytes()).unwrap(), recording);
+///
+/// let mut replayed = InputManager::new();
+/// replayed.start_replay(recording);
+/// replayed.update();
+/// assert!(replayed.is_key_just_pressed("Space"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputRecording {
+    /// Number of input updates covered.
+    /// 覆盖的输入更新数量。
+    pub frame_count: u32,
+
+    /// Events in frame order.
+    /// 按帧顺序排列的事件。
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    /// Parse a recording from JSON.
+    /// 从JSON解析录制。
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| EngineError::InvalidInputRecording(e.to_string()))
+    }
+
+    /// Serialize the recording to JSON.
+    /// 将录制序列化为JSON。
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| EngineError::InvalidInputRecording(e.to_string()))
+    }
+
+    /// Encode the recording as a compact little-endian binary stream.
+    /// 将录制编码为紧凑的小端二进制流。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(13 + self.events.len() * 13);
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&self.frame_count.to_le_bytes());
+        out.extend_from_slice(&(self.events.len() as u32).to_le_bytes());
+
+        for recorded in &self.events {
+            out.extend_from_slice(&recorded.frame.to_le_bytes());
+            match &recorded.event {
+                InputEvent::KeyDown(code) | InputEvent::KeyUp(code) => {
+                    out.push(if matches!(recorded.event, InputEvent::KeyDown(_)) { 0 } else { 1 });
+                    out.extend_from_slice(&(code.len() as u16).to_le_bytes());
+                    out.extend_from_slice(code.as_bytes());
+                }
+                InputEvent::MouseMove { x, y } => {
+                    out.push(2);
+                    out.extend_from_slice(&x.to_le_bytes());
+                    out.extend_from_slice(&y.to_le_bytes());
+                }
+                InputEvent::MouseDown(button) => {
+                    out.push(3);
+                    out.extend_from_slice(&button.to_le_bytes());
+                }
+                InputEvent::MouseUp(button) => {
+                    out.push(4);
+                    out.extend_from_slice(&button.to_le_bytes());
+                }
+                InputEvent::Wheel(delta) => {
+                    out.push(5);
+                    out.extend_from_slice(&delta.to_le_bytes());
+                }
+                InputEvent::TouchStart { id, x, y } | InputEvent::TouchMove { id, x, y } => {
+                    out.push(if matches!(recorded.event, InputEvent::TouchStart { .. }) { 6 } else { 7 });
+                    out.extend_from_slice(&id.to_le_bytes());
+                    out.extend_from_slice(&x.to_le_bytes());
+                    out.extend_from_slice(&y.to_le_bytes());
+                }
+                InputEvent::TouchEnd(id) => {
+                    out.push(8);
+                    out.extend_from_slice(&id.to_le_bytes());
+                }
+                InputEvent::Blur => out.push(9),
+            }
+        }
+        out
+    }
+
+    /// Decode a recording produced by `to_bytes`.
+    /// 解码由`to_bytes`生成的录制。
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(4)? != MAGIC {
+            return Err(EngineError::InvalidInputRecording("Not an input recording".into()));
+        }
+        let version = reader.take(1)?[0];
+        if version != VERSION {
+            return Err(EngineError::InvalidInputRecording(format!("Unsupported version {}", version)));
+        }
+        let frame_count = reader.u32()?;
+        let count = reader.u32()? as usize;
+
+        // Each event takes at least 5 bytes | 每个事件至少占5字节
+        let mut events = Vec::with_capacity(count.min(bytes.len() / 5));
+        for _ in 0..count {
+            let frame = reader.u32()?;
+            let event = match reader.take(1)?[0] {
+                tag @ (0 | 1) => {
+                    let len = u16::from_le_bytes(reader.array()?) as usize;
+                    let code = std::str::from_utf8(reader.take(len)?)
+                        .map_err(|e| EngineError::InvalidInputRecording(e.to_string()))?
+                        .to_string();
+                    if tag == 0 { InputEvent::KeyDown(code) } else { InputEvent::KeyUp(code) }
+                }
+                2 => InputEvent::MouseMove { x: reader.f32()?, y: reader.f32()? },
+                3 => InputEvent::MouseDown(i16::from_le_bytes(reader.array()?)),
+                4 => InputEvent::MouseUp(i16::from_le_bytes(reader.array()?)),
+                5 => InputEvent::Wheel(reader.f32()?),
+                tag @ (6 | 7) => {
+                    let (id, x, y) = (reader.u32()? as i32, reader.f32()?, reader.f32()?);
+                    if tag == 6 { InputEvent::TouchStart { id, x, y } } else { InputEvent::TouchMove { id, x, y } }
+                }
+                8 => InputEvent::TouchEnd(reader.u32()? as i32),
+                9 => InputEvent::Blur,
+                tag => {
+                    return Err(EngineError::InvalidInputRecording(format!("Unknown event tag {}", tag)))
+                }
+            };
+            events.push(RecordedEvent { frame, event });
+        }
+        Ok(Self { frame_count, events })
+    }
+}
+
+/// Bounds-checked little-endian reader.
+/// 带边界检查的小端读取器。
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.offset + len;
+        let slice = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or_else(|| EngineError::InvalidInputRecording("Unexpected end of data".into()))?;
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+}
+
+/// Playback position within a recording.
+/// 录制中的回放位置。
+#[derive(Debug, Clone)]
+pub struct InputReplay {
+    /// Recording being replayed.
+    /// 正在回放的录制。
+    recording: InputRecording,
+
+    /// Next frame to replay.
+    /// 下一个要回放的帧。
+    frame: u32,
+
+    /// Index of the next event.
+    /// 下一个事件的索引。
+    cursor: usize,
+}
+
+impl InputReplay {
+    /// Start replaying a recording from its first frame.
+    /// 从第一帧开始回放录制。
+    pub fn new(recording: InputRecording) -> Self {
+        Self { recording, frame: 0, cursor: 0 }
+    }
+
+    /// Next frame to replay.
+    /// 下一个要回放的帧。
+    #[inline]
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Check if every recorded frame has been replayed.
+    /// 检查是否已回放所有录制帧。
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frame_count
+    }
+
+    /// Events of the next frame, advancing playback.
+    /// 下一帧的事件，并推进回放。
+    pub fn next_frame(&mut self) -> Vec<InputEvent> {
+        let events = &self.recording.events[self.cursor..];
+        let count = events.iter().take_while(|e| e.frame <= self.frame).count();
+        self.cursor += count;
+        self.frame += 1;
+        events[..count].iter().map(|e| e.event.clone()).collect()
+    }
+}
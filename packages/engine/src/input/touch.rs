@@ -43,12 +43,12 @@ impl TouchPoint {
         }
     }
 
-    /// Update touch position.
-    /// 更新触摸位置。
+    /// Update touch position; moves within a frame accumulate into `delta`.
+    /// 更新触摸位置；同一帧内的移动累加到`delta`。
     pub fn update_position(&mut self, x: f32, y: f32) {
         self.prev_position = self.position;
         self.position = Vec2::new(x, y);
-        self.delta = self.position - self.prev_position;
+        self.delta = self.delta + (self.position - self.prev_position);
     }
 }
 
@@ -67,6 +67,10 @@ pub struct TouchState {
     /// Touch IDs that ended this frame.
     /// 本帧结束的触摸ID。
     just_ended: Vec<i32>,
+
+    /// Final state of touches that ended this frame.
+    /// 本帧结束的触摸的最终状态。
+    ended: Vec<TouchPoint>,
 }
 
 impl TouchState {
@@ -95,8 +99,9 @@ impl TouchState {
     /// Handle touch end event.
     /// 处理触摸结束事件。
     pub fn touch_end(&mut self, id: i32) {
-        if self.touches.remove(&id).is_some() {
+        if let Some(touch) = self.touches.remove(&id) {
             self.just_ended.push(id);
+            self.ended.push(touch);
         }
     }
 
@@ -142,11 +147,19 @@ impl TouchState {
         &self.just_ended
     }
 
+    /// Get the final state of touches that ended this frame.
+    /// 获取本帧结束的触摸的最终状态。
+    #[inline]
+    pub fn ended_touches(&self) -> &[TouchPoint] {
+        &self.ended
+    }
+
     /// Update state for new frame.
     /// 为新帧更新状态。
     pub fn update(&mut self) {
         self.just_started.clear();
         self.just_ended.clear();
+        self.ended.clear();
 
         // Reset deltas | 重置增量
         for touch in self.touches.values_mut() {
@@ -160,5 +173,6 @@ impl TouchState {
         self.touches.clear();
         self.just_started.clear();
         self.just_ended.clear();
+        self.ended.clear();
     }
 }
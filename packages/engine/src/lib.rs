@@ -11,6 +11,7 @@
 //! - `math` - Mathematical primitives (vectors, matrices) | 数学基元（向量、矩阵）
 //! - `resource` - Asset loading and management | 资源加载和管理
 //! - `input` - Keyboard, mouse, and touch input | 键盘、鼠标和触摸输入
+//! - `animation` - Sprite frame animation | 精灵帧动画
 //! - `platform` - Platform abstraction layer | 平台抽象层
 //!
 //! # Example | 示例
@@ -41,6 +42,7 @@ pub mod platform;
 pub mod renderer;
 pub mod resource;
 pub mod input;
+pub mod animation;
 
 // Re-exports | 重新导出
 pub use crate::core::{Engine, EngineConfig};
@@ -143,6 +145,9 @@ impl GameEngine {
     /// * `uvs` - Float32Array [u0, v0, u1, v1] per sprite | 每个精灵的UV坐标
     /// * `colors` - Uint32Array of packed RGBA colors | 打包的RGBA颜色数组
     /// * `material_ids` - Uint32Array of material IDs (0 = default) | 材质ID数组（0 = 默认）
+    /// * `sort_keys` - Optional Float32Array [layer, orderInLayer, ySort] per sprite;
+    ///                 without it sprites draw in submission order
+    ///                 可选，每个精灵的排序数据；未提供时按提交顺序绘制
     #[wasm_bindgen(js_name = submitSpriteBatch)]
     pub fn submit_sprite_batch(
         &mut self,
@@ -151,12 +156,181 @@ impl GameEngine {
         uvs: &[f32],
         colors: &[u32],
         material_ids: &[u32],
+        sort_keys: Option<Vec<f32>>,
     ) -> std::result::Result<(), JsValue> {
         self.engine
-            .submit_sprite_batch(transforms, texture_ids, uvs, colors, material_ids)
+            .submit_sprite_batch(
+                transforms,
+                texture_ids,
+                uvs,
+                colors,
+                material_ids,
+                sort_keys.as_deref().unwrap_or_default(),
+            )
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Statistics of the last frame rendered to the main canvas.
+    /// 上一次渲染到主画布的帧的统计。
+    ///
+    /// # Returns | 返回
+    /// Float32Array [drawCalls, batches, spritesSubmitted, spritesCulled,
+    /// verticesUploaded, instancesUploaded, textureBinds, shaderSwitches,
+    /// materialSwitches, gpuTimeMs] with gpuTimeMs = -1 when unavailable
+    /// 统计数组，GPU时间不可用时为-1
+    #[wasm_bindgen(js_name = getRenderStats)]
+    pub fn get_render_stats(&self) -> Vec<f32> {
+        self.engine.frame_stats().to_array().to_vec()
+    }
+
+    /// Statistics of the last frame rendered to a viewport, laid out as in `getRenderStats`.
+    /// 上一次渲染到视口的帧的统计，布局同`getRenderStats`。
+    ///
+    /// # Returns | 返回
+    /// Empty array if the viewport has not been rendered (GPU time is always -1)
+    /// 视口尚未渲染时返回空数组（GPU时间始终为-1）
+    #[wasm_bindgen(js_name = getViewportRenderStats)]
+    pub fn get_viewport_render_stats(&self, viewport_id: &str) -> Vec<f32> {
+        self.engine
+            .viewport_stats(viewport_id)
+            .map_or_else(Vec::new, |stats| stats.to_array().to_vec())
+    }
+
+    /// Enable or disable GPU frame timing.
+    /// 启用或禁用GPU帧计时。
+    ///
+    /// # Returns | 返回
+    /// Whether timing is active; false when `EXT_disjoint_timer_query_webgl2`
+    /// is unavailable | 计时是否生效；扩展不可用时为false
+    #[wasm_bindgen(js_name = setGpuTiming)]
+    pub fn set_gpu_timing(&mut self, enabled: bool) -> bool {
+        self.engine.set_gpu_timing(enabled)
+    }
+
+    /// Enable or disable culling of sprites outside the camera view (on by default).
+    /// 启用或禁用相机视图外精灵的剔除（默认开启）。
+    ///
+    /// Disable it when material shaders move vertices beyond the sprite quad.
+    /// 当材质着色器将顶点移出精灵四边形时请禁用。
+    #[wasm_bindgen(js_name = setSpriteCulling)]
+    pub fn set_sprite_culling(&mut self, enabled: bool) {
+        self.engine.set_sprite_culling(enabled);
+    }
+
+    /// Sprite counts of the last rendered view.
+    /// 上次渲染视图的精灵数量。
+    ///
+    /// # Returns | 返回
+    /// Array of [drawn, culled] | 数组 [drawn, culled]
+    #[wasm_bindgen(js_name = getSpriteCullStats)]
+    pub fn get_sprite_cull_stats(&self) -> Vec<u32> {
+        let (drawn, culled) = self.engine.sprite_cull_stats();
+        vec![drawn as u32, culled as u32]
+    }
+
+    /// Set picking IDs for the sprites of the next sprite submission.
+    /// 为下一次精灵提交的精灵设置拾取ID。
+    ///
+    /// Sprites with ID 0 are not pickable. The next `submitSpriteBatch` or
+    /// `submitAnimatedSprites` call consumes the IDs and fails if their count
+    /// does not match its sprites. Picks see the sprites of the last rendered frame.
+    /// ID为0的精灵不可拾取。下一次`submitSpriteBatch`或`submitAnimatedSprites`调用
+    /// 会消耗这些ID，数量与其精灵不符时调用失败。拾取针对上一渲染帧的精灵。
+    ///
+    /// # Arguments | 参数
+    /// * `pick_ids` - Uint32Array with one ID per sprite | 每个精灵一个ID的数组
+    #[wasm_bindgen(js_name = setSpritePickIds)]
+    pub fn set_sprite_pick_ids(&mut self, pick_ids: &[u32]) {
+        self.engine.set_sprite_pick_ids(pick_ids);
+    }
+
+    /// Choose how screen-space picks are resolved.
+    /// 选择屏幕空间拾取的解析方式。
+    ///
+    /// # Arguments | 参数
+    /// * `gpu` - Use a pixel-accurate id buffer instead of rotated-quad tests | 使用像素精确的ID缓冲区代替旋转四边形测试
+    /// * `alpha_threshold` - Minimum texture alpha counted as a hit in GPU mode | GPU模式下视为命中的最小纹理alpha
+    #[wasm_bindgen(js_name = setPickMode)]
+    pub fn set_pick_mode(&mut self, gpu: bool, alpha_threshold: f32) {
+        let mode = if gpu { renderer::PickMode::Gpu } else { renderer::PickMode::Cpu };
+        self.engine.set_pick_mode(mode, alpha_threshold);
+    }
+
+    /// Pick sprites at a screen position.
+    /// 拾取屏幕位置处的精灵。
+    ///
+    /// # Returns | 返回
+    /// Picking IDs in draw order, topmost last | 按绘制顺序的拾取ID，最上层在最后
+    #[wasm_bindgen(js_name = pick)]
+    pub fn pick(&mut self, screen_x: f32, screen_y: f32) -> std::result::Result<Vec<u32>, JsValue> {
+        self.engine
+            .pick(screen_x, screen_y)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Pick sprites overlapping a screen rectangle given by two corners.
+    /// 拾取与由两个角给定的屏幕矩形重叠的精灵。
+    ///
+    /// # Returns | 返回
+    /// Picking IDs in draw order, topmost last | 按绘制顺序的拾取ID，最上层在最后
+    #[wasm_bindgen(js_name = pickRect)]
+    pub fn pick_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) -> std::result::Result<Vec<u32>, JsValue> {
+        self.engine
+            .pick_rect(x0, y0, x1, y1)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Pick sprites at a world position (always uses quad tests).
+    /// 拾取世界位置处的精灵（始终使用四边形测试）。
+    ///
+    /// # Returns | 返回
+    /// Picking IDs in draw order, topmost last | 按绘制顺序的拾取ID，最上层在最后
+    #[wasm_bindgen(js_name = pickWorld)]
+    pub fn pick_world(&self, x: f32, y: f32) -> Vec<u32> {
+        self.engine.pick_world(x, y)
+    }
+
+    /// Pick sprites overlapping a world rectangle (always uses quad tests).
+    /// 拾取与世界矩形重叠的精灵（始终使用四边形测试）。
+    ///
+    /// # Arguments | 参数
+    /// * `x`, `y` - Bottom-left corner | 左下角
+    /// * `width`, `height` - Size | 尺寸
+    ///
+    /// # Returns | 返回
+    /// Picking IDs in draw order, topmost last | 按绘制顺序的拾取ID，最上层在最后
+    #[wasm_bindgen(js_name = pickWorldRect)]
+    pub fn pick_world_rect(&self, x: f32, y: f32, width: f32, height: f32) -> Vec<u32> {
+        self.engine.pick_world_rect(x, y, width, height)
+    }
+
+    /// Draw sprites using a texture as nine-slices (9-patch).
+    /// 将使用该纹理的精灵绘制为九宫格。
+    ///
+    /// Corners keep their texel size, and the sprite's scale sets the target
+    /// size. The nine sub-quads go through the regular batch, so sorting and
+    /// materials apply as usual.
+    /// 角保持纹素尺寸，精灵的缩放决定目标尺寸。九个子四边形通过常规批处理绘制，
+    /// 因此排序和材质照常生效。
+    ///
+    /// # Arguments | 参数
+    /// * `texture_id` - Texture or atlas region ID | 纹理或图集区域ID
+    /// * `left`, `right`, `top`, `bottom` - Border insets in texels | 边框内距（纹素）
+    /// * `mode` - Bit 0 tiles the center, bit 1 tiles the edges (0 = stretch all)
+    ///            位0平铺中心，位1平铺边（0 = 全部拉伸）
+    #[wasm_bindgen(js_name = setNineSlice)]
+    pub fn set_nine_slice(&mut self, texture_id: u32, left: f32, right: f32, top: f32, bottom: f32, mode: u8) {
+        let slice = renderer::NineSlice::new([left, right, top, bottom]).with_mode_flags(mode);
+        self.engine.set_nine_slice(texture_id, Some(slice));
+    }
+
+    /// Draw sprites using a texture as plain quads again.
+    /// 将使用该纹理的精灵恢复为普通四边形绘制。
+    #[wasm_bindgen(js_name = clearNineSlice)]
+    pub fn clear_nine_slice(&mut self, texture_id: u32) {
+        self.engine.set_nine_slice(texture_id, None);
+    }
+
     /// Render the current frame.
     /// 渲染当前帧。
     pub fn render(&mut self) -> std::result::Result<(), JsValue> {
@@ -202,6 +376,185 @@ impl GameEngine {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Load a texture and wait for it.
+    /// 加载纹理并等待其完成。
+    ///
+    /// # Returns | 返回
+    /// Promise resolving to [width, height], or rejecting with the load error
+    /// (missing file, CORS, decode failure).
+    /// 解析为[width, height]的Promise，或以加载错误（文件缺失、CORS、解码失败）拒绝。
+    #[wasm_bindgen(js_name = loadTextureAsync)]
+    pub fn load_texture_async(&mut self, id: u32, url: &str) -> js_sys::Promise {
+        match self.engine.load_texture(id, url) {
+            Ok(()) => self.texture_promise(id, false),
+            Err(e) => js_sys::Promise::reject(&JsValue::from_str(&e.to_string())),
+        }
+    }
+
+    /// Load a texture by path and wait for it.
+    /// 按路径加载纹理并等待其完成。
+    ///
+    /// # Returns | 返回
+    /// Promise resolving to [id, width, height], or rejecting with the load error.
+    /// 解析为[id, width, height]的Promise，或以加载错误拒绝。
+    #[wasm_bindgen(js_name = loadTextureByPathAsync)]
+    pub fn load_texture_by_path_async(&mut self, path: &str) -> js_sys::Promise {
+        match self.engine.load_texture_by_path(path) {
+            Ok(id) => self.texture_promise(id, true),
+            Err(e) => js_sys::Promise::reject(&JsValue::from_str(&e.to_string())),
+        }
+    }
+
+    /// Wait for a texture that is loading or already loaded.
+    /// 等待正在加载或已加载的纹理。
+    ///
+    /// # Returns | 返回
+    /// Promise resolving to [width, height], or rejecting with the load error.
+    /// 解析为[width, height]的Promise，或以加载错误拒绝。
+    #[wasm_bindgen(js_name = whenTextureLoaded)]
+    pub fn when_texture_loaded(&self, id: u32) -> js_sys::Promise {
+        self.texture_promise(id, false)
+    }
+
+    /// Load a texture from URL with sampling options.
+    /// 使用采样选项从URL加载纹理。
+    ///
+    /// # Arguments | 参数
+    /// * `id` - Unique texture identifier | 唯一纹理标识符
+    /// * `url` - Image URL to load | 要加载的图片URL
+    /// * `filter` - 0 = nearest (pixel art), 1 = linear | 0 = 最近（像素画），1 = 线性
+    /// * `mipmaps` - Generate and sample mipmaps | 生成并采样mipmap
+    /// * `wrap` - 0 = clamp, 1 = repeat, 2 = mirrored repeat | 0 = 夹取，1 = 重复，2 = 镜像重复
+    /// * `premultiply_alpha` - Premultiply on upload, pairs with blend mode 5 (PremultipliedAlpha) | 上传时预乘，配合混合模式5（预乘alpha）
+    #[wasm_bindgen(js_name = loadTextureWithSampler)]
+    pub fn load_texture_with_sampler(
+        &mut self,
+        id: u32,
+        url: &str,
+        filter: u8,
+        mipmaps: bool,
+        wrap: u8,
+        premultiply_alpha: bool,
+    ) -> std::result::Result<(), JsValue> {
+        let sampler = renderer::TextureSampler::new(
+            renderer::TextureFilter::from_index(filter),
+            mipmaps,
+            renderer::TextureWrap::from_index(wrap),
+        );
+        self.engine
+            .load_texture_with(id, url, renderer::TextureLoadOptions { sampler, premultiply_alpha })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Change how a texture is sampled.
+    /// 更改纹理的采样方式。
+    ///
+    /// Setting it on an atlas region changes its whole page.
+    /// 对图集区域设置会更改其整个页面。
+    ///
+    /// # Arguments | 参数
+    /// * `id` - Texture ID | 纹理ID
+    /// * `filter` - 0 = nearest (pixel art), 1 = linear | 0 = 最近（像素画），1 = 线性
+    /// * `mipmaps` - Generate and sample mipmaps | 生成并采样mipmap
+    /// * `wrap` - 0 = clamp, 1 = repeat, 2 = mirrored repeat | 0 = 夹取，1 = 重复，2 = 镜像重复
+    /// * `anisotropy` - Maximum anisotropy, 1 to disable | 最大各向异性，1表示禁用
+    #[wasm_bindgen(js_name = setTextureSampler)]
+    pub fn set_texture_sampler(
+        &mut self,
+        id: u32,
+        filter: u8,
+        mipmaps: bool,
+        wrap: u8,
+        anisotropy: f32,
+    ) -> std::result::Result<(), JsValue> {
+        let sampler = renderer::TextureSampler::new(
+            renderer::TextureFilter::from_index(filter),
+            mipmaps,
+            renderer::TextureWrap::from_index(wrap),
+        )
+        .with_anisotropy(anisotropy);
+        self.engine
+            .set_texture_sampler(id, sampler)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Check if a texture's colors were multiplied by alpha on upload.
+    /// 检查纹理颜色是否在上传时已乘以alpha。
+    #[wasm_bindgen(js_name = isTexturePremultiplied)]
+    pub fn is_texture_premultiplied(&self, id: u32) -> bool {
+        self.engine.is_texture_premultiplied(id)
+    }
+
+    /// Get the load state of a texture loaded from a URL.
+    /// 获取从URL加载的纹理的加载状态。
+    ///
+    /// # Returns | 返回
+    /// 0 = not loaded from a URL, 1 = pending, 2 = loaded, 3 = failed
+    /// 0 = 非URL加载，1 = 加载中，2 = 已加载，3 = 失败
+    #[wasm_bindgen(js_name = getTextureLoadState)]
+    pub fn get_texture_load_state(&self, id: u32) -> u8 {
+        match self.engine.texture_load_state(id) {
+            None => 0,
+            Some(renderer::TextureLoadState::Pending) => 1,
+            Some(renderer::TextureLoadState::Loaded { .. }) => 2,
+            Some(renderer::TextureLoadState::Failed(_)) => 3,
+        }
+    }
+
+    /// Get the error of a failed texture load.
+    /// 获取失败纹理加载的错误。
+    #[wasm_bindgen(js_name = getTextureLoadError)]
+    pub fn get_texture_load_error(&self, id: u32) -> Option<String> {
+        match self.engine.texture_load_state(id) {
+            Some(renderer::TextureLoadState::Failed(message)) => {
+                Some(EngineError::TextureLoadFailed(message).to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Load an image into a shared texture atlas page.
+    /// 将图像加载到共享纹理图集页面。
+    ///
+    /// The returned ID can be used anywhere a texture ID is accepted; sprites
+    /// using regions of the same page are drawn in one batch.
+    /// 返回的ID可在任何接受纹理ID的地方使用；使用同一页面区域的精灵在一个批次中绘制。
+    ///
+    /// Images whose size differs from `width` x `height` fail to load.
+    /// 尺寸与`width` x `height`不同的图像加载失败。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Image path/URL to load | 要加载的图片路径/URL
+    /// * `width` - Image width in pixels | 图像宽度（像素）
+    /// * `height` - Image height in pixels | 图像高度（像素）
+    #[wasm_bindgen(js_name = loadTextureToAtlas)]
+    pub fn load_texture_to_atlas(&mut self, path: &str, width: u32, height: u32) -> std::result::Result<u32, JsValue> {
+        self.engine
+            .load_texture_to_atlas(path, width, height)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Get an atlas region.
+    /// 获取图集区域。
+    ///
+    /// # Returns | 返回
+    /// Array of [pageTextureId, u0, v0, u1, v1, width, height], or undefined
+    /// if the ID is not an atlas region.
+    /// 数组 [pageTextureId, u0, v0, u1, v1, width, height]，如果ID不是图集区域则为undefined。
+    #[wasm_bindgen(js_name = getAtlasRegion)]
+    pub fn get_atlas_region(&self, id: u32) -> Option<Vec<f32>> {
+        self.engine.get_atlas_region(id).map(|(page, uv, width, height)| {
+            vec![page as f32, uv[0], uv[1], uv[2], uv[3], width as f32, height as f32]
+        })
+    }
+
+    /// Set the size of newly created atlas pages (default 2048).
+    /// 设置新建图集页面的尺寸（默认2048）。
+    #[wasm_bindgen(js_name = setAtlasPageSize)]
+    pub fn set_atlas_page_size(&mut self, size: u32) {
+        self.engine.set_atlas_page_size(size);
+    }
+
     /// Get texture ID by path.
     /// 按路径获取纹理ID。
     ///
@@ -241,279 +594,1857 @@ impl GameEngine {
         self.engine.update_input();
     }
 
-    /// Resize viewport.
-    /// 调整视口大小。
+    /// Check if a key was pressed since the last `updateInput`.
+    /// 检查某个键是否在上次`updateInput`后被按下。
+    #[wasm_bindgen(js_name = isKeyJustPressed)]
+    pub fn is_key_just_pressed(&self, key_code: &str) -> bool {
+        self.engine.is_key_just_pressed(key_code)
+    }
+
+    /// Check if a key was released since the last `updateInput`.
+    /// 检查某个键是否在上次`updateInput`后被释放。
+    #[wasm_bindgen(js_name = isKeyJustReleased)]
+    pub fn is_key_just_released(&self, key_code: &str) -> bool {
+        self.engine.is_key_just_released(key_code)
+    }
+
+    /// Check if a mouse button is held (0 = left, 1 = middle, 2 = right).
+    /// 检查鼠标按钮是否按住（0 = 左，1 = 中，2 = 右）。
+    #[wasm_bindgen(js_name = isMouseButtonDown)]
+    pub fn is_mouse_button_down(&self, button: i16) -> bool {
+        input::MouseButton::from_index(button)
+            .is_some_and(|b| self.engine.input().mouse.is_button_down(b))
+    }
+
+    /// Check if a mouse button was pressed since the last `updateInput`.
+    /// 检查鼠标按钮是否在上次`updateInput`后被按下。
+    #[wasm_bindgen(js_name = isMouseButtonJustPressed)]
+    pub fn is_mouse_button_just_pressed(&self, button: i16) -> bool {
+        input::MouseButton::from_index(button)
+            .is_some_and(|b| self.engine.input().mouse.is_button_just_pressed(b))
+    }
+
+    /// Check if a mouse button was released since the last `updateInput`.
+    /// 检查鼠标按钮是否在上次`updateInput`后被释放。
+    #[wasm_bindgen(js_name = isMouseButtonJustReleased)]
+    pub fn is_mouse_button_just_released(&self, button: i16) -> bool {
+        input::MouseButton::from_index(button)
+            .is_some_and(|b| self.engine.input().mouse.is_button_just_released(b))
+    }
+
+    /// Mouse position in canvas pixels.
+    /// canvas像素坐标下的鼠标位置。
+    ///
+    /// # Returns | 返回
+    /// Array of [x, y] | 数组 [x, y]
+    #[wasm_bindgen(js_name = getMousePosition)]
+    pub fn get_mouse_position(&self) -> Vec<f32> {
+        let position = self.engine.input().mouse.position;
+        vec![position.x, position.y]
+    }
+
+    /// Mouse position in world coordinates.
+    /// 世界坐标下的鼠标位置。
+    ///
+    /// # Returns | 返回
+    /// Array of [x, y] | 数组 [x, y]
+    #[wasm_bindgen(js_name = getMouseWorldPosition)]
+    pub fn get_mouse_world_position(&self) -> Vec<f32> {
+        let (x, y) = self.engine.mouse_world_position();
+        vec![x, y]
+    }
+
+    /// Mouse movement over the last input update, in canvas pixels.
+    /// 上次输入更新期间的鼠标移动（canvas像素）。
+    ///
+    /// # Returns | 返回
+    /// Array of [dx, dy] | 数组 [dx, dy]
+    #[wasm_bindgen(js_name = getMouseDelta)]
+    pub fn get_mouse_delta(&self) -> Vec<f32> {
+        let delta = self.engine.input().mouse.delta;
+        vec![delta.x, delta.y]
+    }
+
+    /// Wheel scroll since the last `updateInput`, in pixels (positive = down).
+    /// 自上次`updateInput`以来的滚轮滚动量（像素，正值 = 向下）。
+    #[wasm_bindgen(js_name = getScrollDelta)]
+    pub fn get_scroll_delta(&self) -> f32 {
+        self.engine.input().mouse.scroll_delta
+    }
+
+    /// Active touches sorted by ID.
+    /// 按ID排序的活动触摸。
+    ///
+    /// # Returns | 返回
+    /// Array of [id, x, y, dx, dy] per touch in canvas pixels | 每个触摸的 [id, x, y, dx, dy]（canvas像素）
+    #[wasm_bindgen(js_name = getTouches)]
+    pub fn get_touches(&self) -> Vec<f32> {
+        let mut touches: Vec<_> = self.engine.input().touch.get_touches().collect();
+        touches.sort_by_key(|t| t.id);
+        touches
+            .iter()
+            .flat_map(|t| [t.id as f32, t.position.x, t.position.y, t.delta.x, t.delta.y])
+            .collect()
+    }
+
+    /// IDs of touches that started since the last `updateInput`.
+    /// 自上次`updateInput`以来开始的触摸ID。
+    #[wasm_bindgen(js_name = getTouchesStarted)]
+    pub fn get_touches_started(&self) -> Vec<i32> {
+        self.engine.input().touch.just_started().to_vec()
+    }
+
+    /// IDs of touches that ended since the last `updateInput`.
+    /// 自上次`updateInput`以来结束的触摸ID。
+    #[wasm_bindgen(js_name = getTouchesEnded)]
+    pub fn get_touches_ended(&self) -> Vec<i32> {
+        self.engine.input().touch.just_ended().to_vec()
+    }
+
+    /// Indices of connected gamepads.
+    /// 已连接游戏手柄的索引。
+    #[wasm_bindgen(js_name = getGamepads)]
+    pub fn get_gamepads(&self) -> Vec<u32> {
+        self.engine.input().gamepads.connected().collect()
+    }
+
+    /// Indices of gamepads connected since the last `updateInput`.
+    /// 自上次`updateInput`以来连接的游戏手柄索引。
+    #[wasm_bindgen(js_name = getGamepadsConnected)]
+    pub fn get_gamepads_connected(&self) -> Vec<u32> {
+        self.engine.input().gamepads.just_connected().to_vec()
+    }
+
+    /// Indices of gamepads disconnected since the last `updateInput`.
+    /// 自上次`updateInput`以来断开的游戏手柄索引。
+    #[wasm_bindgen(js_name = getGamepadsDisconnected)]
+    pub fn get_gamepads_disconnected(&self) -> Vec<u32> {
+        self.engine.input().gamepads.just_disconnected().to_vec()
+    }
+
+    /// Device description of a gamepad.
+    /// 游戏手柄的设备描述。
+    #[wasm_bindgen(js_name = getGamepadId)]
+    pub fn get_gamepad_id(&self, index: u32) -> Option<String> {
+        self.engine.input().gamepads.pad(index).map(|pad| pad.id.clone())
+    }
+
+    /// Check whether a gamepad uses the standard mapping.
+    /// 检查游戏手柄是否使用标准映射。
+    #[wasm_bindgen(js_name = isGamepadStandard)]
+    pub fn is_gamepad_standard(&self, index: u32) -> bool {
+        self.engine.input().gamepads.pad(index).is_some_and(|pad| pad.standard)
+    }
+
+    /// Check if a gamepad button is held.
+    /// 检查游戏手柄按钮是否按住。
     ///
     /// # Arguments | 参数
-    /// * `width` - New viewport width | 新视口宽度
-    /// * `height` - New viewport height | 新视口高度
-    pub fn resize(&mut self, width: u32, height: u32) {
-        self.engine.resize(width as f32, height as f32);
+    /// * `index` - Gamepad index | 手柄索引
+    /// * `button` - Button index (standard mapping: 0 = south, 12-15 = d-pad) | 按钮索引（标准映射：0 = 下方键，12-15 = 方向键）
+    #[wasm_bindgen(js_name = isGamepadButtonDown)]
+    pub fn is_gamepad_button_down(&self, index: u32, button: usize) -> bool {
+        self.engine.input().gamepads.pad(index).is_some_and(|pad| pad.is_button_down(button))
     }
 
-    /// Set camera position, zoom, and rotation.
-    /// 设置相机位置、缩放和旋转。
+    /// Check if a gamepad button was pressed since the last `updateInput`.
+    /// 检查游戏手柄按钮是否在上次`updateInput`后被按下。
+    #[wasm_bindgen(js_name = isGamepadButtonJustPressed)]
+    pub fn is_gamepad_button_just_pressed(&self, index: u32, button: usize) -> bool {
+        self.engine.input().gamepads.pad(index).is_some_and(|pad| pad.is_button_just_pressed(button))
+    }
+
+    /// Check if a gamepad button was released since the last `updateInput`.
+    /// 检查游戏手柄按钮是否在上次`updateInput`后被释放。
+    #[wasm_bindgen(js_name = isGamepadButtonJustReleased)]
+    pub fn is_gamepad_button_just_released(&self, index: u32, button: usize) -> bool {
+        self.engine.input().gamepads.pad(index).is_some_and(|pad| pad.is_button_just_released(button))
+    }
+
+    /// Analog value of a gamepad button in [0, 1], e.g. a trigger.
+    /// 游戏手柄按钮的模拟值，范围[0, 1]，例如扳机。
+    #[wasm_bindgen(js_name = getGamepadButtonValue)]
+    pub fn get_gamepad_button_value(&self, index: u32, button: usize) -> f32 {
+        self.engine.input().gamepads.pad(index).map_or(0.0, |pad| pad.button_value(button))
+    }
+
+    /// Gamepad axis value in [-1, 1] after the dead zone.
+    /// 应用死区后的游戏手柄轴值，范围[-1, 1]。
+    #[wasm_bindgen(js_name = getGamepadAxis)]
+    pub fn get_gamepad_axis(&self, index: u32, axis: usize) -> f32 {
+        self.engine.input().gamepads.pad(index).map_or(0.0, |pad| pad.axis(axis))
+    }
+
+    /// Set the radial dead zone applied to gamepad sticks (default 0.15).
+    /// 设置应用于游戏手柄摇杆的径向死区（默认0.15）。
+    #[wasm_bindgen(js_name = setGamepadDeadZone)]
+    pub fn set_gamepad_dead_zone(&mut self, dead_zone: f32) {
+        self.engine.input_mut().gamepads.set_dead_zone(dead_zone);
+    }
+
+    /// Rumble a gamepad where supported.
+    /// 在支持时使游戏手柄震动。
     ///
     /// # Arguments | 参数
-    /// * `x` - Camera X position | 相机X位置
-    /// * `y` - Camera Y position | 相机Y位置
-    /// * `zoom` - Zoom level | 缩放级别
-    /// * `rotation` - Rotation in radians | 旋转角度（弧度）
-    #[wasm_bindgen(js_name = setCamera)]
-    pub fn set_camera(&mut self, x: f32, y: f32, zoom: f32, rotation: f32) {
-        self.engine.set_camera(x, y, zoom, rotation);
+    /// * `index` - Gamepad index | 手柄索引
+    /// * `duration_ms` - Duration in milliseconds | 持续时间（毫秒）
+    /// * `strong` - Low-frequency magnitude in [0, 1] | 低频强度
+    /// * `weak` - High-frequency magnitude in [0, 1] | 高频强度
+    ///
+    /// # Returns | 返回
+    /// Whether the effect was started | 是否开始了效果
+    #[wasm_bindgen(js_name = rumbleGamepad)]
+    pub fn rumble_gamepad(&self, index: u32, duration_ms: f32, strong: f32, weak: f32) -> bool {
+        self.engine.input().gamepads.rumble(index, duration_ms, strong, weak)
+    }
+
+    /// Evaluate input actions. Call once per frame after `updateInput`.
+    /// 计算输入动作。应在`updateInput`之后每帧调用一次。
+    ///
+    /// # Arguments | 参数
+    /// * `dt` - Seconds since the last call | 距上次调用的秒数
+    #[wasm_bindgen(js_name = updateActions)]
+    pub fn update_actions(&mut self, dt: f32) {
+        self.engine.update_actions(dt);
+    }
+
+    /// Replace the action bindings from JSON, resetting player contexts.
+    /// 从JSON替换动作绑定，并重置玩家上下文。
+    #[wasm_bindgen(js_name = loadInputBindings)]
+    pub fn load_input_bindings(&mut self, json: &str) -> std::result::Result<(), JsValue> {
+        self.engine
+            .actions_mut()
+            .load_json(json)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Current action bindings as JSON, including runtime rebinds.
+    /// 当前动作绑定的JSON，包含运行时重绑定。
+    #[wasm_bindgen(js_name = getInputBindings)]
+    pub fn get_input_bindings(&self) -> std::result::Result<String, JsValue> {
+        self.engine
+            .actions()
+            .bindings()
+            .to_json()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Replace or append one binding of an action.
+    /// 替换或追加动作的一个绑定。
+    ///
+    /// # Arguments | 参数
+    /// * `context` - Context name | 上下文名称
+    /// * `action` - Action name | 动作名称
+    /// * `index` - Binding index; equal to the count appends | 绑定索引；等于数量时追加
+    /// * `binding_json` - Binding as JSON | 绑定的JSON
+    #[wasm_bindgen(js_name = rebindAction)]
+    pub fn rebind_action(&mut self, context: &str, action: &str, index: usize, binding_json: &str) -> std::result::Result<(), JsValue> {
+        let binding = input::Binding::from_json(binding_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.engine
+            .actions_mut()
+            .rebind(context, action, index, binding)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Source JSON of the first input a player pressed since the last `updateInput`.
+    /// 玩家自上次`updateInput`以来第一个按下的输入源的JSON。
+    ///
+    /// Poll while showing a "press a key" prompt, then pass the source to `rebindAction`.
+    /// 在显示"请按键"提示时轮询，然后将输入源传给`rebindAction`。
+    #[wasm_bindgen(js_name = captureInputSource)]
+    pub fn capture_input_source(&self, player: usize) -> Option<String> {
+        let source = self.engine.actions().capture_source(player, self.engine.input())?;
+        source.to_json().ok()
+    }
+
+    /// Add a player and return its index. Player 0 exists by default.
+    /// 添加玩家并返回其索引。默认存在0号玩家。
+    ///
+    /// # Arguments | 参数
+    /// * `gamepad` - Gamepad index, if any | 手柄索引（如有）
+    /// * `keyboard_mouse` - Whether keyboard and mouse drive the player | 键盘和鼠标是否驱动该玩家
+    /// * `touch` - Whether touches drive the player | 触摸是否驱动该玩家
+    #[wasm_bindgen(js_name = addInputPlayer)]
+    pub fn add_input_player(&mut self, gamepad: Option<u32>, keyboard_mouse: bool, touch: bool) -> usize {
+        self.engine.actions_mut().add_player(input::InputDevices {
+            gamepad,
+            keyboard_mouse,
+            touch,
+            viewport: (0.0, 0.0),
+        })
+    }
+
+    /// Enable or disable an action context for a player.
+    /// 为玩家启用或禁用动作上下文。
+    #[wasm_bindgen(js_name = setInputContextEnabled)]
+    pub fn set_input_context_enabled(&mut self, player: usize, context: &str, enabled: bool) {
+        self.engine.actions_mut().set_context_enabled(player, context, enabled);
+    }
+
+    /// Check if an action is active for a player.
+    /// 检查玩家的动作是否激活。
+    #[wasm_bindgen(js_name = isActionActive)]
+    pub fn is_action_active(&self, player: usize, action: &str) -> bool {
+        self.engine.actions().is_active(player, action)
+    }
+
+    /// Check if an action became active in the last `updateActions`.
+    /// 检查动作是否在上次`updateActions`中变为激活。
+    #[wasm_bindgen(js_name = isActionTriggered)]
+    pub fn is_action_triggered(&self, player: usize, action: &str) -> bool {
+        self.engine.actions().is_triggered(player, action)
+    }
+
+    /// Check if an action stopped being active in the last `updateActions`.
+    /// 检查动作是否在上次`updateActions`中停止激活。
+    #[wasm_bindgen(js_name = isActionReleased)]
+    pub fn is_action_released(&self, player: usize, action: &str) -> bool {
+        self.engine.actions().is_released(player, action)
+    }
+
+    /// Axis value of an action.
+    /// 动作的轴值。
+    #[wasm_bindgen(js_name = getActionValue)]
+    pub fn get_action_value(&self, player: usize, action: &str) -> f32 {
+        self.engine.actions().value(player, action)
+    }
+
+    /// Vector value of an action as [x, y], y up.
+    /// 动作的向量值[x, y]，y向上。
+    #[wasm_bindgen(js_name = getActionVector)]
+    pub fn get_action_vector(&self, player: usize, action: &str) -> Vec<f32> {
+        let value = self.engine.actions().vector(player, action);
+        vec![value.x, value.y]
+    }
+
+    /// Recognize touch gestures. Call once per frame after `updateInput`.
+    /// 识别触摸手势。应在`updateInput`之后每帧调用一次。
+    ///
+    /// # Arguments | 参数
+    /// * `dt` - Seconds since the last call | 距上次调用的秒数
+    #[wasm_bindgen(js_name = updateGestures)]
+    pub fn update_gestures(&mut self, dt: f32) {
+        self.engine.update_gestures(dt);
+    }
+
+    /// Gestures recognized in the last `updateGestures`.
+    /// 上次`updateGestures`中识别出的手势。
+    ///
+    /// Flat array of [kind, x, y, a, b, c] per gesture, positions in canvas pixels:
+    /// 每个手势为扁平数组[kind, x, y, a, b, c]，位置单位为canvas像素：
+    /// - 0 tap, 1 double tap, 2 long press | 0 轻击，1 双击，2 长按
+    /// - 3 swipe: a, b = velocity, c = direction (0 left, 1 right, 2 up, 3 down) | 3 滑动：a、b = 速度，c = 方向
+    /// - 4 pinch: a = scale | 4 捏合：a = 缩放比
+    /// - 5 rotate: a = angle in radians | 5 旋转：a = 弧度
+    /// - 6 pan: a, b = delta | 6 平移：a、b = 增量
+    #[wasm_bindgen(js_name = getGestures)]
+    pub fn get_gestures(&self) -> Vec<f32> {
+        use input::GestureEvent;
+        let mut out = Vec::with_capacity(self.engine.gestures().events().len() * 6);
+        for event in self.engine.gestures().events() {
+            let record = match *event {
+                GestureEvent::Tap { position } => [0.0, position.x, position.y, 0.0, 0.0, 0.0],
+                GestureEvent::DoubleTap { position } => [1.0, position.x, position.y, 0.0, 0.0, 0.0],
+                GestureEvent::LongPress { position } => [2.0, position.x, position.y, 0.0, 0.0, 0.0],
+                GestureEvent::Swipe { direction, velocity, position } => {
+                    [3.0, position.x, position.y, velocity.x, velocity.y, direction as u8 as f32]
+                }
+                GestureEvent::Pinch { center, scale } => [4.0, center.x, center.y, scale, 0.0, 0.0],
+                GestureEvent::Rotate { center, angle } => [5.0, center.x, center.y, angle, 0.0, 0.0],
+                GestureEvent::Pan { center, delta } => [6.0, center.x, center.y, delta.x, delta.y, 0.0],
+            };
+            out.extend_from_slice(&record);
+        }
+        out
+    }
+
+    /// Set gesture thresholds from JSON; omitted fields keep their defaults.
+    /// 从JSON设置手势阈值；省略的字段保持默认值。
+    ///
+    /// Fields: tapMaxDuration, tapMaxDistance, doubleTapMaxGap, doubleTapMaxDistance,
+    /// longPressDuration, swipeMinDistance, swipeMinVelocity, pinchThreshold,
+    /// rotateThreshold, panThreshold.
+    /// 字段与上方列出的相同。
+    #[wasm_bindgen(js_name = setGestureConfig)]
+    pub fn set_gesture_config(&mut self, json: &str) -> std::result::Result<(), JsValue> {
+        self.engine.gestures_mut().config = serde_json::from_str::<input::GestureConfig>(json)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(())
+    }
+
+    /// Let two-finger pans and pinches move and zoom the camera.
+    /// 让双指平移和捏合移动和缩放相机。
+    #[wasm_bindgen(js_name = setGestureCamera)]
+    pub fn set_gesture_camera(&mut self, pan: bool, pinch: bool) {
+        self.engine.set_gesture_camera(pan, pinch);
+    }
+
+    /// Start recording input events applied by `updateInput`.
+    /// 开始录制`updateInput`应用的输入事件。
+    #[wasm_bindgen(js_name = startInputRecording)]
+    pub fn start_input_recording(&mut self) {
+        self.engine.input_mut().start_recording();
+    }
+
+    /// Stop recording and return it as a compact binary stream.
+    /// 停止录制并以紧凑二进制流返回。
+    #[wasm_bindgen(js_name = stopInputRecording)]
+    pub fn stop_input_recording(&mut self) -> Option<Vec<u8>> {
+        self.engine.input_mut().stop_recording().map(|recording| recording.to_bytes())
+    }
+
+    /// Stop recording and return it as JSON.
+    /// 停止录制并以JSON返回。
+    #[wasm_bindgen(js_name = stopInputRecordingJson)]
+    pub fn stop_input_recording_json(&mut self) -> std::result::Result<Option<String>, JsValue> {
+        self.engine
+            .input_mut()
+            .stop_recording()
+            .map(|recording| recording.to_json())
+            .transpose()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Check if input is being recorded.
+    /// 检查是否正在录制输入。
+    #[wasm_bindgen(js_name = isRecordingInput)]
+    pub fn is_recording_input(&self) -> bool {
+        self.engine.input().is_recording()
+    }
+
+    /// Replay a binary recording in place of live input, one frame per `updateInput`.
+    /// 代替实时输入回放二进制录制，每次`updateInput`一帧。
+    ///
+    /// Use a fixed timestep while replaying to reproduce the session exactly.
+    /// 回放时使用固定时间步长以精确重现会话。
+    #[wasm_bindgen(js_name = startInputReplay)]
+    pub fn start_input_replay(&mut self, data: &[u8]) -> std::result::Result<(), JsValue> {
+        let recording = input::InputRecording::from_bytes(data).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.engine.input_mut().start_replay(recording);
+        Ok(())
+    }
+
+    /// Replay a JSON recording in place of live input.
+    /// 代替实时输入回放JSON录制。
+    #[wasm_bindgen(js_name = startInputReplayJson)]
+    pub fn start_input_replay_json(&mut self, json: &str) -> std::result::Result<(), JsValue> {
+        let recording = input::InputRecording::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.engine.input_mut().start_replay(recording);
+        Ok(())
+    }
+
+    /// Stop replaying and return to live input.
+    /// 停止回放并恢复实时输入。
+    #[wasm_bindgen(js_name = stopInputReplay)]
+    pub fn stop_input_replay(&mut self) {
+        self.engine.input_mut().stop_replay();
+    }
+
+    /// Check if a replay is in progress; false once it has finished.
+    /// 检查是否正在回放；回放结束后为false。
+    #[wasm_bindgen(js_name = isReplayingInput)]
+    pub fn is_replaying_input(&self) -> bool {
+        self.engine.input().is_replaying()
+    }
+
+    /// Next frame index of the replay in progress, or -1.
+    /// 进行中回放的下一帧索引，无回放时为-1。
+    #[wasm_bindgen(js_name = getInputReplayFrame)]
+    pub fn get_input_replay_frame(&self) -> i32 {
+        self.engine.input().replay().map_or(-1, |replay| replay.frame() as i32)
+    }
+
+    /// Inject a key event, e.g. from a host without DOM listeners.
+    /// 注入按键事件，例如来自没有DOM监听器的宿主。
+    ///
+    /// Injected events are applied on the next `updateInput`, like DOM events.
+    /// 注入的事件与DOM事件一样在下一次`updateInput`时应用。
+    ///
+    /// # Arguments | 参数
+    /// * `key_code` - Key code as in `KeyboardEvent.code` | 与`KeyboardEvent.code`相同的键码
+    /// * `down` - Pressed or released | 按下或释放
+    #[wasm_bindgen(js_name = injectKeyEvent)]
+    pub fn inject_key_event(&self, key_code: &str, down: bool) {
+        let code = key_code.to_string();
+        self.engine.push_input_event(if down { input::InputEvent::KeyDown(code) } else { input::InputEvent::KeyUp(code) });
+    }
+
+    /// Inject a mouse move in canvas pixels.
+    /// 注入canvas像素坐标的鼠标移动。
+    #[wasm_bindgen(js_name = injectMouseMove)]
+    pub fn inject_mouse_move(&self, x: f32, y: f32) {
+        self.engine.push_input_event(input::InputEvent::MouseMove { x, y });
+    }
+
+    /// Inject a mouse button press or release (0 = left, 1 = middle, 2 = right).
+    /// 注入鼠标按钮按下或释放（0 = 左，1 = 中，2 = 右）。
+    #[wasm_bindgen(js_name = injectMouseButton)]
+    pub fn inject_mouse_button(&self, button: i16, down: bool) {
+        self.engine.push_input_event(if down { input::InputEvent::MouseDown(button) } else { input::InputEvent::MouseUp(button) });
+    }
+
+    /// Inject a wheel scroll in pixels (positive = down).
+    /// 注入以像素为单位的滚轮滚动（正值 = 向下）。
+    #[wasm_bindgen(js_name = injectWheel)]
+    pub fn inject_wheel(&self, delta: f32) {
+        self.engine.push_input_event(input::InputEvent::Wheel(delta));
+    }
+
+    /// Inject a touch event in canvas pixels.
+    /// 注入canvas像素坐标的触摸事件。
+    ///
+    /// # Arguments | 参数
+    /// * `phase` - 0 = start, 1 = move, 2 = end or cancel | 0 = 开始，1 = 移动，2 = 结束或取消
+    /// * `id` - Touch identifier | 触摸标识符
+    /// * `x`, `y` - Position | 位置
+    #[wasm_bindgen(js_name = injectTouchEvent)]
+    pub fn inject_touch_event(&self, phase: u8, id: i32, x: f32, y: f32) {
+        let event = match phase {
+            0 => input::InputEvent::TouchStart { id, x, y },
+            1 => input::InputEvent::TouchMove { id, x, y },
+            _ => input::InputEvent::TouchEnd(id),
+        };
+        self.engine.push_input_event(event);
+    }
+
+    /// Resize viewport.
+    /// 调整视口大小。
+    ///
+    /// # Arguments | 参数
+    /// * `width` - New viewport width | 新视口宽度
+    /// * `height` - New viewport height | 新视口高度
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.engine.resize(width as f32, height as f32);
+    }
+
+    /// Set camera position, zoom, and rotation.
+    /// 设置相机位置、缩放和旋转。
+    ///
+    /// # Arguments | 参数
+    /// * `x` - Camera X position | 相机X位置
+    /// * `y` - Camera Y position | 相机Y位置
+    /// * `zoom` - Zoom level | 缩放级别
+    /// * `rotation` - Rotation in radians | 旋转角度（弧度）
+    #[wasm_bindgen(js_name = setCamera)]
+    pub fn set_camera(&mut self, x: f32, y: f32, zoom: f32, rotation: f32) {
+        self.engine.set_camera(x, y, zoom, rotation);
+    }
+
+    /// Get camera state.
+    /// 获取相机状态。
+    ///
+    /// # Returns | 返回
+    /// Array of [x, y, zoom, rotation] | 数组 [x, y, zoom, rotation]
+    #[wasm_bindgen(js_name = getCamera)]
+    pub fn get_camera(&self) -> Vec<f32> {
+        let (x, y, zoom, rotation) = self.engine.get_camera();
+        vec![x, y, zoom, rotation]
+    }
+
+    /// Follow a target; call every frame with the target's new position.
+    /// 跟随目标；每帧使用目标的新位置调用。
+    ///
+    /// # Arguments | 参数
+    /// * `x`, `y` - Target position | 目标位置
+    /// * `dead_zone_x`, `dead_zone_y` - Half size of the dead zone in world units | 死区半尺寸（世界单位）
+    /// * `smooth_time` - Damping in seconds (0 = snap) | 阻尼秒数（0 = 立即到达）
+    #[wasm_bindgen(js_name = setCameraFollow)]
+    pub fn set_camera_follow(&mut self, x: f32, y: f32, dead_zone_x: f32, dead_zone_y: f32, smooth_time: f32) {
+        self.engine.camera_mut().follow = Some(renderer::CameraFollow {
+            target: math::Vec2::new(x, y),
+            dead_zone: math::Vec2::new(dead_zone_x, dead_zone_y),
+            smooth_time,
+        });
+    }
+
+    /// Stop following.
+    /// 停止跟随。
+    #[wasm_bindgen(js_name = clearCameraFollow)]
+    pub fn clear_camera_follow(&mut self) {
+        self.engine.camera_mut().follow = None;
+    }
+
+    /// Keep the view inside a world rectangle.
+    /// 使视图保持在世界矩形内。
+    ///
+    /// # Arguments | 参数
+    /// * `x`, `y` - Bottom-left corner | 左下角
+    /// * `width`, `height` - Size | 尺寸
+    #[wasm_bindgen(js_name = setCameraBounds)]
+    pub fn set_camera_bounds(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.engine.camera_mut().bounds = Some(math::Rect::new(x, y, width, height));
+    }
+
+    /// Remove the camera bounds.
+    /// 移除相机边界。
+    #[wasm_bindgen(js_name = clearCameraBounds)]
+    pub fn clear_camera_bounds(&mut self) {
+        self.engine.camera_mut().bounds = None;
+    }
+
+    /// Configure screen shake.
+    /// 配置屏幕震动。
+    ///
+    /// # Arguments | 参数
+    /// * `max_offset` - Largest offset in screen pixels | 最大偏移（屏幕像素）
+    /// * `max_angle` - Largest rotation in radians | 最大旋转（弧度）
+    /// * `frequency` - Noise samples per second | 每秒噪声采样数
+    /// * `decay` - Trauma lost per second | 每秒损失的创伤值
+    #[wasm_bindgen(js_name = setCameraShake)]
+    pub fn set_camera_shake(&mut self, max_offset: f32, max_angle: f32, frequency: f32, decay: f32) {
+        let shake = &mut self.engine.camera_mut().shake;
+        shake.max_offset = math::Vec2::splat(max_offset);
+        shake.max_angle = max_angle;
+        shake.frequency = frequency;
+        shake.decay = decay;
+    }
+
+    /// Add screen shake trauma (0-1); the shake strength is trauma squared.
+    /// 增加屏幕震动创伤值（0-1）；震动强度为创伤值的平方。
+    #[wasm_bindgen(js_name = addCameraTrauma)]
+    pub fn add_camera_trauma(&mut self, amount: f32) {
+        self.engine.camera_mut().shake.add_trauma(amount);
+    }
+
+    /// Zoom smoothly while keeping a world point fixed on screen.
+    /// 平滑缩放，同时保持某个世界点在屏幕上的位置不变。
+    ///
+    /// # Arguments | 参数
+    /// * `zoom` - Target zoom level | 目标缩放级别
+    /// * `anchor_x`, `anchor_y` - World point kept fixed | 保持不动的世界点
+    /// * `smooth_time` - Damping in seconds (0 = instant) | 阻尼秒数（0 = 立即）
+    #[wasm_bindgen(js_name = zoomCameraTo)]
+    pub fn zoom_camera_to(&mut self, zoom: f32, anchor_x: f32, anchor_y: f32, smooth_time: f32) {
+        self.engine
+            .camera_mut()
+            .zoom_to(zoom, math::Vec2::new(anchor_x, anchor_y), smooth_time);
+    }
+
+    /// Snap the rendered camera position to whole screen pixels.
+    /// 将渲染的相机位置对齐到整数屏幕像素。
+    #[wasm_bindgen(js_name = setCameraPixelSnap)]
+    pub fn set_camera_pixel_snap(&mut self, enabled: bool) {
+        self.engine.camera_mut().pixel_snap = enabled;
+    }
+
+    /// Apply camera behaviors for one frame; call before `render`.
+    /// 应用一帧的相机行为；在`render`之前调用。
+    #[wasm_bindgen(js_name = updateCamera)]
+    pub fn update_camera(&mut self, delta_seconds: f32) {
+        self.engine.update_camera(delta_seconds);
+    }
+
+    /// Convert screen coordinates to world coordinates.
+    /// 将屏幕坐标转换为世界坐标。
+    ///
+    /// # Arguments | 参数
+    /// * `screen_x` - Screen X coordinate (0 = left edge of canvas)
+    /// * `screen_y` - Screen Y coordinate (0 = top edge of canvas)
+    ///
+    /// # Returns | 返回
+    /// Array of [world_x, world_y] | 数组 [world_x, world_y]
+    #[wasm_bindgen(js_name = screenToWorld)]
+    pub fn screen_to_world(&self, screen_x: f32, screen_y: f32) -> Vec<f32> {
+        let (x, y) = self.engine.screen_to_world(screen_x, screen_y);
+        vec![x, y]
+    }
+
+    /// Convert world coordinates to screen coordinates.
+    /// 将世界坐标转换为屏幕坐标。
+    ///
+    /// # Arguments | 参数
+    /// * `world_x` - World X coordinate
+    /// * `world_y` - World Y coordinate
+    ///
+    /// # Returns | 返回
+    /// Array of [screen_x, screen_y] | 数组 [screen_x, screen_y]
+    #[wasm_bindgen(js_name = worldToScreen)]
+    pub fn world_to_screen(&self, world_x: f32, world_y: f32) -> Vec<f32> {
+        let (x, y) = self.engine.world_to_screen(world_x, world_y);
+        vec![x, y]
+    }
+
+    /// Set grid visibility.
+    /// 设置网格可见性。
+    #[wasm_bindgen(js_name = setShowGrid)]
+    pub fn set_show_grid(&mut self, show: bool) {
+        self.engine.set_show_grid(show);
+    }
+
+    /// Set clear color (background color).
+    /// 设置清除颜色（背景颜色）。
+    ///
+    /// # Arguments | 参数
+    /// * `r`, `g`, `b`, `a` - Color components (0.0-1.0) | 颜色分量 (0.0-1.0)
+    #[wasm_bindgen(js_name = setClearColor)]
+    pub fn set_clear_color(&mut self, r: f32, g: f32, b: f32, a: f32) {
+        self.engine.set_clear_color(r, g, b, a);
+    }
+
+    /// Add a rectangle gizmo outline.
+    /// 添加矩形Gizmo边框。
+    ///
+    /// # Arguments | 参数
+    /// * `x` - Center X position | 中心X位置
+    /// * `y` - Center Y position | 中心Y位置
+    /// * `width` - Rectangle width | 矩形宽度
+    /// * `height` - Rectangle height | 矩形高度
+    /// * `rotation` - Rotation in radians | 旋转角度（弧度）
+    /// * `origin_x` - Origin X (0-1) | 原点X (0-1)
+    /// * `origin_y` - Origin Y (0-1) | 原点Y (0-1)
+    /// * `r`, `g`, `b`, `a` - Color (0.0-1.0) | 颜色
+    /// * `show_handles` - Whether to show transform handles | 是否显示变换手柄
+    #[wasm_bindgen(js_name = addGizmoRect)]
+    pub fn add_gizmo_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        rotation: f32,
+        origin_x: f32,
+        origin_y: f32,
+        r: f32,
+        g: f32,
+        b: f32,
+        a: f32,
+        show_handles: bool,
+    ) {
+        self.engine.add_gizmo_rect(x, y, width, height, rotation, origin_x, origin_y, r, g, b, a, show_handles);
+    }
+
+    /// Add a circle gizmo outline.
+    /// 添加圆形Gizmo边框。
+    #[wasm_bindgen(js_name = addGizmoCircle)]
+    pub fn add_gizmo_circle(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        r: f32,
+        g: f32,
+        b: f32,
+        a: f32,
+    ) {
+        self.engine.add_gizmo_circle(x, y, radius, r, g, b, a);
+    }
+
+    /// Add a line gizmo.
+    /// 添加线条Gizmo。
+    #[wasm_bindgen(js_name = addGizmoLine)]
+    pub fn add_gizmo_line(
+        &mut self,
+        points: Vec<f32>,
+        r: f32,
+        g: f32,
+        b: f32,
+        a: f32,
+        closed: bool,
+    ) {
+        self.engine.add_gizmo_line(points, r, g, b, a, closed);
+    }
+
+    /// Add a capsule gizmo outline.
+    /// 添加胶囊Gizmo边框。
+    #[wasm_bindgen(js_name = addGizmoCapsule)]
+    pub fn add_gizmo_capsule(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        half_height: f32,
+        rotation: f32,
+        r: f32,
+        g: f32,
+        b: f32,
+        a: f32,
+    ) {
+        self.engine.add_gizmo_capsule(x, y, radius, half_height, rotation, r, g, b, a);
+    }
+
+    /// Set transform tool mode.
+    /// 设置变换工具模式。
+    ///
+    /// # Arguments | 参数
+    /// * `mode` - 0=Select, 1=Move, 2=Rotate, 3=Scale
+    #[wasm_bindgen(js_name = setTransformMode)]
+    pub fn set_transform_mode(&mut self, mode: u8) {
+        self.engine.set_transform_mode(mode);
+    }
+
+    /// Set gizmo visibility.
+    /// 设置辅助工具可见性。
+    #[wasm_bindgen(js_name = setShowGizmos)]
+    pub fn set_show_gizmos(&mut self, show: bool) {
+        self.engine.set_show_gizmos(show);
+    }
+
+    /// Set editor mode.
+    /// 设置编辑器模式。
+    ///
+    /// When false (runtime mode), editor-only UI like grid, gizmos,
+    /// and axis indicator are automatically hidden.
+    /// 当为 false（运行时模式）时，编辑器专用 UI（如网格、gizmos、坐标轴指示器）会自动隐藏。
+    #[wasm_bindgen(js_name = setEditorMode)]
+    pub fn set_editor_mode(&mut self, is_editor: bool) {
+        self.engine.set_editor_mode(is_editor);
+    }
+
+    /// Get editor mode.
+    /// 获取编辑器模式。
+    #[wasm_bindgen(js_name = isEditorMode)]
+    pub fn is_editor_mode(&self) -> bool {
+        self.engine.is_editor()
+    }
+
+    /// Enable or disable instanced sprite rendering (enabled by default).
+    /// 启用或禁用实例化精灵渲染（默认启用）。
+    ///
+    /// Sprites using custom shaders always take the non-instanced path.
+    /// 使用自定义着色器的精灵始终使用非实例化路径。
+    #[wasm_bindgen(js_name = setInstancingEnabled)]
+    pub fn set_instancing_enabled(&mut self, enabled: bool) {
+        self.engine.set_instancing(enabled);
+    }
+
+    // ===== Multi-viewport API =====
+    // ===== 多视口 API =====
+
+    /// Register a new viewport.
+    /// 注册新视口。
+    ///
+    /// # Arguments | 参数
+    /// * `id` - Unique viewport identifier | 唯一视口标识符
+    /// * `canvas_id` - HTML canvas element ID | HTML canvas元素ID
+    #[wasm_bindgen(js_name = registerViewport)]
+    pub fn register_viewport(&mut self, id: &str, canvas_id: &str) -> std::result::Result<(), JsValue> {
+        self.engine
+            .register_viewport(id, canvas_id)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Unregister a viewport.
+    /// 注销视口。
+    #[wasm_bindgen(js_name = unregisterViewport)]
+    pub fn unregister_viewport(&mut self, id: &str) {
+        self.engine.unregister_viewport(id);
+    }
+
+    /// Set the active viewport.
+    /// 设置活动视口。
+    #[wasm_bindgen(js_name = setActiveViewport)]
+    pub fn set_active_viewport(&mut self, id: &str) -> bool {
+        self.engine.set_active_viewport(id)
+    }
+
+    /// Set camera for a specific viewport.
+    /// 为特定视口设置相机。
+    #[wasm_bindgen(js_name = setViewportCamera)]
+    pub fn set_viewport_camera(&mut self, viewport_id: &str, x: f32, y: f32, zoom: f32, rotation: f32) {
+        self.engine.set_viewport_camera(viewport_id, x, y, zoom, rotation);
+    }
+
+    /// Get camera for a specific viewport.
+    /// 获取特定视口的相机。
+    #[wasm_bindgen(js_name = getViewportCamera)]
+    pub fn get_viewport_camera(&self, viewport_id: &str) -> Option<Vec<f32>> {
+        self.engine
+            .get_viewport_camera(viewport_id)
+            .map(|(x, y, zoom, rotation)| vec![x, y, zoom, rotation])
+    }
+
+    /// Set viewport configuration.
+    /// 设置视口配置。
+    #[wasm_bindgen(js_name = setViewportConfig)]
+    pub fn set_viewport_config(&mut self, viewport_id: &str, show_grid: bool, show_gizmos: bool) {
+        self.engine.set_viewport_config(viewport_id, show_grid, show_gizmos);
+    }
+
+    /// Resize a specific viewport.
+    /// 调整特定视口大小。
+    #[wasm_bindgen(js_name = resizeViewport)]
+    pub fn resize_viewport(&mut self, viewport_id: &str, width: u32, height: u32) {
+        self.engine.resize_viewport(viewport_id, width, height);
+    }
+
+    /// Render to a specific viewport.
+    /// 渲染到特定视口。
+    #[wasm_bindgen(js_name = renderToViewport)]
+    pub fn render_to_viewport(&mut self, viewport_id: &str) -> std::result::Result<(), JsValue> {
+        self.engine
+            .render_to_viewport(viewport_id)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Get all registered viewport IDs.
+    /// 获取所有已注册的视口ID。
+    #[wasm_bindgen(js_name = getViewportIds)]
+    pub fn get_viewport_ids(&self) -> Vec<String> {
+        self.engine.viewport_ids()
+    }
+
+    // ===== Render Texture API =====
+    // ===== 渲染纹理 API =====
+
+    /// Create an offscreen render texture.
+    /// 创建离屏渲染纹理。
+    ///
+    /// The returned ID is a regular texture ID: sprites can sample it and
+    /// render passes can target it.
+    /// 返回的ID是普通纹理ID：精灵可以采样它，渲染通道可以以它为目标。
+    ///
+    /// # Arguments | 参数
+    /// * `width` - Width in pixels | 宽度（像素）
+    /// * `height` - Height in pixels | 高度（像素）
+    #[wasm_bindgen(js_name = createRenderTexture)]
+    pub fn create_render_texture(&mut self, width: u32, height: u32) -> std::result::Result<u32, JsValue> {
+        self.engine
+            .create_render_texture(width, height)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Resize a render texture. Contents are discarded.
+    /// 调整渲染纹理大小。内容会被丢弃。
+    #[wasm_bindgen(js_name = resizeRenderTexture)]
+    pub fn resize_render_texture(&mut self, id: u32, width: u32, height: u32) -> std::result::Result<bool, JsValue> {
+        self.engine
+            .resize_render_texture(id, width, height)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Remove a render texture and any passes targeting it.
+    /// 移除渲染纹理及所有以其为目标的通道。
+    #[wasm_bindgen(js_name = removeRenderTexture)]
+    pub fn remove_render_texture(&mut self, id: u32) -> bool {
+        self.engine.remove_render_texture(id)
+    }
+
+    /// Give a render texture its own camera (e.g. for a minimap).
+    /// 为渲染纹理设置独立相机（例如小地图）。
+    ///
+    /// Without one, the render texture follows the main camera.
+    /// 未设置时，渲染纹理跟随主相机。
+    #[wasm_bindgen(js_name = setRenderTextureCamera)]
+    pub fn set_render_texture_camera(&mut self, id: u32, x: f32, y: f32, zoom: f32, rotation: f32) -> bool {
+        self.engine.set_render_texture_camera(id, x, y, zoom, rotation)
+    }
+
+    /// Make a render texture follow the main camera again.
+    /// 使渲染纹理重新跟随主相机。
+    #[wasm_bindgen(js_name = clearRenderTextureCamera)]
+    pub fn clear_render_texture_camera(&mut self, id: u32) -> bool {
+        self.engine.clear_render_texture_camera(id)
+    }
+
+    /// Set a render texture's clear color.
+    /// 设置渲染纹理的清除颜色。
+    #[wasm_bindgen(js_name = setRenderTextureClearColor)]
+    pub fn set_render_texture_clear_color(&mut self, id: u32, r: f32, g: f32, b: f32, a: f32) -> bool {
+        self.engine.set_render_texture_clear_color(id, r, g, b, a)
+    }
+
+    // ===== Frame Graph API =====
+    // ===== 帧图 API =====
+
+    /// Add a render pass, replacing any pass with the same name.
+    /// 添加渲染通道，替换同名通道。
+    ///
+    /// The default passes are "grid", "world", "lighting", "post", "ui" and "gizmos".
+    /// World passes draw every sorting layer; limit them with `setRenderPassLayers`.
+    /// 默认通道为 "grid"、"world"、"lighting"、"post"、"ui" 和 "gizmos"。
+    /// World通道绘制所有排序层；可通过`setRenderPassLayers`限制。
+    ///
+    /// # Arguments | 参数
+    /// * `name` - Unique pass name | 唯一通道名称
+    /// * `kind` - 0=Grid, 1=World, 2=Gizmos, 3=Blit, 4=PostProcess, 5=Lighting | 通道类型
+    /// * `target_id` - Render texture to draw into, or undefined for the screen | 目标渲染纹理，undefined表示屏幕
+    /// * `source_id` - Texture to copy (Blit only) | 要拷贝的纹理（仅Blit）
+    /// * `clear` - Clear the target first | 是否先清除目标
+    /// * `before` - Insert before this pass, or undefined to append | 插入到此通道之前，undefined表示追加
+    #[wasm_bindgen(js_name = addRenderPass)]
+    pub fn add_render_pass(
+        &mut self,
+        name: &str,
+        kind: u8,
+        target_id: Option<u32>,
+        source_id: u32,
+        clear: bool,
+        before: Option<String>,
+    ) -> std::result::Result<(), JsValue> {
+        let kind = renderer::RenderPassKind::from_code(kind, source_id)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown render pass kind: {}", kind)))?;
+        let pass = renderer::RenderPass::new(name, kind)
+            .with_target(target_id)
+            .with_clear(clear);
+        self.engine.add_render_pass(pass, before.as_deref());
+        Ok(())
+    }
+
+    /// Remove a render pass by name.
+    /// 按名称移除渲染通道。
+    #[wasm_bindgen(js_name = removeRenderPass)]
+    pub fn remove_render_pass(&mut self, name: &str) -> bool {
+        self.engine.remove_render_pass(name)
+    }
+
+    /// Enable or disable a render pass.
+    /// 启用或禁用渲染通道。
+    #[wasm_bindgen(js_name = setRenderPassEnabled)]
+    pub fn set_render_pass_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        self.engine.set_render_pass_enabled(name, enabled)
+    }
+
+    /// Limit a World pass to a range of sorting layers.
+    /// 将World通道限制在排序层范围内。
+    ///
+    /// By default "world" draws layers below 200 (the UI layer) and "ui" draws the rest.
+    /// 默认 "world" 绘制低于200（UI层）的层，"ui" 绘制其余层。
+    ///
+    /// # Arguments | 参数
+    /// * `name` - Pass name | 通道名称
+    /// * `min_layer` - Lowest sorting layer drawn | 绘制的最低排序层
+    /// * `max_layer` - Highest sorting layer drawn | 绘制的最高排序层
+    #[wasm_bindgen(js_name = setRenderPassLayers)]
+    pub fn set_render_pass_layers(&mut self, name: &str, min_layer: i32, max_layer: i32) -> bool {
+        self.engine.set_render_pass_layers(name, min_layer, max_layer)
+    }
+
+    /// Restore the default grid → world → lighting → post → ui → gizmos passes.
+    /// 恢复默认的 网格 → 世界 → 光照 → 后处理 → UI → Gizmo 通道。
+    #[wasm_bindgen(js_name = resetRenderPasses)]
+    pub fn reset_render_passes(&mut self) {
+        self.engine.reset_render_passes();
+    }
+
+    /// Get render pass names in execution order.
+    /// 按执行顺序获取渲染通道名称。
+    #[wasm_bindgen(js_name = getRenderPasses)]
+    pub fn get_render_passes(&self) -> Vec<String> {
+        self.engine.frame_graph().passes().iter().map(|p| p.name.clone()).collect()
+    }
+
+    // ===== Post Processing API =====
+    // ===== 后处理 API =====
+
+    /// Add a post-processing effect, replacing an effect with the same name.
+    /// 添加后处理效果，替换同名效果。
+    ///
+    /// Effects run in the "post" render pass, in the order they were added.
+    /// 效果在 "post" 渲染通道中按添加顺序执行。
+    ///
+    /// # Arguments | 参数
+    /// * `viewport_id` - Viewport ID, or undefined for the main canvas | 视口ID，undefined表示主画布
+    /// * `name` - Unique effect name | 唯一效果名称
+    /// * `kind` - 0=Bloom, 1=ColorGrading, 2=Vignette, 3=Pixelate, 4=CRT, 5=Custom | 效果类型
+    /// * `param` - LUT texture ID for ColorGrading (0 = none), shader ID for Custom | ColorGrading的LUT纹理ID（0表示无），Custom的着色器ID
+    #[wasm_bindgen(js_name = addPostEffect)]
+    pub fn add_post_effect(
+        &mut self,
+        viewport_id: Option<String>,
+        name: &str,
+        kind: u8,
+        param: u32,
+    ) -> std::result::Result<(), JsValue> {
+        let kind = renderer::PostEffectKind::from_code(kind, param)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown post effect kind: {}", kind)))?;
+        if self.engine.add_post_effect(viewport_id.as_deref(), renderer::PostEffect::new(name, kind)) {
+            Ok(())
+        } else {
+            Err(JsValue::from_str(&format!("Viewport not found: {}", viewport_id.unwrap_or_default())))
+        }
+    }
+
+    /// Remove a post-processing effect.
+    /// 移除后处理效果。
+    #[wasm_bindgen(js_name = removePostEffect)]
+    pub fn remove_post_effect(&mut self, viewport_id: Option<String>, name: &str) -> bool {
+        self.engine.remove_post_effect(viewport_id.as_deref(), name)
+    }
+
+    /// Enable or disable a post-processing effect.
+    /// 启用或禁用后处理效果。
+    #[wasm_bindgen(js_name = setPostEffectEnabled)]
+    pub fn set_post_effect_enabled(&mut self, viewport_id: Option<String>, name: &str, enabled: bool) -> bool {
+        self.engine.set_post_effect_enabled(viewport_id.as_deref(), name, enabled)
+    }
+
+    /// Set a post-processing effect uniform.
+    /// 设置后处理效果的uniform。
+    ///
+    /// # Arguments | 参数
+    /// * `values` - 1 to 4 floats, set as float/vec2/vec3/vec4 | 1到4个浮点数，对应float/vec2/vec3/vec4
+    #[wasm_bindgen(js_name = setPostEffectUniform)]
+    pub fn set_post_effect_uniform(
+        &mut self,
+        viewport_id: Option<String>,
+        name: &str,
+        uniform: &str,
+        values: &[f32],
+    ) -> bool {
+        let value = match *values {
+            [x] => renderer::UniformValue::Float(x),
+            [x, y] => renderer::UniformValue::Vec2([x, y]),
+            [x, y, z] => renderer::UniformValue::Vec3([x, y, z]),
+            [x, y, z, w] => renderer::UniformValue::Vec4([x, y, z, w]),
+            _ => return false,
+        };
+        self.engine.set_post_effect_uniform(viewport_id.as_deref(), name, uniform, value)
+    }
+
+    /// Remove all post-processing effects.
+    /// 移除所有后处理效果。
+    #[wasm_bindgen(js_name = clearPostEffects)]
+    pub fn clear_post_effects(&mut self, viewport_id: Option<String>) -> bool {
+        self.engine.clear_post_effects(viewport_id.as_deref())
+    }
+
+    /// Get post-processing effect names in execution order.
+    /// 按执行顺序获取后处理效果名称。
+    #[wasm_bindgen(js_name = getPostEffects)]
+    pub fn get_post_effects(&self, viewport_id: Option<String>) -> Vec<String> {
+        self.engine.post_effect_names(viewport_id.as_deref())
+    }
+
+    // ===== Lighting API =====
+    // ===== 光照 API =====
+
+    /// Submit lights for this frame.
+    /// 提交本帧的光源。
+    ///
+    /// # Arguments | 参数
+    /// * `data` - Float32Array, 12 floats per light:
+    ///   [kind, x, y, height, r, g, b, intensity, radius, direction, coneAngle, softness]
+    ///   kind: 0=Point, 1=Spot, 2=Global (ambient)
+    ///   每个光源12个浮点数；kind: 0=点光源, 1=聚光灯, 2=全局（环境光）
+    #[wasm_bindgen(js_name = submitLights)]
+    pub fn submit_lights(&mut self, data: &[f32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .submit_lights(data)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Submit shadow-casting polygons for this frame.
+    /// 提交本帧投射阴影的多边形。
+    ///
+    /// # Arguments | 参数
+    /// * `points` - Float32Array of [x, y] points of all polygons | 所有多边形的[x, y]点
+    /// * `counts` - Uint32Array of point counts per polygon | 每个多边形的点数
+    #[wasm_bindgen(js_name = submitOccluders)]
+    pub fn submit_occluders(&mut self, points: &[f32], counts: &[u32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .submit_occluders(points, counts)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Assign a normal map to a texture (0 removes it).
+    /// 为纹理指定法线贴图（0表示移除）。
+    ///
+    /// Sprites using `texture_id` are shaded with the normal map when lit.
+    /// 使用`texture_id`的精灵在被照亮时使用法线贴图着色。
+    #[wasm_bindgen(js_name = setNormalMap)]
+    pub fn set_normal_map(&mut self, texture_id: u32, normal_texture_id: u32) {
+        let normal = if normal_texture_id == 0 { None } else { Some(normal_texture_id) };
+        self.engine.set_normal_map(texture_id, normal);
+    }
+
+    /// Enable or disable lighting for a viewport (undefined = main canvas).
+    /// 为视口启用或禁用光照（undefined表示主画布）。
+    #[wasm_bindgen(js_name = setLightingEnabled)]
+    pub fn set_lighting_enabled(&mut self, viewport_id: Option<String>, enabled: bool) -> bool {
+        self.engine.set_lighting_enabled(viewport_id.as_deref(), enabled)
+    }
+
+    /// Set the light size used for soft shadows, in world units.
+    /// 设置软阴影使用的光源尺寸（世界单位）。
+    #[wasm_bindgen(js_name = setShadowSoftness)]
+    pub fn set_shadow_softness(&mut self, softness: f32) {
+        self.engine.set_shadow_softness(softness);
+    }
+
+    // ===== Tilemap API =====
+    // ===== 瓦片地图 API =====
+
+    /// Register a tileset texture, returning its ID.
+    /// 注册瓦片集纹理，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `texture_id` - Texture or atlas region ID | 纹理或图集区域ID
+    /// * `tile_width`, `tile_height` - Tile size in texels | 瓦片尺寸（纹素）
+    /// * `margin` - Border around the tile grid in texels | 瓦片网格周围边距（纹素）
+    /// * `spacing` - Gap between tiles in texels | 瓦片间距（纹素）
+    #[wasm_bindgen(js_name = registerTileset)]
+    pub fn register_tileset(&mut self, texture_id: u32, tile_width: u32, tile_height: u32, margin: u32, spacing: u32) -> u32 {
+        self.engine
+            .add_tileset(renderer::Tileset::new(texture_id, tile_width, tile_height, margin, spacing))
+    }
+
+    /// Animate a tile of a tileset (empty frames stop the animation).
+    /// 为瓦片集的瓦片设置动画（空帧表示停止动画）。
+    ///
+    /// # Arguments | 参数
+    /// * `tileset_id` - Tileset ID | 瓦片集ID
+    /// * `tile` - Local index of the animated tile | 动画瓦片的局部索引
+    /// * `frames` - Local tile indices of the frames | 各帧的局部瓦片索引
+    /// * `frame_duration` - Seconds per frame | 每帧秒数
+    #[wasm_bindgen(js_name = setTileAnimation)]
+    pub fn set_tile_animation(&mut self, tileset_id: u32, tile: u32, frames: Vec<u32>, frame_duration: f32) -> bool {
+        let animation = if frames.is_empty() {
+            None
+        } else {
+            Some(renderer::TileAnimation { frames, frame_duration })
+        };
+        self.engine.set_tile_animation(tileset_id, tile, animation)
+    }
+
+    /// Create an empty tilemap layer, returning its ID.
+    /// 创建空瓦片地图层，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `width`, `height` - Size in tiles | 尺寸（瓦片数）
+    /// * `tile_width`, `tile_height` - Tile size in world units | 瓦片的世界单位尺寸
+    #[wasm_bindgen(js_name = createTilemapLayer)]
+    pub fn create_tilemap_layer(&mut self, width: u32, height: u32, tile_width: f32, tile_height: f32) -> u32 {
+        let layer = renderer::TilemapLayer::new(width, height, math::Vec2::new(tile_width, tile_height));
+        self.engine.create_tilemap_layer(layer)
+    }
+
+    /// Remove a tilemap layer.
+    /// 移除瓦片地图层。
+    #[wasm_bindgen(js_name = removeTilemapLayer)]
+    pub fn remove_tilemap_layer(&mut self, layer_id: u32) -> bool {
+        self.engine.remove_tilemap_layer(layer_id)
+    }
+
+    /// Use a tileset for tile IDs starting at `first_gid`.
+    /// 对从`first_gid`开始的瓦片ID使用瓦片集。
+    #[wasm_bindgen(js_name = setTilemapTileset)]
+    pub fn set_tilemap_tileset(&mut self, layer_id: u32, first_gid: u32, tileset_id: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .map(|layer| layer.set_tileset(first_gid, tileset_id))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Upload all tiles of a layer.
+    /// 上传层的所有瓦片。
+    ///
+    /// # Arguments | 参数
+    /// * `layer_id` - Layer ID | 层ID
+    /// * `tiles` - Uint32Array of tile GIDs, row by row from the top (0 = empty);
+    ///             the top three bits flip the tile as in Tiled
+    ///             瓦片GID数组，从顶行开始逐行排列（0 = 空）；最高三位与Tiled一样用于翻转瓦片
+    #[wasm_bindgen(js_name = setTilemapTiles)]
+    pub fn set_tilemap_tiles(&mut self, layer_id: u32, tiles: &[u32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .and_then(|layer| layer.set_tiles(tiles))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Replace a rectangular region of tiles; only the chunks it touches are rebuilt.
+    /// 替换矩形区域的瓦片；仅重建其涉及的分块。
+    ///
+    /// # Arguments | 参数
+    /// * `layer_id` - Layer ID | 层ID
+    /// * `x`, `y` - Top-left tile of the region | 区域左上角瓦片
+    /// * `width` - Region width in tiles | 区域宽度（瓦片数）
+    /// * `tiles` - Uint32Array of region tile GIDs, row by row | 区域瓦片GID数组，逐行排列
+    #[wasm_bindgen(js_name = setTilemapRegion)]
+    pub fn set_tilemap_region(&mut self, layer_id: u32, x: u32, y: u32, width: u32, tiles: &[u32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .and_then(|layer| layer.set_region(x, y, width, tiles))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set a single tile.
+    /// 设置单个瓦片。
+    #[wasm_bindgen(js_name = setTilemapTile)]
+    pub fn set_tilemap_tile(&mut self, layer_id: u32, x: u32, y: u32, gid: u32) -> std::result::Result<(), JsValue> {
+        self.set_tilemap_region(layer_id, x, y, 1, &[gid])
+    }
+
+    /// Set the world position of a layer's top-left corner.
+    /// 设置层左上角的世界坐标。
+    #[wasm_bindgen(js_name = setTilemapLayerPosition)]
+    pub fn set_tilemap_layer_position(&mut self, layer_id: u32, x: f32, y: f32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .map(|layer| layer.position = math::Vec2::new(x, y))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set a layer's draw order relative to sprites (drawn below sprites with equal keys).
+    /// 设置层相对于精灵的绘制顺序（绘制在键相同的精灵之下）。
+    #[wasm_bindgen(js_name = setTilemapLayerSort)]
+    pub fn set_tilemap_layer_sort(&mut self, layer_id: u32, layer: i32, order_in_layer: i32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .map(|tilemap| tilemap.sort = renderer::SortKey::new(layer, order_in_layer))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set a layer's packed RGBA tint color.
+    /// 设置层的打包RGBA着色颜色。
+    #[wasm_bindgen(js_name = setTilemapLayerColor)]
+    pub fn set_tilemap_layer_color(&mut self, layer_id: u32, color: u32) -> std::result::Result<(), JsValue> {
+        let color = math::Color::from_packed(color);
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .map(|layer| layer.set_color([color.r, color.g, color.b, color.a]))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Show or hide a layer.
+    /// 显示或隐藏层。
+    #[wasm_bindgen(js_name = setTilemapLayerVisible)]
+    pub fn set_tilemap_layer_visible(&mut self, layer_id: u32, visible: bool) -> std::result::Result<(), JsValue> {
+        self.engine
+            .tilemap_layer_mut(layer_id)
+            .map(|layer| layer.visible = visible)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Advance tile animations by a frame's delta time.
+    /// 按帧间隔时间推进瓦片动画。
+    #[wasm_bindgen(js_name = updateTilemaps)]
+    pub fn update_tilemaps(&mut self, delta_seconds: f32) {
+        self.engine.update_tilemaps(delta_seconds);
+    }
+
+    // ===== Particle API =====
+    // ===== 粒子 API =====
+
+    /// Create a particle emitter, returning its ID.
+    /// 创建粒子发射器，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `config` - Float32Array [shape, shapeA, shapeB, rate, maxParticles, duration,
+    ///              lifetimeMin, lifetimeMax, speedMin, speedMax, direction, spread,
+    ///              sizeMin, sizeMax, rotationMin, rotationMax, angularMin, angularMax,
+    ///              gravityX, gravityY, damping]; shape 0 = point, 1 = circle(radius),
+    ///              2 = ring(radius), 3 = rectangle(width, height); angles in radians
+    ///              打包的发射器配置；角度为弧度
+    /// * `texture_id` - Particle texture (0 = white) | 粒子纹理（0 = 白色）
+    /// * `material_id` - Material for shader and blend mode (0 = default) | 用于着色器和混合模式的材质（0 = 默认）
+    /// * `seed` - Random seed; equal seeds simulate identically | 随机种子；相同种子模拟结果相同
+    #[wasm_bindgen(js_name = createParticleEmitter)]
+    pub fn create_particle_emitter(
+        &mut self,
+        config: &[f32],
+        texture_id: u32,
+        material_id: u32,
+        seed: u32,
+    ) -> std::result::Result<u32, JsValue> {
+        let config = renderer::EmitterConfig::from_data(config)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let mut emitter = renderer::ParticleEmitter::new(config, seed as u64);
+        emitter.texture_id = texture_id;
+        emitter.material_id = material_id;
+        self.engine
+            .create_particle_emitter(emitter)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Remove a particle emitter.
+    /// 移除粒子发射器。
+    #[wasm_bindgen(js_name = removeParticleEmitter)]
+    pub fn remove_particle_emitter(&mut self, emitter_id: u32) -> bool {
+        self.engine.remove_particle_emitter(emitter_id)
+    }
+
+    /// Move an emitter; live particles stay where they are.
+    /// 移动发射器；存活的粒子保持原位。
+    #[wasm_bindgen(js_name = setParticleEmitterPosition)]
+    pub fn set_particle_emitter_position(&mut self, emitter_id: u32, x: f32, y: f32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .particle_emitter_mut(emitter_id)
+            .map(|emitter| emitter.position = math::Vec2::new(x, y))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set an emitter's draw order relative to sprites (drawn above sprites with equal keys).
+    /// 设置发射器相对于精灵的绘制顺序（绘制在键相同的精灵之上）。
+    #[wasm_bindgen(js_name = setParticleEmitterSort)]
+    pub fn set_particle_emitter_sort(&mut self, emitter_id: u32, layer: i32, order_in_layer: i32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .particle_emitter_mut(emitter_id)
+            .map(|emitter| emitter.sort = renderer::SortKey::new(layer, order_in_layer))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Start or stop rate and burst emission; live particles keep simulating.
+    /// 开始或停止速率和爆发发射；存活的粒子继续模拟。
+    #[wasm_bindgen(js_name = setParticleEmitterPlaying)]
+    pub fn set_particle_emitter_playing(&mut self, emitter_id: u32, playing: bool) -> std::result::Result<(), JsValue> {
+        self.engine
+            .particle_emitter_mut(emitter_id)
+            .map(|emitter| emitter.playing = playing)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Spawn particles immediately.
+    /// 立即生成粒子。
+    #[wasm_bindgen(js_name = emitParticles)]
+    pub fn emit_particles(&mut self, emitter_id: u32, count: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .particle_emitter_mut(emitter_id)
+            .map(|emitter| emitter.emit(count))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Remove all particles and restart the emitter from its seed.
+    /// 移除所有粒子并从种子重新启动发射器。
+    #[wasm_bindgen(js_name = resetParticleEmitter)]
+    pub fn reset_particle_emitter(&mut self, emitter_id: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .particle_emitter_mut(emitter_id)
+            .map(|emitter| emitter.reset())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set the color over lifetime from Float32Array [t, r, g, b, a] keys (t in 0..1).
+    /// 从[t, r, g, b, a]关键帧设置生命周期内的颜色（t在0..1之间）。
+    #[wasm_bindgen(js_name = setParticleColorCurve)]
+    pub fn set_particle_color_curve(&mut self, emitter_id: u32, keys: &[f32]) -> std::result::Result<(), JsValue> {
+        renderer::particles::Curve::from_data(keys)
+            .and_then(|curve| {
+                self.engine
+                    .particle_emitter_mut(emitter_id)
+                    .map(|emitter| emitter.config.color_over_lifetime = curve)
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set the size multiplier over lifetime from Float32Array [t, value] keys.
+    /// 从[t, value]关键帧设置生命周期内的尺寸乘数。
+    #[wasm_bindgen(js_name = setParticleSizeCurve)]
+    pub fn set_particle_size_curve(&mut self, emitter_id: u32, keys: &[f32]) -> std::result::Result<(), JsValue> {
+        renderer::particles::Curve::from_data(keys)
+            .and_then(|curve| {
+                self.engine
+                    .particle_emitter_mut(emitter_id)
+                    .map(|emitter| emitter.config.size_over_lifetime = curve)
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set the velocity multiplier over lifetime from Float32Array [t, value] keys.
+    /// 从[t, value]关键帧设置生命周期内的速度乘数。
+    #[wasm_bindgen(js_name = setParticleSpeedCurve)]
+    pub fn set_particle_speed_curve(&mut self, emitter_id: u32, keys: &[f32]) -> std::result::Result<(), JsValue> {
+        renderer::particles::Curve::from_data(keys)
+            .and_then(|curve| {
+                self.engine
+                    .particle_emitter_mut(emitter_id)
+                    .map(|emitter| emitter.config.speed_over_lifetime = curve)
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Set timed bursts from Float32Array [time, count, cycles, interval] entries (cycles 0 = forever).
+    /// 从[time, count, cycles, interval]条目设置定时爆发（cycles为0表示无限）。
+    #[wasm_bindgen(js_name = setParticleBursts)]
+    pub fn set_particle_bursts(&mut self, emitter_id: u32, bursts: &[f32]) -> std::result::Result<(), JsValue> {
+        renderer::EmitterConfig::parse_bursts(bursts)
+            .and_then(|bursts| {
+                self.engine
+                    .particle_emitter_mut(emitter_id)
+                    .map(|emitter| emitter.set_bursts(bursts))
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Get the number of live particles of an emitter.
+    /// 获取发射器的存活粒子数。
+    #[wasm_bindgen(js_name = getParticleCount)]
+    pub fn get_particle_count(&self, emitter_id: u32) -> u32 {
+        self.engine.particle_count(emitter_id)
+    }
+
+    /// Advance all particle emitters by a frame's delta time.
+    /// 按帧间隔时间推进所有粒子发射器。
+    #[wasm_bindgen(js_name = updateParticles)]
+    pub fn update_particles(&mut self, delta_seconds: f32) {
+        self.engine.update_particles(delta_seconds);
+    }
+
+    // ===== Animation API =====
+    // ===== 动画 API =====
+
+    /// Create an animation clip, returning its ID.
+    /// 创建动画片段，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `texture_ids` - Uint32Array texture or atlas region per frame | 每帧的纹理或图集区域
+    /// * `uvs` - Float32Array [u0, v0, u1, v1] per frame, or empty for whole regions | 每帧的UV，为空表示整个区域
+    /// * `durations` - Float32Array seconds per frame | 每帧秒数
+    /// * `mode` - 0 = loop, 1 = ping-pong, 2 = once | 0 = 循环，1 = 往返，2 = 单次
+    #[wasm_bindgen(js_name = createAnimationClip)]
+    pub fn create_animation_clip(
+        &mut self,
+        texture_ids: &[u32],
+        uvs: &[f32],
+        durations: &[f32],
+        mode: u8,
+    ) -> std::result::Result<u32, JsValue> {
+        let mode = animation::PlayMode::from_code(mode).unwrap_or_default();
+        let clip = animation::AnimationClip::from_data(texture_ids, uvs, durations, mode)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(self.engine.add_animation_clip(clip))
+    }
+
+    /// Remove an animation clip.
+    /// 移除动画片段。
+    #[wasm_bindgen(js_name = removeAnimationClip)]
+    pub fn remove_animation_clip(&mut self, clip_id: u32) -> bool {
+        self.engine.remove_animation_clip(clip_id)
+    }
+
+    /// Raise an event whenever an animator enters a clip frame.
+    /// 每当动画器进入片段的某帧时触发事件。
+    #[wasm_bindgen(js_name = addAnimationEvent)]
+    pub fn add_animation_event(&mut self, clip_id: u32, frame: u32, event_id: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .add_animation_event(clip_id, frame as usize, event_id)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Create an animator playing a clip, returning its ID.
+    /// 创建播放片段的动画器，返回其ID。
+    #[wasm_bindgen(js_name = createAnimator)]
+    pub fn create_animator(&mut self, clip_id: u32) -> std::result::Result<u32, JsValue> {
+        self.engine
+            .create_animator(clip_id)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Remove an animator.
+    /// 移除动画器。
+    #[wasm_bindgen(js_name = removeAnimator)]
+    pub fn remove_animator(&mut self, animator_id: u32) -> bool {
+        self.engine.remove_animator(animator_id)
+    }
+
+    /// Restart an animator with a clip.
+    /// 使用片段重新启动动画器。
+    #[wasm_bindgen(js_name = playAnimation)]
+    pub fn play_animation(&mut self, animator_id: u32, clip_id: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .play_animation(animator_id, clip_id)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Get camera state.
-    /// 获取相机状态。
-    ///
-    /// # Returns | 返回
-    /// Array of [x, y, zoom, rotation] | 数组 [x, y, zoom, rotation]
-    #[wasm_bindgen(js_name = getCamera)]
-    pub fn get_camera(&self) -> Vec<f32> {
-        let (x, y, zoom, rotation) = self.engine.get_camera();
-        vec![x, y, zoom, rotation]
+    /// Set an animator's playback rate multiplier.
+    /// 设置动画器的播放速率倍数。
+    #[wasm_bindgen(js_name = setAnimatorSpeed)]
+    pub fn set_animator_speed(&mut self, animator_id: u32, speed: f32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .animator_mut(animator_id)
+            .map(|animator| animator.set_speed(speed))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Convert screen coordinates to world coordinates.
-    /// 将屏幕坐标转换为世界坐标。
-    ///
-    /// # Arguments | 参数
-    /// * `screen_x` - Screen X coordinate (0 = left edge of canvas)
-    /// * `screen_y` - Screen Y coordinate (0 = top edge of canvas)
-    ///
-    /// # Returns | 返回
-    /// Array of [world_x, world_y] | 数组 [world_x, world_y]
-    #[wasm_bindgen(js_name = screenToWorld)]
-    pub fn screen_to_world(&self, screen_x: f32, screen_y: f32) -> Vec<f32> {
-        let (x, y) = self.engine.screen_to_world(screen_x, screen_y);
-        vec![x, y]
+    /// Pause or resume an animator.
+    /// 暂停或恢复动画器。
+    #[wasm_bindgen(js_name = setAnimatorPaused)]
+    pub fn set_animator_paused(&mut self, animator_id: u32, paused: bool) -> std::result::Result<(), JsValue> {
+        self.engine
+            .animator_mut(animator_id)
+            .map(|animator| animator.set_paused(paused))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Convert world coordinates to screen coordinates.
-    /// 将世界坐标转换为屏幕坐标。
-    ///
-    /// # Arguments | 参数
-    /// * `world_x` - World X coordinate
-    /// * `world_y` - World Y coordinate
-    ///
-    /// # Returns | 返回
-    /// Array of [screen_x, screen_y] | 数组 [screen_x, screen_y]
-    #[wasm_bindgen(js_name = worldToScreen)]
-    pub fn world_to_screen(&self, world_x: f32, world_y: f32) -> Vec<f32> {
-        let (x, y) = self.engine.world_to_screen(world_x, world_y);
-        vec![x, y]
+    /// Frame index shown by an animator (-1 if unknown).
+    /// 动画器显示的帧索引（未知时为-1）。
+    #[wasm_bindgen(js_name = getAnimatorFrame)]
+    pub fn get_animator_frame(&self, animator_id: u32) -> i32 {
+        self.engine.animator_frame(animator_id).map_or(-1, |frame| frame as i32)
     }
 
-    /// Set grid visibility.
-    /// 设置网格可见性。
-    #[wasm_bindgen(js_name = setShowGrid)]
-    pub fn set_show_grid(&mut self, show: bool) {
-        self.engine.set_show_grid(show);
+    /// Check whether an animator's once-mode clip has ended.
+    /// 检查动画器的单次模式片段是否已结束。
+    #[wasm_bindgen(js_name = isAnimationFinished)]
+    pub fn is_animation_finished(&self, animator_id: u32) -> bool {
+        self.engine.is_animation_finished(animator_id)
     }
 
-    /// Set clear color (background color).
-    /// 设置清除颜色（背景颜色）。
+    /// Advance all animators by a frame's delta time.
+    /// 按帧间隔时间推进所有动画器。
+    #[wasm_bindgen(js_name = updateAnimations)]
+    pub fn update_animations(&mut self, delta_seconds: f32) {
+        self.engine.update_animations(delta_seconds);
+    }
+
+    /// Take the events raised since the last call.
+    /// 取出自上次调用以来触发的事件。
     ///
-    /// # Arguments | 参数
-    /// * `r`, `g`, `b`, `a` - Color components (0.0-1.0) | 颜色分量 (0.0-1.0)
-    #[wasm_bindgen(js_name = setClearColor)]
-    pub fn set_clear_color(&mut self, r: f32, g: f32, b: f32, a: f32) {
-        self.engine.set_clear_color(r, g, b, a);
+    /// Returns [animatorId, eventId, frame] per event; eventId 0xFFFFFFFF
+    /// marks the end of a once-mode clip.
+    /// 每个事件返回[animatorId, eventId, frame]；eventId为0xFFFFFFFF表示单次模式片段结束。
+    #[wasm_bindgen(js_name = drainAnimationEvents)]
+    pub fn drain_animation_events(&mut self) -> Vec<u32> {
+        self.engine
+            .drain_animation_events()
+            .flat_map(|event| [event.animator_id, event.event_id, event.frame])
+            .collect()
     }
 
-    /// Add a rectangle gizmo outline.
-    /// 添加矩形Gizmo边框。
+    /// Submit sprites showing the current frame of their animators.
+    /// 提交显示其动画器当前帧的精灵。
     ///
     /// # Arguments | 参数
-    /// * `x` - Center X position | 中心X位置
-    /// * `y` - Center Y position | 中心Y位置
-    /// * `width` - Rectangle width | 矩形宽度
-    /// * `height` - Rectangle height | 矩形高度
-    /// * `rotation` - Rotation in radians | 旋转角度（弧度）
-    /// * `origin_x` - Origin X (0-1) | 原点X (0-1)
-    /// * `origin_y` - Origin Y (0-1) | 原点Y (0-1)
-    /// * `r`, `g`, `b`, `a` - Color (0.0-1.0) | 颜色
-    /// * `show_handles` - Whether to show transform handles | 是否显示变换手柄
-    #[wasm_bindgen(js_name = addGizmoRect)]
-    pub fn add_gizmo_rect(
+    /// * `animator_ids` - Uint32Array animator per sprite | 每个精灵的动画器
+    /// * `transforms`, `colors`, `material_ids`, `sort_keys` - As for `submitSpriteBatch` | 与`submitSpriteBatch`相同
+    #[wasm_bindgen(js_name = submitAnimatedSprites)]
+    pub fn submit_animated_sprites(
         &mut self,
-        x: f32,
-        y: f32,
-        width: f32,
-        height: f32,
-        rotation: f32,
-        origin_x: f32,
-        origin_y: f32,
-        r: f32,
-        g: f32,
-        b: f32,
-        a: f32,
-        show_handles: bool,
-    ) {
-        self.engine.add_gizmo_rect(x, y, width, height, rotation, origin_x, origin_y, r, g, b, a, show_handles);
+        animator_ids: &[u32],
+        transforms: &[f32],
+        colors: &[u32],
+        material_ids: &[u32],
+        sort_keys: Option<Vec<f32>>,
+    ) -> std::result::Result<(), JsValue> {
+        self.engine
+            .submit_animated_sprites(
+                animator_ids,
+                transforms,
+                colors,
+                material_ids,
+                sort_keys.as_deref().unwrap_or_default(),
+            )
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Add a circle gizmo outline.
-    /// 添加圆形Gizmo边框。
-    #[wasm_bindgen(js_name = addGizmoCircle)]
-    pub fn add_gizmo_circle(
+    // ===== Text API =====
+    // ===== 文本 API =====
+
+    /// Load a BMFont (.fnt text format) whose pages are already loaded as textures.
+    /// 加载BMFont（.fnt文本格式），其页面已作为纹理加载。
+    ///
+    /// # Arguments | 参数
+    /// * `source` - .fnt file contents | .fnt文件内容
+    /// * `page_texture_ids` - Texture ID for each page | 每个页面的纹理ID
+    /// * `kind` - 0 = from file, 1 = bitmap, 2 = SDF, 3 = MSDF | 0 = 从文件读取，1 = 位图，2 = SDF，3 = MSDF
+    #[wasm_bindgen(js_name = loadFont)]
+    pub fn load_font(
         &mut self,
-        x: f32,
-        y: f32,
-        radius: f32,
-        r: f32,
-        g: f32,
-        b: f32,
-        a: f32,
-    ) {
-        self.engine.add_gizmo_circle(x, y, radius, r, g, b, a);
+        source: &str,
+        page_texture_ids: Vec<u32>,
+        kind: u8,
+    ) -> std::result::Result<u32, JsValue> {
+        self.engine
+            .load_font(source, page_texture_ids, renderer::FontKind::from_code(kind))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Add a line gizmo.
-    /// 添加线条Gizmo。
-    #[wasm_bindgen(js_name = addGizmoLine)]
-    pub fn add_gizmo_line(
-        &mut self,
-        points: Vec<f32>,
-        r: f32,
-        g: f32,
-        b: f32,
-        a: f32,
-        closed: bool,
-    ) {
-        self.engine.add_gizmo_line(points, r, g, b, a, closed);
+    /// Remove a font.
+    /// 移除字体。
+    #[wasm_bindgen(js_name = removeFont)]
+    pub fn remove_font(&mut self, font_id: u32) -> bool {
+        self.engine.remove_font(font_id)
     }
 
-    /// Add a capsule gizmo outline.
-    /// 添加胶囊Gizmo边框。
-    #[wasm_bindgen(js_name = addGizmoCapsule)]
-    pub fn add_gizmo_capsule(
+    /// Submit a string for this frame.
+    /// 提交本帧的字符串。
+    ///
+    /// # Arguments | 参数
+    /// * `x`, `y` - Anchor at the top of the first line | 第一行顶部的锚点
+    /// * `color` - Packed RGBA color | 打包的RGBA颜色
+    /// * `style` - Optional Float32Array [size, maxWidth, align, lineSpacing,
+    ///   letterSpacing, rotation, layer, orderInLayer] | 可选的文本样式
+    #[wasm_bindgen(js_name = submitText)]
+    pub fn submit_text(
         &mut self,
+        font_id: u32,
+        text: &str,
         x: f32,
         y: f32,
-        radius: f32,
-        half_height: f32,
-        rotation: f32,
-        r: f32,
-        g: f32,
-        b: f32,
-        a: f32,
-    ) {
-        self.engine.add_gizmo_capsule(x, y, radius, half_height, rotation, r, g, b, a);
+        color: u32,
+        style: Option<Vec<f32>>,
+    ) -> std::result::Result<(), JsValue> {
+        Self::text_style(style, color)
+            .and_then(|style| self.engine.submit_text(font_id, text, glam::Vec2::new(x, y), &style))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Set transform tool mode.
-    /// 设置变换工具模式。
-    ///
-    /// # Arguments | 参数
-    /// * `mode` - 0=Select, 1=Move, 2=Rotate, 3=Scale
-    #[wasm_bindgen(js_name = setTransformMode)]
-    pub fn set_transform_mode(&mut self, mode: u8) {
-        self.engine.set_transform_mode(mode);
+    /// Measure a string: [width, height, lineCount].
+    /// 测量字符串：[宽度, 高度, 行数]。
+    #[wasm_bindgen(js_name = measureText)]
+    pub fn measure_text(
+        &self,
+        font_id: u32,
+        text: &str,
+        style: Option<Vec<f32>>,
+    ) -> std::result::Result<Vec<f32>, JsValue> {
+        Self::text_style(style, 0xFFFF_FFFF)
+            .and_then(|style| self.engine.measure_text(font_id, text, &style))
+            .map(|layout| vec![layout.width, layout.height, layout.lines as f32])
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Set gizmo visibility.
-    /// 设置辅助工具可见性。
-    #[wasm_bindgen(js_name = setShowGizmos)]
-    pub fn set_show_gizmos(&mut self, show: bool) {
-        self.engine.set_show_gizmos(show);
+    fn text_style(style: Option<Vec<f32>>, color: u32) -> Result<renderer::TextStyle> {
+        match style {
+            Some(data) => renderer::TextStyle::from_data(&data, color),
+            None => Ok(renderer::TextStyle { color, ..renderer::TextStyle::default() }),
+        }
     }
 
-    /// Set editor mode.
-    /// 设置编辑器模式。
-    ///
-    /// When false (runtime mode), editor-only UI like grid, gizmos,
-    /// and axis indicator are automatically hidden.
-    /// 当为 false（运行时模式）时，编辑器专用 UI（如网格、gizmos、坐标轴指示器）会自动隐藏。
-    #[wasm_bindgen(js_name = setEditorMode)]
-    pub fn set_editor_mode(&mut self, is_editor: bool) {
-        self.engine.set_editor_mode(is_editor);
-    }
+    // ===== Shape API =====
+    // ===== 图形 API =====
 
-    /// Get editor mode.
-    /// 获取编辑器模式。
-    #[wasm_bindgen(js_name = isEditorMode)]
-    pub fn is_editor_mode(&self) -> bool {
-        self.engine.is_editor()
+    /// Fill a path for this frame.
+    /// 为本帧填充路径。
+    ///
+    /// # Arguments | 参数
+    /// * `commands` - Float32Array of path commands: 0 moveTo x y, 1 lineTo x y,
+    ///   2 quadTo cx cy x y, 3 cubicTo c1x c1y c2x c2y x y, 4 arc cx cy r start sweep,
+    ///   5 close, 6 rect x y w h, 7 roundedRect x y w h r, 8 circle cx cy r,
+    ///   9 ellipse cx cy rx ry | 路径命令
+    /// * `paint` - Float32Array [0, r, g, b, a], [1, x0, y0, x1, y1, stops...] or
+    ///   [2, cx, cy, radius, stops...] with stops as (offset, r, g, b, a) | 纯色、线性或径向渐变
+    /// * `layer`, `order_in_layer` - Draw order among sprites | 在精灵之间的绘制顺序
+    #[wasm_bindgen(js_name = fillPath)]
+    pub fn fill_path(
+        &mut self,
+        commands: &[f32],
+        paint: &[f32],
+        layer: i32,
+        order_in_layer: i32,
+    ) -> std::result::Result<(), JsValue> {
+        self.engine
+            .fill_path(commands, paint, renderer::SortKey::new(layer, order_in_layer))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    // ===== Multi-viewport API =====
-    // ===== 多视口 API =====
-
-    /// Register a new viewport.
-    /// 注册新视口。
+    /// Stroke a path for this frame.
+    /// 为本帧描边路径。
     ///
     /// # Arguments | 参数
-    /// * `id` - Unique viewport identifier | 唯一视口标识符
-    /// * `canvas_id` - HTML canvas element ID | HTML canvas元素ID
-    #[wasm_bindgen(js_name = registerViewport)]
-    pub fn register_viewport(&mut self, id: &str, canvas_id: &str) -> std::result::Result<(), JsValue> {
+    /// * `commands` - Path commands, as for `fillPath` | 路径命令，与`fillPath`相同
+    /// * `paint` - Paint, as for `fillPath` | 填充，与`fillPath`相同
+    /// * `stroke` - Float32Array [width, join (0 miter, 1 round, 2 bevel),
+    ///   cap (0 butt, 1 square, 2 round), miterLimit] | 描边样式
+    /// * `layer`, `order_in_layer` - Draw order among sprites | 在精灵之间的绘制顺序
+    #[wasm_bindgen(js_name = strokePath)]
+    pub fn stroke_path(
+        &mut self,
+        commands: &[f32],
+        paint: &[f32],
+        stroke: &[f32],
+        layer: i32,
+        order_in_layer: i32,
+    ) -> std::result::Result<(), JsValue> {
         self.engine
-            .register_viewport(id, canvas_id)
+            .stroke_path(commands, paint, stroke, renderer::SortKey::new(layer, order_in_layer))
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Unregister a viewport.
-    /// 注销视口。
-    #[wasm_bindgen(js_name = unregisterViewport)]
-    pub fn unregister_viewport(&mut self, id: &str) {
-        self.engine.unregister_viewport(id);
+    // ===== Mesh API =====
+    // ===== 网格 API =====
+
+    /// Create a triangle mesh, returning its ID.
+    /// 创建三角形网格，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `positions` - Float32Array [x, y] per vertex | 每个顶点的位置
+    /// * `uvs` - Float32Array [u, v] per vertex, within the drawn texture region | 每个顶点的UV，位于绘制的纹理区域内
+    /// * `indices` - Uint32Array, three per triangle | 每个三角形三个索引
+    /// * `colors` - Optional packed RGBA color per vertex | 可选的每顶点打包RGBA颜色
+    #[wasm_bindgen(js_name = createMesh)]
+    pub fn create_mesh(
+        &mut self,
+        positions: &[f32],
+        uvs: &[f32],
+        indices: &[u32],
+        colors: Option<Vec<u32>>,
+    ) -> std::result::Result<u32, JsValue> {
+        let mut mesh = renderer::Mesh2D::new(positions, uvs, indices)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        if let Some(colors) = colors {
+            mesh.set_colors(&colors).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        }
+        Ok(self.engine.create_mesh(mesh))
     }
 
-    /// Set the active viewport.
-    /// 设置活动视口。
-    #[wasm_bindgen(js_name = setActiveViewport)]
-    pub fn set_active_viewport(&mut self, id: &str) -> bool {
-        self.engine.set_active_viewport(id)
+    /// Replace a mesh's vertex positions.
+    /// 替换网格的顶点位置。
+    #[wasm_bindgen(js_name = setMeshPositions)]
+    pub fn set_mesh_positions(&mut self, mesh_id: u32, positions: &[f32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .mesh_mut(mesh_id)
+            .and_then(|mesh| mesh.set_positions(positions))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Set camera for a specific viewport.
-    /// 为特定视口设置相机。
-    #[wasm_bindgen(js_name = setViewportCamera)]
-    pub fn set_viewport_camera(&mut self, viewport_id: &str, x: f32, y: f32, zoom: f32, rotation: f32) {
-        self.engine.set_viewport_camera(viewport_id, x, y, zoom, rotation);
+    /// Set a mesh's packed RGBA vertex colors.
+    /// 设置网格的打包RGBA顶点颜色。
+    #[wasm_bindgen(js_name = setMeshColors)]
+    pub fn set_mesh_colors(&mut self, mesh_id: u32, colors: &[u32]) -> std::result::Result<(), JsValue> {
+        self.engine
+            .mesh_mut(mesh_id)
+            .and_then(|mesh| mesh.set_colors(colors))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Get camera for a specific viewport.
-    /// 获取特定视口的相机。
-    #[wasm_bindgen(js_name = getViewportCamera)]
-    pub fn get_viewport_camera(&self, viewport_id: &str) -> Option<Vec<f32>> {
+    /// Skin a mesh.
+    /// 为网格设置蒙皮。
+    ///
+    /// # Arguments | 参数
+    /// * `bones` - Uint8Array, four palette indices per vertex | 每个顶点四个调色板索引
+    /// * `weights` - Float32Array, four weights per vertex | 每个顶点四个权重
+    #[wasm_bindgen(js_name = setMeshSkin)]
+    pub fn set_mesh_skin(&mut self, mesh_id: u32, bones: &[u8], weights: &[f32]) -> std::result::Result<(), JsValue> {
         self.engine
-            .get_viewport_camera(viewport_id)
-            .map(|(x, y, zoom, rotation)| vec![x, y, zoom, rotation])
+            .mesh_mut(mesh_id)
+            .and_then(|mesh| mesh.set_skin(bones, weights))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Set viewport configuration.
-    /// 设置视口配置。
-    #[wasm_bindgen(js_name = setViewportConfig)]
-    pub fn set_viewport_config(&mut self, viewport_id: &str, show_grid: bool, show_gizmos: bool) {
-        self.engine.set_viewport_config(viewport_id, show_grid, show_gizmos);
+    /// Remove a mesh's skin.
+    /// 移除网格的蒙皮。
+    #[wasm_bindgen(js_name = clearMeshSkin)]
+    pub fn clear_mesh_skin(&mut self, mesh_id: u32) -> std::result::Result<(), JsValue> {
+        self.engine
+            .mesh_mut(mesh_id)
+            .map(|mesh| mesh.clear_skin())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Resize a specific viewport.
-    /// 调整特定视口大小。
-    #[wasm_bindgen(js_name = resizeViewport)]
-    pub fn resize_viewport(&mut self, viewport_id: &str, width: u32, height: u32) {
-        self.engine.resize_viewport(viewport_id, width, height);
+    /// Remove a mesh.
+    /// 移除网格。
+    #[wasm_bindgen(js_name = removeMesh)]
+    pub fn remove_mesh(&mut self, mesh_id: u32) -> bool {
+        self.engine.remove_mesh(mesh_id)
     }
 
-    /// Render to a specific viewport.
-    /// 渲染到特定视口。
-    #[wasm_bindgen(js_name = renderToViewport)]
-    pub fn render_to_viewport(&mut self, viewport_id: &str) -> std::result::Result<(), JsValue> {
+    /// Draw a mesh this frame.
+    /// 在本帧绘制网格。
+    ///
+    /// # Arguments | 参数
+    /// * `draw` - Float32Array [x, y, rotation, scaleX, scaleY, layer, orderInLayer] | 网格变换和排序
+    /// * `texture_id` - Texture or atlas region | 纹理或图集区域
+    /// * `material_id` - Material (0 = default) | 材质（0 = 默认）
+    /// * `color` - Packed RGBA tint | 打包的RGBA色调
+    /// * `bones` - Optional Float32Array [a, b, c, d, tx, ty] per bone, mapping mesh
+    ///   space to local space | 可选的骨骼矩阵，将网格空间映射到局部空间
+    #[wasm_bindgen(js_name = submitMesh)]
+    pub fn submit_mesh(
+        &mut self,
+        mesh_id: u32,
+        draw: &[f32],
+        texture_id: u32,
+        material_id: u32,
+        color: u32,
+        bones: Option<Vec<f32>>,
+    ) -> std::result::Result<(), JsValue> {
+        let draw = renderer::MeshDraw::from_data(draw, texture_id, material_id, color)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
         self.engine
-            .render_to_viewport(viewport_id)
+            .submit_mesh(mesh_id, &draw, bones.as_deref().unwrap_or(&[]))
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Get all registered viewport IDs.
-    /// 获取所有已注册的视口ID。
-    #[wasm_bindgen(js_name = getViewportIds)]
-    pub fn get_viewport_ids(&self) -> Vec<String> {
-        self.engine.viewport_ids()
-    }
-
     // ===== Shader API =====
     // ===== 着色器 API =====
 
@@ -687,3 +2618,26 @@ impl GameEngine {
         self.engine.clear_all_textures();
     }
 }
+
+impl GameEngine {
+    /// Promise settled when a texture finishes loading.
+    /// 纹理加载完成时兑现的Promise。
+    ///
+    /// # Arguments | 参数
+    /// * `id` - Texture ID | 纹理ID
+    /// * `with_id` - Prefix the resolved size with the ID | 在解析的尺寸前附加ID
+    fn texture_promise(&self, id: u32, with_id: bool) -> js_sys::Promise {
+        js_sys::Promise::new(&mut |resolve: js_sys::Function, reject: js_sys::Function| {
+            self.engine.when_texture_loaded(id, Box::new(move |result| {
+                let _ = match result {
+                    Ok((width, height)) => {
+                        let values: &[u32] = if with_id { &[id, width, height] } else { &[width, height] };
+                        let array: js_sys::Array = values.iter().map(|&v| JsValue::from(v)).collect();
+                        resolve.call1(&JsValue::NULL, &array)
+                    }
+                    Err(e) => reject.call1(&JsValue::NULL, &JsValue::from_str(&e.to_string())),
+                };
+            }));
+        })
+    }
+}
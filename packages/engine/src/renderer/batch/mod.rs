@@ -3,6 +3,10 @@
 
 mod sprite_batch;
 mod vertex;
+mod sort_key;
+mod nine_slice;
 
-pub use sprite_batch::{BatchKey, SpriteBatch};
-pub use vertex::{SpriteVertex, VERTEX_SIZE};
+pub use sprite_batch::{BatchKey, BatchRun, SpriteBatch, SpriteBatchData};
+pub use sort_key::{SortKey, SORT_KEY_STRIDE};
+pub use nine_slice::{NineSlice, SliceMode};
+pub use vertex::{SpriteInstance, SpriteVertex, FLOATS_PER_INSTANCE, INSTANCE_SIZE, VERTEX_SIZE};
@@ -0,0 +1,218 @@
+//! Nine-slice (9-patch) sprite expansion.
+//! 九宫格精灵展开。
+
+use super::vertex::SpriteInstance;
+
+/// Upper bound on tiles per slice before falling back to stretching.
+/// 每个切片的最大平铺数，超过时回退为拉伸。
+const MAX_TILES_PER_SLICE: usize = 1024;
+
+/// Leftover length below which no partial tile is emitted.
+/// 低于此剩余长度时不再生成部分平铺。
+const TILE_EPSILON: f32 = 1e-3;
+
+/// How a nine-slice region fills its space.
+/// 九宫格区域填充空间的方式。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SliceMode {
+    /// Stretch the region to fit.
+    /// 拉伸区域以适应。
+    #[default]
+    Stretch,
+    /// Repeat the region at its texel size, cropping the last tile.
+    /// 按纹素尺寸重复区域，裁剪最后一块。
+    Tile,
+}
+
+/// Nine-slice borders and fill modes of a texture.
+/// 纹理的九宫格边框和填充模式。
+///
+/// Corners keep their texel size (1 texel = 1 world unit), edges stretch or
+/// tile along one axis and the center fills the rest. Borders shrink
+/// proportionally when the target is smaller than them.
+/// 角保持纹素尺寸（1纹素 = 1世界单位），边沿一个轴拉伸或平铺，中心填充其余部分。
+/// 当目标小于边框时，边框按比例缩小。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::batch::{NineSlice, SpriteInstance};
+/// let slice = NineSlice::new([8.0, 8.0, 8.0, 8.0]);
+/// let panel = SpriteInstance {
+///     position: [0.0, 0.0],
+///     rotation: 0.0,
+///     size: [200.0, 100.0],
+///     origin: [0.0, 0.0],
+///     uv_rect: [0.0, 0.0, 1.0, 1.0],
+///     color: [1.0; 4],
+/// };
+/// let mut out = Vec::new();
+/// slice.emit(&panel, (32.0, 32.0), &mut out);
+/// assert_eq!(out.len(), 9);
+/// // Bottom-left corner keeps its 8x8 texel size | 左下角保持8x8纹素尺寸
+/// assert_eq!(out[0].size, [8.0, 8.0]);
+///
+/// // Negative sizes mirror the slices | 负尺寸镜像切片
+/// out.clear();
+/// slice.emit(&SpriteInstance { size: [-200.0, 100.0], ..panel }, (32.0, 32.0), &mut out);
+/// assert_eq!(out.len(), 9);
+/// assert_eq!(out[2].size, [-8.0, 8.0]);
+/// assert_eq!(out[2].position, [-192.0, 0.0]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NineSlice {
+    /// Border insets in texels: [left, right, top, bottom].
+    /// 边框内距（纹素）：[左, 右, 上, 下]。
+    pub borders: [f32; 4],
+
+    /// Fill mode of the center.
+    /// 中心的填充模式。
+    pub center: SliceMode,
+
+    /// Fill mode of the edges.
+    /// 边的填充模式。
+    pub edges: SliceMode,
+}
+
+impl NineSlice {
+    /// Create a nine-slice that stretches its center and edges.
+    /// 创建拉伸中心和边的九宫格。
+    pub fn new(borders: [f32; 4]) -> Self {
+        Self {
+            borders: borders.map(|b| b.max(0.0)),
+            center: SliceMode::Stretch,
+            edges: SliceMode::Stretch,
+        }
+    }
+
+    /// Set fill modes from JavaScript flags: bit 0 tiles the center, bit 1 tiles the edges.
+    /// 从JavaScript标志设置填充模式：位0平铺中心，位1平铺边。
+    pub fn with_mode_flags(mut self, flags: u8) -> Self {
+        let mode = |bit: u8| if flags & bit != 0 { SliceMode::Tile } else { SliceMode::Stretch };
+        self.center = mode(1);
+        self.edges = mode(2);
+        self
+    }
+
+    /// Expand a sprite into its slices.
+    /// 将精灵展开为切片。
+    ///
+    /// # Arguments | 参数
+    /// * `sprite` - The whole sprite; `size` is the target size | 整个精灵；`size`为目标尺寸
+    /// * `texture_size` - Size in texels of the texture `sprite.uv_rect` refers to | `sprite.uv_rect`所引用纹理的纹素尺寸
+    /// * `out` - Receives the slices, bottom row first | 接收切片，从底行开始
+    pub fn emit(&self, sprite: &SpriteInstance, texture_size: (f32, f32), out: &mut Vec<SpriteInstance>) {
+        let [width, height] = sprite.size;
+        let [u0, v0, u1, v1] = sprite.uv_rect;
+        let (tex_w, tex_h) = (texture_size.0.max(1.0), texture_size.1.max(1.0));
+        let [left, right, top, bottom] = self.borders;
+
+        // Lay out unmirrored, then mirror by the size's sign | 先按未镜像布局，再按尺寸符号镜像
+        let (abs_w, abs_h) = (width.abs(), height.abs());
+        let (sx, sy) = (width.signum(), height.signum());
+
+        // Shrink borders that do not fit | 缩小放不下的边框
+        let kx = fit_scale(left + right, abs_w);
+        let ky = fit_scale(top + bottom, abs_h);
+        let (l, r, t, b) = (left * kx, right * kx, top * ky, bottom * ky);
+
+        // Geometry columns and rows (Y-up, from the bottom-left) | 几何列和行（Y向上，从左下开始）
+        let xs = [0.0, l, abs_w - r, abs_w];
+        let ys = [0.0, b, abs_h - t, abs_h];
+
+        // UV columns; rows go bottom to top, so V runs from v1 towards v0
+        // UV列；行从下到上，因此V从v1向v0
+        let du = (u1 - u0).signum();
+        let dv = (v1 - v0).signum();
+        let us = [u0, u0 + du * left / tex_w, u1 - du * right / tex_w, u1];
+        let vs = [v1, v1 - dv * bottom / tex_h, v0 + dv * top / tex_h, v0];
+
+        // Texel size of the stretchable middle column and row | 可拉伸中间列和行的纹素尺寸
+        let mid_w = ((u1 - u0).abs() * tex_w - left - right).max(0.0);
+        let mid_h = ((v1 - v0).abs() * tex_h - top - bottom).max(0.0);
+
+        let (sin, cos) = sprite.rotation.sin_cos();
+        let ox = sprite.origin[0] * width;
+        let oy = sprite.origin[1] * height;
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let cell_w = xs[col + 1] - xs[col];
+                let cell_h = ys[row + 1] - ys[row];
+                if cell_w <= 0.0 || cell_h <= 0.0 {
+                    continue;
+                }
+
+                // Tile size along stretched axes, keeping the texel aspect | 沿拉伸轴的平铺尺寸，保持纹素比例
+                let (mode, tile_w, tile_h) = match (col == 1, row == 1) {
+                    (true, true) => (self.center, mid_w, mid_h),
+                    (true, false) => (self.edges, mid_w * ky, cell_h),
+                    (false, true) => (self.edges, cell_w, mid_h * kx),
+                    (false, false) => (SliceMode::Stretch, cell_w, cell_h),
+                };
+
+                // [u_left, v_top, u_right, v_bottom] of the slice | 切片的UV
+                let [ua, va, ub, vb] = [us[col], vs[row + 1], us[col + 1], vs[row]];
+
+                let tiles_x = tile_count(mode, cell_w, tile_w);
+                let tiles_y = tile_count(mode, cell_h, tile_h);
+                let (step_w, step_h) = if tiles_x * tiles_y > MAX_TILES_PER_SLICE {
+                    (cell_w, cell_h)
+                } else {
+                    (if tiles_x > 1 { tile_w } else { cell_w }, if tiles_y > 1 { tile_h } else { cell_h })
+                };
+
+                let mut ty = 0.0;
+                while cell_h - ty > TILE_EPSILON {
+                    let h = step_h.min(cell_h - ty);
+                    let mut tx = 0.0;
+                    while cell_w - tx > TILE_EPSILON {
+                        let w = step_w.min(cell_w - tx);
+
+                        // Crop the tile's UVs from its bottom-left | 从左下角裁剪平铺的UV
+                        let uv_rect = [
+                            ua,
+                            vb + (va - vb) * (h / step_h),
+                            ua + (ub - ua) * (w / step_w),
+                            vb,
+                        ];
+
+                        let lx = (xs[col] + tx) * sx - ox;
+                        let ly = (ys[row] + ty) * sy - oy;
+                        out.push(SpriteInstance {
+                            position: [
+                                sprite.position[0] + lx * cos - ly * sin,
+                                sprite.position[1] + lx * sin + ly * cos,
+                            ],
+                            rotation: sprite.rotation,
+                            size: [w * sx, h * sy],
+                            origin: [0.0, 0.0],
+                            uv_rect,
+                            color: sprite.color,
+                        });
+                        tx += step_w;
+                    }
+                    ty += step_h;
+                }
+            }
+        }
+    }
+}
+
+/// Scale factor that fits borders of total size `borders` into `size`.
+/// 将总尺寸为`borders`的边框放入`size`的缩放系数。
+fn fit_scale(borders: f32, size: f32) -> f32 {
+    if borders > size && borders > 0.0 {
+        size / borders
+    } else {
+        1.0
+    }
+}
+
+/// Number of tiles needed to cover `length`.
+/// 覆盖`length`所需的平铺数。
+fn tile_count(mode: SliceMode, length: f32, tile: f32) -> usize {
+    match mode {
+        SliceMode::Tile if tile > 0.0 => (length / tile).ceil().max(1.0) as usize,
+        _ => 1,
+    }
+}
@@ -0,0 +1,88 @@
+//! Per-sprite draw order keys.
+//! 每个精灵的绘制顺序键。
+
+use std::cmp::Ordering;
+
+/// Sort key data stride (layer, orderInLayer, ySort).
+/// 排序键数据步长。
+pub const SORT_KEY_STRIDE: usize = 3;
+
+/// Draw order of a sprite.
+/// 精灵的绘制顺序。
+///
+/// Matches the `SortingLayer` model of `engine-core`: sprites are ordered by
+/// layer, then by order in layer, then (when Y-sorting) from top to bottom so
+/// lower sprites are drawn in front. Equal keys keep submission order.
+/// 与`engine-core`的`SortingLayer`模型一致：精灵先按层排序，再按层内顺序排序，
+/// 然后（启用Y排序时）从上到下排序，使位置较低的精灵绘制在前面。键相同时保持提交顺序。
+///
+/// # Examples | 示例
+/// ```rust
+/// use std::cmp::Ordering;
+/// use es_engine::renderer::batch::SortKey;
+/// let background = SortKey::new(-100, 0);
+/// let player = SortKey::new(0, 5);
+/// assert_eq!(background.compare(&player), Ordering::Less);
+///
+/// // Y-sorted: the sprite further down is drawn later | Y排序：位置较低的精灵后绘制
+/// let tree = SortKey::new(0, 0).with_y_sort(50.0);
+/// let rock = SortKey::new(0, 0).with_y_sort(10.0);
+/// assert_eq!(tree.compare(&rock), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SortKey {
+    /// Sorting layer order (higher = drawn later, on top).
+    /// 排序层顺序（越大越后绘制，显示在上层）。
+    pub layer: i32,
+
+    /// Order within the layer.
+    /// 层内顺序。
+    pub order: i32,
+
+    /// Depth within the same layer and order (0 when not Y-sorting).
+    /// 同一层和层内顺序中的深度（不进行Y排序时为0）。
+    pub depth: f32,
+}
+
+impl SortKey {
+    /// Create a sort key without Y-sorting.
+    /// 创建不进行Y排序的排序键。
+    #[inline]
+    pub const fn new(layer: i32, order: i32) -> Self {
+        Self { layer, order, depth: 0.0 }
+    }
+
+    /// Sort by world Y within the layer and order (Y-up: higher Y is further back).
+    /// 在层和层内顺序中按世界Y排序（Y向上：Y越大越靠后）。
+    #[inline]
+    pub fn with_y_sort(mut self, y: f32) -> Self {
+        self.depth = -y;
+        self
+    }
+
+    /// Build a key from packed `[layer, orderInLayer, ySort]` data.
+    /// 从打包的`[layer, orderInLayer, ySort]`数据构建键。
+    ///
+    /// # Arguments | 参数
+    /// * `data` - One sprite's sort data | 单个精灵的排序数据
+    /// * `y` - Sprite Y position, used when `ySort` is non-zero | 精灵Y坐标，`ySort`非零时使用
+    #[inline]
+    pub fn from_data(data: &[f32], y: f32) -> Self {
+        let key = Self::new(data[0] as i32, data[1] as i32);
+        if data[2] != 0.0 {
+            key.with_y_sort(y)
+        } else {
+            key
+        }
+    }
+
+    /// Compare two keys in draw order.
+    /// 按绘制顺序比较两个键。
+    #[inline]
+    pub fn compare(&self, other: &Self) -> Ordering {
+        self.layer
+            .cmp(&other.layer)
+            .then(self.order.cmp(&other.order))
+            .then(self.depth.total_cmp(&other.depth))
+    }
+}
@@ -1,15 +1,20 @@
 //! Sprite batch renderer for efficient 2D rendering.
 //! 用于高效2D渲染的精灵批处理渲染器。
 
-use indexmap::IndexMap;
+use std::collections::HashMap;
+
 use web_sys::{
     WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject,
 };
 
 use crate::core::error::{EngineError, Result};
-use crate::math::Color;
+use crate::math::{Color, Rect};
 use crate::resource::TextureManager;
-use super::vertex::FLOATS_PER_VERTEX;
+use super::vertex::{SpriteInstance, SpriteVertex, FLOATS_PER_VERTEX, INSTANCE_SIZE};
+use super::sort_key::{SortKey, SORT_KEY_STRIDE};
+use super::nine_slice::NineSlice;
+use super::super::picking::PickTarget;
+use super::super::stats;
 
 /// Number of vertices per sprite (quad).
 /// 每个精灵的顶点数（四边形）。
@@ -27,6 +32,10 @@ const TRANSFORM_STRIDE: usize = 7;
 /// UV数据步长。
 const UV_STRIDE: usize = 4;
 
+/// Unit quad corners for instancing (top-left, top-right, bottom-right, bottom-left).
+/// 实例化使用的单位四边形角点（左上、右上、右下、左下）。
+const UNIT_QUAD: [f32; 8] = [0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+
 /// Batch key combining material and texture IDs.
 /// 组合材质ID和纹理ID的批次键。
 #[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
@@ -39,6 +48,60 @@ pub struct BatchKey {
     pub texture_id: u32,
 }
 
+/// Consecutive sprites drawn with one draw call.
+/// 使用一次绘制调用绘制的连续精灵。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchRun {
+    /// Material and texture shared by the run.
+    /// 该批次共享的材质和纹理。
+    pub key: BatchKey,
+    /// Index of the first sprite in draw order.
+    /// 按绘制顺序的第一个精灵索引。
+    pub start: usize,
+    /// Number of sprites.
+    /// 精灵数量。
+    pub count: usize,
+}
+
+/// Sprite data for one submission.
+/// 单次提交的精灵数据。
+///
+/// All slices describe the same sprites; see `SpriteBatch::add_sprites`.
+/// 所有切片描述相同的精灵；参见`SpriteBatch::add_sprites`。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpriteBatchData<'a> {
+    /// [x, y, rotation, scaleX, scaleY, originX, originY] per sprite.
+    /// 每个精灵的变换数据。
+    pub transforms: &'a [f32],
+    /// Texture ID for each sprite.
+    /// 每个精灵的纹理ID。
+    pub texture_ids: &'a [u32],
+    /// [u0, v0, u1, v1] per sprite.
+    /// 每个精灵的UV坐标。
+    pub uvs: &'a [f32],
+    /// Packed RGBA color per sprite.
+    /// 每个精灵的打包RGBA颜色。
+    pub colors: &'a [u32],
+    /// Material ID for each sprite (0 = default).
+    /// 每个精灵的材质ID（0 = 默认）。
+    pub material_ids: &'a [u32],
+    /// [layer, orderInLayer, ySort] per sprite, or empty for submission order.
+    /// 每个精灵的排序数据，为空表示按提交顺序。
+    pub sort_keys: &'a [f32],
+    /// Picking ID per sprite (0 = not pickable), or empty when none are pickable.
+    /// 每个精灵的拾取ID（0 = 不可拾取），为空表示均不可拾取。
+    pub pick_ids: &'a [u32],
+}
+
+/// Material, texture and draw order of a submitted sprite.
+/// 已提交精灵的材质、纹理和绘制顺序。
+#[derive(Clone, Copy, Debug)]
+struct SpriteEntry {
+    batch: BatchKey,
+    sort: SortKey,
+    bounds: Rect,
+}
+
 /// Sprite batch renderer.
 /// 精灵批处理渲染器。
 ///
@@ -46,9 +109,12 @@ pub struct BatchKey {
 /// 将多个精灵合并为单次绘制调用以获得最佳性能。
 ///
 /// # Performance | 性能
-/// - Uses dynamic vertex buffer for efficient updates | 使用动态顶点缓冲区以高效更新
-/// - Groups sprites by material and texture to minimize state changes | 按材质和纹理分组精灵以最小化状态更改
-/// - Supports up to 10000+ sprites per batch | 每批次支持10000+精灵
+/// - Instanced path: a static unit quad plus one `SpriteInstance` per sprite,
+///   uploading only the range that changed since last frame | 实例化路径：静态单位四边形加每个精灵一个`SpriteInstance`，仅上传自上一帧以来变化的范围
+/// - CPU path (custom shaders): dynamic vertex buffer with pre-transformed quads | CPU路径（自定义着色器）：预变换四边形的动态顶点缓冲区
+/// - Sorts sprites by `SortKey` and merges adjacent sprites sharing material and texture | 按`SortKey`排序精灵，并合并共享材质和纹理的相邻精灵
+/// - 32-bit indices and buffers that grow on demand, so there is no sprite limit | 32位索引和按需增长的缓冲区，没有精灵数量上限
+/// - Sprites whose bounds miss the camera view are culled before sorting and upload | 包围盒不在相机视图内的精灵在排序和上传前被剔除
 pub struct SpriteBatch {
     /// Vertex array object.
     /// 顶点数组对象。
@@ -62,21 +128,78 @@ pub struct SpriteBatch {
     /// 索引缓冲区对象。
     ibo: WebGlBuffer,
 
-    /// Maximum number of sprites.
-    /// 最大精灵数。
-    max_sprites: usize,
+    /// Vertex array object for the instanced path.
+    /// 实例化路径的顶点数组对象。
+    instance_vao: WebGlVertexArrayObject,
 
-    /// Per-material-texture vertex data buffers (insertion-ordered).
-    /// 按材质和纹理分组的顶点数据缓冲区（保持插入顺序）。
-    ///
-    /// Uses IndexMap to preserve render order - sprites submitted first
-    /// are rendered first (appear behind later sprites).
-    /// 使用 IndexMap 保持渲染顺序 - 先提交的精灵先渲染（显示在后面）。
-    batches: IndexMap<BatchKey, Vec<f32>>,
-
-    /// Total sprite count across all batches.
-    /// 所有批次的总精灵数。
-    sprite_count: usize,
+    /// Persistent per-instance attribute buffer.
+    /// 持久的每实例属性缓冲区。
+    instance_vbo: WebGlBuffer,
+
+    /// Number of sprites the GPU buffers can hold.
+    /// GPU缓冲区可容纳的精灵数。
+    capacity: usize,
+
+    /// Instance data in submission order.
+    /// 按提交顺序的实例数据。
+    instances: Vec<SpriteInstance>,
+
+    /// Batch and sort key of each submitted sprite.
+    /// 每个已提交精灵的批次和排序键。
+    entries: Vec<SpriteEntry>,
+
+    /// Instance data in draw order.
+    /// 按绘制顺序的实例数据。
+    sorted: Vec<SpriteInstance>,
+
+    /// Sort keys in draw order.
+    /// 按绘制顺序的排序键。
+    sorted_keys: Vec<SortKey>,
+
+    /// Copy of the instance buffer contents on the GPU.
+    /// GPU上实例缓冲区内容的副本。
+    uploaded: Vec<SpriteInstance>,
+
+    /// Whether `sorted` has been uploaded this frame.
+    /// 本帧是否已上传`sorted`。
+    instances_uploaded: bool,
+
+    /// Vertices for the CPU path.
+    /// CPU路径的顶点。
+    vertex_scratch: Vec<SpriteVertex>,
+
+    /// Draw calls in draw order.
+    /// 按绘制顺序的绘制调用。
+    runs: Vec<BatchRun>,
+
+    /// Whether sprites were added since the last sort.
+    /// 上次排序后是否添加了精灵。
+    dirty: bool,
+
+    /// Nine-slice borders for each submitted texture ID.
+    /// 每个提交纹理ID的九宫格边框。
+    nine_slices: HashMap<u32, NineSlice>,
+
+    /// Reused buffer for expanded nine-slice sprites.
+    /// 复用的九宫格展开精灵缓冲区。
+    slice_scratch: Vec<SpriteInstance>,
+
+    /// Whether sprites outside the view are skipped.
+    /// 是否跳过视图外的精灵。
+    culling: bool,
+
+    /// View the current draw runs were built for.
+    /// 当前绘制批次所对应的视图。
+    view: Option<Rect>,
+
+    /// Sprites drawn and skipped by the last `prepare`; kept across `clear`.
+    /// 上次`prepare`绘制和跳过的精灵数；`clear`后保留。
+    drawn: usize,
+    culled: usize,
+
+    /// Pickable sprites of this frame with their sort keys.
+    /// 本帧可拾取的精灵及其排序键。
+    pick_targets: Vec<(SortKey, PickTarget)>,
 }
 
 impl SpriteBatch {
@@ -85,8 +208,8 @@ impl SpriteBatch {
     ///
     /// # Arguments | 参数
     /// * `gl` - WebGL2 context | WebGL2上下文
-    /// * `max_sprites` - Maximum sprites per batch | 每批次最大精灵数
-    pub fn new(gl: &WebGl2RenderingContext, max_sprites: usize) -> Result<Self> {
+    /// * `initial_capacity` - Initial sprite capacity; buffers grow beyond it as needed | 初始精灵容量；超出时缓冲区按需增长
+    pub fn new(gl: &WebGl2RenderingContext, initial_capacity: usize) -> Result<Self> {
         // Create VAO | 创建VAO
         let vao = gl
             .create_vertex_array()
@@ -99,59 +222,152 @@ impl SpriteBatch {
             .ok_or(EngineError::BufferCreationFailed)?;
         gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
 
-        // Allocate vertex buffer memory | 分配顶点缓冲区内存
-        let vertex_buffer_size = max_sprites * VERTICES_PER_SPRITE * FLOATS_PER_VERTEX * 4;
-        gl.buffer_data_with_i32(
-            WebGl2RenderingContext::ARRAY_BUFFER,
-            vertex_buffer_size as i32,
-            WebGl2RenderingContext::DYNAMIC_DRAW,
-        );
-
-        // Create and populate index buffer | 创建并填充索引缓冲区
+        // Create index buffer | 创建索引缓冲区
         let ibo = gl
             .create_buffer()
             .ok_or(EngineError::BufferCreationFailed)?;
         gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&ibo));
 
-        let indices = Self::generate_indices(max_sprites);
+        // Set up vertex attributes | 设置顶点属性
+        Self::setup_vertex_attributes(gl);
+
+        // Unbind VAO | 解绑VAO
+        gl.bind_vertex_array(None);
+
+        // Instanced path: static unit quad + per-instance buffer | 实例化路径：静态单位四边形 + 每实例缓冲区
+        let instance_vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&instance_vao));
+
+        let quad_vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&quad_vbo));
         unsafe {
-            let index_array = js_sys::Uint16Array::view(&indices);
+            let quad_array = js_sys::Float32Array::view(&UNIT_QUAD);
             gl.buffer_data_with_array_buffer_view(
-                WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
-                &index_array,
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                &quad_array,
                 WebGl2RenderingContext::STATIC_DRAW,
             );
         }
+        gl.enable_vertex_attrib_array(0);
+        gl.vertex_attrib_pointer_with_i32(0, 2, WebGl2RenderingContext::FLOAT, false, 0, 0);
 
-        // Set up vertex attributes | 设置顶点属性
-        Self::setup_vertex_attributes(gl);
+        // Share the index buffer; only the first 6 indices are used | 共享索引缓冲区；仅使用前6个索引
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&ibo));
+
+        let instance_vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&instance_vbo));
+        for (location, _, _) in SpriteInstance::ATTRIBUTES {
+            gl.enable_vertex_attrib_array(location);
+            gl.vertex_attrib_divisor(location, 1);
+        }
 
-        // Unbind VAO | 解绑VAO
         gl.bind_vertex_array(None);
 
+        let capacity = initial_capacity.max(1);
+        let batch = Self {
+            vao,
+            vbo,
+            ibo,
+            instance_vao,
+            instance_vbo,
+            capacity,
+            instances: Vec::new(),
+            entries: Vec::new(),
+            sorted: Vec::new(),
+            sorted_keys: Vec::new(),
+            uploaded: Vec::new(),
+            instances_uploaded: false,
+            vertex_scratch: Vec::new(),
+            runs: Vec::new(),
+            dirty: false,
+            nine_slices: HashMap::new(),
+            slice_scratch: Vec::new(),
+            culling: true,
+            view: None,
+            drawn: 0,
+            culled: 0,
+            pick_targets: Vec::new(),
+        };
+        batch.allocate_buffers(gl);
+
         log::debug!(
             "SpriteBatch created with capacity: {} sprites | SpriteBatch创建完成，容量: {}个精灵",
-            max_sprites,
-            max_sprites
+            capacity,
+            capacity
         );
 
-        Ok(Self {
-            vao,
-            vbo,
-            ibo,
-            max_sprites,
-            batches: IndexMap::new(),
-            sprite_count: 0,
-        })
+        Ok(batch)
+    }
+
+    /// Allocate GPU buffers for the current capacity. Contents are discarded.
+    /// 按当前容量分配GPU缓冲区。内容会被丢弃。
+    fn allocate_buffers(&self, gl: &WebGl2RenderingContext) {
+        // The index buffer binding is VAO state | 索引缓冲区绑定属于VAO状态
+        gl.bind_vertex_array(Some(&self.vao));
+
+        // Vertex buffer | 顶点缓冲区
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.vbo));
+        gl.buffer_data_with_i32(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            (self.capacity * VERTICES_PER_SPRITE * FLOATS_PER_VERTEX * 4) as i32,
+            WebGl2RenderingContext::DYNAMIC_DRAW,
+        );
+
+        // Index buffer (shared by both VAOs) | 索引缓冲区（两个VAO共享）
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&self.ibo));
+        let indices = Self::generate_indices(self.capacity);
+        unsafe {
+            let index_array = js_sys::Uint32Array::view(&indices);
+            gl.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+                &index_array,
+                WebGl2RenderingContext::STATIC_DRAW,
+            );
+        }
+
+        gl.bind_vertex_array(None);
+
+        // Instance buffer | 实例缓冲区
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.instance_vbo));
+        gl.buffer_data_with_i32(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            (self.capacity * INSTANCE_SIZE) as i32,
+            WebGl2RenderingContext::DYNAMIC_DRAW,
+        );
+    }
+
+    /// Grow the GPU buffers to hold at least `sprites` sprites.
+    /// 增长GPU缓冲区以容纳至少`sprites`个精灵。
+    fn ensure_capacity(&mut self, gl: &WebGl2RenderingContext, sprites: usize) {
+        if sprites <= self.capacity {
+            return;
+        }
+        self.capacity = sprites.max(self.capacity * 2);
+        self.allocate_buffers(gl);
+
+        // Reallocation discarded the uploaded instances | 重新分配丢弃了已上传的实例
+        self.uploaded.clear();
+
+        log::debug!(
+            "SpriteBatch grown to {} sprites | SpriteBatch容量增长至{}个精灵",
+            self.capacity,
+            self.capacity
+        );
     }
 
     /// Generate index buffer data.
     /// 生成索引缓冲区数据。
-    fn generate_indices(max_sprites: usize) -> Vec<u16> {
-        let mut indices = Vec::with_capacity(max_sprites * INDICES_PER_SPRITE);
+    pub(crate) fn generate_indices(capacity: usize) -> Vec<u32> {
+        let mut indices = Vec::with_capacity(capacity * INDICES_PER_SPRITE);
 
-        for i in 0..max_sprites {
-            let base = (i * VERTICES_PER_SPRITE) as u16;
+        for i in 0..capacity {
+            let base = (i * VERTICES_PER_SPRITE) as u32;
             // Two triangles per sprite | 每个精灵两个三角形
             // Triangle 1: 0, 1, 2 | 三角形1
             // Triangle 2: 2, 3, 0 | 三角形2
@@ -168,7 +384,7 @@ impl SpriteBatch {
 
     /// Set up vertex attribute pointers.
     /// 设置顶点属性指针。
-    fn setup_vertex_attributes(gl: &WebGl2RenderingContext) {
+    pub(crate) fn setup_vertex_attributes(gl: &WebGl2RenderingContext) {
         let stride = (FLOATS_PER_VERTEX * 4) as i32;
 
         // Position attribute (location = 0) | 位置属性
@@ -207,32 +423,91 @@ impl SpriteBatch {
 
     /// Clear the batch for a new frame.
     /// 为新帧清空批处理。
+    ///
+    /// The GPU instance buffer is kept, so unchanged sprites are not
+    /// uploaded again next frame.
+    /// GPU实例缓冲区会保留，因此下一帧未变化的精灵不会重新上传。
     pub fn clear(&mut self) {
-        for batch in self.batches.values_mut() {
-            batch.clear();
+        self.instances.clear();
+        self.entries.clear();
+        self.sorted.clear();
+        self.sorted_keys.clear();
+        self.runs.clear();
+        self.dirty = false;
+        self.instances_uploaded = false;
+        self.view = None;
+        self.pick_targets.clear();
+    }
+
+    /// Move this frame's pickable sprites into `out` in draw order.
+    /// 将本帧可拾取的精灵按绘制顺序移入`out`。
+    pub fn take_pick_targets(&mut self, out: &mut Vec<PickTarget>) {
+        self.pick_targets.sort_by(|a, b| a.0.compare(&b.0));
+        out.clear();
+        out.extend(self.pick_targets.drain(..).map(|(_, target)| target));
+    }
+
+    /// Enable or disable view culling (on by default).
+    /// 启用或禁用视图剔除（默认开启）。
+    ///
+    /// Disable it for materials whose shaders move vertices outside the sprite quad.
+    /// 对于着色器会将顶点移出精灵四边形的材质，请禁用剔除。
+    pub fn set_culling(&mut self, enabled: bool) {
+        if self.culling != enabled {
+            self.culling = enabled;
+            self.dirty = true;
+        }
+    }
+
+    /// Whether view culling is enabled.
+    /// 是否启用了视图剔除。
+    #[inline]
+    pub fn culling(&self) -> bool {
+        self.culling
+    }
+
+    /// Set nine-slice borders for a texture, or remove them with `None`.
+    /// 为纹理设置九宫格边框，`None`表示移除。
+    ///
+    /// Sprites submitted with `texture_id` are expanded into slices whose
+    /// corners keep their texel size while the sprite size sets the target size.
+    /// 使用`texture_id`提交的精灵会展开为切片，角保持纹素尺寸，精灵尺寸决定目标尺寸。
+    pub fn set_nine_slice(&mut self, texture_id: u32, slice: Option<NineSlice>) {
+        match slice {
+            Some(slice) => {
+                self.nine_slices.insert(texture_id, slice);
+            }
+            None => {
+                self.nine_slices.remove(&texture_id);
+            }
         }
-        self.sprite_count = 0;
+    }
+
+    /// Get the nine-slice borders of a texture.
+    /// 获取纹理的九宫格边框。
+    #[inline]
+    pub fn nine_slice(&self, texture_id: u32) -> Option<&NineSlice> {
+        self.nine_slices.get(&texture_id)
     }
 
     /// Add sprites from batch data.
     /// 从批处理数据添加精灵。
     ///
     /// # Arguments | 参数
-    /// * `transforms` - [x, y, rotation, scaleX, scaleY, originX, originY] per sprite
-    /// * `texture_ids` - Texture ID for each sprite | 每个精灵的纹理ID
-    /// * `uvs` - [u0, v0, u1, v1] per sprite | 每个精灵的UV坐标
-    /// * `colors` - Packed RGBA color per sprite | 每个精灵的打包RGBA颜色
-    /// * `material_ids` - Material ID for each sprite (0 = default) | 每个精灵的材质ID（0 = 默认）
-    /// * `_texture_manager` - Texture manager for getting texture sizes | 纹理管理器
+    /// * `data` - Per-sprite transforms, textures, UVs, colors, materials and sort keys | 每个精灵的变换、纹理、UV、颜色、材质和排序键
+    /// * `texture_manager` - Texture manager for resolving atlas regions | 用于解析图集区域的纹理管理器
+    ///
+    /// Sprites that reference an atlas region are drawn from its page, so all
+    /// regions on one page share a batch. There is no sprite limit: GPU
+    /// buffers grow when the frame is drawn.
+    /// 引用图集区域的精灵从其页面绘制，因此同一页面上的所有区域共享一个批次。
+    /// 没有精灵数量上限：GPU缓冲区在绘制帧时增长。
     pub fn add_sprites(
         &mut self,
-        transforms: &[f32],
-        texture_ids: &[u32],
-        uvs: &[f32],
-        colors: &[u32],
-        material_ids: &[u32],
-        _texture_manager: &TextureManager,
+        data: &SpriteBatchData,
+        texture_manager: &TextureManager,
     ) -> Result<()> {
+        let SpriteBatchData { transforms, texture_ids, uvs, colors, material_ids, sort_keys, pick_ids } = *data;
         let sprite_count = texture_ids.len();
 
         // Validate input data | 验证输入数据
@@ -268,142 +543,150 @@ impl SpriteBatch {
             )));
         }
 
-        // Check capacity | 检查容量
-        if self.sprite_count + sprite_count > self.max_sprites {
+        if !sort_keys.is_empty() && sort_keys.len() != sprite_count * SORT_KEY_STRIDE {
             return Err(EngineError::InvalidBatchData(format!(
-                "Batch capacity exceeded: {} + {} > {}",
-                self.sprite_count, sprite_count, self.max_sprites
+                "Sort key data length mismatch: expected {}, got {}",
+                sprite_count * SORT_KEY_STRIDE,
+                sort_keys.len()
             )));
         }
 
-        // Add each sprite grouped by material and texture | 按材质和纹理分组添加每个精灵
+        if !pick_ids.is_empty() && pick_ids.len() != sprite_count {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Pick ID data length mismatch: expected {}, got {}",
+                sprite_count,
+                pick_ids.len()
+            )));
+        }
+
+        // Add each sprite in submission order | 按提交顺序添加每个精灵
         for i in 0..sprite_count {
             let t_offset = i * TRANSFORM_STRIDE;
             let uv_offset = i * UV_STRIDE;
 
             let x = transforms[t_offset];
             let y = transforms[t_offset + 1];
-            let rotation = transforms[t_offset + 2];
-            let scale_x = transforms[t_offset + 3];
-            let scale_y = transforms[t_offset + 4];
-            let origin_x = transforms[t_offset + 5];
-            let origin_y = transforms[t_offset + 6];
 
-            let u0 = uvs[uv_offset];
-            let v0 = uvs[uv_offset + 1];
-            let u1 = uvs[uv_offset + 2];
-            let v1 = uvs[uv_offset + 3];
+            // Resolve atlas regions and render targets | 解析图集区域和渲染目标
+            let (texture_id, uv_rect) = texture_manager.resolve_texture(
+                texture_ids[i],
+                [uvs[uv_offset], uvs[uv_offset + 1], uvs[uv_offset + 2], uvs[uv_offset + 3]],
+            );
 
             let color = Color::from_packed(colors[i]);
-            let color_arr = [color.r, color.g, color.b, color.a];
-
-            // scale_x and scale_y are the actual display dimensions
-            // scale_x 和 scale_y 是实际显示尺寸
-            let width = scale_x;
-            let height = scale_y;
 
-            let batch_key = BatchKey {
+            let batch = BatchKey {
                 material_id: material_ids[i],
-                texture_id: texture_ids[i],
+                texture_id,
+            };
+            let sort = if sort_keys.is_empty() {
+                SortKey::default()
+            } else {
+                let k_offset = i * SORT_KEY_STRIDE;
+                SortKey::from_data(&sort_keys[k_offset..k_offset + SORT_KEY_STRIDE], y)
+            };
+
+            // scaleX and scaleY are the actual display dimensions
+            // scaleX 和 scaleY 是实际显示尺寸
+            let instance = SpriteInstance {
+                position: [x, y],
+                rotation: transforms[t_offset + 2],
+                size: [transforms[t_offset + 3], transforms[t_offset + 4]],
+                origin: [transforms[t_offset + 5], transforms[t_offset + 6]],
+                uv_rect,
+                color: [color.r, color.g, color.b, color.a],
             };
 
-            // Get or create batch for this material+texture combination | 获取或创建此材质+纹理组合的批次
-            let batch = self.batches
-                .entry(batch_key)
-                .or_insert_with(Vec::new);
+            let pick_id = pick_ids.get(i).copied().unwrap_or(0);
 
-            // Calculate transformed vertices and add to batch | 计算变换后的顶点并添加到批次
-            Self::add_sprite_vertices_to_batch(
-                batch,
-                x, y, width, height, rotation, origin_x, origin_y,
-                u0, v0, u1, v1, color_arr,
+            // Nine-slice textures expand into several sprites with the same keys
+            // 九宫格纹理展开为具有相同键的多个精灵
+            let slice = self.nine_slices.get(&texture_ids[i]).zip(
+                texture_manager
+                    .get_texture_size(texture_id)
+                    .filter(|&(w, h)| w > 0.0 && h > 0.0),
             );
+            match slice {
+                Some((slice, texture_size)) => {
+                    self.slice_scratch.clear();
+                    slice.emit(&instance, texture_size, &mut self.slice_scratch);
+                    for &part in &self.slice_scratch {
+                        self.entries.push(SpriteEntry { batch, sort, bounds: part.bounds() });
+                        self.instances.push(part);
+                        if pick_id != 0 {
+                            self.pick_targets.push((sort, PickTarget { id: pick_id, instance: part, texture_id }));
+                        }
+                    }
+                }
+                None => {
+                    self.entries.push(SpriteEntry { batch, sort, bounds: instance.bounds() });
+                    self.instances.push(instance);
+                    if pick_id != 0 {
+                        self.pick_targets.push((sort, PickTarget { id: pick_id, instance, texture_id }));
+                    }
+                }
+            }
         }
 
-        self.sprite_count += sprite_count;
+        if sprite_count > 0 {
+            self.dirty = true;
+        }
         Ok(())
     }
 
-    /// Add vertices for a single sprite to a batch.
-    /// 为单个精灵添加顶点到批次。
-    #[inline]
-    fn add_sprite_vertices_to_batch(
-        batch: &mut Vec<f32>,
-        x: f32,
-        y: f32,
-        width: f32,
-        height: f32,
-        rotation: f32,
-        origin_x: f32,
-        origin_y: f32,
-        u0: f32,
-        v0: f32,
-        u1: f32,
-        v1: f32,
-        color: [f32; 4],
-    ) {
-        let cos = rotation.cos();
-        let sin = rotation.sin();
-
-        // Origin offset | 原点偏移
-        // origin (0,0) = bottom-left, (1,1) = top-right
-        // 原点 (0,0) = 左下角, (1,1) = 右上角
-        let ox = origin_x * width;
-        let oy = origin_y * height;
-
-        // Local corner positions (relative to origin) | 局部角点位置（相对于原点）
-        // Y-up coordinate system | Y向上坐标系
-        let corners = [
-            (-ox, height - oy),   // Top-left | 左上
-            (width - ox, height - oy), // Top-right | 右上
-            (width - ox, -oy),    // Bottom-right | 右下
-            (-ox, -oy),           // Bottom-left | 左下
-        ];
-
-        // UV coordinates use image coordinate system (top-left origin, Y-down)
-        // UV坐标使用图像坐标系（左上角为原点，Y轴向下）
-        // Incoming UV: [u0, v0, u1, v1] where v0 < v1
-        // 传入的 UV：[u0, v0, u1, v1] 其中 v0 < v1
-        let tex_coords = [
-            [u0, v0], // Top-left
-            [u1, v0], // Top-right
-            [u1, v1], // Bottom-right
-            [u0, v1], // Bottom-left
-        ];
-
-        // Transform and add each vertex | 变换并添加每个顶点
-        for i in 0..4 {
-            let (lx, ly) = corners[i];
-
-            // Apply rotation | 应用旋转
-            let rx = lx * cos - ly * sin;
-            let ry = lx * sin + ly * cos;
-
-            // Apply translation | 应用平移
-            let px = rx + x;
-            let py = ry + y;
-
-            // Position | 位置
-            batch.push(px);
-            batch.push(py);
-
-            // Texture coordinates | 纹理坐标
-            batch.push(tex_coords[i][0]);
-            batch.push(tex_coords[i][1]);
-
-            // Color | 颜色
-            batch.extend_from_slice(&color);
+    /// Cull, sort submitted sprites and build draw runs.
+    /// 剔除、排序已提交的精灵并构建绘制批次。
+    ///
+    /// Sprites whose bounds miss `view` are skipped when culling is enabled.
+    /// Sorting is stable, so sprites with equal keys keep submission order.
+    /// Only adjacent sprites sharing material and texture are merged.
+    /// 启用剔除时，跳过包围盒与`view`不相交的精灵。排序是稳定的，键相同的精灵保持提交顺序。
+    /// 仅合并共享材质和纹理的相邻精灵。
+    ///
+    /// # Arguments | 参数
+    /// * `view` - Visible world rect, or `None` to draw everything | 可见世界矩形，`None`表示全部绘制
+    pub fn prepare(&mut self, view: Option<Rect>) {
+        let view = view.filter(|_| self.culling);
+        if !self.dirty && self.view == view {
+            return;
+        }
+        self.dirty = false;
+        self.view = view;
+
+        let entries = &self.entries;
+        let mut order: Vec<usize> = match view {
+            Some(view) => (0..entries.len()).filter(|&i| entries[i].bounds.intersects(&view)).collect(),
+            None => (0..entries.len()).collect(),
+        };
+        self.drawn = order.len();
+        self.culled = entries.len() - order.len();
+        order.sort_by(|&a, &b| entries[a].sort.compare(&entries[b].sort));
+
+        self.sorted.clear();
+        self.sorted_keys.clear();
+        self.runs.clear();
+        self.instances_uploaded = false;
+        for (draw_index, &sprite) in order.iter().enumerate() {
+            self.sorted.push(self.instances[sprite]);
+            self.sorted_keys.push(entries[sprite].sort);
+
+            let key = entries[sprite].batch;
+            match self.runs.last_mut() {
+                Some(run) if run.key == key => run.count += 1,
+                _ => self.runs.push(BatchRun { key, start: draw_index, count: 1 }),
+            }
         }
     }
 
     /// Flush a batch to GPU and render.
     /// 将批次刷新到GPU并渲染。
-    fn flush_batch(&self, gl: &WebGl2RenderingContext, vertices: &[f32]) {
+    fn flush_batch(&self, gl: &WebGl2RenderingContext, vertices: &[SpriteVertex]) {
         if vertices.is_empty() {
             return;
         }
 
-        let sprite_count = vertices.len() / (VERTICES_PER_SPRITE * FLOATS_PER_VERTEX);
+        let sprite_count = vertices.len() / VERTICES_PER_SPRITE;
+        let vertices: &[f32] = bytemuck::cast_slice(vertices);
 
         // Bind VAO | 绑定VAO
         gl.bind_vertex_array(Some(&self.vao));
@@ -419,37 +702,150 @@ impl SpriteBatch {
             );
         }
 
+        stats::record_vertices(sprite_count * VERTICES_PER_SPRITE);
+
         // Draw | 绘制
         let index_count = (sprite_count * INDICES_PER_SPRITE) as i32;
         gl.draw_elements_with_i32(
             WebGl2RenderingContext::TRIANGLES,
             index_count,
-            WebGl2RenderingContext::UNSIGNED_SHORT,
+            WebGl2RenderingContext::UNSIGNED_INT,
             0,
         );
+        stats::record_draw_call();
 
         // Unbind VAO | 解绑VAO
         gl.bind_vertex_array(None);
     }
 
-    /// Get all batches for rendering (in insertion order).
-    /// 获取所有批次用于渲染（按插入顺序）。
-    pub fn batches(&self) -> &IndexMap<BatchKey, Vec<f32>> {
-        &self.batches
+    /// Get the sort key of a sprite by draw index (valid after `prepare`).
+    /// 按绘制索引获取精灵的排序键（`prepare`之后有效）。
+    #[inline]
+    pub fn sort_key(&self, draw_index: usize) -> SortKey {
+        self.sorted_keys[draw_index]
+    }
+
+    /// Get draw runs in draw order (valid after `prepare`).
+    /// 按绘制顺序获取绘制批次（`prepare`之后有效）。
+    pub fn runs(&self) -> &[BatchRun] {
+        &self.runs
+    }
+
+    /// Flush a run on the CPU path: transform its quads and render them.
+    /// 在CPU路径上刷新绘制批次：变换其四边形并渲染。
+    ///
+    /// Works with any shader using the sprite vertex layout.
+    /// 适用于任何使用精灵顶点布局的着色器。
+    pub fn flush_run(&mut self, gl: &WebGl2RenderingContext, run: &BatchRun) {
+        let instances = match self.sorted.get(run.start..run.start + run.count) {
+            Some(instances) => instances,
+            None => return,
+        };
+        let mut scratch = std::mem::take(&mut self.vertex_scratch);
+        scratch.clear();
+        scratch.extend(instances.iter().flat_map(|i| i.vertices()));
+
+        self.ensure_capacity(gl, run.count);
+        self.flush_batch(gl, &scratch);
+        self.vertex_scratch = scratch;
     }
 
-    /// Flush a specific batch by key.
-    /// 按键刷新特定批次。
-    pub fn flush_for_batch(&self, gl: &WebGl2RenderingContext, key: &BatchKey) {
-        if let Some(vertices) = self.batches.get(key) {
-            self.flush_batch(gl, vertices);
+    /// Flush a run on the instanced path.
+    /// 在实例化路径上刷新绘制批次。
+    ///
+    /// Requires a shader with the `SPRITE_INSTANCED_VERTEX_SHADER` layout.
+    /// 需要使用`SPRITE_INSTANCED_VERTEX_SHADER`布局的着色器。
+    pub fn flush_run_instanced(&mut self, gl: &WebGl2RenderingContext, run: &BatchRun) {
+        if run.count == 0 || run.start + run.count > self.sorted.len() {
+            return;
         }
+        self.upload_instances(gl);
+
+        gl.bind_vertex_array(Some(&self.instance_vao));
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.instance_vbo));
+
+        // WebGL2 has no base instance, so offset the attributes to the run
+        // WebGL2没有基础实例参数，因此将属性偏移到该批次
+        let base = (run.start * INSTANCE_SIZE) as i32;
+        for (location, size, offset) in SpriteInstance::ATTRIBUTES {
+            gl.vertex_attrib_pointer_with_i32(
+                location,
+                size,
+                WebGl2RenderingContext::FLOAT,
+                false,
+                INSTANCE_SIZE as i32,
+                base + offset,
+            );
+        }
+
+        gl.draw_elements_instanced_with_i32(
+            WebGl2RenderingContext::TRIANGLES,
+            INDICES_PER_SPRITE as i32,
+            WebGl2RenderingContext::UNSIGNED_INT,
+            0,
+            run.count as i32,
+        );
+        stats::record_draw_call();
+
+        gl.bind_vertex_array(None);
+    }
+
+    /// Upload the part of the instance buffer that changed since last upload.
+    /// 上传自上次上传以来实例缓冲区中发生变化的部分。
+    fn upload_instances(&mut self, gl: &WebGl2RenderingContext) {
+        if self.instances_uploaded {
+            return;
+        }
+        self.instances_uploaded = true;
+        self.ensure_capacity(gl, self.sorted.len());
+
+        let len = self.sorted.len();
+        let common = len.min(self.uploaded.len());
+        let differs = |i: &usize| self.sorted[*i] != self.uploaded[*i];
+
+        // Dirty range [first, end) | 脏范围 [first, end)
+        let first = (0..common).find(differs).unwrap_or(common);
+        let end = if len > common {
+            len
+        } else {
+            (first..common).rev().find(differs).map_or(first, |i| i + 1)
+        };
+
+        if first < end {
+            stats::record(|stats| stats.instances_uploaded += (end - first) as u32);
+            gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.instance_vbo));
+            let data: &[f32] = bytemuck::cast_slice(&self.sorted[first..end]);
+            unsafe {
+                let instance_array = js_sys::Float32Array::view(data);
+                gl.buffer_sub_data_with_i32_and_array_buffer_view(
+                    WebGl2RenderingContext::ARRAY_BUFFER,
+                    (first * INSTANCE_SIZE) as i32,
+                    &instance_array,
+                );
+            }
+        }
+
+        self.uploaded.clone_from(&self.sorted);
     }
 
     /// Get current sprite count.
     /// 获取当前精灵数量。
     #[inline]
     pub fn sprite_count(&self) -> usize {
-        self.sprite_count
+        self.entries.len()
+    }
+
+    /// Sprites drawn by the last `prepare`.
+    /// 上次`prepare`绘制的精灵数。
+    #[inline]
+    pub fn drawn_count(&self) -> usize {
+        self.drawn
+    }
+
+    /// Sprites culled by the last `prepare`.
+    /// 上次`prepare`剔除的精灵数。
+    #[inline]
+    pub fn culled_count(&self) -> usize {
+        self.culled
     }
 }
@@ -3,6 +3,8 @@
 
 use bytemuck::{Pod, Zeroable};
 
+use crate::math::{Rect, Vec2};
+
 /// Size of a single sprite vertex in bytes.
 /// 单个精灵顶点的字节大小。
 pub const VERTEX_SIZE: usize = std::mem::size_of::<SpriteVertex>();
@@ -58,3 +60,210 @@ impl Default for SpriteVertex {
         }
     }
 }
+
+/// Size of a single sprite instance in bytes.
+/// 单个精灵实例的字节大小。
+pub const INSTANCE_SIZE: usize = std::mem::size_of::<SpriteInstance>();
+
+/// Number of floats per instance.
+/// 每个实例的浮点数数量。
+pub const FLOATS_PER_INSTANCE: usize = 15;
+
+/// Per-instance sprite data for instanced rendering.
+/// 实例化渲染的每实例精灵数据。
+///
+/// The GPU expands a static unit quad with these values, so a sprite costs
+/// 15 floats instead of 4 vertices.
+/// GPU使用这些值展开静态单位四边形，因此每个精灵只需15个浮点数而不是4个顶点。
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct SpriteInstance {
+    /// Position (x, y).
+    /// 位置。
+    pub position: [f32; 2],
+
+    /// Rotation in radians.
+    /// 旋转（弧度）。
+    pub rotation: f32,
+
+    /// Display size (width, height).
+    /// 显示尺寸。
+    pub size: [f32; 2],
+
+    /// Origin (0,0 = bottom-left, 1,1 = top-right).
+    /// 原点（0,0 = 左下角，1,1 = 右上角）。
+    pub origin: [f32; 2],
+
+    /// UV rectangle (u0, v0, u1, v1), v0 at the top of the image.
+    /// UV矩形，v0为图像顶部。
+    pub uv_rect: [f32; 4],
+
+    /// Color (r, g, b, a).
+    /// 颜色。
+    pub color: [f32; 4],
+}
+
+impl SpriteInstance {
+    /// Byte offsets of each attribute, in shader location order 1-6.
+    /// 各属性的字节偏移，按着色器location 1-6的顺序。
+    pub const ATTRIBUTES: [(u32, i32, i32); 6] = [
+        (1, 2, 0),  // position
+        (2, 1, 8),  // rotation
+        (3, 2, 12), // size
+        (4, 2, 20), // origin
+        (5, 4, 28), // uv_rect
+        (6, 4, 44), // color
+    ];
+
+    /// World-space axis-aligned bounds of this sprite (`y` is the bottom edge).
+    /// 此精灵的世界空间轴对齐包围盒（`y`为下边缘）。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::renderer::batch::SpriteInstance;
+    /// let sprite = SpriteInstance {
+    ///     position: [0.0, 0.0],
+    ///     rotation: std::f32::consts::FRAC_PI_4,
+    ///     size: [2.0, 2.0],
+    ///     origin: [0.5, 0.5],
+    ///     uv_rect: [0.0, 0.0, 1.0, 1.0],
+    ///     color: [1.0; 4],
+    /// };
+    /// let bounds = sprite.bounds();
+    /// assert!((bounds.width - 2.0 * std::f32::consts::SQRT_2).abs() < 1e-5);
+    /// ```
+    pub fn bounds(&self) -> Rect {
+        let [x, y] = self.position;
+        let [width, height] = self.size;
+        let (sin, cos) = self.rotation.sin_cos();
+        let ox = self.origin[0] * width;
+        let oy = self.origin[1] * height;
+
+        let mut min = Vec2::splat(f32::INFINITY);
+        let mut max = Vec2::splat(f32::NEG_INFINITY);
+        for (lx, ly) in [(-ox, -oy), (width - ox, -oy), (width - ox, height - oy), (-ox, height - oy)] {
+            let (cx, cy) = (lx * cos - ly * sin + x, lx * sin + ly * cos + y);
+            min = Vec2::new(min.x.min(cx), min.y.min(cy));
+            max = Vec2::new(max.x.max(cx), max.y.max(cy));
+        }
+        Rect::from_corners(min, max)
+    }
+
+    /// Check whether a world-space point lies inside the rotated sprite quad.
+    /// 检查世界空间点是否位于旋转后的精灵四边形内。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::math::Vec2;
+    /// use es_engine::renderer::batch::SpriteInstance;
+    /// let sprite = SpriteInstance {
+    ///     position: [0.0, 0.0],
+    ///     rotation: std::f32::consts::FRAC_PI_4,
+    ///     size: [2.0, 2.0],
+    ///     origin: [0.5, 0.5],
+    ///     uv_rect: [0.0, 0.0, 1.0, 1.0],
+    ///     color: [1.0; 4],
+    /// };
+    /// assert!(sprite.contains_point(Vec2::new(0.0, 1.3)));
+    /// assert!(!sprite.contains_point(Vec2::new(0.9, 0.9)));
+    /// ```
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let [width, height] = self.size;
+        // Undo rotation around the sprite position | 围绕精灵位置撤销旋转
+        let local = Vec2::new(point.x - self.position[0], point.y - self.position[1]).rotate(-self.rotation);
+        let inside = |v: f32, extent: f32| v >= extent.min(0.0) && v <= extent.max(0.0);
+        inside(local.x + self.origin[0] * width, width) && inside(local.y + self.origin[1] * height, height)
+    }
+
+    /// Check whether the rotated sprite quad overlaps a convex world-space quad.
+    /// 检查旋转后的精灵四边形是否与世界空间凸四边形重叠。
+    ///
+    /// Quads that only touch along an edge or corner overlap.
+    /// 仅在边或角上接触的四边形也视为重叠。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::math::Vec2;
+    /// use es_engine::renderer::batch::SpriteInstance;
+    /// let square = |x0: f32, y0: f32, x1: f32, y1: f32| {
+    ///     [Vec2::new(x0, y0), Vec2::new(x1, y0), Vec2::new(x1, y1), Vec2::new(x0, y1)]
+    /// };
+    /// let sprite = SpriteInstance {
+    ///     position: [0.0, 0.0],
+    ///     rotation: 0.0,
+    ///     size: [2.0, 2.0],
+    ///     origin: [0.5, 0.5],
+    ///     uv_rect: [0.0, 0.0, 1.0, 1.0],
+    ///     color: [1.0; 4],
+    /// };
+    /// assert!(sprite.overlaps_quad(&square(0.5, 0.5, 3.0, 3.0)));
+    /// // Touching edges | 边接触
+    /// assert!(sprite.overlaps_quad(&square(1.0, -1.0, 2.0, 1.0)));
+    /// assert!(!sprite.overlaps_quad(&square(1.1, -1.0, 2.0, 1.0)));
+    ///
+    /// // A diamond misses a square inside its bounding box | 菱形与其包围盒内的正方形不相交
+    /// let diamond = SpriteInstance { rotation: std::f32::consts::FRAC_PI_4, ..sprite };
+    /// assert!(!diamond.overlaps_quad(&square(0.8, 0.8, 1.5, 1.5)));
+    /// assert!(diamond.overlaps_quad(&square(0.5, 0.5, 1.5, 1.5)));
+    /// ```
+    pub fn overlaps_quad(&self, corners: &[Vec2; 4]) -> bool {
+        let own = self.corners();
+
+        // Separating axis test over the edge normals of both quads | 对两个四边形的边法线进行分离轴测试
+        let separated = |quad: &[Vec2; 4]| {
+            (0..4).any(|i| {
+                let edge = quad[(i + 1) % 4] - quad[i];
+                let axis = Vec2::new(-edge.y, edge.x);
+                let project = |points: &[Vec2; 4]| {
+                    points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| {
+                        let d = p.dot(&axis);
+                        (lo.min(d), hi.max(d))
+                    })
+                };
+                let ((a_min, a_max), (b_min, b_max)) = (project(&own), project(corners));
+                a_max < b_min || b_max < a_min
+            })
+        };
+        !separated(&own) && !separated(corners)
+    }
+
+    /// World-space corner positions (top-left, top-right, bottom-right, bottom-left).
+    /// 世界空间角点位置（左上、右上、右下、左下）。
+    pub fn corners(&self) -> [Vec2; 4] {
+        self.vertices().map(|v| Vec2::from(v.position))
+    }
+
+    /// Compute the four transformed vertices of this sprite.
+    /// 计算此精灵变换后的四个顶点。
+    ///
+    /// Used by the non-instanced fallback path. Order: top-left, top-right,
+    /// bottom-right, bottom-left.
+    /// 用于非实例化的回退路径。顺序：左上、右上、右下、左下。
+    pub fn vertices(&self) -> [SpriteVertex; 4] {
+        let [x, y] = self.position;
+        let [width, height] = self.size;
+        let [u0, v0, u1, v1] = self.uv_rect;
+        let cos = self.rotation.cos();
+        let sin = self.rotation.sin();
+
+        // Origin offset | 原点偏移
+        let ox = self.origin[0] * width;
+        let oy = self.origin[1] * height;
+
+        // Local corner positions (Y-up) and UVs (Y-down) | 局部角点位置（Y向上）和UV（Y向下）
+        let corners = [
+            ((-ox, height - oy), [u0, v0]),
+            ((width - ox, height - oy), [u1, v0]),
+            ((width - ox, -oy), [u1, v1]),
+            ((-ox, -oy), [u0, v1]),
+        ];
+
+        corners.map(|((lx, ly), tex_coord)| {
+            SpriteVertex::new(
+                [lx * cos - ly * sin + x, lx * sin + ly * cos + y],
+                tex_coord,
+                self.color,
+            )
+        })
+    }
+}
@@ -1,14 +1,48 @@
 //! 2D camera implementation.
 //! 2D相机实现。
 
-use crate::math::Vec2;
+use crate::math::{Rect, Vec2};
 use glam::Mat3;
 
+use super::camera_behavior::{smoothing, CameraFollow, CameraShake};
+
+/// Animated zoom toward a level around a fixed point.
+/// 围绕固定点向目标级别的缩放动画。
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ZoomTarget {
+    zoom: f32,
+    anchor: Vec2,
+    smooth_time: f32,
+}
+
 /// 2D orthographic camera.
 /// 2D正交相机。
 ///
 /// Provides view and projection matrices for 2D rendering.
 /// 提供用于2D渲染的视图和投影矩阵。
+///
+/// Optional behaviors (follow, bounds, shake, smooth zoom, pixel snapping)
+/// are applied by `update`, which should run once per frame before rendering.
+/// Shake and snapping only affect rendering; `position` stays the logical
+/// camera position.
+/// 可选行为（跟随、边界、震动、平滑缩放、像素对齐）由`update`应用，应在渲染前每帧调用一次。
+/// 震动和对齐仅影响渲染；`position`保持为逻辑相机位置。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::math::{Rect, Vec2};
+/// use es_engine::renderer::{Camera2D, CameraFollow};
+/// let mut camera = Camera2D::new(100.0, 100.0);
+/// camera.follow = Some(CameraFollow { target: Vec2::new(30.0, 0.0), dead_zone: Vec2::new(10.0, 10.0), smooth_time: 0.0 });
+/// camera.update(0.016);
+/// // Target sits on the dead zone edge | 目标位于死区边缘
+/// assert_eq!(camera.position, Vec2::new(20.0, 0.0));
+///
+/// // The view may not leave the world | 视图不能离开世界
+/// camera.bounds = Some(Rect::new(-100.0, -100.0, 160.0, 200.0));
+/// camera.update(0.016);
+/// assert_eq!(camera.position, Vec2::new(10.0, 0.0));
+/// ```
 #[derive(Debug, Clone)]
 pub struct Camera2D {
     /// Camera position in world space.
@@ -30,6 +64,31 @@ pub struct Camera2D {
     /// Viewport height.
     /// 视口高度。
     height: f32,
+
+    /// Target to follow.
+    /// 跟随的目标。
+    pub follow: Option<CameraFollow>,
+
+    /// World area the view stays inside (`y` is the bottom edge).
+    /// 视图保持在其内部的世界区域（`y`为下边缘）。
+    pub bounds: Option<Rect>,
+
+    /// Screen shake.
+    /// 屏幕震动。
+    pub shake: CameraShake,
+
+    /// Snap the rendered position to whole screen pixels.
+    /// 将渲染位置对齐到整数屏幕像素。
+    pub pixel_snap: bool,
+
+    /// Zoom animation in progress.
+    /// 进行中的缩放动画。
+    zoom_target: Option<ZoomTarget>,
+
+    /// Shake offset in world units and rotation from the last update.
+    /// 上次更新得到的震动偏移（世界单位）和旋转。
+    shake_offset: Vec2,
+    shake_angle: f32,
 }
 
 impl Camera2D {
@@ -46,6 +105,13 @@ impl Camera2D {
             zoom: 1.0,
             width,
             height,
+            follow: None,
+            bounds: None,
+            shake: CameraShake::default(),
+            pixel_snap: false,
+            zoom_target: None,
+            shake_offset: Vec2::ZERO,
+            shake_angle: 0.0,
         }
     }
 
@@ -80,24 +146,26 @@ impl Camera2D {
         // 缩放因子：世界单位到NDC
         let sx = 2.0 / self.width * self.zoom;
         let sy = 2.0 / self.height * self.zoom;
+        let position = self.render_position();
+        let rotation = self.render_rotation();
 
         // Handle rotation
         // 处理旋转
-        let cos = self.rotation.cos();
-        let sin = self.rotation.sin();
+        let cos = rotation.cos();
+        let sin = rotation.sin();
 
         // Translation: camera position to NDC
         // 平移：相机位置到NDC
         // We negate position because moving camera right should move world left
         // 取反位置，因为相机向右移动应该使世界向左移动
-        let tx = -self.position.x * sx;
-        let ty = -self.position.y * sy;
+        let tx = -position.x * sx;
+        let ty = -position.y * sy;
 
         // Combine scale, rotation, and translation
         // 组合缩放、旋转和平移
         // Matrix = Scale * Rotation * Translation (applied right to left)
         // 矩阵 = 缩放 * 旋转 * 平移（从右到左应用）
-        if self.rotation != 0.0 {
+        if rotation != 0.0 {
             // With rotation: need to rotate the translation as well
             // 有旋转时：平移也需要旋转
             let rtx = tx * cos - ty * sin;
@@ -129,23 +197,25 @@ impl Camera2D {
         // 将屏幕坐标转换为类NDC坐标（居中，Y向上）
         let centered_x = screen.x - self.width / 2.0;
         let centered_y = self.height / 2.0 - screen.y; // Flip Y
+        let position = self.render_position();
+        let rotation = self.render_rotation();
 
         // Apply inverse zoom and add camera position
         // 应用反向缩放并加上相机位置
-        let world_x = centered_x / self.zoom + self.position.x;
-        let world_y = centered_y / self.zoom + self.position.y;
+        let world_x = centered_x / self.zoom + position.x;
+        let world_y = centered_y / self.zoom + position.y;
 
-        if self.rotation != 0.0 {
+        if rotation != 0.0 {
             // Apply inverse rotation around camera position
             // 围绕相机位置应用反向旋转
-            let dx = world_x - self.position.x;
-            let dy = world_y - self.position.y;
-            let cos = (-self.rotation).cos();
-            let sin = (-self.rotation).sin();
+            let dx = world_x - position.x;
+            let dy = world_y - position.y;
+            let cos = (-rotation).cos();
+            let sin = (-rotation).sin();
 
             Vec2::new(
-                dx * cos - dy * sin + self.position.x,
-                dx * sin + dy * cos + self.position.y,
+                dx * cos - dy * sin + position.x,
+                dx * sin + dy * cos + position.y,
             )
         } else {
             Vec2::new(world_x, world_y)
@@ -158,12 +228,14 @@ impl Camera2D {
     /// World: Y-up | 世界：Y向上
     /// Screen: (0,0) at top-left, Y-down | 屏幕：(0,0)在左上角，Y向下
     pub fn world_to_screen(&self, world: Vec2) -> Vec2 {
-        let dx = world.x - self.position.x;
-        let dy = world.y - self.position.y;
+        let position = self.render_position();
+        let rotation = self.render_rotation();
+        let dx = world.x - position.x;
+        let dy = world.y - position.y;
 
-        let (rx, ry) = if self.rotation != 0.0 {
-            let cos = self.rotation.cos();
-            let sin = self.rotation.sin();
+        let (rx, ry) = if rotation != 0.0 {
+            let cos = rotation.cos();
+            let sin = rotation.sin();
             (dx * cos - dy * sin, dx * sin + dy * cos)
         } else {
             (dx, dy)
@@ -177,6 +249,133 @@ impl Camera2D {
         Vec2::new(screen_x, screen_y)
     }
 
+    /// Get the world-space bounding rectangle of the visible area.
+    /// 获取可见区域的世界空间包围矩形。
+    ///
+    /// With rotation this is the axis-aligned box around the rotated view.
+    /// The rect's `y` is its bottom edge (world Y-up).
+    /// 有旋转时为旋转视图的轴对齐包围盒。矩形的`y`为其下边缘（世界Y向上）。
+    pub fn view_rect(&self) -> Rect {
+        let extent = Self::view_extent(self.width, self.height, self.zoom, self.render_rotation());
+        Rect::from_center(self.render_position(), extent.x * 2.0, extent.y * 2.0)
+    }
+
+    /// Half size of the axis-aligned box around a rotated view.
+    /// 旋转视图轴对齐包围盒的半尺寸。
+    fn view_extent(width: f32, height: f32, zoom: f32, rotation: f32) -> Vec2 {
+        let half_w = width * 0.5 / zoom;
+        let half_h = height * 0.5 / zoom;
+        let (sin, cos) = rotation.sin_cos();
+        Vec2::new(
+            half_w * cos.abs() + half_h * sin.abs(),
+            half_w * sin.abs() + half_h * cos.abs(),
+        )
+    }
+
+    /// Position the view is rendered from, including shake and pixel snapping.
+    /// 渲染视图所用的位置，包含震动和像素对齐。
+    pub fn render_position(&self) -> Vec2 {
+        let position = self.position + self.shake_offset;
+        if !self.pixel_snap || self.render_rotation() != 0.0 {
+            return position;
+        }
+
+        // Keep world points on whole pixels, including odd viewport sizes | 使世界点保持在整数像素上，包括奇数视口尺寸
+        let snap = |p: f32, size: f32| ((p * self.zoom - size * 0.5).round() + size * 0.5) / self.zoom;
+        Vec2::new(snap(position.x, self.width), snap(position.y, self.height))
+    }
+
+    /// Rotation the view is rendered with, including shake.
+    /// 渲染视图所用的旋转，包含震动。
+    #[inline]
+    pub fn render_rotation(&self) -> f32 {
+        self.rotation + self.shake_angle
+    }
+
+    /// Apply behaviors for one frame: smooth zoom, follow, bounds, then shake.
+    /// 应用一帧的行为：平滑缩放、跟随、边界，然后震动。
+    pub fn update(&mut self, delta_seconds: f32) {
+        let delta_seconds = delta_seconds.max(0.0);
+
+        if let Some(target) = self.zoom_target {
+            let t = smoothing(target.smooth_time, delta_seconds);
+            let zoom = (self.zoom.ln() + (target.zoom.ln() - self.zoom.ln()) * t).exp();
+            let done = (zoom - target.zoom).abs() <= target.zoom * 1e-4;
+            self.zoom_at(if done { target.zoom } else { zoom }, target.anchor);
+            if done {
+                self.zoom_target = None;
+            }
+        }
+
+        if let Some(follow) = self.follow {
+            let desired = follow.desired_position(self.position);
+            let t = smoothing(follow.smooth_time, delta_seconds);
+            self.position = self.position + (desired - self.position) * t;
+        }
+
+        if let Some(bounds) = self.bounds {
+            self.position = self.clamp_to_bounds(self.position, bounds);
+        }
+
+        let (offset, angle) = self.shake.update(delta_seconds);
+        self.shake_offset = offset / self.zoom;
+        self.shake_angle = angle;
+    }
+
+    /// Closest position whose view stays inside `bounds`; centered on axes where the view is larger.
+    /// 视图保持在`bounds`内的最近位置；视图更大的轴上居中。
+    pub fn clamp_to_bounds(&self, position: Vec2, bounds: Rect) -> Vec2 {
+        let extent = Self::view_extent(self.width, self.height, self.zoom, self.rotation);
+        let axis = |p: f32, min: f32, size: f32, half: f32| {
+            if size <= half * 2.0 {
+                min + size * 0.5
+            } else {
+                p.clamp(min + half, min + size - half)
+            }
+        };
+        Vec2::new(
+            axis(position.x, bounds.x, bounds.width, extent.x),
+            axis(position.y, bounds.y, bounds.height, extent.y),
+        )
+    }
+
+    /// Set the zoom level while keeping a world point fixed on screen.
+    /// 设置缩放级别，同时保持某个世界点在屏幕上的位置不变。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::math::Vec2;
+    /// use es_engine::renderer::Camera2D;
+    /// let mut camera = Camera2D::new(800.0, 600.0);
+    /// let anchor = Vec2::new(100.0, 50.0);
+    /// let before = camera.world_to_screen(anchor);
+    /// camera.zoom_at(4.0, anchor);
+    /// assert!((camera.world_to_screen(anchor) - before).length() < 1e-3);
+    /// ```
+    pub fn zoom_at(&mut self, zoom: f32, anchor: Vec2) {
+        let previous = self.zoom;
+        self.set_zoom(zoom);
+        self.position = anchor - (anchor - self.position) * (previous / self.zoom);
+    }
+
+    /// Animate the zoom level toward `zoom` around a fixed world point.
+    /// 围绕固定世界点将缩放级别动画过渡到`zoom`。
+    ///
+    /// # Arguments | 参数
+    /// * `zoom` - Target zoom level | 目标缩放级别
+    /// * `anchor` - World point kept fixed on screen | 在屏幕上保持不动的世界点
+    /// * `smooth_time` - Seconds to cover about 63% of the remaining change (0 = instant) | 覆盖剩余变化约63%所需的秒数（0 = 立即）
+    pub fn zoom_to(&mut self, zoom: f32, anchor: Vec2, smooth_time: f32) {
+        self.zoom_target = Some(ZoomTarget { zoom: zoom.clamp(0.01, 100.0), anchor, smooth_time });
+    }
+
+    /// Stop a zoom animation at the current level.
+    /// 在当前级别停止缩放动画。
+    #[inline]
+    pub fn stop_zoom(&mut self) {
+        self.zoom_target = None;
+    }
+
     /// Move camera by delta.
     /// 按增量移动相机。
     #[inline]
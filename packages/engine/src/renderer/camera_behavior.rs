@@ -0,0 +1,176 @@
+//! Camera follow and shake behaviors.
+//! 相机跟随和震动行为。
+
+use crate::math::Vec2;
+
+/// Follow a target, moving only when it leaves a dead zone.
+/// 跟随目标，仅当目标离开死区时移动。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::math::Vec2;
+/// use es_engine::renderer::CameraFollow;
+/// let follow = CameraFollow { dead_zone: Vec2::new(50.0, 20.0), ..CameraFollow::new(Vec2::new(100.0, 0.0)) };
+/// // Target inside the dead zone: the camera stays | 目标在死区内：相机不动
+/// assert_eq!(follow.desired_position(Vec2::new(70.0, 10.0)), Vec2::new(70.0, 10.0));
+/// // Target outside: the camera moves just enough to bring it to the edge | 目标在死区外：相机仅移动到使目标位于边缘
+/// assert_eq!(follow.desired_position(Vec2::new(0.0, -40.0)), Vec2::new(50.0, -20.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraFollow {
+    /// Point to follow in world space.
+    /// 世界空间中的跟随点。
+    pub target: Vec2,
+
+    /// Half size of the dead zone around the camera center, in world units.
+    /// 相机中心周围死区的半尺寸（世界单位）。
+    pub dead_zone: Vec2,
+
+    /// Time in seconds to cover about 63% of the remaining distance (0 = snap).
+    /// 覆盖剩余距离约63%所需的秒数（0 = 立即到达）。
+    pub smooth_time: f32,
+}
+
+impl CameraFollow {
+    /// Follow a target without dead zone or smoothing.
+    /// 无死区、无平滑地跟随目标。
+    pub fn new(target: Vec2) -> Self {
+        Self { target, dead_zone: Vec2::ZERO, smooth_time: 0.0 }
+    }
+
+    /// Closest camera position that keeps the target inside the dead zone.
+    /// 使目标保持在死区内的最近相机位置。
+    pub fn desired_position(&self, position: Vec2) -> Vec2 {
+        let axis = |position: f32, target: f32, half: f32| {
+            let half = half.max(0.0);
+            position.clamp(target - half, target + half)
+        };
+        Vec2::new(
+            axis(position.x, self.target.x, self.dead_zone.x),
+            axis(position.y, self.target.y, self.dead_zone.y),
+        )
+    }
+}
+
+/// Trauma-based screen shake.
+/// 基于创伤值的屏幕震动。
+///
+/// Trauma in `[0, 1]` is added by events and decays over time; the shake
+/// strength is trauma squared, so small hits stay subtle.
+/// 创伤值范围`[0, 1]`，由事件增加并随时间衰减；震动强度为创伤值的平方，因此轻微冲击保持细微。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::math::Vec2;
+/// use es_engine::renderer::CameraShake;
+/// let mut shake = CameraShake::default();
+/// shake.add_trauma(1.5);
+/// assert_eq!(shake.trauma, 1.0);
+/// for _ in 0..30 {
+///     shake.update(1.0 / 60.0);
+/// }
+/// assert!((shake.trauma - 0.5).abs() < 1e-4);
+///
+/// // Trauma decays to zero and the shake stops | 创伤值衰减到零，震动停止
+/// for _ in 0..60 {
+///     shake.update(1.0 / 60.0);
+/// }
+/// assert_eq!(shake.trauma, 0.0);
+/// assert_eq!(shake.update(1.0 / 60.0), (Vec2::ZERO, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraShake {
+    /// Current trauma in `[0, 1]`.
+    /// 当前创伤值，范围`[0, 1]`。
+    pub trauma: f32,
+
+    /// Trauma lost per second.
+    /// 每秒损失的创伤值。
+    pub decay: f32,
+
+    /// Largest offset in screen pixels.
+    /// 最大偏移（屏幕像素）。
+    pub max_offset: Vec2,
+
+    /// Largest rotation in radians.
+    /// 最大旋转（弧度）。
+    pub max_angle: f32,
+
+    /// Noise samples per second.
+    /// 每秒噪声采样数。
+    pub frequency: f32,
+
+    /// Noise time.
+    /// 噪声时间。
+    time: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.0,
+            max_offset: Vec2::new(16.0, 16.0),
+            max_angle: 0.05,
+            frequency: 15.0,
+            time: 0.0,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Add trauma, clamped to 1.
+    /// 增加创伤值，上限为1。
+    #[inline]
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Advance the shake and return its screen offset in pixels and rotation.
+    /// 推进震动并返回屏幕偏移（像素）和旋转。
+    pub fn update(&mut self, delta_seconds: f32) -> (Vec2, f32) {
+        if self.trauma <= 0.0 {
+            self.time = 0.0;
+            return (Vec2::ZERO, 0.0);
+        }
+
+        self.time += delta_seconds;
+        let strength = self.trauma * self.trauma;
+        let t = self.time * self.frequency;
+        self.trauma = (self.trauma - self.decay.max(0.0) * delta_seconds).max(0.0);
+
+        let offset = Vec2::new(
+            self.max_offset.x * strength * noise(t, 0),
+            self.max_offset.y * strength * noise(t, 1),
+        );
+        (offset, self.max_angle * strength * noise(t, 2))
+    }
+}
+
+/// Smooth 1D value noise in `[-1, 1]`, one independent stream per channel.
+/// `[-1, 1]`范围的平滑一维值噪声，每个通道一个独立序列。
+fn noise(t: f32, channel: u32) -> f32 {
+    let lattice = |i: i32| {
+        let mut h = (i as u32).wrapping_mul(0x9E37_79B9) ^ channel.wrapping_mul(0x85EB_CA6B);
+        h ^= h >> 16;
+        h = h.wrapping_mul(0x7FEB_352D);
+        h ^= h >> 15;
+        h as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    let i = t.floor();
+    let f = t - i;
+    let f = f * f * (3.0 - 2.0 * f);
+    let a = lattice(i as i32);
+    a + (lattice(i as i32 + 1) - a) * f
+}
+
+/// Fraction of the remaining distance covered in one step of exponential smoothing.
+/// 指数平滑单步覆盖的剩余距离比例。
+#[inline]
+pub(crate) fn smoothing(smooth_time: f32, delta_seconds: f32) -> f32 {
+    if smooth_time > 0.0 {
+        1.0 - (-delta_seconds / smooth_time).exp()
+    } else {
+        1.0
+    }
+}
@@ -0,0 +1,233 @@
+//! Ordered render passes executed each frame.
+//! 每帧执行的有序渲染通道。
+
+use std::ops::RangeInclusive;
+
+/// First sorting layer drawn by the default "ui" pass (the `UI` sorting layer).
+/// 默认 "ui" 通道绘制的第一个排序层（`UI`排序层）。
+pub const UI_LAYER: i32 = 200;
+
+/// What a render pass draws.
+/// 渲染通道绘制的内容。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderPassKind {
+    /// Editor grid and axes (editor mode only).
+    /// 编辑器网格和坐标轴（仅编辑器模式）。
+    Grid,
+    /// Submitted sprites, tilemaps, particles, shapes and meshes within the pass's `layers`.
+    /// 通道`layers`范围内已提交的精灵、瓦片地图、粒子、图形和网格。
+    World,
+    /// Editor gizmos and axis indicator (editor mode only).
+    /// 编辑器Gizmo和坐标轴指示器（仅编辑器模式）。
+    Gizmos,
+    /// Apply the post-processing stack of the surface being rendered.
+    /// 应用正在渲染的表面的后处理栈。
+    ///
+    /// While the stack has enabled effects, earlier screen passes are
+    /// captured offscreen and this pass writes the processed frame to the
+    /// screen; later passes (e.g. gizmos, UI) are not post-processed.
+    /// The pass target is ignored.
+    /// 当栈中有启用的效果时，之前的屏幕通道会被离屏捕获，此通道将处理后的帧写入屏幕；
+    /// 之后的通道（如Gizmo、UI）不会被后处理。通道目标会被忽略。
+    PostProcess,
+    /// Multiply submitted lights over the target (see `ViewportConfig::lighting`).
+    /// 将已提交的光源与目标相乘（参见`ViewportConfig::lighting`）。
+    ///
+    /// Skipped when lighting is disabled for the surface being rendered.
+    /// 当正在渲染的表面禁用光照时跳过。
+    Lighting,
+    /// Full-screen copy of a texture, alpha-blended over the target.
+    /// 纹理的全屏拷贝，以透明度混合到目标上。
+    Blit {
+        /// Texture ID to copy.
+        /// 要拷贝的纹理ID。
+        source: u32,
+    },
+}
+
+impl RenderPassKind {
+    /// Convert from the numeric code used by the JavaScript API.
+    /// 从JavaScript API使用的数字代码转换。
+    ///
+    /// 0=Grid, 1=World, 2=Gizmos, 3=Blit (uses `source`), 4=PostProcess, 5=Lighting.
+    pub fn from_code(code: u8, source: u32) -> Option<Self> {
+        match code {
+            0 => Some(RenderPassKind::Grid),
+            1 => Some(RenderPassKind::World),
+            2 => Some(RenderPassKind::Gizmos),
+            3 => Some(RenderPassKind::Blit { source }),
+            4 => Some(RenderPassKind::PostProcess),
+            5 => Some(RenderPassKind::Lighting),
+            _ => None,
+        }
+    }
+}
+
+/// A single render pass.
+/// 单个渲染通道。
+#[derive(Clone, Debug)]
+pub struct RenderPass {
+    /// Unique pass name.
+    /// 唯一通道名称。
+    pub name: String,
+
+    /// What the pass draws.
+    /// 通道绘制的内容。
+    pub kind: RenderPassKind,
+
+    /// Render texture ID to draw into, or `None` for the screen.
+    /// 要绘制到的渲染纹理ID，`None`表示屏幕。
+    pub target: Option<u32>,
+
+    /// Whether to clear the target before drawing.
+    /// 绘制前是否清除目标。
+    pub clear: bool,
+
+    /// Whether the pass runs.
+    /// 通道是否执行。
+    pub enabled: bool,
+
+    /// Sorting layers drawn by a World pass.
+    /// World通道绘制的排序层。
+    pub layers: RangeInclusive<i32>,
+}
+
+impl RenderPass {
+    /// Create a pass drawing to the screen.
+    /// 创建绘制到屏幕的通道。
+    pub fn new(name: &str, kind: RenderPassKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            target: None,
+            clear: false,
+            enabled: true,
+            layers: i32::MIN..=i32::MAX,
+        }
+    }
+
+    /// Draw into a render texture instead of the screen.
+    /// 绘制到渲染纹理而不是屏幕。
+    pub fn with_target(mut self, target: Option<u32>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Clear the target before drawing.
+    /// 绘制前清除目标。
+    pub fn with_clear(mut self, clear: bool) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    /// Limit a World pass to a range of sorting layers.
+    /// 将World通道限制在排序层范围内。
+    pub fn with_layers(mut self, layers: RangeInclusive<i32>) -> Self {
+        self.layers = layers;
+        self
+    }
+}
+
+/// Ordered list of render passes.
+/// 有序的渲染通道列表。
+///
+/// Replaces a fixed draw order so scenes can be rendered into textures and
+/// composited (minimaps, portals, post-processing).
+/// 替代固定的绘制顺序，使场景可以渲染到纹理并进行合成（小地图、传送门、后处理）。
+#[derive(Clone, Debug)]
+pub struct FrameGraph {
+    /// Passes in execution order.
+    /// 按执行顺序排列的通道。
+    passes: Vec<RenderPass>,
+}
+
+impl FrameGraph {
+    /// Create an empty frame graph.
+    /// 创建空的帧图。
+    pub fn new() -> Self {
+        Self { passes: Vec::new() }
+    }
+
+    /// Reset to the default grid → world → lighting → post → ui → gizmos order.
+    /// 重置为默认的 网格 → 世界 → 光照 → 后处理 → UI → Gizmo 顺序。
+    ///
+    /// "world" draws layers below `UI_LAYER` and "ui" the rest, so UI is
+    /// neither lit nor post-processed.
+    /// "world" 绘制低于`UI_LAYER`的层，"ui" 绘制其余层，因此UI不受光照和后处理影响。
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Append a pass, replacing any existing pass with the same name.
+    /// 追加通道，替换同名的已有通道。
+    pub fn add_pass(&mut self, pass: RenderPass) {
+        self.remove_pass(&pass.name);
+        self.passes.push(pass);
+    }
+
+    /// Insert a pass before the named pass (or append if not found).
+    /// 在指定通道之前插入通道（未找到则追加）。
+    pub fn insert_before(&mut self, before: &str, pass: RenderPass) {
+        self.remove_pass(&pass.name);
+        match self.index_of(before) {
+            Some(index) => self.passes.insert(index, pass),
+            None => self.passes.push(pass),
+        }
+    }
+
+    /// Remove a pass by name.
+    /// 按名称移除通道。
+    pub fn remove_pass(&mut self, name: &str) -> bool {
+        match self.index_of(name) {
+            Some(index) => {
+                self.passes.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get a pass by name.
+    /// 按名称获取通道。
+    pub fn get(&self, name: &str) -> Option<&RenderPass> {
+        self.passes.iter().find(|p| p.name == name)
+    }
+
+    /// Get a mutable pass by name.
+    /// 按名称获取可变通道。
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut RenderPass> {
+        self.passes.iter_mut().find(|p| p.name == name)
+    }
+
+    /// Remove all passes.
+    /// 移除所有通道。
+    pub fn clear(&mut self) {
+        self.passes.clear();
+    }
+
+    /// Passes in execution order.
+    /// 按执行顺序排列的通道。
+    #[inline]
+    pub fn passes(&self) -> &[RenderPass] {
+        &self.passes
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.passes.iter().position(|p| p.name == name)
+    }
+}
+
+impl Default for FrameGraph {
+    fn default() -> Self {
+        Self {
+            passes: vec![
+                RenderPass::new("grid", RenderPassKind::Grid),
+                RenderPass::new("world", RenderPassKind::World).with_layers(i32::MIN..=UI_LAYER - 1),
+                RenderPass::new("lighting", RenderPassKind::Lighting),
+                RenderPass::new("post", RenderPassKind::PostProcess),
+                RenderPass::new("ui", RenderPassKind::World).with_layers(UI_LAYER..=i32::MAX),
+                RenderPass::new("gizmos", RenderPassKind::Gizmos),
+            ],
+        }
+    }
+}
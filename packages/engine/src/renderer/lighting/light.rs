@@ -0,0 +1,201 @@
+//! Light definitions.
+//! 光源定义。
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Vec2;
+
+/// Light data stride for batch submission.
+/// 批量提交时的光源数据步长。
+///
+/// Layout | 布局:
+/// `[kind, x, y, height, r, g, b, intensity, radius, direction, coneAngle, softness]`
+pub const LIGHT_STRIDE: usize = 12;
+
+/// Light type.
+/// 光源类型。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightKind {
+    /// Omnidirectional light with a radius.
+    /// 带半径的全向光源。
+    Point,
+    /// Cone-shaped light.
+    /// 锥形光源。
+    Spot,
+    /// Uniform light over the whole view (ambient).
+    /// 覆盖整个视图的均匀光照（环境光）。
+    Global,
+}
+
+impl LightKind {
+    /// Convert from the numeric code used by batch submission.
+    /// 从批量提交使用的数字代码转换。
+    ///
+    /// 0=Point, 1=Spot, 2=Global.
+    pub fn from_code(code: f32) -> Option<Self> {
+        match code as i32 {
+            0 => Some(LightKind::Point),
+            1 => Some(LightKind::Spot),
+            2 => Some(LightKind::Global),
+            _ => None,
+        }
+    }
+}
+
+/// A 2D light.
+/// 2D光源。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Light {
+    /// Light type.
+    /// 光源类型。
+    pub kind: LightKind,
+
+    /// Position in world space.
+    /// 世界空间位置。
+    pub position: Vec2,
+
+    /// Height above the sprite plane, used with normal maps.
+    /// 距精灵平面的高度，用于法线贴图。
+    pub height: f32,
+
+    /// Linear RGB color (0.0-1.0).
+    /// 线性RGB颜色（0.0-1.0）。
+    pub color: [f32; 3],
+
+    /// Brightness multiplier.
+    /// 亮度倍数。
+    pub intensity: f32,
+
+    /// Reach in world units (point and spot lights).
+    /// 照射范围（世界单位，点光源和聚光灯）。
+    pub radius: f32,
+
+    /// Spot direction in radians (0 = +X, counter-clockwise).
+    /// 聚光方向（弧度，0 = +X，逆时针）。
+    pub direction: f32,
+
+    /// Spot cone full angle in radians.
+    /// 聚光锥全角（弧度）。
+    pub cone_angle: f32,
+
+    /// Fraction of the cone that fades out at the edge (0.0-1.0).
+    /// 锥形边缘淡出的比例（0.0-1.0）。
+    pub softness: f32,
+}
+
+impl Light {
+    /// Create a point light.
+    /// 创建点光源。
+    pub fn point(position: Vec2, radius: f32, color: [f32; 3], intensity: f32) -> Self {
+        Self {
+            kind: LightKind::Point,
+            position,
+            height: 0.0,
+            color,
+            intensity,
+            radius,
+            direction: 0.0,
+            cone_angle: std::f32::consts::TAU,
+            softness: 0.0,
+        }
+    }
+
+    /// Create a global (ambient) light.
+    /// 创建全局（环境）光源。
+    pub fn global(color: [f32; 3], intensity: f32) -> Self {
+        Self {
+            kind: LightKind::Global,
+            ..Self::point(Vec2::ZERO, 0.0, color, intensity)
+        }
+    }
+
+    /// Parse lights from packed batch data (see `LIGHT_STRIDE`).
+    /// 从打包的批量数据解析光源（参见`LIGHT_STRIDE`）。
+    pub fn parse_batch(data: &[f32]) -> Result<Vec<Light>> {
+        if !data.len().is_multiple_of(LIGHT_STRIDE) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Light data length {} is not a multiple of {}",
+                data.len(),
+                LIGHT_STRIDE
+            )));
+        }
+
+        data.chunks_exact(LIGHT_STRIDE)
+            .map(|l| {
+                let kind = LightKind::from_code(l[0]).ok_or_else(|| {
+                    EngineError::InvalidBatchData(format!("Unknown light kind: {}", l[0]))
+                })?;
+                Ok(Light {
+                    kind,
+                    position: Vec2::new(l[1], l[2]),
+                    height: l[3],
+                    color: [l[4], l[5], l[6]],
+                    intensity: l[7],
+                    radius: l[8],
+                    direction: l[9],
+                    cone_angle: l[10],
+                    softness: l[11].clamp(0.0, 1.0),
+                })
+            })
+            .collect()
+    }
+
+    /// Unshadowed light contribution at a world point (0.0 = none).
+    /// 世界坐标点处的无阴影光照贡献（0.0 = 无）。
+    ///
+    /// Matches the falloff used on the GPU, so gameplay code can ask
+    /// "is this point lit?" without reading pixels back.
+    /// 与GPU使用的衰减一致，游戏逻辑无需回读像素即可查询"该点是否被照亮"。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::math::Vec2;
+    /// use es_engine::renderer::Light;
+    /// let light = Light::point(Vec2::ZERO, 100.0, [1.0, 1.0, 1.0], 1.0);
+    /// assert_eq!(light.attenuation_at(Vec2::ZERO), 1.0);
+    /// assert_eq!(light.attenuation_at(Vec2::new(50.0, 0.0)), 0.25);
+    /// assert_eq!(light.attenuation_at(Vec2::new(150.0, 0.0)), 0.0);
+    /// ```
+    pub fn attenuation_at(&self, point: Vec2) -> f32 {
+        match self.kind {
+            LightKind::Global => self.intensity,
+            LightKind::Point | LightKind::Spot => {
+                if self.radius <= 0.0 {
+                    return 0.0;
+                }
+                let to_point = point - self.position;
+                let distance = to_point.length();
+                if distance >= self.radius {
+                    return 0.0;
+                }
+                let falloff = 1.0 - distance / self.radius;
+                let mut attenuation = falloff * falloff * self.intensity;
+
+                if self.kind == LightKind::Spot && distance > 0.0 {
+                    let (cos_outer, cos_inner) = self.cone_cosines();
+                    let dir = Vec2::new(self.direction.cos(), self.direction.sin());
+                    let cos_angle = to_point.dot(&dir) / distance;
+                    attenuation *= smoothstep(cos_outer, cos_inner, cos_angle);
+                }
+
+                attenuation
+            }
+        }
+    }
+
+    /// Cosines of the outer and inner spot cone half-angles.
+    /// 聚光锥外半角和内半角的余弦值。
+    pub fn cone_cosines(&self) -> (f32, f32) {
+        let outer = (self.cone_angle * 0.5).clamp(0.0, std::f32::consts::PI);
+        let inner = outer * (1.0 - self.softness);
+        // Keep the edges apart so the GLSL smoothstep stays defined
+        // 保持边缘分离，使GLSL的smoothstep有定义
+        (outer.cos(), inner.cos().max(outer.cos() + 1e-4))
+    }
+}
+
+/// GLSL-style smoothstep.
+/// GLSL风格的smoothstep。
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
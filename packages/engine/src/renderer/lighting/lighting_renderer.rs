@@ -0,0 +1,315 @@
+//! Light accumulation renderer.
+//! 光照累积渲染器。
+
+use std::collections::HashMap;
+
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlTexture, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use super::super::camera::Camera2D;
+use super::super::stats;
+use super::super::material::{BlendMode, MaterialManager};
+use super::super::render_texture::RenderTexture;
+use super::super::shader::{ShaderManager, SHADER_ID_LIGHT, MAX_SHADOW_SEGMENTS};
+use super::light::{Light, LightKind};
+use super::occluder::OccluderSet;
+
+/// 2D lighting renderer.
+/// 2D光照渲染器。
+///
+/// Lights and occluders are submitted every frame, like sprites. When the
+/// lighting pass runs, lights are accumulated additively into a light
+/// buffer (cleared to the sum of global lights), which is then multiplied
+/// over the scene.
+/// 光源和遮挡体与精灵一样每帧提交。光照通道执行时，光源以加法方式累积到光照缓冲区
+/// （以全局光之和清除），然后与场景相乘。
+pub struct LightingRenderer {
+    /// Lights submitted this frame.
+    /// 本帧提交的光源。
+    lights: Vec<Light>,
+
+    /// Occluders submitted this frame.
+    /// 本帧提交的遮挡体。
+    occluders: OccluderSet,
+
+    /// Normal map texture for each color texture ID.
+    /// 每个颜色纹理ID对应的法线贴图纹理。
+    normal_maps: HashMap<u32, u32>,
+
+    /// Size of the light source used for shadow penumbrae, in world units.
+    /// 用于阴影半影的光源尺寸（世界单位）。
+    pub shadow_softness: f32,
+
+    /// Accumulated light.
+    /// 累积光照。
+    light_buffer: Option<RenderTexture>,
+
+    /// Sprite normals for the current view.
+    /// 当前视图的精灵法线。
+    normal_buffer: Option<RenderTexture>,
+
+    /// Vertex array for light quads.
+    /// 光源四边形的顶点数组。
+    vao: WebGlVertexArrayObject,
+
+    /// Vertex buffer for light quads.
+    /// 光源四边形的顶点缓冲区。
+    vbo: WebGlBuffer,
+
+    /// Reused segment upload buffer.
+    /// 复用的线段上传缓冲区。
+    segment_scratch: Vec<f32>,
+}
+
+impl LightingRenderer {
+    /// Create a new lighting renderer.
+    /// 创建新的光照渲染器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+        gl.buffer_data_with_i32(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            (8 * std::mem::size_of::<f32>()) as i32,
+            WebGl2RenderingContext::DYNAMIC_DRAW,
+        );
+
+        gl.enable_vertex_attrib_array(0);
+        gl.vertex_attrib_pointer_with_i32(0, 2, WebGl2RenderingContext::FLOAT, false, 0, 0);
+
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            lights: Vec::new(),
+            occluders: OccluderSet::new(),
+            normal_maps: HashMap::new(),
+            shadow_softness: 8.0,
+            light_buffer: None,
+            normal_buffer: None,
+            vao,
+            vbo,
+            segment_scratch: Vec::with_capacity(MAX_SHADOW_SEGMENTS * 4),
+        })
+    }
+
+    /// Submit lights from packed data (see `LIGHT_STRIDE`).
+    /// 从打包数据提交光源（参见`LIGHT_STRIDE`）。
+    pub fn submit_lights(&mut self, data: &[f32]) -> Result<()> {
+        let lights = Light::parse_batch(data)?;
+        self.lights.extend(lights);
+        Ok(())
+    }
+
+    /// Add a single light for this frame.
+    /// 为本帧添加单个光源。
+    pub fn add_light(&mut self, light: Light) {
+        self.lights.push(light);
+    }
+
+    /// Submit occluder polygons.
+    /// 提交遮挡多边形。
+    ///
+    /// # Arguments | 参数
+    /// * `points` - Flat points of all polygons | 所有多边形的扁平点
+    /// * `counts` - Point count of each polygon | 每个多边形的点数
+    pub fn submit_occluders(&mut self, points: &[f32], counts: &[u32]) -> Result<()> {
+        self.occluders.add_batch(points, counts)
+    }
+
+    /// Lights submitted this frame.
+    /// 本帧提交的光源。
+    #[inline]
+    pub fn lights(&self) -> &[Light] {
+        &self.lights
+    }
+
+    /// Occluders submitted this frame.
+    /// 本帧提交的遮挡体。
+    #[inline]
+    pub fn occluders(&self) -> &OccluderSet {
+        &self.occluders
+    }
+
+    /// Assign a normal map to a color texture, or remove it with `None`.
+    /// 为颜色纹理指定法线贴图，`None`表示移除。
+    ///
+    /// Sprites drawn with `texture_id` use the normal map with the same UVs.
+    /// Atlas regions are not supported.
+    /// 使用`texture_id`绘制的精灵以相同的UV使用法线贴图。不支持图集区域。
+    pub fn set_normal_map(&mut self, texture_id: u32, normal_texture_id: Option<u32>) {
+        match normal_texture_id {
+            Some(normal) => {
+                self.normal_maps.insert(texture_id, normal);
+            }
+            None => {
+                self.normal_maps.remove(&texture_id);
+            }
+        }
+    }
+
+    /// Get the normal map of a color texture.
+    /// 获取颜色纹理的法线贴图。
+    #[inline]
+    pub fn normal_map(&self, texture_id: u32) -> Option<u32> {
+        self.normal_maps.get(&texture_id).copied()
+    }
+
+    /// Check whether any normal map is assigned.
+    /// 检查是否指定了任何法线贴图。
+    #[inline]
+    pub fn has_normal_maps(&self) -> bool {
+        !self.normal_maps.is_empty()
+    }
+
+    /// Sum of all global lights.
+    /// 所有全局光之和。
+    pub fn ambient(&self) -> [f32; 3] {
+        self.lights
+            .iter()
+            .filter(|l| l.kind == LightKind::Global)
+            .fold([0.0; 3], |acc, l| {
+                [
+                    acc[0] + l.color[0] * l.intensity,
+                    acc[1] + l.color[1] * l.intensity,
+                    acc[2] + l.color[2] * l.intensity,
+                ]
+            })
+    }
+
+    /// Drop this frame's lights and occluders.
+    /// 丢弃本帧的光源和遮挡体。
+    pub fn clear_frame(&mut self) {
+        self.lights.clear();
+        self.occluders.clear();
+    }
+
+    /// Create or resize the light and normal buffers.
+    /// 创建或调整光照和法线缓冲区大小。
+    pub fn prepare(&mut self, gl: &WebGl2RenderingContext, size: (u32, u32)) -> Result<()> {
+        for buffer in [&mut self.light_buffer, &mut self.normal_buffer] {
+            match buffer {
+                Some(target) => target.resize(gl, size.0, size.1)?,
+                None => *buffer = Some(RenderTexture::new(gl, size.0, size.1)?),
+            }
+        }
+        Ok(())
+    }
+
+    /// Bind and clear the normal buffer for the sprite normal pass.
+    /// 绑定并清除法线缓冲区，用于精灵法线通道。
+    pub fn begin_normal_pass(&mut self, gl: &WebGl2RenderingContext) {
+        if let Some(buffer) = &mut self.normal_buffer {
+            buffer.clear_color = [0.5, 0.5, 1.0, 0.0];
+            buffer.bind(gl);
+            buffer.clear(gl);
+        }
+    }
+
+    /// Accumulate all lights into the light buffer.
+    /// 将所有光源累积到光照缓冲区。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `shaders` - Shader manager | 着色器管理器
+    /// * `camera` - Camera of the target being lit | 被照亮目标的相机
+    /// * `use_normals` - Whether the normal buffer was filled | 法线缓冲区是否已填充
+    pub fn render_lights(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        shaders: &ShaderManager,
+        camera: &Camera2D,
+        use_normals: bool,
+    ) {
+        let [r, g, b] = self.ambient();
+        let light_buffer = match &mut self.light_buffer {
+            Some(buffer) => buffer,
+            None => return,
+        };
+
+        light_buffer.clear_color = [r, g, b, 1.0];
+        light_buffer.bind(gl);
+        light_buffer.clear(gl);
+        let (width, height) = light_buffer.dimensions();
+
+        let shader = match shaders.get_shader(SHADER_ID_LIGHT) {
+            Some(shader) => shader,
+            None => return,
+        };
+        shader.bind(gl);
+        shader.set_uniform_mat3(gl, "u_projection", &camera.projection_matrix().to_cols_array());
+        shader.set_uniform_vec2(gl, "u_resolution", [width as f32, height as f32]);
+        shader.set_uniform_f32(gl, "u_lightSize", self.shadow_softness);
+        shader.set_uniform_f32(gl, "u_useNormals", if use_normals { 1.0 } else { 0.0 });
+        if use_normals {
+            if let Some(normal_buffer) = &self.normal_buffer {
+                gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+                gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(normal_buffer.texture()));
+                shader.set_uniform_i32(gl, "u_normalBuffer", 0);
+            }
+        }
+
+        MaterialManager::apply_blend_mode(gl, BlendMode::Additive);
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.vbo));
+
+        for light in self.lights.iter().filter(|l| l.kind != LightKind::Global && l.radius > 0.0) {
+            let (x, y, radius) = (light.position.x, light.position.y, light.radius);
+
+            // Quad covering the light's reach | 覆盖光照范围的四边形
+            let quad = [
+                x - radius, y - radius,
+                x + radius, y - radius,
+                x - radius, y + radius,
+                x + radius, y + radius,
+            ];
+            unsafe {
+                let array = js_sys::Float32Array::view(&quad);
+                gl.buffer_sub_data_with_i32_and_array_buffer_view(
+                    WebGl2RenderingContext::ARRAY_BUFFER,
+                    0,
+                    &array,
+                );
+            }
+            stats::record_vertices(4);
+
+            let (cos_outer, cos_inner) = light.cone_cosines();
+            shader.set_uniform_vec2(gl, "u_lightPos", [x, y]);
+            shader.set_uniform_f32(gl, "u_lightHeight", light.height);
+            shader.set_uniform_vec3(gl, "u_lightColor", light.color);
+            shader.set_uniform_f32(gl, "u_intensity", light.intensity);
+            shader.set_uniform_f32(gl, "u_radius", radius);
+            shader.set_uniform_f32(gl, "u_isSpot", if light.kind == LightKind::Spot { 1.0 } else { 0.0 });
+            shader.set_uniform_vec2(gl, "u_direction", [light.direction.cos(), light.direction.sin()]);
+            shader.set_uniform_f32(gl, "u_cosOuter", cos_outer);
+            shader.set_uniform_f32(gl, "u_cosInner", cos_inner);
+
+            // Only segments the light can reach | 仅光源可到达的线段
+            self.occluders.segments_near(
+                light.position,
+                radius + self.shadow_softness,
+                MAX_SHADOW_SEGMENTS,
+                &mut self.segment_scratch,
+            );
+            shader.set_uniform_vec4_array(gl, "u_segments", &self.segment_scratch);
+            shader.set_uniform_i32(gl, "u_segmentCount", (self.segment_scratch.len() / 4) as i32);
+
+            gl.draw_arrays(WebGl2RenderingContext::TRIANGLE_STRIP, 0, 4);
+            stats::record_draw_call();
+        }
+
+        gl.bind_vertex_array(None);
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+    }
+
+    /// Accumulated light texture, if the buffers exist.
+    /// 累积光照纹理（如果缓冲区存在）。
+    pub fn light_texture(&self) -> Option<&WebGlTexture> {
+        self.light_buffer.as_ref().map(|b| b.texture())
+    }
+}
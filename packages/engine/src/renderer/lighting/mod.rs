@@ -0,0 +1,10 @@
+//! 2D lighting with normal maps and shadow casting.
+//! 支持法线贴图和阴影投射的2D光照。
+
+mod light;
+mod occluder;
+mod lighting_renderer;
+
+pub use light::{Light, LightKind, LIGHT_STRIDE};
+pub use occluder::OccluderSet;
+pub use lighting_renderer::LightingRenderer;
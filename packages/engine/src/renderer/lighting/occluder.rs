@@ -0,0 +1,156 @@
+//! Shadow-casting occluders.
+//! 投射阴影的遮挡体。
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Vec2;
+
+/// Set of occluder edges for the current frame.
+/// 当前帧的遮挡体边集合。
+///
+/// Polygons are stored as independent segments; shadows are computed by
+/// testing whether the ray from a light to a point crosses any segment.
+/// 多边形以独立线段存储；通过检测从光源到某点的射线是否穿过任意线段来计算阴影。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::math::Vec2;
+/// use es_engine::renderer::OccluderSet;
+/// let mut occluders = OccluderSet::new();
+/// // A 10x10 box centered at (50, 0) | 以(50, 0)为中心的10x10方块
+/// occluders.add_polygon(&[45.0, -5.0, 55.0, -5.0, 55.0, 5.0, 45.0, 5.0]);
+/// assert_eq!(occluders.len(), 4);
+/// assert!(!occluders.is_visible(Vec2::ZERO, Vec2::new(100.0, 0.0)));
+/// assert!(occluders.is_visible(Vec2::ZERO, Vec2::new(0.0, 100.0)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OccluderSet {
+    /// Segments as [ax, ay, bx, by].
+    /// 线段 [ax, ay, bx, by]。
+    segments: Vec<[f32; 4]>,
+}
+
+impl OccluderSet {
+    /// Create an empty set.
+    /// 创建空集合。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a closed polygon from flat `[x0, y0, x1, y1, ...]` points.
+    /// 从扁平的`[x0, y0, x1, y1, ...]`点添加闭合多边形。
+    ///
+    /// Two points add a single segment.
+    /// 两个点只添加一条线段。
+    pub fn add_polygon(&mut self, points: &[f32]) {
+        let count = points.len() / 2;
+        if count < 2 {
+            return;
+        }
+        let edges = if count == 2 { 1 } else { count };
+        for i in 0..edges {
+            let j = (i + 1) % count;
+            self.segments.push([points[i * 2], points[i * 2 + 1], points[j * 2], points[j * 2 + 1]]);
+        }
+    }
+
+    /// Add polygons from packed batch data.
+    /// 从打包的批量数据添加多边形。
+    ///
+    /// # Arguments | 参数
+    /// * `points` - Flat points of all polygons | 所有多边形的扁平点
+    /// * `counts` - Point count of each polygon | 每个多边形的点数
+    pub fn add_batch(&mut self, points: &[f32], counts: &[u32]) -> Result<()> {
+        let total: usize = counts.iter().map(|&c| c as usize).sum();
+        if points.len() != total * 2 {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Occluder point data length mismatch: expected {}, got {}",
+                total * 2,
+                points.len()
+            )));
+        }
+
+        let mut offset = 0;
+        for &count in counts {
+            let end = offset + count as usize * 2;
+            self.add_polygon(&points[offset..end]);
+            offset = end;
+        }
+        Ok(())
+    }
+
+    /// Remove all segments.
+    /// 移除所有线段。
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    /// Number of segments.
+    /// 线段数量。
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Check if empty.
+    /// 检查是否为空。
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Collect up to `max` segments within `radius` of `center`, flattened.
+    /// 收集距`center`在`radius`范围内的最多`max`条线段（扁平化）。
+    pub fn segments_near(&self, center: Vec2, radius: f32, max: usize, out: &mut Vec<f32>) {
+        out.clear();
+        for segment in &self.segments {
+            if out.len() / 4 >= max {
+                break;
+            }
+            let a = Vec2::new(segment[0], segment[1]);
+            let b = Vec2::new(segment[2], segment[3]);
+            if distance_to_segment(center, a, b) <= radius {
+                out.extend_from_slice(segment);
+            }
+        }
+    }
+
+    /// Check whether the straight path between two points is unobstructed.
+    /// 检查两点之间的直线路径是否无遮挡。
+    pub fn is_visible(&self, from: Vec2, to: Vec2) -> bool {
+        !self.segments.iter().any(|s| {
+            segments_intersect(from, to, Vec2::new(s[0], s[1]), Vec2::new(s[2], s[3]))
+        })
+    }
+}
+
+/// Distance from a point to a segment.
+/// 点到线段的距离。
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    let t = if len_sq > 0.0 {
+        ((p - a).dot(&ab) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + ab * t).distance(&p)
+}
+
+/// Check whether segment p0-p1 properly crosses segment q0-q1.
+/// 检查线段p0-p1是否穿过线段q0-q1。
+///
+/// Same test as the light shader: the end points of p are excluded so a
+/// surface does not shadow itself.
+/// 与光照着色器的检测相同：排除p的端点，使表面不会遮挡自身。
+fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> bool {
+    let r = p1 - p0;
+    let s = q1 - q0;
+    let denom = r.cross(&s);
+    if denom.abs() < 1e-6 {
+        return false;
+    }
+    let d = q0 - p0;
+    let t = d.cross(&s) / denom;
+    let u = d.cross(&r) / denom;
+    t > 0.0 && t < 1.0 && (0.0..=1.0).contains(&u)
+}
@@ -0,0 +1,262 @@
+//! Arbitrary and skinned 2D meshes.
+//! 任意和蒙皮2D网格。
+
+use glam::{Affine2, Vec2};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Color;
+use super::super::batch::SpriteVertex;
+
+/// Maximum bone influences per vertex.
+/// 每个顶点的最大骨骼影响数。
+pub const MAX_BONE_INFLUENCES: usize = 4;
+
+/// Bone palette stride: a 2D affine matrix `[a, b, c, d, tx, ty]`.
+/// 骨骼调色板步长：2D仿射矩阵`[a, b, c, d, tx, ty]`。
+pub const BONE_STRIDE: usize = 6;
+
+/// Bone influences of a skinned mesh.
+/// 蒙皮网格的骨骼影响。
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshSkin {
+    /// Palette indices of up to four bones per vertex.
+    /// 每个顶点最多四个骨骼的调色板索引。
+    pub bones: Vec<[u8; MAX_BONE_INFLUENCES]>,
+    /// Normalized weights matching `bones`.
+    /// 与`bones`对应的归一化权重。
+    pub weights: Vec<[f32; MAX_BONE_INFLUENCES]>,
+}
+
+/// A triangle mesh with UVs and vertex colors, optionally skinned.
+/// 带UV和顶点颜色的三角形网格，可选蒙皮。
+///
+/// UVs are relative to the texture or atlas region it is drawn with (v = 0 at
+/// the top). Skinned vertices are deformed by linear blend skinning with a
+/// palette of bone matrices that map mesh space to the mesh's local space
+/// (bone world transform times inverse bind pose).
+/// UV相对于绘制时使用的纹理或图集区域（v = 0为顶部）。蒙皮顶点使用骨骼矩阵调色板
+/// 进行线性混合蒙皮，矩阵将网格空间映射到网格的局部空间（骨骼世界变换乘以逆绑定姿势）。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::mesh::Mesh2D;
+/// use glam::{Affine2, Vec2};
+/// let mut mesh = Mesh2D::new(
+///     &[0.0, 0.0, 10.0, 0.0, 10.0, 10.0],
+///     &[0.0, 1.0, 1.0, 1.0, 1.0, 0.0],
+///     &[0, 1, 2],
+/// ).unwrap();
+/// // Second vertex follows bone 1 | 第二个顶点跟随骨骼1
+/// mesh.set_skin(&[0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0], &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]).unwrap();
+/// let palette = [Affine2::IDENTITY, Affine2::from_translation(Vec2::new(0.0, 5.0))];
+/// let mut out = Vec::new();
+/// mesh.write_vertices(Affine2::IDENTITY, &palette, [0.0, 0.0, 1.0, 1.0], [1.0; 4], &mut out);
+/// assert_eq!(out[1].position, [10.0, 5.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mesh2D {
+    positions: Vec<Vec2>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+    skin: Option<MeshSkin>,
+}
+
+impl Mesh2D {
+    /// Create a mesh from packed data.
+    /// 从打包数据创建网格。
+    ///
+    /// # Arguments | 参数
+    /// * `positions` - [x, y] per vertex | 每个顶点的位置
+    /// * `uvs` - [u, v] per vertex | 每个顶点的UV
+    /// * `indices` - Three vertex indices per triangle | 每个三角形三个顶点索引
+    pub fn new(positions: &[f32], uvs: &[f32], indices: &[u32]) -> Result<Self> {
+        if !positions.len().is_multiple_of(2) || uvs.len() != positions.len() {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh data mismatch: {} position floats, {} UV floats",
+                positions.len(),
+                uvs.len()
+            )));
+        }
+        let vertex_count = positions.len() / 2;
+        Self::validate_indices(indices, vertex_count)?;
+
+        Ok(Self {
+            positions: positions.chunks_exact(2).map(|p| Vec2::new(p[0], p[1])).collect(),
+            uvs: uvs.chunks_exact(2).map(|uv| [uv[0], uv[1]]).collect(),
+            colors: vec![[1.0; 4]; vertex_count],
+            indices: indices.to_vec(),
+            skin: None,
+        })
+    }
+
+    fn validate_indices(indices: &[u32], vertex_count: usize) -> Result<()> {
+        if !indices.len().is_multiple_of(3) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh index count {} is not a multiple of 3",
+                indices.len()
+            )));
+        }
+        if let Some(&index) = indices.iter().find(|&&i| i as usize >= vertex_count) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh index {} out of range for {} vertices",
+                index, vertex_count
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of vertices.
+    /// 顶点数量。
+    #[inline]
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Triangle indices.
+    /// 三角形索引。
+    #[inline]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Bone influences, if skinned.
+    /// 骨骼影响（如已蒙皮）。
+    #[inline]
+    pub fn skin(&self) -> Option<&MeshSkin> {
+        self.skin.as_ref()
+    }
+
+    /// Replace vertex positions, keeping the vertex count.
+    /// 替换顶点位置，保持顶点数量不变。
+    pub fn set_positions(&mut self, positions: &[f32]) -> Result<()> {
+        if positions.len() != self.positions.len() * 2 {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh position length mismatch: expected {}, got {}",
+                self.positions.len() * 2,
+                positions.len()
+            )));
+        }
+        for (position, p) in self.positions.iter_mut().zip(positions.chunks_exact(2)) {
+            *position = Vec2::new(p[0], p[1]);
+        }
+        Ok(())
+    }
+
+    /// Set packed RGBA vertex colors (one per vertex).
+    /// 设置打包的RGBA顶点颜色（每个顶点一个）。
+    pub fn set_colors(&mut self, colors: &[u32]) -> Result<()> {
+        if colors.len() != self.positions.len() {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh color length mismatch: expected {}, got {}",
+                self.positions.len(),
+                colors.len()
+            )));
+        }
+        self.colors = colors
+            .iter()
+            .map(|&c| {
+                let color = Color::from_packed(c);
+                [color.r, color.g, color.b, color.a]
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Set bone influences: four palette indices and four weights per vertex.
+    /// 设置骨骼影响：每个顶点四个调色板索引和四个权重。
+    ///
+    /// Weights are normalized; unused influences have weight 0.
+    /// 权重会被归一化；未使用的影响权重为0。
+    pub fn set_skin(&mut self, bones: &[u8], weights: &[f32]) -> Result<()> {
+        let expected = self.positions.len() * MAX_BONE_INFLUENCES;
+        if bones.len() != expected || weights.len() != expected {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh skin length mismatch: expected {}, got {} bones and {} weights",
+                expected,
+                bones.len(),
+                weights.len()
+            )));
+        }
+
+        let weights = weights
+            .chunks_exact(MAX_BONE_INFLUENCES)
+            .map(|w| {
+                let w = [w[0].max(0.0), w[1].max(0.0), w[2].max(0.0), w[3].max(0.0)];
+                let total: f32 = w.iter().sum();
+                if total > 0.0 { w.map(|x| x / total) } else { [1.0, 0.0, 0.0, 0.0] }
+            })
+            .collect();
+        let bones = bones
+            .chunks_exact(MAX_BONE_INFLUENCES)
+            .map(|b| [b[0], b[1], b[2], b[3]])
+            .collect();
+        self.skin = Some(MeshSkin { bones, weights });
+        Ok(())
+    }
+
+    /// Remove bone influences.
+    /// 移除骨骼影响。
+    pub fn clear_skin(&mut self) {
+        self.skin = None;
+    }
+
+    /// Append world-space vertices.
+    /// 追加世界空间顶点。
+    ///
+    /// # Arguments | 参数
+    /// * `transform` - Mesh local-to-world transform | 网格局部到世界的变换
+    /// * `palette` - Bone matrices; ignored for unskinned meshes, missing bones are identity | 骨骼矩阵；非蒙皮网格忽略，缺失的骨骼视为单位矩阵
+    /// * `uv_rect` - Texture region the mesh UVs map into | 网格UV映射到的纹理区域
+    /// * `tint` - Color multiplied with vertex colors | 与顶点颜色相乘的颜色
+    /// * `out` - Output vertices | 输出顶点
+    pub fn write_vertices(
+        &self,
+        transform: Affine2,
+        palette: &[Affine2],
+        uv_rect: [f32; 4],
+        tint: [f32; 4],
+        out: &mut Vec<SpriteVertex>,
+    ) {
+        let [u0, v0, u1, v1] = uv_rect;
+        out.reserve(self.positions.len());
+        for (i, &position) in self.positions.iter().enumerate() {
+            let local = match &self.skin {
+                Some(skin) if !palette.is_empty() => {
+                    let mut skinned = Vec2::ZERO;
+                    for (&bone, &weight) in skin.bones[i].iter().zip(&skin.weights[i]) {
+                        if weight > 0.0 {
+                            let matrix = palette.get(bone as usize).unwrap_or(&Affine2::IDENTITY);
+                            skinned += matrix.transform_point2(position) * weight;
+                        }
+                    }
+                    skinned
+                }
+                _ => position,
+            };
+            let world = transform.transform_point2(local);
+            let [u, v] = self.uvs[i];
+            let color = self.colors[i];
+            out.push(SpriteVertex::new(
+                world.into(),
+                [u0 + (u1 - u0) * u, v0 + (v1 - v0) * v],
+                std::array::from_fn(|k| color[k] * tint[k]),
+            ));
+        }
+    }
+}
+
+/// Parse a packed bone palette (see `BONE_STRIDE`).
+/// 解析打包的骨骼调色板（参见`BONE_STRIDE`）。
+pub fn parse_palette(data: &[f32], out: &mut Vec<Affine2>) -> Result<()> {
+    if !data.len().is_multiple_of(BONE_STRIDE) {
+        return Err(EngineError::InvalidBatchData(format!(
+            "Bone palette length {} is not a multiple of {}",
+            data.len(),
+            BONE_STRIDE
+        )));
+    }
+    out.clear();
+    out.extend(data.chunks_exact(BONE_STRIDE).map(|m| Affine2::from_cols_array(&[m[0], m[1], m[2], m[3], m[4], m[5]])));
+    Ok(())
+}
@@ -0,0 +1,322 @@
+//! Mesh storage and per-frame mesh batching.
+//! 网格存储和每帧网格批处理。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use glam::{Affine2, Vec2};
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Color;
+use crate::resource::TextureManager;
+use super::super::batch::{BatchKey, SortKey, SpriteBatch, SpriteVertex};
+use super::super::stats;
+use super::mesh2d::Mesh2D;
+
+/// Packed mesh draw stride (x, y, rotation, scaleX, scaleY, layer, orderInLayer).
+/// 打包网格绘制步长。
+pub const MESH_DRAW_STRIDE: usize = 7;
+
+/// Where and how to draw a mesh this frame.
+/// 本帧绘制网格的位置和方式。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshDraw {
+    /// Mesh local-to-world transform.
+    /// 网格局部到世界的变换。
+    pub transform: Affine2,
+    /// Texture or atlas region ID.
+    /// 纹理或图集区域ID。
+    pub texture_id: u32,
+    /// Material ID (0 = default).
+    /// 材质ID（0 = 默认）。
+    pub material_id: u32,
+    /// Tint multiplied with vertex colors.
+    /// 与顶点颜色相乘的色调。
+    pub color: [f32; 4],
+    /// Draw order among sprites.
+    /// 在精灵之间的绘制顺序。
+    pub sort: SortKey,
+}
+
+impl MeshDraw {
+    /// Build from packed data (see `MESH_DRAW_STRIDE`).
+    /// 从打包数据构建（参见`MESH_DRAW_STRIDE`）。
+    pub fn from_data(data: &[f32], texture_id: u32, material_id: u32, color: u32) -> Result<Self> {
+        if data.len() != MESH_DRAW_STRIDE {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Mesh draw length mismatch: expected {}, got {}",
+                MESH_DRAW_STRIDE,
+                data.len()
+            )));
+        }
+
+        let color = Color::from_packed(color);
+        Ok(Self {
+            transform: Affine2::from_scale_angle_translation(
+                Vec2::new(data[3], data[4]),
+                data[2],
+                Vec2::new(data[0], data[1]),
+            ),
+            texture_id,
+            material_id,
+            color: [color.r, color.g, color.b, color.a],
+            sort: SortKey::new(data[5] as i32, data[6] as i32),
+        })
+    }
+}
+
+/// A submitted mesh's triangles.
+/// 已提交网格的三角形。
+#[derive(Clone, Debug)]
+struct MeshRecord {
+    sort: SortKey,
+    batch: BatchKey,
+    indices: Range<usize>,
+}
+
+/// Owns meshes and draws the ones submitted each frame.
+/// 持有网格并绘制每帧提交的网格。
+///
+/// Submitted meshes are transformed and skinned on the CPU into one vertex
+/// stream. After sorting, neighbours sharing sort key, material and texture
+/// page are drawn with a single draw call, so a skeleton whose attachments
+/// live in one atlas renders in one batch.
+/// 提交的网格在CPU上变换和蒙皮到一个顶点流中。排序后，共享排序键、材质和纹理页面的
+/// 相邻网格使用单次绘制调用绘制，因此附件位于同一图集中的骨架只需一个批次。
+pub struct MeshRenderer {
+    /// Meshes by ID.
+    /// 按ID存储的网格。
+    meshes: HashMap<u32, Mesh2D>,
+
+    /// Next mesh ID.
+    /// 下一个网格ID。
+    next_id: u32,
+
+    vao: WebGlVertexArrayObject,
+    vbo: WebGlBuffer,
+    ibo: WebGlBuffer,
+
+    /// World-space vertices of this frame's draws.
+    /// 本帧绘制的世界空间顶点。
+    vertices: Vec<SpriteVertex>,
+
+    /// Indices in submission order.
+    /// 按提交顺序的索引。
+    indices: Vec<u32>,
+
+    /// Draws submitted this frame.
+    /// 本帧提交的绘制。
+    records: Vec<MeshRecord>,
+
+    /// Index ranges in draw order with their key and batch.
+    /// 按绘制顺序的索引范围及其键和批次。
+    groups: Vec<(SortKey, BatchKey, Range<usize>)>,
+
+    /// Whether `groups` and the GPU buffers are stale.
+    /// `groups`和GPU缓冲区是否已过期。
+    dirty: bool,
+
+    /// Buffer sizes in bytes.
+    /// 缓冲区字节大小。
+    vertex_capacity: usize,
+    index_capacity: usize,
+}
+
+impl MeshRenderer {
+    /// Create a new mesh renderer.
+    /// 创建新的网格渲染器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+
+        let ibo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&ibo));
+
+        SpriteBatch::setup_vertex_attributes(gl);
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            meshes: HashMap::new(),
+            next_id: 1,
+            vao,
+            vbo,
+            ibo,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            records: Vec::new(),
+            groups: Vec::new(),
+            dirty: false,
+            vertex_capacity: 0,
+            index_capacity: 0,
+        })
+    }
+
+    /// Add a mesh, returning its ID.
+    /// 添加网格，返回其ID。
+    pub fn add_mesh(&mut self, mesh: Mesh2D) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.meshes.insert(id, mesh);
+        id
+    }
+
+    /// Get a mesh.
+    /// 获取网格。
+    #[inline]
+    pub fn mesh(&self, id: u32) -> Option<&Mesh2D> {
+        self.meshes.get(&id)
+    }
+
+    /// Get a mutable mesh.
+    /// 获取可变网格。
+    #[inline]
+    pub fn mesh_mut(&mut self, id: u32) -> Option<&mut Mesh2D> {
+        self.meshes.get_mut(&id)
+    }
+
+    /// Remove a mesh.
+    /// 移除网格。
+    pub fn remove_mesh(&mut self, id: u32) -> bool {
+        self.meshes.remove(&id).is_some()
+    }
+
+    /// Draw a mesh this frame.
+    /// 在本帧绘制网格。
+    ///
+    /// # Arguments | 参数
+    /// * `mesh_id` - Mesh ID | 网格ID
+    /// * `draw` - Transform, texture, material, tint and sort key | 变换、纹理、材质、色调和排序键
+    /// * `palette` - Bone matrices for skinned meshes | 蒙皮网格的骨骼矩阵
+    /// * `texture_manager` - Resolves atlas regions | 解析图集区域
+    pub fn submit(
+        &mut self,
+        mesh_id: u32,
+        draw: &MeshDraw,
+        palette: &[Affine2],
+        texture_manager: &TextureManager,
+    ) -> Result<()> {
+        let mesh = self
+            .meshes
+            .get(&mesh_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Mesh not found: {}", mesh_id)))?;
+        if mesh.indices().is_empty() {
+            return Ok(());
+        }
+
+        let (texture_id, uv_rect) = texture_manager.resolve_texture(draw.texture_id, [0.0, 0.0, 1.0, 1.0]);
+        let base = self.vertices.len() as u32;
+        mesh.write_vertices(draw.transform, palette, uv_rect, draw.color, &mut self.vertices);
+
+        let start = self.indices.len();
+        self.indices.extend(mesh.indices().iter().map(|&i| base + i));
+        self.records.push(MeshRecord {
+            sort: draw.sort,
+            batch: BatchKey { material_id: draw.material_id, texture_id },
+            indices: start..self.indices.len(),
+        });
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Check whether any mesh was submitted this frame.
+    /// 检查本帧是否提交了任何网格。
+    #[inline]
+    pub fn has_draws(&self) -> bool {
+        !self.records.is_empty()
+    }
+
+    /// Sort draws, upload geometry and return `(key, group)` pairs in draw order.
+    /// 排序绘制、上传几何体，并按绘制顺序返回`(key, group)`对。
+    pub fn prepare(&mut self, gl: &WebGl2RenderingContext) -> Vec<(SortKey, u32)> {
+        if self.dirty {
+            self.dirty = false;
+            self.records.sort_by(|a, b| a.sort.compare(&b.sort));
+
+            // Reorder indices and merge neighbours that share key and batch | 重排索引并合并共享键和批次的相邻绘制
+            let mut ordered = Vec::with_capacity(self.indices.len());
+            self.groups.clear();
+            for record in &self.records {
+                let start = ordered.len();
+                ordered.extend_from_slice(&self.indices[record.indices.clone()]);
+                match self.groups.last_mut() {
+                    Some((key, batch, range))
+                        if key.compare(&record.sort) == Ordering::Equal && *batch == record.batch =>
+                    {
+                        range.end = ordered.len();
+                    }
+                    _ => self.groups.push((record.sort, record.batch, start..ordered.len())),
+                }
+            }
+
+            gl.bind_vertex_array(Some(&self.vao));
+            let target = WebGl2RenderingContext::ARRAY_BUFFER;
+            Self::upload(gl, target, &self.vbo, &mut self.vertex_capacity, bytemuck::cast_slice(&self.vertices));
+            stats::record_vertices(self.vertices.len());
+            let target = WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER;
+            Self::upload(gl, target, &self.ibo, &mut self.index_capacity, bytemuck::cast_slice(&ordered));
+            gl.bind_vertex_array(None);
+        }
+
+        self.groups
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _, _))| (*key, i as u32))
+            .collect()
+    }
+
+    /// Write data into a buffer, growing it when needed.
+    /// 将数据写入缓冲区，必要时扩展。
+    fn upload(gl: &WebGl2RenderingContext, target: u32, buffer: &WebGlBuffer, capacity: &mut usize, data: &[u8]) {
+        gl.bind_buffer(target, Some(buffer));
+        if data.len() > *capacity {
+            *capacity = data.len().max(*capacity * 2);
+            gl.buffer_data_with_i32(target, *capacity as i32, WebGl2RenderingContext::DYNAMIC_DRAW);
+        }
+        gl.buffer_sub_data_with_i32_and_u8_array(target, 0, data);
+    }
+
+    /// Material and texture page of a group.
+    /// 分组的材质和纹理页面。
+    #[inline]
+    pub fn group_batch(&self, group: u32) -> Option<BatchKey> {
+        self.groups.get(group as usize).map(|(_, batch, _)| *batch)
+    }
+
+    /// Draw one group with the bound material and texture.
+    /// 使用已绑定的材质和纹理绘制一个分组。
+    pub fn draw_group(&self, gl: &WebGl2RenderingContext, group: u32) {
+        let Some((_, _, range)) = self.groups.get(group as usize) else {
+            return;
+        };
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.draw_elements_with_i32(
+            WebGl2RenderingContext::TRIANGLES,
+            range.len() as i32,
+            WebGl2RenderingContext::UNSIGNED_INT,
+            (range.start * 4) as i32,
+        );
+        stats::record_draw_call();
+        gl.bind_vertex_array(None);
+    }
+
+    /// Remove all draws submitted this frame.
+    /// 移除本帧提交的所有绘制。
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.records.clear();
+        self.groups.clear();
+        self.dirty = false;
+    }
+}
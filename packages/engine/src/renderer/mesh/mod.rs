@@ -0,0 +1,8 @@
+//! Arbitrary and skinned 2D meshes.
+//! 任意和蒙皮2D网格。
+
+mod mesh2d;
+mod mesh_renderer;
+
+pub use mesh2d::{Mesh2D, MeshSkin, parse_palette, BONE_STRIDE, MAX_BONE_INFLUENCES};
+pub use mesh_renderer::{MeshDraw, MeshRenderer, MESH_DRAW_STRIDE};
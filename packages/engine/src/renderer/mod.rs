@@ -5,19 +5,47 @@ pub mod batch;
 pub mod shader;
 pub mod texture;
 pub mod material;
+pub mod lighting;
+pub mod tilemap;
+pub mod particles;
+pub mod text;
+pub mod shapes;
+pub mod mesh;
 
 mod renderer2d;
 mod camera;
+mod camera_behavior;
 mod grid;
 mod gizmo;
 mod viewport;
+mod render_texture;
+mod frame_graph;
+mod post_process;
+mod stats;
+mod picking;
 
 pub use renderer2d::Renderer2D;
 pub use camera::Camera2D;
-pub use batch::SpriteBatch;
-pub use texture::{Texture, TextureManager};
+pub use camera_behavior::{CameraFollow, CameraShake};
+pub use batch::{SpriteBatch, SpriteBatchData, SortKey, SORT_KEY_STRIDE, NineSlice, SliceMode};
+pub use texture::{
+    Texture, TextureManager, TextureLoadCallback, TextureLoadState, TextureFilter, TextureLoadOptions,
+    TextureSampler, TextureWrap,
+    AtlasPacker, AtlasRegion, PackedRect,
+};
 pub use grid::GridRenderer;
 pub use gizmo::{GizmoRenderer, TransformMode};
 pub use viewport::{RenderTarget, ViewportManager, ViewportConfig};
-pub use shader::{ShaderManager, ShaderProgram, SHADER_ID_DEFAULT_SPRITE};
+pub use render_texture::{RenderTexture, FullscreenQuad};
+pub use frame_graph::{FrameGraph, RenderPass, RenderPassKind, UI_LAYER};
+pub use post_process::{PostEffect, PostEffectKind, PostProcessStack, PostProcessor};
+pub use stats::{GpuTimer, RenderStats, RENDER_STATS_STRIDE};
+pub use picking::{PickMode, PickTarget, SpritePicker};
+pub use lighting::{Light, LightKind, LightingRenderer, OccluderSet, LIGHT_STRIDE};
+pub use tilemap::{TilemapLayer, TilemapRenderer, Tileset, TileAnimation};
+pub use particles::{EmitterConfig, ParticleEmitter, ParticleRenderer};
+pub use text::{Font, FontKind, TextLayout, TextRenderer, TextStyle};
+pub use shapes::{Paint, Path, ShapeRenderer, StrokeStyle};
+pub use mesh::{Mesh2D, MeshDraw, MeshRenderer};
+pub use shader::{ShaderManager, ShaderProgram, SHADER_ID_DEFAULT_SPRITE, SHADER_ID_BLIT};
 pub use material::{Material, MaterialManager, BlendMode, CullMode, UniformValue, MaterialUniforms};
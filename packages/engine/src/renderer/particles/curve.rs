@@ -0,0 +1,106 @@
+//! Keyframe curves over a particle's lifetime.
+//! 粒子生命周期上的关键帧曲线。
+
+use crate::core::error::{EngineError, Result};
+
+/// Values a curve can interpolate.
+/// 曲线可插值的值。
+pub trait CurveValue: Copy {
+    /// Number of floats in packed curve data.
+    /// 打包曲线数据中的浮点数数量。
+    const FLOATS: usize;
+
+    /// Linear interpolation.
+    /// 线性插值。
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+
+    /// Read from packed floats.
+    /// 从打包的浮点数读取。
+    fn from_floats(data: &[f32]) -> Self;
+}
+
+impl CurveValue for f32 {
+    const FLOATS: usize = 1;
+
+    #[inline]
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+
+    #[inline]
+    fn from_floats(data: &[f32]) -> Self {
+        data[0]
+    }
+}
+
+impl CurveValue for [f32; 4] {
+    const FLOATS: usize = 4;
+
+    #[inline]
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+    }
+
+    #[inline]
+    fn from_floats(data: &[f32]) -> Self {
+        [data[0], data[1], data[2], data[3]]
+    }
+}
+
+/// Piecewise linear curve over normalized lifetime `[0, 1]`.
+/// 归一化生命周期`[0, 1]`上的分段线性曲线。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::particles::Curve;
+/// // Grow then shrink | 先变大再缩小
+/// let size = Curve::<f32>::from_data(&[0.0, 1.0, 0.5, 3.0, 1.0, 0.0]).unwrap();
+/// assert_eq!(size.evaluate(0.25), 2.0);
+/// assert_eq!(size.evaluate(0.75), 1.5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curve<T: CurveValue> {
+    /// (time, value) keys sorted by time.
+    /// 按时间排序的（时间，值）关键帧。
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: CurveValue> Curve<T> {
+    /// Curve with the same value over the whole lifetime.
+    /// 整个生命周期内值不变的曲线。
+    pub fn constant(value: T) -> Self {
+        Self { keys: vec![(0.0, value)] }
+    }
+
+    /// Build from packed `[time, value...]` keys.
+    /// 从打包的`[time, value...]`关键帧构建。
+    pub fn from_data(data: &[f32]) -> Result<Self> {
+        let stride = 1 + T::FLOATS;
+        if data.is_empty() || !data.len().is_multiple_of(stride) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Curve data length {} is not a non-zero multiple of {}",
+                data.len(),
+                stride
+            )));
+        }
+
+        let mut keys: Vec<(f32, T)> = data
+            .chunks_exact(stride)
+            .map(|key| (key[0].clamp(0.0, 1.0), T::from_floats(&key[1..])))
+            .collect();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self { keys })
+    }
+
+    /// Value at normalized lifetime `t`.
+    /// 归一化生命周期`t`处的值。
+    pub fn evaluate(&self, t: f32) -> T {
+        let next = self.keys.partition_point(|&(time, _)| time <= t);
+        match (next.checked_sub(1).map(|i| self.keys[i]), self.keys.get(next)) {
+            (Some((t0, a)), Some(&(t1, b))) => T::lerp(a, b, (t - t0) / (t1 - t0)),
+            (Some((_, a)), None) => a,
+            (None, Some(&(_, b))) => b,
+            (None, None) => unreachable!("curves always have a key"),
+        }
+    }
+}
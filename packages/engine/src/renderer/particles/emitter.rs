@@ -0,0 +1,516 @@
+//! Particle emitters and CPU simulation.
+//! 粒子发射器和CPU模拟。
+
+use std::f32::consts::TAU;
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Vec2;
+use super::super::batch::{SortKey, SpriteInstance, SpriteVertex};
+use super::curve::Curve;
+use super::rng::Rng;
+
+/// Packed emitter config length (see `EmitterConfig::from_data`).
+/// 打包发射器配置长度（参见`EmitterConfig::from_data`）。
+pub const EMITTER_CONFIG_STRIDE: usize = 21;
+
+/// Packed burst stride (time, count, cycles, interval).
+/// 打包爆发数据步长（时间、数量、循环次数、间隔）。
+pub const BURST_STRIDE: usize = 4;
+
+/// Area new particles spawn in.
+/// 新粒子的生成区域。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmitterShape {
+    /// All particles start at the emitter position.
+    /// 所有粒子从发射器位置开始。
+    Point,
+    /// Uniformly inside a circle.
+    /// 圆内均匀分布。
+    Circle {
+        /// Circle radius.
+        /// 圆半径。
+        radius: f32,
+    },
+    /// On the edge of a circle, moving outwards.
+    /// 在圆周上，向外运动。
+    Ring {
+        /// Circle radius.
+        /// 圆半径。
+        radius: f32,
+    },
+    /// Uniformly inside a rectangle centered on the emitter.
+    /// 以发射器为中心的矩形内均匀分布。
+    Rectangle {
+        /// Rectangle width.
+        /// 矩形宽度。
+        width: f32,
+        /// Rectangle height.
+        /// 矩形高度。
+        height: f32,
+    },
+}
+
+impl EmitterShape {
+    /// Create from a JavaScript shape code.
+    /// 从JavaScript形状代码创建。
+    ///
+    /// 0 = Point, 1 = Circle(a), 2 = Ring(a), 3 = Rectangle(a, b).
+    pub fn from_code(code: u32, a: f32, b: f32) -> Self {
+        match code {
+            1 => Self::Circle { radius: a },
+            2 => Self::Ring { radius: a },
+            3 => Self::Rectangle { width: a, height: b },
+            _ => Self::Point,
+        }
+    }
+
+    /// Random spawn offset and, for rings, the outward direction.
+    /// 随机生成偏移，以及环形的向外方向。
+    fn sample(&self, rng: &mut Rng) -> (Vec2, Option<f32>) {
+        match *self {
+            Self::Point => (Vec2::ZERO, None),
+            Self::Circle { radius } => {
+                let angle = rng.range(0.0, TAU);
+                let r = radius * rng.next_f32().sqrt();
+                (Vec2::new(angle.cos() * r, angle.sin() * r), None)
+            }
+            Self::Ring { radius } => {
+                let angle = rng.range(0.0, TAU);
+                (Vec2::new(angle.cos() * radius, angle.sin() * radius), Some(angle))
+            }
+            Self::Rectangle { width, height } => (
+                Vec2::new(rng.range(-0.5, 0.5) * width, rng.range(-0.5, 0.5) * height),
+                None,
+            ),
+        }
+    }
+}
+
+/// A timed burst of particles.
+/// 定时爆发的粒子。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Burst {
+    /// Seconds after the emitter starts.
+    /// 发射器启动后的秒数。
+    pub time: f32,
+    /// Particles per burst.
+    /// 每次爆发的粒子数。
+    pub count: u32,
+    /// Number of bursts (0 = repeat forever).
+    /// 爆发次数（0 = 无限重复）。
+    pub cycles: u32,
+    /// Seconds between repeated bursts.
+    /// 重复爆发的间隔秒数。
+    pub interval: f32,
+}
+
+/// Emitter settings.
+/// 发射器设置。
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmitterConfig {
+    /// Spawn area.
+    /// 生成区域。
+    pub shape: EmitterShape,
+    /// Particles per second.
+    /// 每秒粒子数。
+    pub rate: f32,
+    /// Maximum live particles.
+    /// 最大存活粒子数。
+    pub max_particles: usize,
+    /// Emission duration in seconds before looping (0 = continuous).
+    /// 循环前的发射持续时间（秒，0 = 持续发射）。
+    pub duration: f32,
+    /// Lifetime range in seconds.
+    /// 生命周期范围（秒）。
+    pub lifetime: (f32, f32),
+    /// Initial speed range.
+    /// 初始速度范围。
+    pub speed: (f32, f32),
+    /// Emission direction in radians.
+    /// 发射方向（弧度）。
+    pub direction: f32,
+    /// Total spread angle around the direction, in radians.
+    /// 围绕发射方向的总扩散角（弧度）。
+    pub spread: f32,
+    /// Start size range in world units.
+    /// 初始尺寸范围（世界单位）。
+    pub size: (f32, f32),
+    /// Start rotation range in radians.
+    /// 初始旋转范围（弧度）。
+    pub rotation: (f32, f32),
+    /// Angular velocity range in radians per second.
+    /// 角速度范围（弧度/秒）。
+    pub angular_velocity: (f32, f32),
+    /// Constant acceleration.
+    /// 恒定加速度。
+    pub gravity: Vec2,
+    /// Linear velocity damping per second.
+    /// 每秒线性速度阻尼。
+    pub damping: f32,
+    /// Color multiplier over lifetime.
+    /// 生命周期内的颜色乘数。
+    pub color_over_lifetime: Curve<[f32; 4]>,
+    /// Size multiplier over lifetime.
+    /// 生命周期内的尺寸乘数。
+    pub size_over_lifetime: Curve<f32>,
+    /// Velocity multiplier over lifetime.
+    /// 生命周期内的速度乘数。
+    pub speed_over_lifetime: Curve<f32>,
+    /// Timed bursts.
+    /// 定时爆发。
+    pub bursts: Vec<Burst>,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        Self {
+            shape: EmitterShape::Point,
+            rate: 10.0,
+            max_particles: 1000,
+            duration: 0.0,
+            lifetime: (1.0, 1.0),
+            speed: (50.0, 50.0),
+            direction: std::f32::consts::FRAC_PI_2,
+            spread: 0.0,
+            size: (8.0, 8.0),
+            rotation: (0.0, 0.0),
+            angular_velocity: (0.0, 0.0),
+            gravity: Vec2::ZERO,
+            damping: 0.0,
+            color_over_lifetime: Curve::constant([1.0; 4]),
+            size_over_lifetime: Curve::constant(1.0),
+            speed_over_lifetime: Curve::constant(1.0),
+            bursts: Vec::new(),
+        }
+    }
+}
+
+impl EmitterConfig {
+    /// Build from packed data.
+    /// 从打包数据构建。
+    ///
+    /// Layout | 布局: `[shape, shapeA, shapeB, rate, maxParticles, duration,
+    /// lifetimeMin, lifetimeMax, speedMin, speedMax, direction, spread,
+    /// sizeMin, sizeMax, rotationMin, rotationMax, angularMin, angularMax,
+    /// gravityX, gravityY, damping]`. Curves and bursts keep their defaults.
+    /// 曲线和爆发保持默认值。
+    pub fn from_data(data: &[f32]) -> Result<Self> {
+        if data.len() != EMITTER_CONFIG_STRIDE {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Emitter config length mismatch: expected {}, got {}",
+                EMITTER_CONFIG_STRIDE,
+                data.len()
+            )));
+        }
+
+        let range = |i: usize| (data[i].min(data[i + 1]), data[i].max(data[i + 1]));
+        Ok(Self {
+            shape: EmitterShape::from_code(data[0] as u32, data[1], data[2]),
+            rate: data[3].max(0.0),
+            max_particles: data[4].max(0.0) as usize,
+            duration: data[5].max(0.0),
+            lifetime: range(6),
+            speed: range(8),
+            direction: data[10],
+            spread: data[11],
+            size: range(12),
+            rotation: range(14),
+            angular_velocity: range(16),
+            gravity: Vec2::new(data[18], data[19]),
+            damping: data[20].max(0.0),
+            ..Self::default()
+        })
+    }
+
+    /// Parse packed bursts (see `BURST_STRIDE`).
+    /// 解析打包的爆发数据（参见`BURST_STRIDE`）。
+    pub fn parse_bursts(data: &[f32]) -> Result<Vec<Burst>> {
+        if !data.len().is_multiple_of(BURST_STRIDE) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Burst data length {} is not a multiple of {}",
+                data.len(),
+                BURST_STRIDE
+            )));
+        }
+
+        Ok(data
+            .chunks_exact(BURST_STRIDE)
+            .map(|b| Burst {
+                time: b[0].max(0.0),
+                count: b[1].max(0.0) as u32,
+                cycles: b[2].max(0.0) as u32,
+                interval: b[3].max(0.0),
+            })
+            .collect())
+    }
+}
+
+/// A live particle.
+/// 存活的粒子。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Particle {
+    /// World position.
+    /// 世界坐标。
+    pub position: Vec2,
+    /// Velocity in units per second.
+    /// 速度（单位/秒）。
+    pub velocity: Vec2,
+    /// Rotation in radians.
+    /// 旋转（弧度）。
+    pub rotation: f32,
+    /// Angular velocity in radians per second.
+    /// 角速度（弧度/秒）。
+    pub angular_velocity: f32,
+    /// Start size in world units.
+    /// 初始尺寸（世界单位）。
+    pub size: f32,
+    /// Seconds alive.
+    /// 已存活秒数。
+    pub age: f32,
+    /// Total lifetime in seconds.
+    /// 总生命周期（秒）。
+    pub lifetime: f32,
+}
+
+impl Particle {
+    /// Normalized age in `[0, 1]`.
+    /// `[0, 1]`内的归一化年龄。
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        (self.age / self.lifetime).min(1.0)
+    }
+}
+
+/// Spawns and simulates particles.
+/// 生成并模拟粒子。
+///
+/// Simulation runs on the CPU without WebGL, and is deterministic: the same
+/// seed, config and update steps always give the same particles.
+/// 模拟在CPU上运行，不依赖WebGL，并且是确定性的：相同的种子、配置和更新步骤总是得到相同的粒子。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::particles::{EmitterConfig, ParticleEmitter};
+/// let config = EmitterConfig { rate: 100.0, spread: 1.0, ..EmitterConfig::default() };
+/// let mut a = ParticleEmitter::new(config.clone(), 7);
+/// let mut b = ParticleEmitter::new(config, 7);
+/// for _ in 0..30 {
+///     a.update(1.0 / 60.0);
+///     b.update(1.0 / 60.0);
+/// }
+/// assert!(!a.particles().is_empty());
+/// assert_eq!(a.particles(), b.particles());
+///
+/// // Bursts may also be edited through `config` | 也可以通过`config`编辑爆发
+/// use es_engine::renderer::particles::Burst;
+/// a.config.bursts.push(Burst { time: 0.0, count: 10, cycles: 1, interval: 0.0 });
+/// let before = a.particles().len();
+/// a.update(1.0 / 60.0);
+/// assert!(a.particles().len() >= before + 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParticleEmitter {
+    /// Emitter settings.
+    /// 发射器设置。
+    pub config: EmitterConfig,
+
+    /// World position of the emitter.
+    /// 发射器的世界坐标。
+    pub position: Vec2,
+
+    /// Whether rate and bursts emit particles.
+    /// 速率和爆发是否发射粒子。
+    pub playing: bool,
+
+    /// Texture ID (0 = white).
+    /// 纹理ID（0 = 白色）。
+    pub texture_id: u32,
+
+    /// Material ID (0 = default).
+    /// 材质ID（0 = 默认）。
+    pub material_id: u32,
+
+    /// Draw order relative to sprites.
+    /// 相对于精灵的绘制顺序。
+    pub sort: SortKey,
+
+    seed: u64,
+    rng: Rng,
+    particles: Vec<Particle>,
+    /// Seconds since the current emission cycle started.
+    /// 当前发射周期开始后的秒数。
+    time: f32,
+    /// Fractional particles owed by the rate.
+    /// 速率累积的小数粒子数。
+    emit_accumulator: f32,
+    /// Bursts already fired in the current cycle, per burst.
+    /// 当前周期内每个爆发已触发的次数。
+    bursts_fired: Vec<u32>,
+}
+
+impl ParticleEmitter {
+    /// Create a playing emitter.
+    /// 创建正在播放的发射器。
+    pub fn new(config: EmitterConfig, seed: u64) -> Self {
+        Self {
+            bursts_fired: vec![0; config.bursts.len()],
+            config,
+            position: Vec2::ZERO,
+            playing: true,
+            texture_id: 0,
+            material_id: 0,
+            sort: SortKey::default(),
+            seed,
+            rng: Rng::new(seed),
+            particles: Vec::new(),
+            time: 0.0,
+            emit_accumulator: 0.0,
+        }
+    }
+
+    /// Live particles.
+    /// 存活的粒子。
+    #[inline]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Replace the bursts and restart their schedule.
+    /// 替换爆发设置并重新开始其计划。
+    pub fn set_bursts(&mut self, bursts: Vec<Burst>) {
+        self.bursts_fired = vec![0; bursts.len()];
+        self.config.bursts = bursts;
+    }
+
+    /// Remove all particles and restart from the seed.
+    /// 移除所有粒子并从种子重新开始。
+    pub fn reset(&mut self) {
+        self.rng = Rng::new(self.seed);
+        self.particles.clear();
+        self.time = 0.0;
+        self.emit_accumulator = 0.0;
+        self.bursts_fired.iter_mut().for_each(|fired| *fired = 0);
+    }
+
+    /// Spawn particles immediately, up to `max_particles`.
+    /// 立即生成粒子，最多`max_particles`个。
+    pub fn emit(&mut self, count: u32) {
+        for _ in 0..count {
+            if self.particles.len() >= self.config.max_particles {
+                break;
+            }
+            let particle = self.spawn();
+            self.particles.push(particle);
+        }
+    }
+
+    /// Advance the simulation.
+    /// 推进模拟。
+    pub fn update(&mut self, delta_seconds: f32) {
+        let dt = delta_seconds.max(0.0);
+
+        // Age, move and drop expired particles | 增加年龄、移动并移除过期粒子
+        let config = &self.config;
+        let drag = 1.0 / (1.0 + config.damping * dt);
+        self.particles.retain_mut(|p| {
+            p.age += dt;
+            if p.age >= p.lifetime {
+                return false;
+            }
+            p.velocity = (p.velocity + config.gravity * dt) * drag;
+            let speed = config.speed_over_lifetime.evaluate(p.progress());
+            p.position = p.position + p.velocity * (speed * dt);
+            p.rotation += p.angular_velocity * dt;
+            true
+        });
+
+        if self.playing {
+            self.update_emission(dt);
+        }
+    }
+
+    /// Write four vertices per particle (see `SpriteInstance::vertices`).
+    /// 为每个粒子写入四个顶点（参见`SpriteInstance::vertices`）。
+    ///
+    /// # Arguments | 参数
+    /// * `uv_rect` - Resolved texture UVs | 已解析的纹理UV
+    /// * `out` - Vertex buffer to append to | 要追加的顶点缓冲区
+    pub fn write_vertices(&self, uv_rect: [f32; 4], out: &mut Vec<SpriteVertex>) {
+        for p in &self.particles {
+            let t = p.progress();
+            let size = p.size * self.config.size_over_lifetime.evaluate(t);
+            let instance = SpriteInstance {
+                position: [p.position.x, p.position.y],
+                rotation: p.rotation,
+                size: [size, size],
+                origin: [0.5, 0.5],
+                uv_rect,
+                color: self.config.color_over_lifetime.evaluate(t),
+            };
+            out.extend_from_slice(&instance.vertices());
+        }
+    }
+
+    /// Rate and burst emission.
+    /// 速率和爆发发射。
+    fn update_emission(&mut self, dt: f32) {
+        self.time += dt;
+
+        // `config` is public, so bursts may have changed without `set_bursts`
+        // `config`是公开的，因此爆发可能未经`set_bursts`而被修改
+        self.bursts_fired.resize(self.config.bursts.len(), 0);
+
+        // Bursts due in (previous, time] | 在(previous, time]内到期的爆发
+        for i in 0..self.config.bursts.len() {
+            let burst = self.config.bursts[i];
+            loop {
+                let fired = self.bursts_fired[i];
+                if burst.cycles != 0 && fired >= burst.cycles {
+                    break;
+                }
+                if fired > 0 && burst.interval <= 0.0 {
+                    break;
+                }
+                let due = burst.time + fired as f32 * burst.interval;
+                if due > self.time {
+                    break;
+                }
+                self.bursts_fired[i] += 1;
+                self.emit(burst.count);
+            }
+        }
+
+        self.emit_accumulator += self.config.rate * dt;
+        let count = self.emit_accumulator.floor();
+        self.emit_accumulator -= count;
+        self.emit(count as u32);
+
+        // Loop the emission cycle | 循环发射周期
+        let duration = self.config.duration;
+        if duration > 0.0 && self.time >= duration {
+            self.time -= duration;
+            self.bursts_fired.iter_mut().for_each(|fired| *fired = 0);
+        }
+    }
+
+    /// Create one particle.
+    /// 创建一个粒子。
+    fn spawn(&mut self) -> Particle {
+        let config = &self.config;
+        let rng = &mut self.rng;
+
+        let (offset, outward) = config.shape.sample(rng);
+        let direction = outward.unwrap_or(config.direction) + rng.range(-0.5, 0.5) * config.spread;
+        let speed = rng.range(config.speed.0, config.speed.1);
+
+        Particle {
+            position: self.position + offset,
+            velocity: Vec2::new(direction.cos() * speed, direction.sin() * speed),
+            rotation: rng.range(config.rotation.0, config.rotation.1),
+            angular_velocity: rng.range(config.angular_velocity.0, config.angular_velocity.1),
+            size: rng.range(config.size.0, config.size.1),
+            age: 0.0,
+            lifetime: rng.range(config.lifetime.0, config.lifetime.1).max(f32::EPSILON),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Deterministic particle simulation and rendering.
+//! 确定性粒子模拟和渲染。
+
+mod rng;
+mod curve;
+mod emitter;
+mod particle_renderer;
+
+pub use rng::Rng;
+pub use curve::{Curve, CurveValue};
+pub use emitter::{
+    Burst, EmitterConfig, EmitterShape, Particle, ParticleEmitter, BURST_STRIDE, EMITTER_CONFIG_STRIDE,
+};
+pub use particle_renderer::ParticleRenderer;
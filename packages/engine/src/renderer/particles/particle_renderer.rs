@@ -0,0 +1,213 @@
+//! Particle emitter renderer.
+//! 粒子发射器渲染器。
+
+use std::collections::HashMap;
+
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use crate::resource::TextureManager;
+use super::super::batch::{SortKey, SpriteBatch, SpriteVertex, VERTEX_SIZE};
+use super::super::stats;
+use super::emitter::ParticleEmitter;
+
+/// An emitter and its vertex stream.
+/// 发射器及其顶点流。
+struct EmitterStream {
+    emitter: ParticleEmitter,
+    vao: WebGlVertexArrayObject,
+    vbo: WebGlBuffer,
+    /// Particles the vertex buffer can hold.
+    /// 顶点缓冲区可容纳的粒子数。
+    capacity: usize,
+}
+
+/// Owns particle emitters and draws each through its own vertex stream.
+/// 持有粒子发射器，并通过各自的顶点流绘制。
+///
+/// Emitters persist across frames and are advanced by `update`. Each one is
+/// drawn in a single draw call with its material, sorted among sprites by
+/// its `SortKey` (above sprites with the same key).
+/// 发射器跨帧保留，并由`update`推进。每个发射器使用其材质以单次绘制调用绘制，
+/// 并按其`SortKey`在精灵之间排序（位于键相同的精灵之上）。
+pub struct ParticleRenderer {
+    /// Emitters by ID.
+    /// 按ID存储的发射器。
+    emitters: HashMap<u32, EmitterStream>,
+
+    /// Next emitter ID.
+    /// 下一个发射器ID。
+    next_id: u32,
+
+    /// Quad indices shared by all emitters.
+    /// 所有发射器共享的四边形索引。
+    index_buffer: WebGlBuffer,
+
+    /// Quads the index buffer covers.
+    /// 索引缓冲区覆盖的四边形数。
+    index_capacity: usize,
+
+    /// Reused vertex buffer.
+    /// 复用的顶点缓冲区。
+    vertex_scratch: Vec<SpriteVertex>,
+}
+
+impl ParticleRenderer {
+    /// Create a new particle renderer.
+    /// 创建新的粒子渲染器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let index_buffer = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+
+        Ok(Self {
+            emitters: HashMap::new(),
+            next_id: 1,
+            index_buffer,
+            index_capacity: 0,
+            vertex_scratch: Vec::new(),
+        })
+    }
+
+    /// Add an emitter, returning its ID.
+    /// 添加发射器，返回其ID。
+    pub fn add_emitter(&mut self, gl: &WebGl2RenderingContext, emitter: ParticleEmitter) -> Result<u32> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&self.index_buffer));
+        SpriteBatch::setup_vertex_attributes(gl);
+
+        gl.bind_vertex_array(None);
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.emitters.insert(id, EmitterStream { emitter, vao, vbo, capacity: 0 });
+        Ok(id)
+    }
+
+    /// Get an emitter.
+    /// 获取发射器。
+    #[inline]
+    pub fn emitter(&self, id: u32) -> Option<&ParticleEmitter> {
+        self.emitters.get(&id).map(|s| &s.emitter)
+    }
+
+    /// Get a mutable emitter.
+    /// 获取可变发射器。
+    #[inline]
+    pub fn emitter_mut(&mut self, id: u32) -> Option<&mut ParticleEmitter> {
+        self.emitters.get_mut(&id).map(|s| &mut s.emitter)
+    }
+
+    /// Remove an emitter and release its buffers.
+    /// 移除发射器并释放其缓冲区。
+    pub fn remove_emitter(&mut self, gl: &WebGl2RenderingContext, id: u32) -> bool {
+        match self.emitters.remove(&id) {
+            Some(stream) => {
+                gl.delete_vertex_array(Some(&stream.vao));
+                gl.delete_buffer(Some(&stream.vbo));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether any emitter exists.
+    /// 检查是否存在任何发射器。
+    #[inline]
+    pub fn has_emitters(&self) -> bool {
+        !self.emitters.is_empty()
+    }
+
+    /// Advance all emitters.
+    /// 推进所有发射器。
+    pub fn update(&mut self, delta_seconds: f32) {
+        for stream in self.emitters.values_mut() {
+            stream.emitter.update(delta_seconds);
+        }
+    }
+
+    /// IDs and sort keys of emitters with live particles, in draw order.
+    /// 有存活粒子的发射器的ID和排序键，按绘制顺序排列。
+    pub fn draw_order(&self) -> Vec<(SortKey, u32)> {
+        let mut order: Vec<(SortKey, u32)> = self
+            .emitters
+            .iter()
+            .filter(|(_, s)| !s.emitter.particles().is_empty())
+            .map(|(&id, s)| (s.emitter.sort, id))
+            .collect();
+        order.sort_by(|a, b| a.0.compare(&b.0).then(a.1.cmp(&b.1)));
+        order
+    }
+
+    /// Upload and draw an emitter's particles with the bound material.
+    /// 使用已绑定的材质上传并绘制发射器的粒子。
+    pub fn draw_emitter(&mut self, gl: &WebGl2RenderingContext, texture_manager: &TextureManager, id: u32) {
+        let Some(stream) = self.emitters.get_mut(&id) else {
+            return;
+        };
+        let count = stream.emitter.particles().len();
+        if count == 0 {
+            return;
+        }
+
+        // Grow the shared index buffer | 扩展共享索引缓冲区
+        if count > self.index_capacity {
+            self.index_capacity = count.max(self.index_capacity * 2);
+            let indices = SpriteBatch::generate_indices(self.index_capacity);
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&self.index_buffer));
+            unsafe {
+                let index_array = js_sys::Uint32Array::view(&indices);
+                gl.buffer_data_with_array_buffer_view(
+                    WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+                    &index_array,
+                    WebGl2RenderingContext::STATIC_DRAW,
+                );
+            }
+        }
+
+        let (texture_id, uv_rect) = texture_manager.resolve_texture(stream.emitter.texture_id, [0.0, 0.0, 1.0, 1.0]);
+        self.vertex_scratch.clear();
+        stream.emitter.write_vertices(uv_rect, &mut self.vertex_scratch);
+        let vertices: &[f32] = bytemuck::cast_slice(&self.vertex_scratch);
+
+        gl.bind_vertex_array(Some(&stream.vao));
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&stream.vbo));
+        if count > stream.capacity {
+            stream.capacity = count.max(stream.capacity * 2);
+            gl.buffer_data_with_i32(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                (stream.capacity * 4 * VERTEX_SIZE) as i32,
+                WebGl2RenderingContext::DYNAMIC_DRAW,
+            );
+        }
+        unsafe {
+            let vertex_array = js_sys::Float32Array::view(vertices);
+            gl.buffer_sub_data_with_i32_and_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                0,
+                &vertex_array,
+            );
+        }
+
+        stats::record_vertices(count * 4);
+
+        texture_manager.bind_texture(texture_id, 0);
+        gl.draw_elements_with_i32(
+            WebGl2RenderingContext::TRIANGLES,
+            (count * 6) as i32,
+            WebGl2RenderingContext::UNSIGNED_INT,
+            0,
+        );
+        stats::record_draw_call();
+        gl.bind_vertex_array(None);
+    }
+}
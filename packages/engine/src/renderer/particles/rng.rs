@@ -0,0 +1,63 @@
+//! Seeded random numbers for deterministic simulation.
+//! 用于确定性模拟的种子随机数。
+
+/// Small seeded random number generator (SplitMix64).
+/// 小型种子随机数生成器（SplitMix64）。
+///
+/// The same seed always produces the same sequence on every platform.
+/// 相同的种子在所有平台上总是产生相同的序列。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::particles::Rng;
+/// let mut a = Rng::new(42);
+/// let mut b = Rng::new(42);
+/// assert_eq!(a.next_u32(), b.next_u32());
+/// let x = a.range(2.0, 3.0);
+/// assert!((2.0..3.0).contains(&x));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator from a seed.
+    /// 从种子创建生成器。
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next 64 random bits.
+    /// 下一个64位随机数。
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Next 32 random bits.
+    /// 下一个32位随机数。
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniform float in `[0, 1)`.
+    /// `[0, 1)`内的均匀浮点数。
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Uniform float in `[min, max)` (returns `min` when the range is empty).
+    /// `[min, max)`内的均匀浮点数（范围为空时返回`min`）。
+    #[inline]
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
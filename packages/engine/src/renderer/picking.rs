@@ -0,0 +1,317 @@
+//! Sprite picking in screen and world space.
+//! 屏幕空间和世界空间的精灵拾取。
+
+use std::collections::HashMap;
+
+use glam::Mat3;
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::{Rect, Vec2};
+use crate::resource::TextureManager;
+use super::batch::{SpriteBatch, SpriteInstance, SpriteVertex};
+use super::camera::Camera2D;
+use super::material::{BlendMode, MaterialManager};
+use super::render_texture::RenderTexture;
+use super::shader::ShaderProgram;
+use super::stats;
+
+/// A pickable sprite from the last rendered frame.
+/// 上一渲染帧中的可拾取精灵。
+#[derive(Clone, Copy, Debug)]
+pub struct PickTarget {
+    /// Picking ID given at submission (never 0).
+    /// 提交时给定的拾取ID（不为0）。
+    pub id: u32,
+    /// Sprite quad and resolved UVs.
+    /// 精灵四边形和解析后的UV。
+    pub instance: SpriteInstance,
+    /// Resolved texture (atlas page) ID.
+    /// 解析后的纹理（图集页面）ID。
+    pub texture_id: u32,
+}
+
+/// How screen-space picks are resolved.
+/// 屏幕空间拾取的解析方式。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PickMode {
+    /// Test rotated sprite quads on the CPU.
+    /// 在CPU上测试旋转后的精灵四边形。
+    #[default]
+    Cpu,
+    /// Render sprite IDs into an offscreen buffer and read it back, skipping
+    /// texels below the alpha threshold.
+    /// 将精灵ID渲染到离屏缓冲区并读回，跳过低于alpha阈值的纹素。
+    Gpu,
+}
+
+/// Resolves picks against the pickable sprites of the last rendered frame.
+/// 针对上一渲染帧的可拾取精灵解析拾取。
+///
+/// Sprites are pickable when submitted with a non-zero picking ID. Results
+/// hold each ID once, sorted by draw order, so the topmost sprite comes last.
+/// CPU mode tests sprite quads; GPU mode is pixel-accurate and ignores
+/// transparent texels, at the cost of a small render and a read-back.
+/// 以非零拾取ID提交的精灵可被拾取。结果中每个ID只出现一次并按绘制顺序排列，
+/// 因此最上层的精灵在最后。CPU模式测试精灵四边形；GPU模式精确到像素并忽略透明纹素，
+/// 代价是一次小型渲染和读回。
+pub struct SpritePicker {
+    /// Pickable sprites of the last frame in draw order.
+    /// 上一帧按绘制顺序的可拾取精灵。
+    targets: Vec<PickTarget>,
+
+    /// Screen-space pick mode.
+    /// 屏幕空间拾取模式。
+    mode: PickMode,
+
+    /// Minimum texture alpha that counts as a hit in GPU mode.
+    /// GPU模式下视为命中的最小纹理alpha。
+    alpha_threshold: f32,
+
+    vao: WebGlVertexArrayObject,
+    vbo: WebGlBuffer,
+    ibo: WebGlBuffer,
+
+    /// ID buffer, created on the first GPU pick.
+    /// ID缓冲区，在首次GPU拾取时创建。
+    id_buffer: Option<RenderTexture>,
+
+    /// Reused vertex and index data.
+    /// 复用的顶点和索引数据。
+    vertices: Vec<SpriteVertex>,
+    indices: Vec<u32>,
+}
+
+impl SpritePicker {
+    /// Create a new picker.
+    /// 创建新的拾取器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+
+        let ibo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&ibo));
+
+        SpriteBatch::setup_vertex_attributes(gl);
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            targets: Vec::new(),
+            mode: PickMode::Cpu,
+            alpha_threshold: 0.5,
+            vao,
+            vbo,
+            ibo,
+            id_buffer: None,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        })
+    }
+
+    /// Get the screen-space pick mode.
+    /// 获取屏幕空间拾取模式。
+    #[inline]
+    pub fn mode(&self) -> PickMode {
+        self.mode
+    }
+
+    /// Set the screen-space pick mode.
+    /// 设置屏幕空间拾取模式。
+    #[inline]
+    pub fn set_mode(&mut self, mode: PickMode) {
+        self.mode = mode;
+    }
+
+    /// Set the minimum texture alpha that counts as a hit in GPU mode.
+    /// 设置GPU模式下视为命中的最小纹理alpha。
+    #[inline]
+    pub fn set_alpha_threshold(&mut self, threshold: f32) {
+        self.alpha_threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    /// Pickable sprites of the last frame in draw order.
+    /// 上一帧按绘制顺序的可拾取精灵。
+    #[inline]
+    pub fn targets(&self) -> &[PickTarget] {
+        &self.targets
+    }
+
+    /// Keep the pickable sprites of the frame being finished.
+    /// 保留即将结束的帧中的可拾取精灵。
+    pub fn capture(&mut self, batch: &mut SpriteBatch) {
+        batch.take_pick_targets(&mut self.targets);
+    }
+
+    /// IDs of sprites containing a world-space point.
+    /// 包含世界空间点的精灵ID。
+    pub fn pick_point(&self, point: Vec2) -> Vec<u32> {
+        self.collect(|target| target.instance.contains_point(point))
+    }
+
+    /// IDs of sprites overlapping a convex world-space quad.
+    /// 与世界空间凸四边形重叠的精灵ID。
+    pub fn pick_area(&self, corners: &[Vec2; 4]) -> Vec<u32> {
+        self.collect(|target| target.instance.overlaps_quad(corners))
+    }
+
+    /// IDs of sprites overlapping a world-space rectangle.
+    /// 与世界空间矩形重叠的精灵ID。
+    pub fn pick_rect(&self, rect: &Rect) -> Vec<u32> {
+        let (min, max) = (rect.min(), rect.max());
+        self.pick_area(&[min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)])
+    }
+
+    /// IDs of hit targets, each once, in draw order.
+    /// 命中目标的ID，每个一次，按绘制顺序。
+    fn collect(&self, hit: impl Fn(&PickTarget) -> bool) -> Vec<u32> {
+        // Walk topmost first so each ID keeps its highest position | 从最上层开始遍历，使每个ID保留其最高位置
+        let mut ids: Vec<u32> = Vec::new();
+        for target in self.targets.iter().rev().filter(|t| hit(t)) {
+            if !ids.contains(&target.id) {
+                ids.push(target.id);
+            }
+        }
+        ids.reverse();
+        ids
+    }
+
+    /// Pick through the ID buffer inside a screen-space pixel rectangle.
+    /// 在屏幕空间像素矩形内通过ID缓冲区拾取。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `texture_manager` - Binds sprite textures for alpha tests | 绑定精灵纹理用于alpha测试
+    /// * `shader` - Picking shader | 拾取着色器
+    /// * `camera` - Camera the frame was drawn with | 绘制该帧的相机
+    /// * `area` - [x0, y0, x1, y1] in screen pixels (Y down) | 屏幕像素坐标（Y向下）
+    pub fn pick_gpu(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        shader: &ShaderProgram,
+        camera: &Camera2D,
+        area: [f32; 4],
+    ) -> Result<Vec<u32>> {
+        let (screen_w, screen_h) = (camera.viewport_width(), camera.viewport_height());
+        let x0 = area[0].min(area[2]).floor().max(0.0);
+        let y0 = area[1].min(area[3]).floor().max(0.0);
+        let x1 = area[0].max(area[2]).ceil().max(x0 + 1.0).min(screen_w);
+        let y1 = area[1].max(area[3]).ceil().max(y0 + 1.0).min(screen_h);
+        if self.targets.is_empty() || x1 <= x0 || y1 <= y0 {
+            return Ok(Vec::new());
+        }
+        let (width, height) = ((x1 - x0) as u32, (y1 - y0) as u32);
+
+        if self.id_buffer.is_none() {
+            self.id_buffer = Some(RenderTexture::new(gl, width, height)?);
+        }
+        let Some(id_buffer) = self.id_buffer.as_mut() else {
+            return Ok(Vec::new());
+        };
+        id_buffer.resize(gl, width, height)?;
+
+        // Stretch the picked pixels over the whole buffer | 将拾取的像素拉伸到整个缓冲区
+        let (nx0, nx1) = (x0 / screen_w * 2.0 - 1.0, x1 / screen_w * 2.0 - 1.0);
+        let (ny0, ny1) = (1.0 - y1 / screen_h * 2.0, 1.0 - y0 / screen_h * 2.0);
+        let crop = Mat3::from_cols_array(&[
+            2.0 / (nx1 - nx0), 0.0, 0.0,
+            0.0, 2.0 / (ny1 - ny0), 0.0,
+            -(nx1 + nx0) / (nx1 - nx0), -(ny1 + ny0) / (ny1 - ny0), 1.0,
+        ]);
+        let projection = crop * camera.projection_matrix();
+
+        // Encode IDs as vertex colors | 将ID编码为顶点颜色
+        self.vertices.clear();
+        self.indices.clear();
+        for target in &self.targets {
+            let color = target.id.to_le_bytes().map(|b| b as f32 / 255.0);
+            let base = self.vertices.len() as u32;
+            self.vertices.extend(target.instance.vertices().map(|mut v| {
+                v.color = color;
+                v
+            }));
+            self.indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 3, base]);
+        }
+
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&self.vbo));
+        gl.buffer_data_with_u8_array(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            bytemuck::cast_slice(&self.vertices),
+            WebGl2RenderingContext::STREAM_DRAW,
+        );
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&self.ibo));
+        gl.buffer_data_with_u8_array(
+            WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+            bytemuck::cast_slice(&self.indices),
+            WebGl2RenderingContext::STREAM_DRAW,
+        );
+        stats::record_vertices(self.vertices.len());
+
+        id_buffer.bind(gl);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+        MaterialManager::apply_blend_mode(gl, BlendMode::None);
+        shader.bind(gl);
+        shader.set_uniform_mat3(gl, "u_projection", &projection.to_cols_array());
+        shader.set_uniform_i32(gl, "u_texture", 0);
+        shader.set_uniform_f32(gl, "u_alphaThreshold", self.alpha_threshold);
+
+        // One draw per run of targets sharing a texture | 共享纹理的每组连续目标绘制一次
+        let mut start = 0;
+        while start < self.targets.len() {
+            let texture_id = self.targets[start].texture_id;
+            let count = self.targets[start..]
+                .iter()
+                .take_while(|t| t.texture_id == texture_id)
+                .count();
+            texture_manager.bind_texture(texture_id, 0);
+            gl.draw_elements_with_i32(
+                WebGl2RenderingContext::TRIANGLES,
+                (count * 6) as i32,
+                WebGl2RenderingContext::UNSIGNED_INT,
+                (start * 6 * 4) as i32,
+            );
+            stats::record_draw_call();
+            start += count;
+        }
+        gl.bind_vertex_array(None);
+
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let read = gl.read_pixels_with_opt_u8_array(
+            0,
+            0,
+            width as i32,
+            height as i32,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            Some(&mut pixels),
+        );
+
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+        gl.viewport(0, 0, screen_w as i32, screen_h as i32);
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+        read.map_err(|e| EngineError::WebGLError(format!("Failed to read pick buffer: {:?}", e)))?;
+
+        // Order hit IDs by their topmost draw position | 按最高绘制位置排序命中的ID
+        let order: HashMap<u32, usize> = self.targets.iter().enumerate().map(|(i, t)| (t.id, i)).collect();
+        let mut ids: Vec<u32> = pixels
+            .chunks_exact(4)
+            .map(|p| u32::from_le_bytes([p[0], p[1], p[2], p[3]]))
+            .filter(|&id| id != 0)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.sort_by_key(|id| order.get(id).copied().unwrap_or(0));
+        Ok(ids)
+    }
+}
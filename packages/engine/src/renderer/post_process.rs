@@ -0,0 +1,510 @@
+//! Full-screen post-processing stack.
+//! 全屏后处理栈。
+
+use web_sys::{WebGl2RenderingContext, WebGlTexture};
+
+use crate::core::error::Result;
+use crate::resource::TextureManager;
+use super::material::{BlendMode, MaterialManager, MaterialUniforms};
+use super::render_texture::{FullscreenQuad, RenderTexture};
+use super::shader::{
+    ShaderManager, ShaderProgram, SHADER_ID_BLIT, SHADER_ID_BLOOM_EXTRACT, SHADER_ID_BLUR,
+    SHADER_ID_BLOOM_COMPOSITE, SHADER_ID_COLOR_GRADING, SHADER_ID_VIGNETTE,
+    SHADER_ID_PIXELATE, SHADER_ID_CRT,
+};
+
+/// Post-processing effect type.
+/// 后处理效果类型。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostEffectKind {
+    /// Bright-pass, blur and additive composite.
+    /// 亮部提取、模糊并叠加合成。
+    Bloom,
+    /// Exposure, contrast, saturation and optional LUT.
+    /// 曝光、对比度、饱和度及可选LUT。
+    ColorGrading {
+        /// LUT strip texture ID (a standalone texture, not an atlas region).
+        /// LUT条带纹理ID（独立纹理，不能是图集区域）。
+        lut_texture_id: Option<u32>,
+    },
+    /// Darkened edges.
+    /// 边缘暗角。
+    Vignette,
+    /// Blocky low-resolution look.
+    /// 低分辨率块状效果。
+    Pixelate,
+    /// Curved screen, scanlines and chromatic aberration.
+    /// 曲面屏、扫描线和色差。
+    Crt,
+    /// User fragment shader compiled with `compile_shader_with_id`.
+    /// 使用`compile_shader_with_id`编译的用户片段着色器。
+    ///
+    /// The shader must use `FULLSCREEN_VERTEX_SHADER` (or the same
+    /// `a_position`/`v_texCoord` interface). It receives `u_texture` and
+    /// `u_resolution` plus the effect uniforms.
+    /// 着色器必须使用`FULLSCREEN_VERTEX_SHADER`（或相同的`a_position`/`v_texCoord`接口），
+    /// 会收到`u_texture`和`u_resolution`以及效果uniform。
+    Custom {
+        /// Shader ID.
+        /// 着色器ID。
+        shader_id: u32,
+    },
+}
+
+impl PostEffectKind {
+    /// Convert from the numeric code used by the JavaScript API.
+    /// 从JavaScript API使用的数字代码转换。
+    ///
+    /// 0=Bloom, 1=ColorGrading (`param` = LUT texture ID, 0 = none),
+    /// 2=Vignette, 3=Pixelate, 4=Crt, 5=Custom (`param` = shader ID).
+    pub fn from_code(code: u8, param: u32) -> Option<Self> {
+        match code {
+            0 => Some(PostEffectKind::Bloom),
+            1 => Some(PostEffectKind::ColorGrading {
+                lut_texture_id: if param == 0 { None } else { Some(param) },
+            }),
+            2 => Some(PostEffectKind::Vignette),
+            3 => Some(PostEffectKind::Pixelate),
+            4 => Some(PostEffectKind::Crt),
+            5 => Some(PostEffectKind::Custom { shader_id: param }),
+            _ => None,
+        }
+    }
+}
+
+/// A single post-processing effect.
+/// 单个后处理效果。
+#[derive(Clone, Debug)]
+pub struct PostEffect {
+    /// Unique effect name within its stack.
+    /// 在栈内唯一的效果名称。
+    pub name: String,
+
+    /// Effect type.
+    /// 效果类型。
+    pub kind: PostEffectKind,
+
+    /// Shader parameters.
+    /// 着色器参数。
+    pub uniforms: MaterialUniforms,
+
+    /// Whether the effect runs.
+    /// 效果是否执行。
+    pub enabled: bool,
+}
+
+impl PostEffect {
+    /// Create an effect with default parameters for its type.
+    /// 创建带有该类型默认参数的效果。
+    ///
+    /// | Effect | Uniforms (default) |
+    /// |--------|--------------------|
+    /// | Bloom | `u_threshold` (0.8), `u_softKnee` (0.5), `u_intensity` (1.0), `u_radius` (1.0), `u_tint` (white) |
+    /// | ColorGrading | `u_exposure` (0.0), `u_contrast` (1.0), `u_saturation` (1.0), `u_intensity` (1.0), `u_lutSize` (16) |
+    /// | Vignette | `u_intensity` (0.5), `u_radius` (0.9), `u_smoothness` (0.5), `u_color` (black) |
+    /// | Pixelate | `u_pixelSize` (4.0) |
+    /// | Crt | `u_curvature` (0.08), `u_scanlineIntensity` (0.25), `u_chromaticAberration` (1.5) |
+    pub fn new(name: &str, kind: PostEffectKind) -> Self {
+        let mut uniforms = MaterialUniforms::new();
+        match kind {
+            PostEffectKind::Bloom => {
+                uniforms.set_float("u_threshold", 0.8);
+                uniforms.set_float("u_softKnee", 0.5);
+                uniforms.set_float("u_intensity", 1.0);
+                uniforms.set_float("u_radius", 1.0);
+                uniforms.set_color("u_tint", 1.0, 1.0, 1.0, 1.0);
+            }
+            PostEffectKind::ColorGrading { .. } => {
+                uniforms.set_float("u_exposure", 0.0);
+                uniforms.set_float("u_contrast", 1.0);
+                uniforms.set_float("u_saturation", 1.0);
+                uniforms.set_float("u_intensity", 1.0);
+                uniforms.set_float("u_lutSize", 16.0);
+            }
+            PostEffectKind::Vignette => {
+                uniforms.set_float("u_intensity", 0.5);
+                uniforms.set_float("u_radius", 0.9);
+                uniforms.set_float("u_smoothness", 0.5);
+                uniforms.set_color("u_color", 0.0, 0.0, 0.0, 1.0);
+            }
+            PostEffectKind::Pixelate => {
+                uniforms.set_float("u_pixelSize", 4.0);
+            }
+            PostEffectKind::Crt => {
+                uniforms.set_float("u_curvature", 0.08);
+                uniforms.set_float("u_scanlineIntensity", 0.25);
+                uniforms.set_float("u_chromaticAberration", 1.5);
+            }
+            PostEffectKind::Custom { .. } => {}
+        }
+
+        Self {
+            name: name.to_string(),
+            kind,
+            uniforms,
+            enabled: true,
+        }
+    }
+
+    /// Shader that writes the effect's final output.
+    /// 写入效果最终输出的着色器。
+    pub fn shader_id(&self) -> u32 {
+        match self.kind {
+            PostEffectKind::Bloom => SHADER_ID_BLOOM_COMPOSITE,
+            PostEffectKind::ColorGrading { .. } => SHADER_ID_COLOR_GRADING,
+            PostEffectKind::Vignette => SHADER_ID_VIGNETTE,
+            PostEffectKind::Pixelate => SHADER_ID_PIXELATE,
+            PostEffectKind::Crt => SHADER_ID_CRT,
+            PostEffectKind::Custom { shader_id } => shader_id,
+        }
+    }
+}
+
+/// Ordered list of post-processing effects.
+/// 有序的后处理效果列表。
+#[derive(Clone, Debug, Default)]
+pub struct PostProcessStack {
+    /// Effects in execution order.
+    /// 按执行顺序排列的效果。
+    effects: Vec<PostEffect>,
+}
+
+impl PostProcessStack {
+    /// Create an empty stack.
+    /// 创建空栈。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an effect at the end, or replace the effect with the same name in place.
+    /// 在末尾添加效果，或原位替换同名效果。
+    pub fn add(&mut self, effect: PostEffect) {
+        match self.effects.iter_mut().find(|e| e.name == effect.name) {
+            Some(existing) => *existing = effect,
+            None => self.effects.push(effect),
+        }
+    }
+
+    /// Remove an effect by name.
+    /// 按名称移除效果。
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.effects.len();
+        self.effects.retain(|e| e.name != name);
+        self.effects.len() != len
+    }
+
+    /// Get a mutable effect by name.
+    /// 按名称获取可变效果。
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut PostEffect> {
+        self.effects.iter_mut().find(|e| e.name == name)
+    }
+
+    /// Remove all effects.
+    /// 移除所有效果。
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+
+    /// Effects in execution order.
+    /// 按执行顺序排列的效果。
+    #[inline]
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    /// Check whether any effect is enabled.
+    /// 检查是否有启用的效果。
+    pub fn has_enabled(&self) -> bool {
+        self.effects.iter().any(|e| e.enabled)
+    }
+}
+
+/// Intermediate targets used by the post processor.
+/// 后处理器使用的中间目标。
+struct PostTargets {
+    /// Captured scene.
+    /// 捕获的场景。
+    scene: RenderTexture,
+    /// Ping-pong buffer A.
+    /// 乒乓缓冲区A。
+    ping: RenderTexture,
+    /// Ping-pong buffer B.
+    /// 乒乓缓冲区B。
+    pong: RenderTexture,
+    /// Half-resolution bloom buffer A.
+    /// 半分辨率泛光缓冲区A。
+    bloom_a: RenderTexture,
+    /// Half-resolution bloom buffer B.
+    /// 半分辨率泛光缓冲区B。
+    bloom_b: RenderTexture,
+}
+
+/// GPU state shared by all effect draws in one `apply` call.
+/// 一次`apply`调用中所有效果绘制共享的GPU状态。
+struct PostContext<'a> {
+    gl: &'a WebGl2RenderingContext,
+    shaders: &'a ShaderManager,
+    quad: &'a FullscreenQuad,
+    texture_manager: &'a TextureManager,
+}
+
+/// Runs a `PostProcessStack` over a captured frame.
+/// 在捕获的帧上执行`PostProcessStack`。
+///
+/// The scene is rendered into an offscreen target, each enabled effect reads
+/// the previous result and writes into the other ping-pong buffer, and the
+/// last effect writes straight to the screen.
+/// 场景先渲染到离屏目标，每个启用的效果读取上一步结果并写入另一个乒乓缓冲区，
+/// 最后一个效果直接写入屏幕。
+#[derive(Default)]
+pub struct PostProcessor {
+    /// Lazily created targets.
+    /// 延迟创建的目标。
+    targets: Option<PostTargets>,
+}
+
+impl PostProcessor {
+    /// Create a post processor. Targets are created on first use.
+    /// 创建后处理器。目标在首次使用时创建。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepare targets for a surface and start capturing the scene.
+    /// 为表面准备目标并开始捕获场景。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `surface` - Surface size in pixels | 表面尺寸（像素）
+    /// * `clear_color` - Background color of the captured scene | 捕获场景的背景颜色
+    pub fn begin(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        surface: (u32, u32),
+        clear_color: [f32; 4],
+    ) -> Result<()> {
+        let (width, height) = surface;
+        let (half_width, half_height) = ((width / 2).max(1), (height / 2).max(1));
+
+        match &mut self.targets {
+            Some(targets) => {
+                targets.scene.resize(gl, width, height)?;
+                targets.ping.resize(gl, width, height)?;
+                targets.pong.resize(gl, width, height)?;
+                targets.bloom_a.resize(gl, half_width, half_height)?;
+                targets.bloom_b.resize(gl, half_width, half_height)?;
+            }
+            None => {
+                self.targets = Some(PostTargets {
+                    scene: RenderTexture::new(gl, width, height)?,
+                    ping: RenderTexture::new(gl, width, height)?,
+                    pong: RenderTexture::new(gl, width, height)?,
+                    bloom_a: RenderTexture::new(gl, half_width, half_height)?,
+                    bloom_b: RenderTexture::new(gl, half_width, half_height)?,
+                });
+            }
+        }
+
+        if let Some(targets) = &mut self.targets {
+            targets.scene.clear_color = clear_color;
+            targets.scene.bind(gl);
+            targets.scene.clear(gl);
+        }
+
+        Ok(())
+    }
+
+    /// Bind the scene capture target.
+    /// 绑定场景捕获目标。
+    pub fn bind_scene(&self, gl: &WebGl2RenderingContext) {
+        if let Some(targets) = &self.targets {
+            targets.scene.bind(gl);
+        }
+    }
+
+    /// Run enabled effects over the captured scene and write the result to the screen.
+    /// 在捕获的场景上执行启用的效果，并将结果写入屏幕。
+    pub fn apply(
+        &self,
+        gl: &WebGl2RenderingContext,
+        shaders: &ShaderManager,
+        quad: &FullscreenQuad,
+        texture_manager: &TextureManager,
+        stack: &PostProcessStack,
+    ) {
+        let targets = match &self.targets {
+            Some(targets) => targets,
+            None => return,
+        };
+        let ctx = PostContext { gl, shaders, quad, texture_manager };
+        let effects: Vec<&PostEffect> = stack.effects().iter().filter(|e| e.enabled).collect();
+
+        // Effects replace pixels rather than blend | 效果替换像素而不是混合
+        gl.disable(WebGl2RenderingContext::BLEND);
+
+        let mut source = &targets.scene;
+        for (index, effect) in effects.iter().enumerate() {
+            let dest = if index + 1 == effects.len() {
+                None
+            } else if std::ptr::eq(source, &targets.ping) {
+                Some(&targets.pong)
+            } else {
+                Some(&targets.ping)
+            };
+
+            Self::run_effect(&ctx, targets, effect, source, dest);
+
+            if let Some(dest) = dest {
+                source = dest;
+            }
+        }
+
+        // Nothing enabled: copy the scene through unchanged | 没有启用的效果：原样拷贝场景
+        if effects.is_empty() {
+            Self::bind_output(&ctx, targets, None);
+            if let Some(shader) = Self::prepare(&ctx, SHADER_ID_BLIT, None, source) {
+                shader.set_uniform_vec4(gl, "u_uvRect", [0.0, 1.0, 1.0, 0.0]);
+            }
+            quad.draw(gl);
+        }
+
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+        gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+    }
+
+    /// Draw one effect from `source` into `dest` (`None` = screen).
+    /// 将单个效果从`source`绘制到`dest`（`None`表示屏幕）。
+    fn run_effect(
+        ctx: &PostContext,
+        targets: &PostTargets,
+        effect: &PostEffect,
+        source: &RenderTexture,
+        dest: Option<&RenderTexture>,
+    ) {
+        let gl = ctx.gl;
+
+        match effect.kind {
+            PostEffectKind::Bloom => {
+                // Bright pass at half resolution | 半分辨率亮部提取
+                targets.bloom_a.bind(gl);
+                Self::prepare(ctx, SHADER_ID_BLOOM_EXTRACT, Some(effect), source);
+                ctx.quad.draw(gl);
+
+                // Separable blur | 可分离模糊
+                let (bloom_w, bloom_h) = targets.bloom_a.dimensions();
+                targets.bloom_b.bind(gl);
+                if let Some(shader) = Self::prepare(ctx, SHADER_ID_BLUR, Some(effect), &targets.bloom_a) {
+                    shader.set_uniform_vec2(gl, "u_direction", [1.0 / bloom_w as f32, 0.0]);
+                }
+                ctx.quad.draw(gl);
+
+                targets.bloom_a.bind(gl);
+                if let Some(shader) = Self::prepare(ctx, SHADER_ID_BLUR, Some(effect), &targets.bloom_b) {
+                    shader.set_uniform_vec2(gl, "u_direction", [0.0, 1.0 / bloom_h as f32]);
+                }
+                ctx.quad.draw(gl);
+
+                // Composite | 合成
+                Self::bind_output(ctx, targets, dest);
+                if let Some(shader) = Self::prepare(ctx, SHADER_ID_BLOOM_COMPOSITE, Some(effect), source) {
+                    Self::bind_unit(gl, 1, targets.bloom_a.texture());
+                    shader.set_uniform_i32(gl, "u_bloom", 1);
+                }
+                ctx.quad.draw(gl);
+            }
+            PostEffectKind::ColorGrading { lut_texture_id } => {
+                Self::bind_output(ctx, targets, dest);
+                if let Some(shader) = Self::prepare(ctx, SHADER_ID_COLOR_GRADING, Some(effect), source) {
+                    match lut_texture_id {
+                        Some(lut) => {
+                            ctx.texture_manager.bind_texture(lut, 1);
+                            shader.set_uniform_i32(gl, "u_lut", 1);
+                            shader.set_uniform_f32(gl, "u_useLut", 1.0);
+                        }
+                        None => shader.set_uniform_f32(gl, "u_useLut", 0.0),
+                    }
+                }
+                ctx.quad.draw(gl);
+            }
+            _ => {
+                Self::bind_output(ctx, targets, dest);
+                Self::prepare(ctx, effect.shader_id(), Some(effect), source);
+                ctx.quad.draw(gl);
+            }
+        }
+    }
+
+    /// Bind a shader with the common inputs set.
+    /// 绑定着色器并设置通用输入。
+    ///
+    /// Falls back to a plain copy when the effect shader does not exist.
+    /// 当效果着色器不存在时回退为直接拷贝。
+    fn prepare<'a>(
+        ctx: &'a PostContext,
+        shader_id: u32,
+        effect: Option<&PostEffect>,
+        source: &RenderTexture,
+    ) -> Option<&'a ShaderProgram> {
+        let gl = ctx.gl;
+
+        let shader = match ctx.shaders.get_shader(shader_id) {
+            Some(shader) => shader,
+            None => {
+                log::warn!(
+                    "Post effect shader {} not found, copying | 未找到后处理着色器 {}，直接拷贝",
+                    shader_id, shader_id
+                );
+                let blit = ctx.shaders.get_shader(SHADER_ID_BLIT)?;
+                blit.bind(gl);
+                Self::bind_unit(gl, 0, source.texture());
+                blit.set_uniform_i32(gl, "u_texture", 0);
+                blit.set_uniform_vec4(gl, "u_uvRect", [0.0, 1.0, 1.0, 0.0]);
+                return None;
+            }
+        };
+
+        shader.bind(gl);
+        Self::bind_unit(gl, 0, source.texture());
+        shader.set_uniform_i32(gl, "u_texture", 0);
+
+        let (width, height) = source.dimensions();
+        shader.set_uniform_vec2(gl, "u_resolution", [width as f32, height as f32]);
+
+        if let Some(effect) = effect {
+            effect.uniforms.apply_to_shader(gl, shader);
+        }
+
+        Some(shader)
+    }
+
+    /// Bind an effect output: a ping-pong buffer or the screen.
+    /// 绑定效果输出：乒乓缓冲区或屏幕。
+    fn bind_output(ctx: &PostContext, targets: &PostTargets, dest: Option<&RenderTexture>) {
+        match dest {
+            Some(dest) => dest.bind(ctx.gl),
+            None => {
+                let (width, height) = targets.scene.dimensions();
+                ctx.gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+                ctx.gl.viewport(0, 0, width as i32, height as i32);
+            }
+        }
+    }
+
+    /// Bind a raw texture to a texture unit.
+    /// 将原始纹理绑定到纹理单元。
+    fn bind_unit(gl: &WebGl2RenderingContext, unit: u32, texture: &WebGlTexture) {
+        gl.active_texture(WebGl2RenderingContext::TEXTURE0 + unit);
+        gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(texture));
+    }
+
+    /// Delete all targets.
+    /// 删除所有目标。
+    pub fn delete(&mut self, gl: &WebGl2RenderingContext) {
+        if let Some(targets) = self.targets.take() {
+            for target in [targets.scene, targets.ping, targets.pong, targets.bloom_a, targets.bloom_b] {
+                target.delete(gl);
+                gl.delete_texture(Some(target.texture()));
+            }
+        }
+    }
+}
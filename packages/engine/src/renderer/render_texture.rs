@@ -0,0 +1,270 @@
+//! Offscreen render targets that can be sampled as textures.
+//! 可作为纹理采样的离屏渲染目标。
+
+use web_sys::{WebGl2RenderingContext, WebGlFramebuffer, WebGlTexture};
+
+use crate::core::error::{EngineError, Result};
+use super::camera::Camera2D;
+use super::stats;
+
+/// Offscreen render texture.
+/// 离屏渲染纹理。
+///
+/// Wraps a framebuffer with a single RGBA color attachment. The color
+/// texture is registered with the `TextureManager`, so sprites can sample
+/// it by texture ID like any loaded image.
+/// 封装带有单个RGBA颜色附件的帧缓冲区。颜色纹理会注册到`TextureManager`，
+/// 因此精灵可以像普通图像一样通过纹理ID采样它。
+pub struct RenderTexture {
+    /// Framebuffer object.
+    /// 帧缓冲区对象。
+    framebuffer: WebGlFramebuffer,
+
+    /// Color attachment.
+    /// 颜色附件。
+    texture: WebGlTexture,
+
+    /// Width in pixels.
+    /// 宽度（像素）。
+    width: u32,
+
+    /// Height in pixels.
+    /// 高度（像素）。
+    height: u32,
+
+    /// Camera used when rendering into this texture.
+    /// 渲染到此纹理时使用的相机。
+    ///
+    /// `None` follows the frame camera.
+    /// `None`表示跟随帧相机。
+    pub camera: Option<Camera2D>,
+
+    /// Clear color (RGBA).
+    /// 清除颜色 (RGBA)。
+    pub clear_color: [f32; 4],
+}
+
+impl RenderTexture {
+    /// Create a new render texture.
+    /// 创建新的渲染纹理。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `width` - Width in pixels | 宽度（像素）
+    /// * `height` - Height in pixels | 高度（像素）
+    pub fn new(gl: &WebGl2RenderingContext, width: u32, height: u32) -> Result<Self> {
+        let width = width.max(1);
+        let height = height.max(1);
+
+        let texture = gl
+            .create_texture()
+            .ok_or_else(|| EngineError::FramebufferCreationFailed("Failed to create color texture".into()))?;
+        Self::allocate_storage(gl, &texture, width, height)?;
+
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or_else(|| EngineError::FramebufferCreationFailed("Failed to create framebuffer".into()))?;
+
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(
+            WebGl2RenderingContext::FRAMEBUFFER,
+            WebGl2RenderingContext::COLOR_ATTACHMENT0,
+            WebGl2RenderingContext::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+
+        let status = gl.check_framebuffer_status(WebGl2RenderingContext::FRAMEBUFFER);
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+
+        if status != WebGl2RenderingContext::FRAMEBUFFER_COMPLETE {
+            gl.delete_framebuffer(Some(&framebuffer));
+            gl.delete_texture(Some(&texture));
+            return Err(EngineError::FramebufferCreationFailed(format!(
+                "Framebuffer incomplete, status 0x{:X}",
+                status
+            )));
+        }
+
+        log::debug!(
+            "RenderTexture created ({}x{}) | 渲染纹理已创建 ({}x{})",
+            width, height, width, height
+        );
+
+        Ok(Self {
+            framebuffer,
+            texture,
+            width,
+            height,
+            camera: None,
+            clear_color: [0.0, 0.0, 0.0, 0.0],
+        })
+    }
+
+    /// Allocate (or reallocate) the color texture storage.
+    /// 分配（或重新分配）颜色纹理存储。
+    fn allocate_storage(
+        gl: &WebGl2RenderingContext,
+        texture: &WebGlTexture,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
+        gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(texture));
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            WebGl2RenderingContext::TEXTURE_2D,
+            0,
+            WebGl2RenderingContext::RGBA as i32,
+            width as i32,
+            height as i32,
+            0,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            None,
+        )
+        .map_err(|e| EngineError::FramebufferCreationFailed(format!("{:?}", e)))?;
+
+        for (param, value) in [
+            (WebGl2RenderingContext::TEXTURE_WRAP_S, WebGl2RenderingContext::CLAMP_TO_EDGE),
+            (WebGl2RenderingContext::TEXTURE_WRAP_T, WebGl2RenderingContext::CLAMP_TO_EDGE),
+            (WebGl2RenderingContext::TEXTURE_MIN_FILTER, WebGl2RenderingContext::LINEAR),
+            (WebGl2RenderingContext::TEXTURE_MAG_FILTER, WebGl2RenderingContext::LINEAR),
+        ] {
+            gl.tex_parameteri(WebGl2RenderingContext::TEXTURE_2D, param, value as i32);
+        }
+
+        Ok(())
+    }
+
+    /// Resize the color attachment. Contents are discarded.
+    /// 调整颜色附件大小。内容会被丢弃。
+    pub fn resize(&mut self, gl: &WebGl2RenderingContext, width: u32, height: u32) -> Result<()> {
+        let width = width.max(1);
+        let height = height.max(1);
+        if width == self.width && height == self.height {
+            return Ok(());
+        }
+
+        Self::allocate_storage(gl, &self.texture, width, height)?;
+        self.width = width;
+        self.height = height;
+
+        if let Some(camera) = &mut self.camera {
+            camera.set_viewport(width as f32, height as f32);
+        }
+
+        Ok(())
+    }
+
+    /// Bind as the current render target and set the viewport to cover it.
+    /// 绑定为当前渲染目标，并设置视口覆盖整个纹理。
+    pub fn bind(&self, gl: &WebGl2RenderingContext) {
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&self.framebuffer));
+        gl.viewport(0, 0, self.width as i32, self.height as i32);
+    }
+
+    /// Clear with the configured clear color.
+    /// 使用配置的清除颜色清除。
+    pub fn clear(&self, gl: &WebGl2RenderingContext) {
+        let [r, g, b, a] = self.clear_color;
+        gl.clear_color(r, g, b, a);
+        gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+    }
+
+    /// Camera to render with, given the camera of the current frame.
+    /// 给定当前帧相机，返回渲染此纹理时使用的相机。
+    ///
+    /// Without its own camera, the frame camera is used with the viewport
+    /// resized to this texture, keeping one world unit per pixel at zoom 1.
+    /// 没有独立相机时使用帧相机，并将视口调整为纹理尺寸，使缩放为1时1个世界单位对应1像素。
+    pub fn resolve_camera(&self, frame_camera: &Camera2D) -> Camera2D {
+        match &self.camera {
+            Some(camera) => camera.clone(),
+            None => {
+                let mut camera = frame_camera.clone();
+                camera.set_viewport(self.width as f32, self.height as f32);
+                camera
+            }
+        }
+    }
+
+    /// Get or create this texture's own camera.
+    /// 获取或创建此纹理的独立相机。
+    pub fn camera_mut(&mut self) -> &mut Camera2D {
+        let (width, height) = (self.width as f32, self.height as f32);
+        self.camera.get_or_insert_with(|| Camera2D::new(width, height))
+    }
+
+    /// Get the color texture handle.
+    /// 获取颜色纹理句柄。
+    #[inline]
+    pub fn texture(&self) -> &WebGlTexture {
+        &self.texture
+    }
+
+    /// Get dimensions.
+    /// 获取尺寸。
+    #[inline]
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Delete the framebuffer.
+    /// 删除帧缓冲区。
+    ///
+    /// The color texture is owned by the `TextureManager` and deleted there.
+    /// 颜色纹理归`TextureManager`所有，并在那里删除。
+    pub fn delete(&self, gl: &WebGl2RenderingContext) {
+        gl.delete_framebuffer(Some(&self.framebuffer));
+    }
+}
+
+/// Full-screen quad geometry for texture passes.
+/// 用于纹理通道的全屏四边形几何体。
+pub struct FullscreenQuad {
+    /// Vertex array object.
+    /// 顶点数组对象。
+    vao: web_sys::WebGlVertexArrayObject,
+}
+
+impl FullscreenQuad {
+    /// Create the quad.
+    /// 创建四边形。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+
+        // Triangle strip covering clip space | 覆盖裁剪空间的三角形带
+        let vertices: [f32; 8] = [-1.0, -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0];
+        unsafe {
+            let array = js_sys::Float32Array::view(&vertices);
+            gl.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                &array,
+                WebGl2RenderingContext::STATIC_DRAW,
+            );
+        }
+
+        gl.enable_vertex_attrib_array(0);
+        gl.vertex_attrib_pointer_with_i32(0, 2, WebGl2RenderingContext::FLOAT, false, 0, 0);
+
+        gl.bind_vertex_array(None);
+
+        Ok(Self { vao })
+    }
+
+    /// Draw the quad with the currently bound program.
+    /// 使用当前绑定的程序绘制四边形。
+    pub fn draw(&self, gl: &WebGl2RenderingContext) {
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.draw_arrays(WebGl2RenderingContext::TRIANGLE_STRIP, 0, 4);
+        gl.bind_vertex_array(None);
+        stats::record_draw_call();
+    }
+}
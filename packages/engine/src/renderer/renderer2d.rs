@@ -1,15 +1,59 @@
 //! Main 2D renderer implementation.
 //! 主2D渲染器实现。
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use glam::Mat3;
 use wasm_bindgen::JsCast;
 use web_sys::WebGl2RenderingContext;
 
-use crate::core::error::Result;
-use crate::resource::TextureManager;
-use super::batch::SpriteBatch;
+use crate::core::error::{EngineError, Result};
+use crate::math::{Rect, Vec2};
+use crate::resource::{Texture, TextureManager};
+use super::batch::{BatchRun, NineSlice, SortKey, SpriteBatch, SpriteBatchData};
 use super::camera::Camera2D;
-use super::shader::ShaderManager;
-use super::material::MaterialManager;
+use super::shader::{
+    ShaderManager, SHADER_ID_BLIT, SHADER_ID_DEFAULT_SPRITE, SHADER_ID_SPRITE_INSTANCED,
+    SHADER_ID_SDF_TEXT, SHADER_ID_SHAPE, SHADER_ID_SPRITE_NORMAL, SHADER_ID_PICK,
+};
+use super::material::{BlendMode, Material, MaterialManager};
+use super::render_texture::{FullscreenQuad, RenderTexture};
+use super::post_process::{PostProcessStack, PostProcessor};
+use super::lighting::LightingRenderer;
+use super::tilemap::TilemapRenderer;
+use super::particles::ParticleRenderer;
+use super::text::{Font, FontKind, TextLayout, TextRenderer, TextStyle};
+use super::shapes::{Paint, Path, ShapeRenderer, StrokeStyle};
+use super::mesh::{MeshDraw, MeshRenderer};
+use super::picking::{PickMode, SpritePicker};
+use super::stats;
+
+/// Bound material and texture while drawing sprite runs.
+/// 绘制精灵批次时已绑定的材质和纹理。
+#[derive(Clone, Copy)]
+struct RunState {
+    material_id: u32,
+    texture_id: u32,
+    instanced: bool,
+}
+
+impl Default for RunState {
+    fn default() -> Self {
+        Self { material_id: u32::MAX, texture_id: u32::MAX, instanced: false }
+    }
+}
+
+/// Persistent drawable sorted among sprites.
+/// 在精灵之间排序的持久可绘制对象。
+#[derive(Clone, Copy)]
+enum LayerItem {
+    Tilemap(u32),
+    Particles(u32),
+    Shapes(u32),
+    Meshes(u32),
+}
 
 /// 2D renderer with batched sprite rendering.
 /// 带批处理精灵渲染的2D渲染器。
@@ -46,6 +90,50 @@ pub struct Renderer2D {
     /// 当前激活的材质ID。
     #[allow(dead_code)]
     current_material_id: u32,
+
+    /// Render textures keyed by their texture ID.
+    /// 按纹理ID索引的渲染纹理。
+    render_textures: HashMap<u32, RenderTexture>,
+
+    /// Full-screen quad for blits.
+    /// 用于纹理拷贝的全屏四边形。
+    fullscreen_quad: FullscreenQuad,
+
+    /// Post-processing targets and effect runner.
+    /// 后处理目标和效果执行器。
+    post_processor: PostProcessor,
+
+    /// Lights, occluders and light buffers.
+    /// 光源、遮挡体和光照缓冲区。
+    lighting: LightingRenderer,
+
+    /// Draw default-shader sprites with instancing.
+    /// 使用实例化绘制默认着色器的精灵。
+    instancing: bool,
+
+    /// Persistent tilemap layers.
+    /// 持久的瓦片地图层。
+    tilemaps: TilemapRenderer,
+
+    /// Particle emitters.
+    /// 粒子发射器。
+    particles: ParticleRenderer,
+
+    /// Loaded fonts.
+    /// 已加载的字体。
+    text: TextRenderer,
+
+    /// Vector shapes submitted this frame.
+    /// 本帧提交的矢量图形。
+    shapes: ShapeRenderer,
+
+    /// Meshes and this frame's mesh draws.
+    /// 网格及本帧的网格绘制。
+    meshes: MeshRenderer,
+
+    /// Pickable sprites of the last frame.
+    /// 上一帧的可拾取精灵。
+    picker: SpritePicker,
 }
 
 impl Renderer2D {
@@ -54,11 +142,18 @@ impl Renderer2D {
     ///
     /// # Arguments | 参数
     /// * `gl` - WebGL2 context | WebGL2上下文
-    /// * `max_sprites` - Maximum sprites per batch | 每批次最大精灵数
+    /// * `max_sprites` - Initial sprite capacity (grows as needed) | 初始精灵容量（按需增长）
     pub fn new(gl: &WebGl2RenderingContext, max_sprites: usize) -> Result<Self> {
         let sprite_batch = SpriteBatch::new(gl, max_sprites)?;
         let shader_manager = ShaderManager::new(gl)?;
         let material_manager = MaterialManager::new();
+        let fullscreen_quad = FullscreenQuad::new(gl)?;
+        let lighting = LightingRenderer::new(gl)?;
+        let tilemaps = TilemapRenderer::new(gl)?;
+        let particles = ParticleRenderer::new(gl)?;
+        let shapes = ShapeRenderer::new(gl)?;
+        let meshes = MeshRenderer::new(gl)?;
+        let picker = SpritePicker::new(gl)?;
 
         // Get canvas size for camera | 获取canvas尺寸用于相机
         let canvas = gl.canvas()
@@ -81,6 +176,17 @@ impl Renderer2D {
             clear_color: [0.1, 0.1, 0.12, 1.0],
             current_shader_id: 0,
             current_material_id: 0,
+            render_textures: HashMap::new(),
+            fullscreen_quad,
+            post_processor: PostProcessor::new(),
+            lighting,
+            instancing: true,
+            tilemaps,
+            particles,
+            text: TextRenderer::new(),
+            shapes,
+            meshes,
+            picker,
         })
     }
 
@@ -88,97 +194,679 @@ impl Renderer2D {
     /// 提交精灵批次数据进行渲染。
     ///
     /// # Arguments | 参数
-    /// * `transforms` - Transform data for each sprite | 每个精灵的变换数据
-    /// * `texture_ids` - Texture ID for each sprite | 每个精灵的纹理ID
-    /// * `uvs` - UV coordinates for each sprite | 每个精灵的UV坐标
-    /// * `colors` - Packed color for each sprite | 每个精灵的打包颜色
-    /// * `material_ids` - Material ID for each sprite (0 = default) | 每个精灵的材质ID（0 = 默认）
+    /// * `data` - Per-sprite transforms, textures, UVs, colors, materials and sort keys | 每个精灵的变换、纹理、UV、颜色、材质和排序键
     /// * `texture_manager` - Texture manager | 纹理管理器
     pub fn submit_batch(
         &mut self,
-        transforms: &[f32],
-        texture_ids: &[u32],
-        uvs: &[f32],
-        colors: &[u32],
-        material_ids: &[u32],
+        data: &SpriteBatchData,
         texture_manager: &TextureManager,
     ) -> Result<()> {
-        self.sprite_batch.add_sprites(
-            transforms,
-            texture_ids,
-            uvs,
-            colors,
-            material_ids,
-            texture_manager,
-        )
+        self.sprite_batch.add_sprites(data, texture_manager)
     }
 
     /// Render the current frame.
     /// 渲染当前帧。
+    ///
+    /// Draws submitted sprites with the renderer camera and clears the batch.
+    /// 使用渲染器相机绘制已提交的精灵并清空批处理。
     pub fn render(&mut self, gl: &WebGl2RenderingContext, texture_manager: &TextureManager) -> Result<()> {
-        use super::batch::BatchKey;
+        let camera = self.camera.clone();
+        self.draw_sprites(gl, texture_manager, &camera, None, i32::MIN..=i32::MAX)?;
+        self.end_frame();
+        Ok(())
+    }
 
-        if self.sprite_batch.sprite_count() == 0 {
+    /// Draw submitted sprites, tilemap layers, particles, shapes and meshes to the bound target without clearing the batch.
+    /// 将已提交的精灵、瓦片地图层、粒子、图形和网格绘制到当前绑定的目标，不清空批处理。
+    ///
+    /// Can be called several times per frame, e.g. once per render pass.
+    /// Tilemap layers, particle emitters, shapes and meshes are interleaved with sprites by sort key.
+    /// 每帧可调用多次，例如每个渲染通道一次。瓦片地图层、粒子发射器、图形和网格按排序键与精灵交错绘制。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL context | WebGL上下文
+    /// * `texture_manager` - Texture manager | 纹理管理器
+    /// * `camera` - Camera to draw with | 绘制使用的相机
+    /// * `target` - Bound render texture; sprites and items sampling it are skipped | 已绑定的渲染纹理；采样它的精灵和项会被跳过
+    /// * `layers` - Sorting layers to draw | 要绘制的排序层
+    pub fn draw_sprites(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        camera: &Camera2D,
+        target: Option<u32>,
+        layers: RangeInclusive<i32>,
+    ) -> Result<()> {
+        if self.sprite_batch.sprite_count() == 0
+            && !self.tilemaps.has_layers()
+            && !self.particles.has_emitters()
+            && !self.shapes.has_shapes()
+            && !self.meshes.has_draws()
+        {
             return Ok(());
         }
 
-        // Collect non-empty batch keys | 收集非空批次键
-        let batch_keys: Vec<BatchKey> = self.sprite_batch.batches()
-            .iter()
-            .filter(|(_, vertices)| !vertices.is_empty())
-            .map(|(key, _)| *key)
-            .collect();
+        // Get projection matrix once | 一次性获取投影矩阵
+        let projection = camera.projection_matrix();
+        let view = camera.view_rect();
+
+        // Cull and sort into draw runs | 剔除并排序为绘制批次
+        self.sprite_batch.prepare(Some(view));
+        let (submitted, culled) = (self.sprite_batch.sprite_count(), self.sprite_batch.culled_count());
+        stats::record(|stats| {
+            stats.sprites_submitted = submitted as u32;
+            stats.sprites_culled = culled as u32;
+        });
 
         // Track current state to minimize state changes | 跟踪当前状态以最小化状态切换
-        let mut current_material_id: u32 = u32::MAX;
-        let mut current_texture_id: u32 = u32::MAX;
+        let mut state = RunState::default();
 
-        // Get projection matrix once | 一次性获取投影矩阵
-        let projection = self.camera.projection_matrix();
+        let layer_range = layers;
+        let mut layers: Vec<(SortKey, LayerItem)> = self
+            .tilemaps
+            .draw_order()
+            .into_iter()
+            .map(|(key, id)| (key, LayerItem::Tilemap(id)))
+            .chain(self.particles.draw_order().into_iter().map(|(key, id)| (key, LayerItem::Particles(id))))
+            .chain(self.shapes.prepare(gl).into_iter().map(|(key, group)| (key, LayerItem::Shapes(group))))
+            .chain(self.meshes.prepare(gl).into_iter().map(|(key, group)| (key, LayerItem::Meshes(group))))
+            .collect();
+        layers.retain(|(key, _)| layer_range.contains(&key.layer));
+        layers.sort_by(|a, b| a.0.compare(&b.0));
+        let layer_count = layers.len();
+        if let Some(target) = target {
+            layers.retain(|&(_, item)| !self.layer_item_uses_texture(item, target));
+        }
+        let feedback_items = layer_count - layers.len();
+        let mut next_layer = 0;
 
-        for batch_key in batch_keys {
-            // Switch material if needed | 如需切换材质
-            if batch_key.material_id != current_material_id {
-                current_material_id = batch_key.material_id;
+        let runs = self.sprite_batch.runs().to_vec();
 
-                // Get material (fallback to default if not found) | 获取材质（未找到则回退到默认）
-                let material = self.material_manager.get_material(batch_key.material_id)
-                    .unwrap_or_else(|| self.material_manager.get_default_material());
+        // Sprites are sorted, so the layer range is one index range | 精灵已排序，因此层范围是一个索引范围
+        let drawn = runs.last().map_or(0, |r| r.start + r.count);
+        let layer_of = |i: usize| self.sprite_batch.sort_key(i).layer;
+        let first = (0..drawn).find(|&i| layer_of(i) >= *layer_range.start()).unwrap_or(drawn);
+        let last = (first..drawn).find(|&i| layer_of(i) > *layer_range.end()).unwrap_or(drawn);
 
-                // Bind shader | 绑定Shader
-                let shader = self.shader_manager.get_shader(material.shader_id)
-                    .unwrap_or_else(|| self.shader_manager.get_default_shader());
-                shader.bind(gl);
+        let mut feedback_sprites = 0;
+        for &run in &runs {
+            let start = run.start.max(first);
+            let end = (run.start + run.count).min(last);
+            if start >= end {
+                continue;
+            }
+            let mut run = BatchRun { start, count: end - start, ..run };
 
-                // Apply blend mode | 应用混合模式
-                MaterialManager::apply_blend_mode(gl, material.blend_mode);
+            // A texture cannot be sampled while it is being rendered to | 纹理不能在渲染到自身时被采样
+            let feedback = target == Some(run.key.texture_id);
+            if feedback {
+                feedback_sprites += run.count;
+            }
 
-                // Set projection matrix | 设置投影矩阵
-                shader.set_uniform_mat3(gl, "u_projection", &projection.to_cols_array());
+            // Split the run around layers that sort inside it | 在排序位于批次内的层处拆分批次
+            while let Some(&(key, item)) = layers.get(next_layer) {
+                if key.compare(&self.sprite_batch.sort_key(end - 1)) != Ordering::Less {
+                    break;
+                }
+                let split = (run.start..end)
+                    .find(|&i| key.compare(&self.sprite_batch.sort_key(i)) == Ordering::Less)
+                    .unwrap_or(end);
+                if split > run.start && !feedback {
+                    self.draw_run(gl, texture_manager, &projection, BatchRun { count: split - run.start, ..run }, &mut state);
+                }
+                run = BatchRun { start: split, count: end - split, ..run };
 
-                // Set texture sampler | 设置纹理采样器
-                shader.set_uniform_i32(gl, "u_texture", 0);
+                self.draw_layer_item(gl, texture_manager, &projection, &view, item)?;
+                state = RunState::default();
+                next_layer += 1;
+            }
 
-                // Apply material uniforms | 应用材质uniform
-                material.uniforms.apply_to_shader(gl, shader);
+            if run.count > 0 && !feedback {
+                self.draw_run(gl, texture_manager, &projection, run, &mut state);
             }
+        }
+
+        if let Some(target) = target.filter(|_| feedback_sprites + feedback_items > 0) {
+            log::warn!(
+                "Skipped {} sprites and {} tilemap/particle/mesh items sampling render texture {} while rendering to it | 跳过了{}个精灵和{}个瓦片地图/粒子/网格项，它们在渲染到渲染纹理{}时采样它",
+                feedback_sprites, feedback_items, target, feedback_sprites, feedback_items, target
+            );
+        }
+
+        // Layers above every sprite | 位于所有精灵之上的层
+        for &(_, item) in &layers[next_layer..] {
+            self.draw_layer_item(gl, texture_manager, &projection, &view, item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draw one sprite run, switching material and texture as needed.
+    /// 绘制一个精灵批次，按需切换材质和纹理。
+    fn draw_run(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        projection: &Mat3,
+        run: BatchRun,
+        state: &mut RunState,
+    ) {
+        let batch_key = run.key;
+
+        // Switch material if needed | 如需切换材质
+        if batch_key.material_id != state.material_id {
+            state.material_id = batch_key.material_id;
+            stats::record(|stats| stats.material_switches += 1);
+
+            // Get material (fallback to default if not found) | 获取材质（未找到则回退到默认）
+            let material = self.material_manager.get_material(batch_key.material_id)
+                .unwrap_or_else(|| self.material_manager.get_default_material());
+
+            // Default-shader materials use the instanced variant; custom shaders use the CPU path
+            // 默认着色器材质使用实例化变体；自定义着色器使用CPU路径
+            state.instanced = self.instancing && material.shader_id == SHADER_ID_DEFAULT_SPRITE;
+            let shader_id = if state.instanced { SHADER_ID_SPRITE_INSTANCED } else { material.shader_id };
+
+            // Bind shader | 绑定Shader
+            let shader = self.shader_manager.get_shader(shader_id)
+                .unwrap_or_else(|| self.shader_manager.get_default_shader());
+            shader.bind(gl);
+
+            // Apply blend mode | 应用混合模式
+            MaterialManager::apply_blend_mode(gl, material.blend_mode);
+
+            // Set projection matrix | 设置投影矩阵
+            shader.set_uniform_mat3(gl, "u_projection", &projection.to_cols_array());
+
+            // Set texture sampler | 设置纹理采样器
+            shader.set_uniform_i32(gl, "u_texture", 0);
+
+            // Apply material uniforms | 应用材质uniform
+            material.uniforms.apply_to_shader(gl, shader);
+        }
 
-            // Switch texture if needed | 如需切换纹理
-            if batch_key.texture_id != current_texture_id {
-                current_texture_id = batch_key.texture_id;
-                texture_manager.bind_texture(batch_key.texture_id, 0);
+        // Switch texture if needed | 如需切换纹理
+        if batch_key.texture_id != state.texture_id {
+            state.texture_id = batch_key.texture_id;
+            texture_manager.bind_texture(batch_key.texture_id, 0);
+        }
+
+        // Flush this run | 刷新此批次
+        stats::record(|stats| stats.batches += 1);
+        if state.instanced {
+            self.sprite_batch.flush_run_instanced(gl, &run);
+        } else {
+            self.sprite_batch.flush_run(gl, &run);
+        }
+    }
+
+    /// Draw a tilemap layer with the default sprite shader, an emitter or mesh group with its material, or a shape group.
+    /// 使用默认精灵着色器绘制瓦片地图层，使用材质绘制发射器或网格分组，或绘制图形分组。
+    fn draw_layer_item(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        projection: &Mat3,
+        view: &Rect,
+        item: LayerItem,
+    ) -> Result<()> {
+        match item {
+            LayerItem::Tilemap(id) => {
+                let shader = self.shader_manager.get_default_shader();
+                shader.bind(gl);
+                shader.set_uniform_i32(gl, "u_texture", 0);
+                MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+                self.tilemaps.draw_layer(gl, texture_manager, shader, projection, view, id)
+            }
+            LayerItem::Particles(id) => {
+                let material_id = self.particles.emitter(id).map_or(0, |e| e.material_id);
+                self.bind_material(gl, projection, material_id);
+                self.particles.draw_emitter(gl, texture_manager, id);
+                Ok(())
+            }
+            LayerItem::Shapes(group) => {
+                let shader = self.shader_manager.get_shader(SHADER_ID_SHAPE)
+                    .unwrap_or_else(|| self.shader_manager.get_default_shader());
+                shader.bind(gl);
+                MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+                shader.set_uniform_mat3(gl, "u_projection", &projection.to_cols_array());
+                self.shapes.draw_group(gl, group);
+                Ok(())
+            }
+            LayerItem::Meshes(group) => {
+                if let Some(batch) = self.meshes.group_batch(group) {
+                    self.bind_material(gl, projection, batch.material_id);
+                    texture_manager.bind_texture(batch.texture_id, 0);
+                    self.meshes.draw_group(gl, group);
+                }
+                Ok(())
             }
+        }
+    }
 
-            // Flush this batch | 刷新此批次
-            self.sprite_batch.flush_for_batch(gl, &batch_key);
+    /// Check whether a layer item samples a texture.
+    /// 检查层项是否采样某个纹理。
+    fn layer_item_uses_texture(&self, item: LayerItem, texture_id: u32) -> bool {
+        match item {
+            LayerItem::Tilemap(id) => self.tilemaps.layer_uses_texture(id, texture_id),
+            LayerItem::Particles(id) => self.particles.emitter(id).is_some_and(|e| e.texture_id == texture_id),
+            LayerItem::Shapes(_) => false,
+            LayerItem::Meshes(group) => self.meshes.group_batch(group).is_some_and(|b| b.texture_id == texture_id),
         }
+    }
+
+    /// Bind a material's shader, blend mode and uniforms for the CPU vertex layout.
+    /// 为CPU顶点布局绑定材质的着色器、混合模式和uniform。
+    fn bind_material(&self, gl: &WebGl2RenderingContext, projection: &Mat3, material_id: u32) {
+        let material = self.material_manager.get_material(material_id)
+            .unwrap_or_else(|| self.material_manager.get_default_material());
+        let shader = self.shader_manager.get_shader(material.shader_id)
+            .unwrap_or_else(|| self.shader_manager.get_default_shader());
+        stats::record(|stats| stats.material_switches += 1);
+        shader.bind(gl);
+        MaterialManager::apply_blend_mode(gl, material.blend_mode);
+        shader.set_uniform_mat3(gl, "u_projection", &projection.to_cols_array());
+        shader.set_uniform_i32(gl, "u_texture", 0);
+        material.uniforms.apply_to_shader(gl, shader);
+    }
 
-        // Clear batch for next frame | 清空批处理以供下一帧使用
+    /// Finish the frame, clearing submitted sprites, shapes, meshes, lights and occluders.
+    /// 结束当前帧，清空已提交的精灵、图形、网格、光源和遮挡体。
+    pub fn end_frame(&mut self) {
+        self.picker.capture(&mut self.sprite_batch);
         self.sprite_batch.clear();
+        self.shapes.clear();
+        self.meshes.clear();
+        self.lighting.clear_frame();
+    }
+
+    /// Copy a texture over the whole bound target, alpha-blended.
+    /// 将纹理以透明度混合方式拷贝到整个当前绑定的目标。
+    ///
+    /// Atlas regions and render textures are resolved like sprite textures.
+    /// 图集区域和渲染纹理的解析方式与精灵纹理相同。
+    pub fn blit(&self, gl: &WebGl2RenderingContext, texture_manager: &TextureManager, texture_id: u32) {
+        let (texture_id, uv) = texture_manager.resolve_texture(texture_id, [0.0, 0.0, 1.0, 1.0]);
 
+        let shader = match self.shader_manager.get_shader(SHADER_ID_BLIT) {
+            Some(shader) => shader,
+            None => return,
+        };
+        shader.bind(gl);
+        shader.set_uniform_i32(gl, "u_texture", 0);
+        shader.set_uniform_vec4(gl, "u_uvRect", uv);
+
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+        texture_manager.bind_texture(texture_id, 0);
+        self.fullscreen_quad.draw(gl);
+    }
+
+    // ============= Lighting =============
+    // ============= 光照 =============
+
+    /// Render the light buffer for a target of the given size.
+    /// 为指定尺寸的目标渲染光照缓冲区。
+    ///
+    /// When normal maps are assigned, submitted sprites are first drawn into
+    /// the normal buffer. Leaves the light buffer bound.
+    /// 当指定了法线贴图时，先将已提交的精灵绘制到法线缓冲区。结束时光照缓冲区保持绑定。
+    pub fn render_lighting(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        camera: &Camera2D,
+        size: (u32, u32),
+    ) -> Result<()> {
+        self.lighting.prepare(gl, size)?;
+
+        let use_normals = self.lighting.has_normal_maps() && self.sprite_batch.sprite_count() > 0;
+        if use_normals {
+            self.sprite_batch.prepare(Some(camera.view_rect()));
+            self.lighting.begin_normal_pass(gl);
+            self.draw_normals(gl, texture_manager, camera);
+        }
+
+        self.lighting.render_lights(gl, &self.shader_manager, camera, use_normals);
         Ok(())
     }
 
+    /// Multiply the light buffer over the bound target.
+    /// 将光照缓冲区与当前绑定的目标相乘。
+    pub fn composite_lighting(&self, gl: &WebGl2RenderingContext) {
+        let light_texture = match self.lighting.light_texture() {
+            Some(texture) => texture,
+            None => return,
+        };
+        let shader = match self.shader_manager.get_shader(SHADER_ID_BLIT) {
+            Some(shader) => shader,
+            None => return,
+        };
+        shader.bind(gl);
+        shader.set_uniform_i32(gl, "u_texture", 0);
+        // Render targets are stored bottom-up | 渲染目标自下而上存储
+        shader.set_uniform_vec4(gl, "u_uvRect", [0.0, 1.0, 1.0, 0.0]);
+
+        MaterialManager::apply_blend_mode(gl, BlendMode::Multiply);
+        gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+        gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(light_texture));
+        self.fullscreen_quad.draw(gl);
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+    }
+
+    /// Draw submitted sprites' normal maps into the bound normal buffer.
+    /// 将已提交精灵的法线贴图绘制到当前绑定的法线缓冲区。
+    fn draw_normals(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        camera: &Camera2D,
+    ) {
+        let shader = match self.shader_manager.get_shader(SHADER_ID_SPRITE_NORMAL) {
+            Some(shader) => shader,
+            None => return,
+        };
+        shader.bind(gl);
+        shader.set_uniform_mat3(gl, "u_projection", &camera.projection_matrix().to_cols_array());
+        shader.set_uniform_i32(gl, "u_texture", 0);
+        shader.set_uniform_i32(gl, "u_normalMap", 1);
+
+        // Normals replace, never blend | 法线直接替换，不混合
+        MaterialManager::apply_blend_mode(gl, BlendMode::None);
+
+        let runs = self.sprite_batch.runs().to_vec();
+        for run in &runs {
+            texture_manager.bind_texture(run.key.texture_id, 0);
+            match self.lighting.normal_map(run.key.texture_id) {
+                Some(normal_id) => {
+                    texture_manager.bind_texture(normal_id, 1);
+                    shader.set_uniform_f32(gl, "u_hasNormalMap", 1.0);
+                }
+                None => shader.set_uniform_f32(gl, "u_hasNormalMap", 0.0),
+            }
+            self.sprite_batch.flush_run(gl, run);
+        }
+
+        gl.active_texture(WebGl2RenderingContext::TEXTURE0);
+        MaterialManager::apply_blend_mode(gl, BlendMode::Alpha);
+    }
+
+    /// Get lighting renderer reference.
+    /// 获取光照渲染器引用。
+    #[inline]
+    pub fn lighting(&self) -> &LightingRenderer {
+        &self.lighting
+    }
+
+    /// Get mutable lighting renderer reference.
+    /// 获取可变光照渲染器引用。
+    #[inline]
+    pub fn lighting_mut(&mut self) -> &mut LightingRenderer {
+        &mut self.lighting
+    }
+
+    /// Enable or disable culling of sprites outside the camera view.
+    /// 启用或禁用相机视图外精灵的剔除。
+    pub fn set_sprite_culling(&mut self, enabled: bool) {
+        self.sprite_batch.set_culling(enabled);
+    }
+
+    /// Sprites drawn and culled by the last draw.
+    /// 上次绘制中绘制和剔除的精灵数。
+    pub fn sprite_cull_stats(&self) -> (usize, usize) {
+        (self.sprite_batch.drawn_count(), self.sprite_batch.culled_count())
+    }
+
+    /// Set how screen-space picks are resolved.
+    /// 设置屏幕空间拾取的解析方式。
+    ///
+    /// # Arguments | 参数
+    /// * `mode` - CPU quad tests or GPU id buffer | CPU四边形测试或GPU ID缓冲区
+    /// * `alpha_threshold` - Minimum texture alpha hit in GPU mode | GPU模式下命中的最小纹理alpha
+    pub fn set_pick_mode(&mut self, mode: PickMode, alpha_threshold: f32) {
+        self.picker.set_mode(mode);
+        self.picker.set_alpha_threshold(alpha_threshold);
+    }
+
+    /// IDs of pickable sprites under a screen position, in draw order.
+    /// 屏幕位置下可拾取精灵的ID，按绘制顺序。
+    ///
+    /// Tests the sprites of the last finished frame against the renderer camera.
+    /// 使用渲染器相机测试上一已完成帧的精灵。
+    pub fn pick(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        screen: Vec2,
+    ) -> Result<Vec<u32>> {
+        match self.picker.mode() {
+            PickMode::Cpu => Ok(self.picker.pick_point(self.camera.screen_to_world(screen))),
+            PickMode::Gpu => self.pick_gpu(gl, texture_manager, [screen.x, screen.y, screen.x, screen.y]),
+        }
+    }
+
+    /// IDs of pickable sprites overlapping a screen rectangle, in draw order.
+    /// 与屏幕矩形重叠的可拾取精灵ID，按绘制顺序。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `texture_manager` - Texture manager | 纹理管理器
+    /// * `a` - One corner in screen pixels | 屏幕像素坐标的一个角
+    /// * `b` - Opposite corner in screen pixels | 屏幕像素坐标的对角
+    pub fn pick_rect(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        a: Vec2,
+        b: Vec2,
+    ) -> Result<Vec<u32>> {
+        match self.picker.mode() {
+            PickMode::Cpu => {
+                // A rotated camera turns the screen rectangle into a rotated quad | 旋转的相机使屏幕矩形变为旋转四边形
+                let corners = [a, Vec2::new(b.x, a.y), b, Vec2::new(a.x, b.y)]
+                    .map(|corner| self.camera.screen_to_world(corner));
+                Ok(self.picker.pick_area(&corners))
+            }
+            PickMode::Gpu => self.pick_gpu(gl, texture_manager, [a.x, a.y, b.x, b.y]),
+        }
+    }
+
+    fn pick_gpu(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        area: [f32; 4],
+    ) -> Result<Vec<u32>> {
+        let shader = self
+            .shader_manager
+            .get_shader(SHADER_ID_PICK)
+            .ok_or_else(|| EngineError::WebGLError("Picking shader missing".into()))?;
+        self.picker.pick_gpu(gl, texture_manager, shader, &self.camera, area)
+    }
+
+    /// IDs of pickable sprites containing a world position, in draw order.
+    /// 包含世界位置的可拾取精灵ID，按绘制顺序。
+    pub fn pick_world(&self, point: Vec2) -> Vec<u32> {
+        self.picker.pick_point(point)
+    }
+
+    /// IDs of pickable sprites overlapping a world rectangle, in draw order.
+    /// 与世界矩形重叠的可拾取精灵ID，按绘制顺序。
+    pub fn pick_world_rect(&self, rect: &Rect) -> Vec<u32> {
+        self.picker.pick_rect(rect)
+    }
+
+    /// Set nine-slice borders for a texture, or remove them with `None`.
+    /// 为纹理设置九宫格边框，`None`表示移除。
+    pub fn set_nine_slice(&mut self, texture_id: u32, slice: Option<NineSlice>) {
+        self.sprite_batch.set_nine_slice(texture_id, slice);
+    }
+
+    /// Get the tilemap renderer.
+    /// 获取瓦片地图渲染器。
+    #[inline]
+    pub fn tilemaps(&self) -> &TilemapRenderer {
+        &self.tilemaps
+    }
+
+    /// Get the mutable tilemap renderer.
+    /// 获取可变瓦片地图渲染器。
+    #[inline]
+    pub fn tilemaps_mut(&mut self) -> &mut TilemapRenderer {
+        &mut self.tilemaps
+    }
+
+    /// Get the particle renderer.
+    /// 获取粒子渲染器。
+    #[inline]
+    pub fn particles(&self) -> &ParticleRenderer {
+        &self.particles
+    }
+
+    /// Get the mutable particle renderer.
+    /// 获取可变粒子渲染器。
+    #[inline]
+    pub fn particles_mut(&mut self) -> &mut ParticleRenderer {
+        &mut self.particles
+    }
+
+    /// Load a font, returning its ID.
+    /// 加载字体，返回其ID。
+    ///
+    /// Distance-field fonts get their own material using the SDF text shader.
+    /// 距离场字体使用SDF文本着色器的专用材质。
+    ///
+    /// # Arguments | 参数
+    /// * `font` - Parsed font | 已解析的字体
+    /// * `page_textures` - Texture ID for each font page | 每个字体页面的纹理ID
+    pub fn load_font(&mut self, font: Font, page_textures: Vec<u32>) -> Result<u32> {
+        let material_id = if font.kind.is_distance_field() {
+            let mut material = Material::with_shader("SdfText", SHADER_ID_SDF_TEXT);
+            material
+                .set_float("u_distanceRange", font.distance_range)
+                .set_float("u_msdf", if font.kind == FontKind::Msdf { 1.0 } else { 0.0 });
+            self.material_manager.register_material(material)
+        } else {
+            0
+        };
+
+        self.text.add_font(font, page_textures, material_id).inspect_err(|_| {
+            if material_id != 0 {
+                self.material_manager.remove_material(material_id);
+            }
+        })
+    }
+
+    /// Remove a font and its material.
+    /// 移除字体及其材质。
+    pub fn remove_font(&mut self, font_id: u32) -> bool {
+        match self.text.remove_font(font_id) {
+            Some(material_id) => {
+                if material_id != 0 {
+                    self.material_manager.remove_material(material_id);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Lay out a string and submit its glyphs as sprites.
+    /// 布局字符串并将其字形作为精灵提交。
+    ///
+    /// # Arguments | 参数
+    /// * `font_id` - Font ID | 字体ID
+    /// * `text` - String to draw | 要绘制的字符串
+    /// * `position` - Anchor at the top of the first line | 第一行顶部的锚点
+    /// * `style` - Size, color, wrapping and alignment | 尺寸、颜色、换行和对齐
+    /// * `texture_manager` - Texture manager | 纹理管理器
+    pub fn submit_text(
+        &mut self,
+        font_id: u32,
+        text: &str,
+        position: glam::Vec2,
+        style: &TextStyle,
+        texture_manager: &TextureManager,
+    ) -> Result<()> {
+        let data = self.text.build(font_id, text, position, style)?;
+        self.sprite_batch.add_sprites(&data, texture_manager)
+    }
+
+    /// Lay out a string without drawing it.
+    /// 布局字符串但不绘制。
+    pub fn measure_text(&self, font_id: u32, text: &str, style: &TextStyle) -> Result<TextLayout> {
+        self.text.measure(font_id, text, style)
+    }
+
+    /// Size of one screen pixel in world units at the current zoom.
+    /// 当前缩放下一个屏幕像素对应的世界单位尺寸。
+    #[inline]
+    pub fn pixel_size(&self) -> f32 {
+        1.0 / self.camera.zoom.max(f32::EPSILON)
+    }
+
+    /// Fill a path for this frame, anti-aliased over one screen pixel.
+    /// 为本帧填充路径，在一个屏幕像素内抗锯齿。
+    pub fn fill_shape(&mut self, path: &Path, paint: &Paint, sort: SortKey) {
+        let aa = self.pixel_size();
+        self.shapes.fill(path, paint, sort, aa);
+    }
+
+    /// Stroke a path for this frame, anti-aliased over one screen pixel.
+    /// 为本帧描边路径，在一个屏幕像素内抗锯齿。
+    pub fn stroke_shape(&mut self, path: &Path, style: &StrokeStyle, paint: &Paint, sort: SortKey) {
+        let aa = self.pixel_size();
+        self.shapes.stroke(path, style, paint, sort, aa);
+    }
+
+    /// Draw a mesh this frame.
+    /// 在本帧绘制网格。
+    pub fn submit_mesh(
+        &mut self,
+        mesh_id: u32,
+        draw: &MeshDraw,
+        palette: &[glam::Affine2],
+        texture_manager: &TextureManager,
+    ) -> Result<()> {
+        self.meshes.submit(mesh_id, draw, palette, texture_manager)
+    }
+
+    /// Get the mesh renderer.
+    /// 获取网格渲染器。
+    #[inline]
+    pub fn meshes(&self) -> &MeshRenderer {
+        &self.meshes
+    }
+
+    /// Get the mutable mesh renderer.
+    /// 获取可变网格渲染器。
+    #[inline]
+    pub fn meshes_mut(&mut self) -> &mut MeshRenderer {
+        &mut self.meshes
+    }
+
+    /// Get the text renderer.
+    /// 获取文本渲染器。
+    #[inline]
+    pub fn text(&self) -> &TextRenderer {
+        &self.text
+    }
+
+    /// Enable or disable instanced sprite rendering.
+    /// 启用或禁用实例化精灵渲染。
+    ///
+    /// When disabled, every sprite takes the CPU-transformed vertex path.
+    /// 禁用时，所有精灵都使用CPU变换顶点路径。
+    pub fn set_instancing(&mut self, enabled: bool) {
+        self.instancing = enabled;
+    }
+
+    /// Check whether instanced sprite rendering is enabled.
+    /// 检查是否启用了实例化精灵渲染。
+    #[inline]
+    pub fn instancing(&self) -> bool {
+        self.instancing
+    }
+
     /// Get mutable reference to camera.
     /// 获取相机的可变引用。
     #[inline]
@@ -211,6 +899,133 @@ impl Renderer2D {
         self.camera.set_viewport(width, height);
     }
 
+    // ============= Post Processing =============
+    // ============= 后处理 =============
+
+    /// Start capturing the scene for post-processing.
+    /// 开始捕获场景用于后处理。
+    pub fn begin_post_process(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        surface: (u32, u32),
+        clear_color: [f32; 4],
+    ) -> Result<()> {
+        self.post_processor.begin(gl, surface, clear_color)
+    }
+
+    /// Bind the post-processing scene capture target.
+    /// 绑定后处理场景捕获目标。
+    pub fn bind_post_process_target(&self, gl: &WebGl2RenderingContext) {
+        self.post_processor.bind_scene(gl);
+    }
+
+    /// Apply a post-processing stack to the captured scene and output to the screen.
+    /// 对捕获的场景应用后处理栈并输出到屏幕。
+    pub fn apply_post_process(
+        &self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        stack: &PostProcessStack,
+    ) {
+        self.post_processor.apply(
+            gl,
+            &self.shader_manager,
+            &self.fullscreen_quad,
+            texture_manager,
+            stack,
+        );
+    }
+
+    // ============= Render Textures =============
+    // ============= 渲染纹理 =============
+
+    /// Create a render texture, returning its texture ID.
+    /// 创建渲染纹理，返回其纹理ID。
+    ///
+    /// The ID can be used as a sprite texture once something is rendered into it.
+    /// 渲染内容后，该ID可作为精灵纹理使用。
+    pub fn create_render_texture(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &mut TextureManager,
+        width: u32,
+        height: u32,
+    ) -> Result<u32> {
+        let render_texture = RenderTexture::new(gl, width, height)?;
+        let (width, height) = render_texture.dimensions();
+        let texture = Texture::render_target(render_texture.texture().clone(), width, height);
+        let id = texture_manager.register_texture(texture);
+        self.render_textures.insert(id, render_texture);
+        Ok(id)
+    }
+
+    /// Resize a render texture. Contents are discarded.
+    /// 调整渲染纹理大小。内容会被丢弃。
+    pub fn resize_render_texture(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &mut TextureManager,
+        id: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<bool> {
+        let render_texture = match self.render_textures.get_mut(&id) {
+            Some(rt) => rt,
+            None => return Ok(false),
+        };
+        render_texture.resize(gl, width, height)?;
+        let (width, height) = render_texture.dimensions();
+        texture_manager.set_texture_size(id, width, height);
+        Ok(true)
+    }
+
+    /// Remove a render texture and its texture.
+    /// 移除渲染纹理及其纹理。
+    pub fn remove_render_texture(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &mut TextureManager,
+        id: u32,
+    ) -> bool {
+        match self.render_textures.remove(&id) {
+            Some(render_texture) => {
+                render_texture.delete(gl);
+                texture_manager.remove_texture(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all render textures.
+    /// 移除所有渲染纹理。
+    ///
+    /// Their textures are expected to be cleared from the texture manager separately.
+    /// 其纹理应由纹理管理器单独清除。
+    pub fn clear_render_textures(&mut self, gl: &WebGl2RenderingContext) {
+        for (_, render_texture) in self.render_textures.drain() {
+            render_texture.delete(gl);
+        }
+    }
+
+    /// Get a render texture.
+    /// 获取渲染纹理。
+    pub fn render_texture(&self, id: u32) -> Option<&RenderTexture> {
+        self.render_textures.get(&id)
+    }
+
+    /// Get a mutable render texture.
+    /// 获取可变渲染纹理。
+    pub fn render_texture_mut(&mut self, id: u32) -> Option<&mut RenderTexture> {
+        self.render_textures.get_mut(&id)
+    }
+
+    /// Check if a render texture exists.
+    /// 检查渲染纹理是否存在。
+    pub fn has_render_texture(&self, id: u32) -> bool {
+        self.render_textures.contains_key(&id)
+    }
+
     // ============= Shader Management =============
     // ============= 着色器管理 =============
 
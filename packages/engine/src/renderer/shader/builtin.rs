@@ -32,6 +32,50 @@ void main() {
 }
 "#;
 
+/// Instanced sprite vertex shader source.
+/// 实例化精灵顶点着色器源代码。
+///
+/// Expands a unit quad per instance; pairs with `SPRITE_FRAGMENT_SHADER`.
+/// Attribute layout matches `SpriteInstance`.
+/// 为每个实例展开单位四边形；与`SPRITE_FRAGMENT_SHADER`配合使用。属性布局与`SpriteInstance`一致。
+pub const SPRITE_INSTANCED_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+// Per-vertex unit quad corner (0-1) | 每顶点单位四边形角点（0-1）
+layout(location = 0) in vec2 a_corner;
+
+// Per-instance attributes | 每实例属性
+layout(location = 1) in vec2 a_position;
+layout(location = 2) in float a_rotation;
+layout(location = 3) in vec2 a_size;
+layout(location = 4) in vec2 a_origin;
+layout(location = 5) in vec4 a_uvRect;
+layout(location = 6) in vec4 a_color;
+
+uniform mat3 u_projection;
+
+out vec2 v_texCoord;
+out vec4 v_color;
+
+void main() {
+    // Local corner relative to origin (Y-up) | 相对于原点的局部角点（Y向上）
+    vec2 local = (a_corner - a_origin) * a_size;
+    float c = cos(a_rotation);
+    float s = sin(a_rotation);
+    vec2 world = vec2(local.x * c - local.y * s, local.x * s + local.y * c) + a_position;
+
+    vec3 pos = u_projection * vec3(world, 1.0);
+    gl_Position = vec4(pos.xy, 0.0, 1.0);
+
+    // v0 is the top of the image | v0为图像顶部
+    v_texCoord = vec2(
+        mix(a_uvRect.x, a_uvRect.z, a_corner.x),
+        mix(a_uvRect.w, a_uvRect.y, a_corner.y)
+    );
+    v_color = a_color;
+}
+"#;
+
 /// Sprite fragment shader source.
 /// 精灵片段着色器源代码。
 ///
@@ -61,3 +105,90 @@ void main() {
     }
 }
 "#;
+
+/// Vector shape fragment shader source.
+/// 矢量图形片段着色器源代码。
+///
+/// Used with `SPRITE_VERTEX_SHADER`; outputs the vertex color, whose alpha
+/// carries the anti-aliasing coverage.
+/// 与`SPRITE_VERTEX_SHADER`配合使用；输出顶点颜色，其alpha包含抗锯齿覆盖率。
+pub const SHAPE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+in vec4 v_color;
+
+out vec4 fragColor;
+
+void main() {
+    fragColor = v_color;
+}
+"#;
+
+/// Picking id-buffer fragment shader source.
+/// 拾取ID缓冲区片段着色器源代码。
+///
+/// Used with `SPRITE_VERTEX_SHADER`; writes the vertex color, which encodes
+/// the sprite's picking ID, where the texture alpha reaches `u_alphaThreshold`.
+/// 与`SPRITE_VERTEX_SHADER`配合使用；在纹理alpha达到`u_alphaThreshold`处写入编码了
+/// 精灵拾取ID的顶点颜色。
+pub const PICK_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+in vec4 v_color;
+
+uniform sampler2D u_texture;
+uniform float u_alphaThreshold;
+
+out vec4 fragColor;
+
+void main() {
+    // Skip transparent texels | 跳过透明纹素
+    if (texture(u_texture, v_texCoord).a < u_alphaThreshold) {
+        discard;
+    }
+    fragColor = v_color;
+}
+"#;
+
+/// Full-screen quad vertex shader source.
+/// 全屏四边形顶点着色器源代码。
+///
+/// Expects clip-space positions in [-1, 1] and derives texture coordinates from them.
+/// 输入为[-1, 1]范围的裁剪空间位置，并由此推导纹理坐标。
+pub const FULLSCREEN_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+layout(location = 0) in vec2 a_position;
+
+out vec2 v_texCoord;
+
+void main() {
+    v_texCoord = a_position * 0.5 + 0.5;
+    gl_Position = vec4(a_position, 0.0, 1.0);
+}
+"#;
+
+/// Blit fragment shader source.
+/// 纹理拷贝片段着色器源代码。
+///
+/// Copies a UV rect of a texture onto the current target. `u_uvRect` is
+/// [u0, v0, u1, v1] with v0 at the top of the image, as for sprites.
+/// 将纹理的UV矩形复制到当前目标。`u_uvRect`为[u0, v0, u1, v1]，
+/// 与精灵相同，v0对应图像顶部。
+pub const BLIT_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform vec4 u_uvRect;
+
+out vec4 fragColor;
+
+void main() {
+    vec2 uv = mix(u_uvRect.xy, u_uvRect.zw, vec2(v_texCoord.x, 1.0 - v_texCoord.y));
+    fragColor = texture(u_texture, uv);
+}
+"#;
@@ -0,0 +1,156 @@
+//! Built-in 2D lighting shader sources.
+//! 内置2D光照着色器源代码。
+
+/// Maximum occluder segments tested per light.
+/// 每个光源检测的最大遮挡线段数。
+pub const MAX_SHADOW_SEGMENTS: usize = 64;
+
+/// Light volume vertex shader source.
+/// 光照体积顶点着色器源代码。
+///
+/// Draws a world-space quad around each light.
+/// 在每个光源周围绘制世界空间四边形。
+pub const LIGHT_VERTEX_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+layout(location = 0) in vec2 a_position;
+
+uniform mat3 u_projection;
+
+out vec2 v_worldPos;
+
+void main() {
+    v_worldPos = a_position;
+    vec3 pos = u_projection * vec3(a_position, 1.0);
+    gl_Position = vec4(pos.xy, 0.0, 1.0);
+}
+"#;
+
+/// Light fragment shader source.
+/// 光照片段着色器源代码。
+///
+/// Quadratic falloff, spot cone, optional normal mapping and soft shadows
+/// ray-cast against `u_segments` from several points across the light.
+/// 二次衰减、聚光锥、可选法线贴图，以及从光源上多个采样点对`u_segments`进行光线投射的软阴影。
+pub const LIGHT_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+#define MAX_SEGMENTS 64
+
+in vec2 v_worldPos;
+
+uniform vec2 u_lightPos;
+uniform float u_lightHeight;
+uniform vec3 u_lightColor;
+uniform float u_intensity;
+uniform float u_radius;
+uniform float u_isSpot;
+uniform vec2 u_direction;
+uniform float u_cosOuter;
+uniform float u_cosInner;
+
+uniform sampler2D u_normalBuffer;
+uniform float u_useNormals;
+uniform vec2 u_resolution;
+
+uniform vec4 u_segments[MAX_SEGMENTS];
+uniform int u_segmentCount;
+uniform float u_lightSize;
+
+out vec4 fragColor;
+
+bool crosses(vec2 p0, vec2 p1, vec2 q0, vec2 q1) {
+    vec2 r = p1 - p0;
+    vec2 s = q1 - q0;
+    float denom = r.x * s.y - r.y * s.x;
+    if (abs(denom) < 1e-6) {
+        return false;
+    }
+    vec2 d = q0 - p0;
+    float t = (d.x * s.y - d.y * s.x) / denom;
+    float u = (d.x * r.y - d.y * r.x) / denom;
+    return t > 0.0 && t < 1.0 && u >= 0.0 && u <= 1.0;
+}
+
+float visibility(vec2 from, vec2 to) {
+    for (int i = 0; i < MAX_SEGMENTS; i++) {
+        if (i >= u_segmentCount) {
+            break;
+        }
+        if (crosses(from, to, u_segments[i].xy, u_segments[i].zw)) {
+            return 0.0;
+        }
+    }
+    return 1.0;
+}
+
+void main() {
+    vec2 toFrag = v_worldPos - u_lightPos;
+    float dist = length(toFrag);
+    if (dist >= u_radius) {
+        discard;
+    }
+
+    float falloff = 1.0 - dist / u_radius;
+    float attenuation = falloff * falloff;
+
+    // Spot cone | 聚光锥
+    if (u_isSpot > 0.5 && dist > 0.0) {
+        attenuation *= smoothstep(u_cosOuter, u_cosInner, dot(toFrag / dist, u_direction));
+    }
+
+    // Normal mapping (alpha marks pixels that have a normal map) | 法线贴图（alpha标记有法线贴图的像素）
+    if (u_useNormals > 0.5) {
+        vec4 normalSample = texture(u_normalBuffer, gl_FragCoord.xy / u_resolution);
+        vec3 normal = normalize(normalSample.xyz * 2.0 - 1.0);
+        vec3 toLight = normalize(vec3(-toFrag, u_lightHeight));
+        attenuation *= mix(1.0, max(dot(normal, toLight), 0.0), normalSample.a);
+    }
+
+    // Soft shadows: average visibility from points across the light | 软阴影：对光源上多个点的可见性取平均
+    if (u_segmentCount > 0 && attenuation > 0.0) {
+        vec2 side = dist > 0.0 ? vec2(-toFrag.y, toFrag.x) / dist * u_lightSize : vec2(0.0);
+        float visible = 0.0;
+        for (int k = -2; k <= 2; k++) {
+            visible += visibility(u_lightPos + side * (float(k) * 0.5), v_worldPos);
+        }
+        attenuation *= visible / 5.0;
+    }
+
+    fragColor = vec4(u_lightColor * u_intensity * attenuation, 1.0);
+}
+"#;
+
+/// Sprite normal pass fragment shader source.
+/// 精灵法线通道片段着色器源代码。
+///
+/// Used with `SPRITE_VERTEX_SHADER`. Writes the normal map (`u_normalMap`)
+/// where the sprite is opaque; sprites without a normal map write alpha 0
+/// so lights skip normal shading there.
+/// 与`SPRITE_VERTEX_SHADER`配合使用。在精灵不透明处写入法线贴图（`u_normalMap`）；
+/// 没有法线贴图的精灵写入alpha 0，使光照在该处跳过法线着色。
+pub const SPRITE_NORMAL_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+in vec4 v_color;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_normalMap;
+uniform float u_hasNormalMap;
+
+out vec4 fragColor;
+
+void main() {
+    float alpha = texture(u_texture, v_texCoord).a * v_color.a;
+    if (alpha < 0.5) {
+        discard;
+    }
+
+    if (u_hasNormalMap > 0.5) {
+        fragColor = vec4(texture(u_normalMap, v_texCoord).rgb, 1.0);
+    } else {
+        fragColor = vec4(0.5, 0.5, 1.0, 0.0);
+    }
+}
+"#;
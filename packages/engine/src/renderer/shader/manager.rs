@@ -6,12 +6,79 @@ use web_sys::WebGl2RenderingContext;
 
 use crate::core::error::Result;
 use super::program::ShaderProgram;
-use super::builtin::{SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER};
+use super::builtin::{
+    SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER, SPRITE_INSTANCED_VERTEX_SHADER,
+    FULLSCREEN_VERTEX_SHADER, BLIT_FRAGMENT_SHADER, SHAPE_FRAGMENT_SHADER,
+    PICK_FRAGMENT_SHADER,
+};
+use super::post::{
+    BLOOM_EXTRACT_FRAGMENT_SHADER, BLUR_FRAGMENT_SHADER, BLOOM_COMPOSITE_FRAGMENT_SHADER,
+    COLOR_GRADING_FRAGMENT_SHADER, VIGNETTE_FRAGMENT_SHADER, PIXELATE_FRAGMENT_SHADER,
+    CRT_FRAGMENT_SHADER,
+};
+use super::lighting::{LIGHT_VERTEX_SHADER, LIGHT_FRAGMENT_SHADER, SPRITE_NORMAL_FRAGMENT_SHADER};
+use super::text::SDF_TEXT_FRAGMENT_SHADER;
 
 /// Reserved shader IDs for built-in shaders.
 /// 内置着色器的保留ID。
 pub const SHADER_ID_DEFAULT_SPRITE: u32 = 0;
 
+/// Built-in full-screen texture copy shader.
+/// 内置全屏纹理拷贝着色器。
+pub const SHADER_ID_BLIT: u32 = 1;
+
+/// Built-in bloom bright-pass shader.
+/// 内置泛光亮部提取着色器。
+pub const SHADER_ID_BLOOM_EXTRACT: u32 = 2;
+
+/// Built-in separable blur shader.
+/// 内置可分离模糊着色器。
+pub const SHADER_ID_BLUR: u32 = 3;
+
+/// Built-in bloom composite shader.
+/// 内置泛光合成着色器。
+pub const SHADER_ID_BLOOM_COMPOSITE: u32 = 4;
+
+/// Built-in color grading shader.
+/// 内置颜色分级着色器。
+pub const SHADER_ID_COLOR_GRADING: u32 = 5;
+
+/// Built-in vignette shader.
+/// 内置暗角着色器。
+pub const SHADER_ID_VIGNETTE: u32 = 6;
+
+/// Built-in pixelation shader.
+/// 内置像素化着色器。
+pub const SHADER_ID_PIXELATE: u32 = 7;
+
+/// Built-in CRT shader.
+/// 内置CRT着色器。
+pub const SHADER_ID_CRT: u32 = 8;
+
+/// Built-in 2D light shader.
+/// 内置2D光照着色器。
+pub const SHADER_ID_LIGHT: u32 = 9;
+
+/// Built-in sprite normal pass shader.
+/// 内置精灵法线通道着色器。
+pub const SHADER_ID_SPRITE_NORMAL: u32 = 10;
+
+/// Built-in instanced sprite shader.
+/// 内置实例化精灵着色器。
+pub const SHADER_ID_SPRITE_INSTANCED: u32 = 11;
+
+/// Built-in SDF/MSDF text shader.
+/// 内置SDF/MSDF文本着色器。
+pub const SHADER_ID_SDF_TEXT: u32 = 12;
+
+/// Built-in vector shape shader.
+/// 内置矢量图形着色器。
+pub const SHADER_ID_SHAPE: u32 = 13;
+
+/// Built-in picking id-buffer shader.
+/// 内置拾取ID缓冲区着色器。
+pub const SHADER_ID_PICK: u32 = 14;
+
 /// Shader manager for compiling and caching shader programs.
 /// 着色器管理器，用于编译和缓存着色器程序。
 ///
@@ -44,13 +111,31 @@ impl ShaderManager {
             shader_sources: HashMap::new(),
         };
 
-        // Compile built-in sprite shader | 编译内置精灵着色器
-        let default_shader = ShaderProgram::new(gl, SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER)?;
-        manager.shaders.insert(SHADER_ID_DEFAULT_SPRITE, default_shader);
-        manager.shader_sources.insert(
-            SHADER_ID_DEFAULT_SPRITE,
-            (SPRITE_VERTEX_SHADER.to_string(), SPRITE_FRAGMENT_SHADER.to_string()),
-        );
+        // Compile built-in shaders | 编译内置着色器
+        for (shader_id, vertex_source, fragment_source) in [
+            (SHADER_ID_DEFAULT_SPRITE, SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER),
+            (SHADER_ID_BLIT, FULLSCREEN_VERTEX_SHADER, BLIT_FRAGMENT_SHADER),
+            (SHADER_ID_BLOOM_EXTRACT, FULLSCREEN_VERTEX_SHADER, BLOOM_EXTRACT_FRAGMENT_SHADER),
+            (SHADER_ID_BLUR, FULLSCREEN_VERTEX_SHADER, BLUR_FRAGMENT_SHADER),
+            (SHADER_ID_BLOOM_COMPOSITE, FULLSCREEN_VERTEX_SHADER, BLOOM_COMPOSITE_FRAGMENT_SHADER),
+            (SHADER_ID_COLOR_GRADING, FULLSCREEN_VERTEX_SHADER, COLOR_GRADING_FRAGMENT_SHADER),
+            (SHADER_ID_VIGNETTE, FULLSCREEN_VERTEX_SHADER, VIGNETTE_FRAGMENT_SHADER),
+            (SHADER_ID_PIXELATE, FULLSCREEN_VERTEX_SHADER, PIXELATE_FRAGMENT_SHADER),
+            (SHADER_ID_CRT, FULLSCREEN_VERTEX_SHADER, CRT_FRAGMENT_SHADER),
+            (SHADER_ID_LIGHT, LIGHT_VERTEX_SHADER, LIGHT_FRAGMENT_SHADER),
+            (SHADER_ID_SPRITE_NORMAL, SPRITE_VERTEX_SHADER, SPRITE_NORMAL_FRAGMENT_SHADER),
+            (SHADER_ID_SPRITE_INSTANCED, SPRITE_INSTANCED_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER),
+            (SHADER_ID_SDF_TEXT, SPRITE_VERTEX_SHADER, SDF_TEXT_FRAGMENT_SHADER),
+            (SHADER_ID_SHAPE, SPRITE_VERTEX_SHADER, SHAPE_FRAGMENT_SHADER),
+            (SHADER_ID_PICK, SPRITE_VERTEX_SHADER, PICK_FRAGMENT_SHADER),
+        ] {
+            let shader = ShaderProgram::new(gl, vertex_source, fragment_source)?;
+            manager.shaders.insert(shader_id, shader);
+            manager.shader_sources.insert(
+                shader_id,
+                (vertex_source.to_string(), fragment_source.to_string()),
+            );
+        }
 
         log::info!("ShaderManager initialized with {} built-in shaders | 着色器管理器初始化完成，内置着色器数量: {}",
             manager.shaders.len(), manager.shaders.len());
@@ -3,8 +3,31 @@
 
 mod program;
 mod builtin;
+mod post;
+mod lighting;
+mod text;
 mod manager;
 
 pub use program::ShaderProgram;
-pub use builtin::{SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER};
-pub use manager::{ShaderManager, SHADER_ID_DEFAULT_SPRITE};
+pub use builtin::{
+    SPRITE_VERTEX_SHADER, SPRITE_FRAGMENT_SHADER, SPRITE_INSTANCED_VERTEX_SHADER,
+    FULLSCREEN_VERTEX_SHADER, BLIT_FRAGMENT_SHADER, SHAPE_FRAGMENT_SHADER,
+    PICK_FRAGMENT_SHADER,
+};
+pub use post::{
+    BLOOM_EXTRACT_FRAGMENT_SHADER, BLUR_FRAGMENT_SHADER, BLOOM_COMPOSITE_FRAGMENT_SHADER,
+    COLOR_GRADING_FRAGMENT_SHADER, VIGNETTE_FRAGMENT_SHADER, PIXELATE_FRAGMENT_SHADER,
+    CRT_FRAGMENT_SHADER,
+};
+pub use lighting::{
+    LIGHT_VERTEX_SHADER, LIGHT_FRAGMENT_SHADER, SPRITE_NORMAL_FRAGMENT_SHADER,
+    MAX_SHADOW_SEGMENTS,
+};
+pub use text::SDF_TEXT_FRAGMENT_SHADER;
+pub use manager::{
+    ShaderManager, SHADER_ID_DEFAULT_SPRITE, SHADER_ID_BLIT,
+    SHADER_ID_BLOOM_EXTRACT, SHADER_ID_BLUR, SHADER_ID_BLOOM_COMPOSITE,
+    SHADER_ID_COLOR_GRADING, SHADER_ID_VIGNETTE, SHADER_ID_PIXELATE, SHADER_ID_CRT,
+    SHADER_ID_LIGHT, SHADER_ID_SPRITE_NORMAL, SHADER_ID_SPRITE_INSTANCED,
+    SHADER_ID_SDF_TEXT, SHADER_ID_SHAPE, SHADER_ID_PICK,
+};
@@ -0,0 +1,232 @@
+//! Built-in post-processing shader sources.
+//! 内置后处理着色器源代码。
+//!
+//! All post-processing shaders run on a full-screen quad with
+//! `FULLSCREEN_VERTEX_SHADER` and read the previous result from `u_texture`.
+//! 所有后处理着色器都使用`FULLSCREEN_VERTEX_SHADER`绘制全屏四边形，
+//! 并从`u_texture`读取上一步的结果。
+
+/// Bloom bright-pass fragment shader source.
+/// 泛光亮部提取片段着色器源代码。
+///
+/// Keeps pixels brighter than `u_threshold`, with a soft knee.
+/// 保留亮度高于`u_threshold`的像素，带柔和过渡。
+pub const BLOOM_EXTRACT_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform float u_threshold;
+uniform float u_softKnee;
+
+out vec4 fragColor;
+
+void main() {
+    vec4 color = texture(u_texture, v_texCoord);
+    float brightness = max(color.r, max(color.g, color.b));
+
+    // Soft threshold curve | 柔和阈值曲线
+    float knee = u_threshold * u_softKnee + 1e-5;
+    float soft = clamp(brightness - u_threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee);
+    float contribution = max(soft, brightness - u_threshold) / max(brightness, 1e-5);
+
+    fragColor = vec4(color.rgb * contribution, 1.0);
+}
+"#;
+
+/// Separable Gaussian blur fragment shader source.
+/// 可分离高斯模糊片段着色器源代码。
+///
+/// `u_direction` is one texel along the blur axis; `u_radius` scales the taps.
+/// `u_direction`为沿模糊方向的一个纹素；`u_radius`缩放采样间距。
+pub const BLUR_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform vec2 u_direction;
+uniform float u_radius;
+
+out vec4 fragColor;
+
+const float weights[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec2 stepSize = u_direction * u_radius;
+    vec3 result = texture(u_texture, v_texCoord).rgb * weights[0];
+    for (int i = 1; i < 5; i++) {
+        vec2 offset = stepSize * float(i);
+        result += texture(u_texture, v_texCoord + offset).rgb * weights[i];
+        result += texture(u_texture, v_texCoord - offset).rgb * weights[i];
+    }
+    fragColor = vec4(result, 1.0);
+}
+"#;
+
+/// Bloom composite fragment shader source.
+/// 泛光合成片段着色器源代码。
+///
+/// Adds the blurred bright pass (`u_bloom`) back onto the scene.
+/// 将模糊后的亮部（`u_bloom`）叠加回场景。
+pub const BLOOM_COMPOSITE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_bloom;
+uniform float u_intensity;
+uniform vec4 u_tint;
+
+out vec4 fragColor;
+
+void main() {
+    vec4 scene = texture(u_texture, v_texCoord);
+    vec3 bloom = texture(u_bloom, v_texCoord).rgb * u_tint.rgb;
+    fragColor = vec4(scene.rgb + bloom * u_intensity, scene.a);
+}
+"#;
+
+/// Color grading fragment shader source.
+/// 颜色分级片段着色器源代码。
+///
+/// Applies exposure, contrast and saturation, then an optional 2D LUT strip
+/// (`u_lut`, `u_lutSize` slices laid out horizontally, blue selects the slice,
+/// red increases to the right and green increases downwards).
+/// 应用曝光、对比度和饱和度，然后可选地应用2D LUT条带
+/// （`u_lut`，`u_lutSize`个切片水平排列，蓝色选择切片，红色向右递增，绿色向下递增）。
+pub const COLOR_GRADING_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform sampler2D u_lut;
+uniform float u_useLut;
+uniform float u_lutSize;
+uniform float u_intensity;
+uniform float u_exposure;
+uniform float u_contrast;
+uniform float u_saturation;
+
+out vec4 fragColor;
+
+vec3 sampleLut(vec3 color) {
+    float size = u_lutSize;
+    float blue = color.b * (size - 1.0);
+    float slice0 = floor(blue);
+    float slice1 = min(slice0 + 1.0, size - 1.0);
+    float u = (color.r * (size - 1.0) + 0.5) / (size * size);
+    float v = (color.g * (size - 1.0) + 0.5) / size;
+    vec3 a = texture(u_lut, vec2(u + slice0 / size, v)).rgb;
+    vec3 b = texture(u_lut, vec2(u + slice1 / size, v)).rgb;
+    return mix(a, b, blue - slice0);
+}
+
+void main() {
+    vec4 source = texture(u_texture, v_texCoord);
+    vec3 color = source.rgb * exp2(u_exposure);
+    color = (color - 0.5) * u_contrast + 0.5;
+    float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    color = clamp(mix(vec3(luma), color, u_saturation), 0.0, 1.0);
+
+    if (u_useLut > 0.5) {
+        color = mix(color, sampleLut(color), u_intensity);
+    }
+
+    fragColor = vec4(color, source.a);
+}
+"#;
+
+/// Vignette fragment shader source.
+/// 暗角片段着色器源代码。
+pub const VIGNETTE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform float u_intensity;
+uniform float u_radius;
+uniform float u_smoothness;
+uniform vec4 u_color;
+
+out vec4 fragColor;
+
+void main() {
+    vec4 source = texture(u_texture, v_texCoord);
+    float dist = distance(v_texCoord, vec2(0.5)) * 1.41421356;
+    float vignette = smoothstep(u_radius, u_radius - u_smoothness, dist);
+    float amount = (1.0 - vignette) * u_intensity * u_color.a;
+    fragColor = vec4(mix(source.rgb, u_color.rgb, amount), source.a);
+}
+"#;
+
+/// Pixelation fragment shader source.
+/// 像素化片段着色器源代码。
+pub const PIXELATE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform vec2 u_resolution;
+uniform float u_pixelSize;
+
+out vec4 fragColor;
+
+void main() {
+    vec2 cell = max(u_pixelSize, 1.0) / u_resolution;
+    vec2 uv = (floor(v_texCoord / cell) + 0.5) * cell;
+    fragColor = texture(u_texture, uv);
+}
+"#;
+
+/// CRT monitor fragment shader source.
+/// CRT显示器片段着色器源代码。
+///
+/// Barrel distortion, scanlines and chromatic aberration.
+/// 桶形畸变、扫描线和色差。
+pub const CRT_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+
+uniform sampler2D u_texture;
+uniform vec2 u_resolution;
+uniform float u_curvature;
+uniform float u_scanlineIntensity;
+uniform float u_chromaticAberration;
+
+out vec4 fragColor;
+
+void main() {
+    // Barrel distortion | 桶形畸变
+    vec2 centered = v_texCoord * 2.0 - 1.0;
+    centered *= 1.0 + u_curvature * dot(centered, centered);
+    vec2 uv = centered * 0.5 + 0.5;
+
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        fragColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    // Chromatic aberration | 色差
+    vec2 shift = (uv - 0.5) * u_chromaticAberration / u_resolution;
+    vec4 source = texture(u_texture, uv);
+    vec3 color = vec3(
+        texture(u_texture, uv + shift).r,
+        source.g,
+        texture(u_texture, uv - shift).b
+    );
+
+    // Scanlines | 扫描线
+    float scanline = sin(uv.y * u_resolution.y * 3.14159265) * 0.5 + 0.5;
+    color *= mix(1.0, scanline, u_scanlineIntensity);
+
+    fragColor = vec4(color, source.a);
+}
+"#;
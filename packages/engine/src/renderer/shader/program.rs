@@ -3,6 +3,7 @@
 
 use web_sys::{WebGl2RenderingContext, WebGlProgram, WebGlShader, WebGlUniformLocation};
 use crate::core::error::{EngineError, Result};
+use super::super::stats;
 
 /// Compiled shader program.
 /// 已编译的Shader程序。
@@ -113,6 +114,7 @@ impl ShaderProgram {
     #[inline]
     pub fn bind(&self, gl: &WebGl2RenderingContext) {
         gl.use_program(Some(&self.program));
+        stats::record(|stats| stats.shader_switches += 1);
     }
 
     /// Get uniform location by name.
@@ -151,4 +153,72 @@ impl ShaderProgram {
             gl.uniform1i(Some(&location), value);
         }
     }
+
+    /// Set a float uniform.
+    /// 设置浮点uniform。
+    pub fn set_uniform_f32(
+        &self,
+        gl: &WebGl2RenderingContext,
+        name: &str,
+        value: f32,
+    ) {
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            gl.uniform1f(Some(&location), value);
+        }
+    }
+
+    /// Set a vec2 uniform.
+    /// 设置vec2 uniform。
+    pub fn set_uniform_vec2(
+        &self,
+        gl: &WebGl2RenderingContext,
+        name: &str,
+        value: [f32; 2],
+    ) {
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            gl.uniform2f(Some(&location), value[0], value[1]);
+        }
+    }
+
+    /// Set a vec3 uniform.
+    /// 设置vec3 uniform。
+    pub fn set_uniform_vec3(
+        &self,
+        gl: &WebGl2RenderingContext,
+        name: &str,
+        value: [f32; 3],
+    ) {
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            gl.uniform3f(Some(&location), value[0], value[1], value[2]);
+        }
+    }
+
+    /// Set a vec4 uniform.
+    /// 设置vec4 uniform。
+    pub fn set_uniform_vec4(
+        &self,
+        gl: &WebGl2RenderingContext,
+        name: &str,
+        value: [f32; 4],
+    ) {
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            gl.uniform4f(Some(&location), value[0], value[1], value[2], value[3]);
+        }
+    }
+
+    /// Set a vec4 array uniform.
+    /// 设置vec4数组uniform。
+    pub fn set_uniform_vec4_array(
+        &self,
+        gl: &WebGl2RenderingContext,
+        name: &str,
+        values: &[f32],
+    ) {
+        if values.is_empty() {
+            return;
+        }
+        if let Some(location) = self.get_uniform_location(gl, name) {
+            gl.uniform4fv_with_f32_array(Some(&location), values);
+        }
+    }
 }
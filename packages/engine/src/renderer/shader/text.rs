@@ -0,0 +1,45 @@
+//! Built-in text shader sources.
+//! 内置文本着色器源代码。
+
+/// Signed distance field text fragment shader source.
+/// 有向距离场文本片段着色器源代码。
+///
+/// Used with `SPRITE_VERTEX_SHADER`. Reads the distance from the alpha
+/// channel (SDF) or the median of RGB (MSDF, `u_msdf` = 1), and scales the
+/// edge by the on-screen size of `u_distanceRange` texels so glyphs stay
+/// sharp at any zoom.
+/// 与`SPRITE_VERTEX_SHADER`配合使用。从alpha通道（SDF）或RGB中值（MSDF，`u_msdf` = 1）读取距离，
+/// 并按`u_distanceRange`纹素在屏幕上的尺寸缩放边缘，使字形在任意缩放下保持清晰。
+pub const SDF_TEXT_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+
+in vec2 v_texCoord;
+in vec4 v_color;
+
+uniform sampler2D u_texture;
+uniform float u_distanceRange;
+uniform float u_msdf;
+
+out vec4 fragColor;
+
+float median(float r, float g, float b) {
+    return max(min(r, g), min(max(r, g), b));
+}
+
+void main() {
+    vec4 sample_ = texture(u_texture, v_texCoord);
+    float distance = mix(sample_.a, median(sample_.r, sample_.g, sample_.b), u_msdf);
+
+    // Distance range in screen pixels | 屏幕像素中的距离范围
+    vec2 unitRange = vec2(u_distanceRange) / vec2(textureSize(u_texture, 0));
+    vec2 screenTexSize = vec2(1.0) / fwidth(v_texCoord);
+    float screenPxRange = max(0.5 * dot(unitRange, screenTexSize), 1.0);
+
+    float opacity = clamp(screenPxRange * (distance - 0.5) + 0.5, 0.0, 1.0);
+    fragColor = vec4(v_color.rgb, v_color.a * opacity);
+
+    if (fragColor.a < 0.01) {
+        discard;
+    }
+}
+"#;
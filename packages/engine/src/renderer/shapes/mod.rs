@@ -0,0 +1,15 @@
+//! Anti-aliased vector shapes tessellated on the CPU.
+//! 在CPU上细分的抗锯齿矢量图形。
+
+mod path;
+mod paint;
+mod tessellator;
+mod shape_renderer;
+
+pub use path::{
+    Contour, Path, PATH_MOVE_TO, PATH_LINE_TO, PATH_QUAD_TO, PATH_CUBIC_TO, PATH_ARC, PATH_CLOSE,
+    PATH_RECT, PATH_ROUNDED_RECT, PATH_CIRCLE, PATH_ELLIPSE,
+};
+pub use paint::{GradientStop, Paint, PAINT_SOLID, PAINT_LINEAR, PAINT_RADIAL, GRADIENT_STOP_STRIDE};
+pub use tessellator::{LineCap, LineJoin, ShapeMesh, StrokeStyle, STROKE_STYLE_STRIDE};
+pub use shape_renderer::ShapeRenderer;
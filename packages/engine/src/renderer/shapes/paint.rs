@@ -0,0 +1,171 @@
+//! Solid and gradient shape paints.
+//! 纯色和渐变图形填充。
+
+use glam::Vec2;
+
+use crate::core::error::{EngineError, Result};
+
+/// Paint type codes for packed paint data.
+/// 打包填充数据的类型代码。
+///
+/// * `[PAINT_SOLID, r, g, b, a]`
+/// * `[PAINT_LINEAR, x0, y0, x1, y1, (offset, r, g, b, a)...]`
+/// * `[PAINT_RADIAL, cx, cy, radius, (offset, r, g, b, a)...]`
+pub const PAINT_SOLID: u32 = 0;
+/// Linear gradient paint code.
+/// 线性渐变填充代码。
+pub const PAINT_LINEAR: u32 = 1;
+/// Radial gradient paint code.
+/// 径向渐变填充代码。
+pub const PAINT_RADIAL: u32 = 2;
+
+/// Gradient stop stride (offset, r, g, b, a).
+/// 渐变色标步长。
+pub const GRADIENT_STOP_STRIDE: usize = 5;
+
+/// A color at a position along a gradient.
+/// 渐变上某位置的颜色。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    /// Position in `[0, 1]`.
+    /// 位置，范围`[0, 1]`。
+    pub offset: f32,
+    /// Color (RGBA, 0-1).
+    /// 颜色（RGBA，0-1）。
+    pub color: [f32; 4],
+}
+
+/// How a shape is colored.
+/// 图形的着色方式。
+#[derive(Clone, Debug, PartialEq)]
+pub enum Paint {
+    /// Single color.
+    /// 单一颜色。
+    Solid([f32; 4]),
+    /// Gradient along the line from `start` to `end`.
+    /// 沿`start`到`end`直线的渐变。
+    Linear {
+        /// Point at offset 0 | 偏移0处的点
+        start: Vec2,
+        /// Point at offset 1 | 偏移1处的点
+        end: Vec2,
+        /// Stops sorted by offset | 按偏移排序的色标
+        stops: Vec<GradientStop>,
+    },
+    /// Gradient from `center` out to `radius`.
+    /// 从`center`向外到`radius`的渐变。
+    Radial {
+        /// Center at offset 0 | 偏移0处的中心
+        center: Vec2,
+        /// Distance at offset 1 | 偏移1处的距离
+        radius: f32,
+        /// Stops sorted by offset | 按偏移排序的色标
+        stops: Vec<GradientStop>,
+    },
+}
+
+impl Paint {
+    /// Build from packed data (see `PAINT_SOLID`).
+    /// 从打包数据构建（参见`PAINT_SOLID`）。
+    pub fn from_data(data: &[f32]) -> Result<Self> {
+        let Some(&kind) = data.first() else {
+            return Err(EngineError::InvalidBatchData("Paint data is empty".into()));
+        };
+        let header = match kind as u32 {
+            PAINT_SOLID => 4,
+            PAINT_LINEAR => 4,
+            PAINT_RADIAL => 3,
+            other => {
+                return Err(EngineError::InvalidBatchData(format!("Unknown paint type: {}", other)));
+            }
+        };
+        if data.len() < 1 + header {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Paint data too short: expected at least {}, got {}",
+                1 + header,
+                data.len()
+            )));
+        }
+
+        let h = &data[1..=header];
+        if kind as u32 == PAINT_SOLID {
+            return Ok(Self::Solid([h[0], h[1], h[2], h[3]]));
+        }
+
+        let stops = Self::parse_stops(&data[1 + header..])?;
+        Ok(if kind as u32 == PAINT_LINEAR {
+            Self::Linear { start: Vec2::new(h[0], h[1]), end: Vec2::new(h[2], h[3]), stops }
+        } else {
+            Self::Radial { center: Vec2::new(h[0], h[1]), radius: h[2], stops }
+        })
+    }
+
+    fn parse_stops(data: &[f32]) -> Result<Vec<GradientStop>> {
+        if data.is_empty() || !data.len().is_multiple_of(GRADIENT_STOP_STRIDE) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Gradient stop data length {} is not a non-zero multiple of {}",
+                data.len(),
+                GRADIENT_STOP_STRIDE
+            )));
+        }
+
+        let mut stops: Vec<GradientStop> = data
+            .chunks_exact(GRADIENT_STOP_STRIDE)
+            .map(|s| GradientStop { offset: s[0].clamp(0.0, 1.0), color: [s[1], s[2], s[3], s[4]] })
+            .collect();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        Ok(stops)
+    }
+
+    /// Color at a world position.
+    /// 世界位置处的颜色。
+    pub fn color_at(&self, point: Vec2) -> [f32; 4] {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Linear { stops, .. } => {
+                let (origin, axis) = self.linear_axis().unwrap_or((Vec2::ZERO, Vec2::ZERO));
+                Self::sample(stops, (point - origin).dot(axis))
+            }
+            Self::Radial { center, radius, stops } => {
+                let t = if *radius > 0.0 { point.distance(*center) / radius } else { 1.0 };
+                Self::sample(stops, t)
+            }
+        }
+    }
+
+    /// Origin and axis of a linear gradient, scaled so `(p - origin) · axis` is the offset.
+    /// 线性渐变的原点和轴，已缩放使`(p - origin) · axis`即为偏移。
+    pub fn linear_axis(&self) -> Option<(Vec2, Vec2)> {
+        match self {
+            Self::Linear { start, end, .. } => {
+                let direction = *end - *start;
+                let length_squared = direction.length_squared();
+                let axis = if length_squared > 0.0 { direction / length_squared } else { Vec2::ZERO };
+                Some((*start, axis))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gradient stops, empty for solid paints.
+    /// 渐变色标，纯色填充时为空。
+    pub fn stops(&self) -> &[GradientStop] {
+        match self {
+            Self::Solid(_) => &[],
+            Self::Linear { stops, .. } | Self::Radial { stops, .. } => stops,
+        }
+    }
+
+    fn sample(stops: &[GradientStop], t: f32) -> [f32; 4] {
+        let next = stops.partition_point(|s| s.offset <= t);
+        match (next.checked_sub(1).map(|i| stops[i]), stops.get(next)) {
+            (Some(a), Some(b)) => {
+                let f = (t - a.offset) / (b.offset - a.offset);
+                std::array::from_fn(|i| a.color[i] + (b.color[i] - a.color[i]) * f)
+            }
+            (Some(a), None) => a.color,
+            (None, Some(b)) => b.color,
+            (None, None) => [0.0; 4],
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Path building and curve flattening.
+//! 路径构建和曲线展平。
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use glam::Vec2;
+
+use crate::core::error::{EngineError, Result};
+
+/// `[MOVE_TO, x, y]` starts a new subpath.
+/// 开始新的子路径。
+pub const PATH_MOVE_TO: u32 = 0;
+/// `[LINE_TO, x, y]`
+pub const PATH_LINE_TO: u32 = 1;
+/// `[QUAD_TO, cx, cy, x, y]`
+pub const PATH_QUAD_TO: u32 = 2;
+/// `[CUBIC_TO, c1x, c1y, c2x, c2y, x, y]`
+pub const PATH_CUBIC_TO: u32 = 3;
+/// `[ARC, cx, cy, radius, startAngle, sweep]`, angles counter-clockwise from +X.
+/// 角度从+X轴逆时针计算。
+pub const PATH_ARC: u32 = 4;
+/// `[CLOSE]` closes the current subpath.
+/// 闭合当前子路径。
+pub const PATH_CLOSE: u32 = 5;
+/// `[RECT, x, y, width, height]`, (x, y) = bottom-left.
+/// （x, y）= 左下角。
+pub const PATH_RECT: u32 = 6;
+/// `[ROUNDED_RECT, x, y, width, height, radius]`
+pub const PATH_ROUNDED_RECT: u32 = 7;
+/// `[CIRCLE, cx, cy, radius]`
+pub const PATH_CIRCLE: u32 = 8;
+/// `[ELLIPSE, cx, cy, radiusX, radiusY]`
+pub const PATH_ELLIPSE: u32 = 9;
+
+/// Maximum line segments a single curve is flattened into.
+/// 单条曲线展平后的最大线段数。
+const MAX_CURVE_SEGMENTS: usize = 256;
+
+/// Points closer than this are merged.
+/// 距离小于此值的点将被合并。
+const POINT_EPSILON: f32 = 1e-5;
+
+/// A flattened subpath.
+/// 展平后的子路径。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Contour {
+    /// Points without consecutive duplicates.
+    /// 不含连续重复的点。
+    pub points: Vec<Vec2>,
+    /// Whether the last point connects back to the first.
+    /// 最后一个点是否连接回第一个点。
+    pub closed: bool,
+}
+
+/// A 2D path of lines, curves and arcs, flattened as it is built.
+/// 由直线、曲线和圆弧组成的2D路径，在构建时展平。
+///
+/// World space is Y-up, so positive angles and sweeps turn counter-clockwise.
+/// 世界空间为Y向上，因此正角度和扫掠为逆时针。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::shapes::Path;
+/// use glam::Vec2;
+/// let mut path = Path::new(0.25);
+/// path.move_to(Vec2::ZERO);
+/// path.line_to(Vec2::new(10.0, 0.0));
+/// path.quad_to(Vec2::new(10.0, 10.0), Vec2::new(0.0, 10.0));
+/// path.close();
+/// path.circle(Vec2::new(50.0, 0.0), 5.0);
+/// assert_eq!(path.contours().len(), 2);
+/// assert!(path.contours()[0].closed);
+/// assert!(path.contours()[0].points.len() > 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Path {
+    contours: Vec<Contour>,
+    /// Maximum distance between a curve and its flattened lines.
+    /// 曲线与其展平线段之间的最大距离。
+    tolerance: f32,
+    /// Whether the last contour accepts more points.
+    /// 最后一个轮廓是否接受更多点。
+    open: bool,
+}
+
+impl Path {
+    /// Create an empty path.
+    /// 创建空路径。
+    ///
+    /// # Arguments | 参数
+    /// * `tolerance` - Flattening tolerance in world units | 展平容差（世界单位）
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            contours: Vec::new(),
+            tolerance: tolerance.max(1e-4),
+            open: false,
+        }
+    }
+
+    /// Build a path from packed commands (see the `PATH_*` constants).
+    /// 从打包命令构建路径（参见`PATH_*`常量）。
+    pub fn from_commands(data: &[f32], tolerance: f32) -> Result<Self> {
+        let mut path = Self::new(tolerance);
+        let mut i = 0;
+        while i < data.len() {
+            let command = data[i] as u32;
+            let argument_count = match command {
+                PATH_MOVE_TO | PATH_LINE_TO => 2,
+                PATH_QUAD_TO => 4,
+                PATH_CUBIC_TO => 6,
+                PATH_ARC => 5,
+                PATH_CLOSE => 0,
+                PATH_RECT => 4,
+                PATH_ROUNDED_RECT => 5,
+                PATH_CIRCLE => 3,
+                PATH_ELLIPSE => 4,
+                _ => {
+                    return Err(EngineError::InvalidBatchData(format!(
+                        "Unknown path command {} at {}",
+                        command, i
+                    )));
+                }
+            };
+            let Some(a) = data.get(i + 1..i + 1 + argument_count) else {
+                return Err(EngineError::InvalidBatchData(format!(
+                    "Path command {} at {} needs {} arguments",
+                    command, i, argument_count
+                )));
+            };
+
+            match command {
+                PATH_MOVE_TO => path.move_to(Vec2::new(a[0], a[1])),
+                PATH_LINE_TO => path.line_to(Vec2::new(a[0], a[1])),
+                PATH_QUAD_TO => path.quad_to(Vec2::new(a[0], a[1]), Vec2::new(a[2], a[3])),
+                PATH_CUBIC_TO => path.cubic_to(Vec2::new(a[0], a[1]), Vec2::new(a[2], a[3]), Vec2::new(a[4], a[5])),
+                PATH_ARC => path.arc(Vec2::new(a[0], a[1]), a[2], a[3], a[4]),
+                PATH_CLOSE => path.close(),
+                PATH_RECT => path.rect(a[0], a[1], a[2], a[3]),
+                PATH_ROUNDED_RECT => path.rounded_rect(a[0], a[1], a[2], a[3], a[4]),
+                PATH_CIRCLE => path.circle(Vec2::new(a[0], a[1]), a[2]),
+                _ => path.ellipse(Vec2::new(a[0], a[1]), a[2], a[3]),
+            }
+            i += 1 + argument_count;
+        }
+        Ok(path)
+    }
+
+    /// Flattened subpaths.
+    /// 展平后的子路径。
+    #[inline]
+    pub fn contours(&self) -> &[Contour] {
+        &self.contours
+    }
+
+    /// Start a new subpath.
+    /// 开始新的子路径。
+    pub fn move_to(&mut self, point: Vec2) {
+        self.contours.push(Contour { points: vec![point], closed: false });
+        self.open = true;
+    }
+
+    /// Add a straight line.
+    /// 添加直线。
+    pub fn line_to(&mut self, point: Vec2) {
+        if !self.open {
+            self.move_to(point);
+            return;
+        }
+        self.push_point(point);
+    }
+
+    /// Add a quadratic bezier curve.
+    /// 添加二次贝塞尔曲线。
+    pub fn quad_to(&mut self, control: Vec2, point: Vec2) {
+        let start = self.current_or(control);
+        // Chord error is |p0 - 2c + p1| / (4n²) | 弦误差为|p0 - 2c + p1| / (4n²)
+        let deviation = (start - 2.0 * control + point).length();
+        let segments = self.segments_for(deviation / 4.0);
+        for k in 1..=segments {
+            let t = k as f32 / segments as f32;
+            let u = 1.0 - t;
+            self.push_point(start * (u * u) + control * (2.0 * u * t) + point * (t * t));
+        }
+    }
+
+    /// Add a cubic bezier curve.
+    /// 添加三次贝塞尔曲线。
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, point: Vec2) {
+        let start = self.current_or(control1);
+        let deviation = (start - 2.0 * control1 + control2)
+            .length()
+            .max((control1 - 2.0 * control2 + point).length());
+        let segments = self.segments_for(deviation * 0.75);
+        for k in 1..=segments {
+            let t = k as f32 / segments as f32;
+            let u = 1.0 - t;
+            self.push_point(
+                start * (u * u * u)
+                    + control1 * (3.0 * u * u * t)
+                    + control2 * (3.0 * u * t * t)
+                    + point * (t * t * t),
+            );
+        }
+    }
+
+    /// Add a circular arc, connected to the current point by a line.
+    /// 添加圆弧，通过直线与当前点相连。
+    ///
+    /// # Arguments | 参数
+    /// * `center` - Arc center | 圆弧中心
+    /// * `radius` - Arc radius | 圆弧半径
+    /// * `start_angle` - Start angle in radians | 起始角度（弧度）
+    /// * `sweep` - Signed sweep in radians, positive = counter-clockwise | 有符号扫掠角（弧度），正值 = 逆时针
+    pub fn arc(&mut self, center: Vec2, radius: f32, start_angle: f32, sweep: f32) {
+        let sweep = sweep.clamp(-TAU, TAU);
+        let segments = self.arc_segments(radius, sweep);
+        for k in 0..=segments {
+            let angle = start_angle + sweep * k as f32 / segments as f32;
+            self.line_to(center + Vec2::from_angle(angle) * radius);
+        }
+    }
+
+    /// Close the current subpath.
+    /// 闭合当前子路径。
+    pub fn close(&mut self) {
+        if !self.open {
+            return;
+        }
+        if let Some(contour) = self.contours.last_mut() {
+            if contour.points.len() > 1 && contour.points[0].distance(*contour.points.last().unwrap()) <= POINT_EPSILON {
+                contour.points.pop();
+            }
+            contour.closed = true;
+        }
+        self.open = false;
+    }
+
+    /// Add a closed rectangle; (x, y) is the bottom-left corner.
+    /// 添加闭合矩形；（x, y）为左下角。
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.move_to(Vec2::new(x, y));
+        self.line_to(Vec2::new(x + width, y));
+        self.line_to(Vec2::new(x + width, y + height));
+        self.line_to(Vec2::new(x, y + height));
+        self.close();
+    }
+
+    /// Add a closed rectangle with rounded corners.
+    /// 添加带圆角的闭合矩形。
+    pub fn rounded_rect(&mut self, x: f32, y: f32, width: f32, height: f32, radius: f32) {
+        let radius = radius.min(width.abs() * 0.5).min(height.abs() * 0.5);
+        if radius <= POINT_EPSILON {
+            self.rect(x, y, width, height);
+            return;
+        }
+
+        self.open = false;
+        let corners = [
+            (Vec2::new(x + width - radius, y + radius), -FRAC_PI_2),
+            (Vec2::new(x + width - radius, y + height - radius), 0.0),
+            (Vec2::new(x + radius, y + height - radius), FRAC_PI_2),
+            (Vec2::new(x + radius, y + radius), PI),
+        ];
+        for (center, start) in corners {
+            self.arc(center, radius, start, FRAC_PI_2);
+        }
+        self.close();
+    }
+
+    /// Add a closed circle.
+    /// 添加闭合圆。
+    pub fn circle(&mut self, center: Vec2, radius: f32) {
+        self.ellipse(center, radius, radius);
+    }
+
+    /// Add a closed axis-aligned ellipse.
+    /// 添加闭合的轴对齐椭圆。
+    pub fn ellipse(&mut self, center: Vec2, radius_x: f32, radius_y: f32) {
+        let segments = self.arc_segments(radius_x.abs().max(radius_y.abs()), TAU);
+        self.open = false;
+        for k in 0..segments {
+            let angle = TAU * k as f32 / segments as f32;
+            let (sin, cos) = angle.sin_cos();
+            self.line_to(center + Vec2::new(cos * radius_x, sin * radius_y));
+        }
+        self.close();
+    }
+
+    /// Current point, or start a subpath at `fallback`.
+    /// 当前点，或在`fallback`处开始子路径。
+    fn current_or(&mut self, fallback: Vec2) -> Vec2 {
+        match self.contours.last().and_then(|c| c.points.last()) {
+            Some(&point) if self.open => point,
+            _ => {
+                self.move_to(fallback);
+                fallback
+            }
+        }
+    }
+
+    fn push_point(&mut self, point: Vec2) {
+        let contour = self.contours.last_mut().expect("open path has a contour");
+        if contour.points.last().is_none_or(|last| last.distance(point) > POINT_EPSILON) {
+            contour.points.push(point);
+        }
+    }
+
+    /// Uniform segments keeping chord error `deviation / n²` within tolerance.
+    /// 使弦误差`deviation / n²`保持在容差内的均匀分段数。
+    fn segments_for(&self, deviation: f32) -> usize {
+        ((deviation / self.tolerance).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+    }
+
+    fn arc_segments(&self, radius: f32, sweep: f32) -> usize {
+        let radius = radius.abs();
+        if radius <= self.tolerance {
+            return 4;
+        }
+        let step = 2.0 * (1.0 - self.tolerance / radius).acos();
+        ((sweep.abs() / step).ceil() as usize).clamp(4, MAX_CURVE_SEGMENTS)
+    }
+}
@@ -0,0 +1,239 @@
+//! Per-frame vector shape renderer.
+//! 每帧矢量图形渲染器。
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use super::super::batch::{SortKey, SpriteBatch, SpriteVertex};
+use super::super::stats;
+use super::paint::Paint;
+use super::path::Path;
+use super::tessellator::{ShapeMesh, StrokeStyle};
+
+/// A submitted shape's triangles.
+/// 已提交图形的三角形。
+#[derive(Clone, Debug)]
+struct ShapeDraw {
+    sort: SortKey,
+    indices: Range<usize>,
+}
+
+/// Tessellates filled and stroked paths and draws them among sprites.
+/// 细分填充和描边路径，并在精灵之间绘制。
+///
+/// Shapes are submitted every frame like sprites and cleared by `clear`.
+/// Shapes with equal sort keys share one draw call and are drawn above
+/// sprites with the same key.
+/// 图形像精灵一样每帧提交，并由`clear`清空。排序键相同的图形共享一次绘制调用，
+/// 并绘制在键相同的精灵之上。
+pub struct ShapeRenderer {
+    vao: WebGlVertexArrayObject,
+    vbo: WebGlBuffer,
+    ibo: WebGlBuffer,
+
+    /// Vertices of all shapes this frame.
+    /// 本帧所有图形的顶点。
+    vertices: Vec<SpriteVertex>,
+
+    /// Indices of all shapes in submission order.
+    /// 按提交顺序排列的所有图形索引。
+    indices: Vec<u32>,
+
+    /// Submitted shapes.
+    /// 已提交的图形。
+    shapes: Vec<ShapeDraw>,
+
+    /// Index ranges in draw order, one per sort key.
+    /// 按绘制顺序的索引范围，每个排序键一个。
+    groups: Vec<(SortKey, Range<usize>)>,
+
+    /// Tessellation scratch.
+    /// 细分暂存。
+    mesh: ShapeMesh,
+
+    /// Whether `groups` and the GPU buffers are stale.
+    /// `groups`和GPU缓冲区是否已过期。
+    dirty: bool,
+
+    /// Buffer sizes in bytes.
+    /// 缓冲区字节大小。
+    vertex_capacity: usize,
+    index_capacity: usize,
+}
+
+impl ShapeRenderer {
+    /// Create a new shape renderer.
+    /// 创建新的图形渲染器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+
+        let ibo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&ibo));
+
+        SpriteBatch::setup_vertex_attributes(gl);
+        gl.bind_vertex_array(None);
+
+        Ok(Self {
+            vao,
+            vbo,
+            ibo,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            shapes: Vec::new(),
+            groups: Vec::new(),
+            mesh: ShapeMesh::default(),
+            dirty: false,
+            vertex_capacity: 0,
+            index_capacity: 0,
+        })
+    }
+
+    /// Fill a path.
+    /// 填充路径。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Path to fill | 要填充的路径
+    /// * `paint` - Solid color or gradient | 纯色或渐变
+    /// * `sort` - Draw order among sprites | 在精灵之间的绘制顺序
+    /// * `aa` - Anti-aliasing fringe width in world units | 抗锯齿边缘宽度（世界单位）
+    pub fn fill(&mut self, path: &Path, paint: &Paint, sort: SortKey, aa: f32) {
+        self.mesh.clear();
+        self.mesh.fill(path, aa);
+        self.push_mesh(paint, sort);
+    }
+
+    /// Stroke a path.
+    /// 描边路径。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Path to stroke | 要描边的路径
+    /// * `style` - Width, joins and caps | 宽度、连接和端点
+    /// * `paint` - Solid color or gradient | 纯色或渐变
+    /// * `sort` - Draw order among sprites | 在精灵之间的绘制顺序
+    /// * `aa` - Anti-aliasing fringe width in world units | 抗锯齿边缘宽度（世界单位）
+    pub fn stroke(&mut self, path: &Path, style: &StrokeStyle, paint: &Paint, sort: SortKey, aa: f32) {
+        self.mesh.clear();
+        self.mesh.stroke(path, style, aa, aa * 0.25);
+        self.push_mesh(paint, sort);
+    }
+
+    /// Color the scratch mesh and append it to this frame's geometry.
+    /// 为暂存网格着色并追加到本帧几何体。
+    fn push_mesh(&mut self, paint: &Paint, sort: SortKey) {
+        if self.mesh.indices.is_empty() {
+            return;
+        }
+
+        // Split at interior stops so linear gradients are exact | 在内部色标处拆分，使线性渐变精确
+        if let Some((origin, axis)) = paint.linear_axis() {
+            let stops = paint.stops();
+            if stops.len() > 2 {
+                let offsets: Vec<f32> = stops[1..stops.len() - 1].iter().map(|s| s.offset).collect();
+                self.mesh.split_along(origin, axis, &offsets);
+            }
+        }
+
+        let base = self.vertices.len() as u32;
+        for (&position, &coverage) in self.mesh.positions.iter().zip(&self.mesh.coverage) {
+            let [r, g, b, a] = paint.color_at(position);
+            self.vertices.push(SpriteVertex::new(position.into(), [0.0, 0.0], [r, g, b, a * coverage]));
+        }
+        let start = self.indices.len();
+        self.indices.extend(self.mesh.indices.iter().map(|&i| base + i));
+        self.shapes.push(ShapeDraw { sort, indices: start..self.indices.len() });
+        self.dirty = true;
+    }
+
+    /// Check whether any shape was submitted this frame.
+    /// 检查本帧是否提交了任何图形。
+    #[inline]
+    pub fn has_shapes(&self) -> bool {
+        !self.shapes.is_empty()
+    }
+
+    /// Sort shapes, upload geometry and return `(key, group)` pairs in draw order.
+    /// 排序图形、上传几何体，并按绘制顺序返回`(key, group)`对。
+    pub fn prepare(&mut self, gl: &WebGl2RenderingContext) -> Vec<(SortKey, u32)> {
+        if self.dirty {
+            self.dirty = false;
+            self.shapes.sort_by(|a, b| a.sort.compare(&b.sort));
+
+            // Reorder indices by key and merge equal keys | 按键重排索引并合并相同的键
+            let mut ordered = Vec::with_capacity(self.indices.len());
+            self.groups.clear();
+            for shape in &self.shapes {
+                let start = ordered.len();
+                ordered.extend_from_slice(&self.indices[shape.indices.clone()]);
+                match self.groups.last_mut() {
+                    Some((key, range)) if key.compare(&shape.sort) == Ordering::Equal => range.end = ordered.len(),
+                    _ => self.groups.push((shape.sort, start..ordered.len())),
+                }
+            }
+
+            gl.bind_vertex_array(Some(&self.vao));
+            let target = WebGl2RenderingContext::ARRAY_BUFFER;
+            Self::upload(gl, target, &self.vbo, &mut self.vertex_capacity, bytemuck::cast_slice(&self.vertices));
+            stats::record_vertices(self.vertices.len());
+            let target = WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER;
+            Self::upload(gl, target, &self.ibo, &mut self.index_capacity, bytemuck::cast_slice(&ordered));
+            gl.bind_vertex_array(None);
+        }
+
+        self.groups
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (*key, i as u32))
+            .collect()
+    }
+
+    /// Write data into a buffer, growing it when needed.
+    /// 将数据写入缓冲区，必要时扩展。
+    fn upload(gl: &WebGl2RenderingContext, target: u32, buffer: &WebGlBuffer, capacity: &mut usize, data: &[u8]) {
+        gl.bind_buffer(target, Some(buffer));
+        if data.len() > *capacity {
+            *capacity = data.len().max(*capacity * 2);
+            gl.buffer_data_with_i32(target, *capacity as i32, WebGl2RenderingContext::DYNAMIC_DRAW);
+        }
+        gl.buffer_sub_data_with_i32_and_u8_array(target, 0, data);
+    }
+
+    /// Draw one group with the bound shader.
+    /// 使用已绑定的着色器绘制一个分组。
+    pub fn draw_group(&self, gl: &WebGl2RenderingContext, group: u32) {
+        let Some((_, range)) = self.groups.get(group as usize) else {
+            return;
+        };
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.draw_elements_with_i32(
+            WebGl2RenderingContext::TRIANGLES,
+            range.len() as i32,
+            WebGl2RenderingContext::UNSIGNED_INT,
+            (range.start * 4) as i32,
+        );
+        stats::record_draw_call();
+        gl.bind_vertex_array(None);
+    }
+
+    /// Remove all shapes submitted this frame.
+    /// 移除本帧提交的所有图形。
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.shapes.clear();
+        self.groups.clear();
+        self.dirty = false;
+    }
+}
@@ -0,0 +1,515 @@
+//! Fill and stroke tessellation with anti-aliased fringes.
+//! 带抗锯齿边缘的填充和描边细分。
+
+use glam::Vec2;
+
+use crate::core::error::{EngineError, Result};
+use super::path::{Contour, Path};
+
+/// Packed stroke style stride (width, join, cap, miterLimit).
+/// 打包描边样式步长。
+pub const STROKE_STYLE_STRIDE: usize = 4;
+
+/// Longest fringe extrusion at sharp fill corners, in fringe widths.
+/// 填充尖角处边缘的最长挤出量（以边缘宽度计）。
+const MAX_FRINGE_MITER: f32 = 2.0;
+
+/// Shape of the corner between two stroked segments.
+/// 两段描边之间拐角的形状。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Sharp corner, beveled past the miter limit.
+    /// 尖角，超过斜接限制时斜切。
+    #[default]
+    Miter,
+    /// Rounded corner.
+    /// 圆角。
+    Round,
+    /// Flat cut corner.
+    /// 平切角。
+    Bevel,
+}
+
+impl LineJoin {
+    /// Create from a JavaScript code (0 = miter, 1 = round, 2 = bevel).
+    /// 从JavaScript代码创建（0 = 斜接，1 = 圆角，2 = 斜切）。
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Round,
+            2 => Self::Bevel,
+            _ => Self::Miter,
+        }
+    }
+}
+
+/// Shape of the ends of an open stroke.
+/// 开放描边端点的形状。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends exactly at the end point.
+    /// 恰好在端点处结束。
+    #[default]
+    Butt,
+    /// Extends half the width past the end point.
+    /// 超出端点半个线宽。
+    Square,
+    /// Half circle around the end point.
+    /// 围绕端点的半圆。
+    Round,
+}
+
+impl LineCap {
+    /// Create from a JavaScript code (0 = butt, 1 = square, 2 = round).
+    /// 从JavaScript代码创建（0 = 平头，1 = 方头，2 = 圆头）。
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Square,
+            2 => Self::Round,
+            _ => Self::Butt,
+        }
+    }
+}
+
+/// Stroke width, joins and caps.
+/// 描边宽度、连接和端点。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// Line width in world units.
+    /// 线宽（世界单位）。
+    pub width: f32,
+    /// Corner shape.
+    /// 拐角形状。
+    pub join: LineJoin,
+    /// End shape.
+    /// 端点形状。
+    pub cap: LineCap,
+    /// Longest miter, as a multiple of half the width, before beveling.
+    /// 斜切前的最长斜接长度（半线宽的倍数）。
+    pub miter_limit: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self { width: 1.0, join: LineJoin::Miter, cap: LineCap::Butt, miter_limit: 4.0 }
+    }
+}
+
+impl StrokeStyle {
+    /// Build from packed data (see `STROKE_STYLE_STRIDE`).
+    /// 从打包数据构建（参见`STROKE_STYLE_STRIDE`）。
+    pub fn from_data(data: &[f32]) -> Result<Self> {
+        if data.len() != STROKE_STYLE_STRIDE {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Stroke style length mismatch: expected {}, got {}",
+                STROKE_STYLE_STRIDE,
+                data.len()
+            )));
+        }
+
+        Ok(Self {
+            width: data[0].max(0.0),
+            join: LineJoin::from_code(data[1] as u32),
+            cap: LineCap::from_code(data[2] as u32),
+            miter_limit: data[3].max(1.0),
+        })
+    }
+}
+
+/// A cross-section of a stroke: offsets per unit half-width from `center`.
+/// 描边的截面：相对于`center`的单位半线宽偏移。
+#[derive(Clone, Copy, Debug)]
+struct Station {
+    center: Vec2,
+    left: Vec2,
+    right: Vec2,
+    alpha: f32,
+}
+
+/// Triangles with per-vertex anti-aliasing coverage.
+/// 带逐顶点抗锯齿覆盖率的三角形。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::shapes::{Path, ShapeMesh, StrokeStyle};
+/// let mut path = Path::new(0.25);
+/// path.rect(0.0, 0.0, 10.0, 10.0);
+/// let mut mesh = ShapeMesh::default();
+/// mesh.fill(&path, 0.0);
+/// assert_eq!(mesh.indices.len(), 6);
+/// mesh.clear();
+/// mesh.stroke(&path, &StrokeStyle::default(), 1.0, 0.25);
+/// assert!(mesh.coverage.iter().any(|&c| c == 0.0));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ShapeMesh {
+    /// Vertex positions.
+    /// 顶点位置。
+    pub positions: Vec<Vec2>,
+    /// Vertex coverage (0 = outside, 1 = inside).
+    /// 顶点覆盖率（0 = 外部，1 = 内部）。
+    pub coverage: Vec<f32>,
+    /// Triangle indices.
+    /// 三角形索引。
+    pub indices: Vec<u32>,
+}
+
+impl ShapeMesh {
+    /// Remove all geometry.
+    /// 移除所有几何体。
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.coverage.clear();
+        self.indices.clear();
+    }
+
+    /// Fill each subpath as a simple polygon.
+    /// 将每个子路径作为简单多边形填充。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Path to fill (open subpaths are closed) | 要填充的路径（开放子路径会被闭合）
+    /// * `aa` - Fringe width in world units (0 = aliased) | 边缘宽度（世界单位，0 = 无抗锯齿）
+    pub fn fill(&mut self, path: &Path, aa: f32) {
+        for contour in path.contours() {
+            if contour.points.len() < 3 {
+                continue;
+            }
+            let area = signed_area(&contour.points);
+            if area.abs() <= f32::EPSILON {
+                continue;
+            }
+            // Work counter-clockwise | 使用逆时针顺序
+            let points: Vec<Vec2> = if area > 0.0 {
+                contour.points.clone()
+            } else {
+                contour.points.iter().rev().copied().collect()
+            };
+
+            let n = points.len();
+            let base = self.positions.len() as u32;
+            let extrusions: Vec<Vec2> = (0..n)
+                .map(|i| {
+                    let previous = points[(i + n - 1) % n];
+                    let next = points[(i + 1) % n];
+                    let n0 = outward_normal(points[i] - previous);
+                    let n1 = outward_normal(next - points[i]);
+                    miter(n0, n1, MAX_FRINGE_MITER)
+                })
+                .collect();
+
+            // Inner ring at full coverage, then outer ring at zero | 完全覆盖的内环，然后是零覆盖的外环
+            let inner: Vec<Vec2> = (0..n).map(|i| points[i] - extrusions[i] * aa * 0.5).collect();
+            self.positions.extend_from_slice(&inner);
+            self.coverage.extend(std::iter::repeat_n(1.0, n));
+
+            for [a, b, c] in triangulate(&inner) {
+                self.indices.extend_from_slice(&[base + a as u32, base + b as u32, base + c as u32]);
+            }
+
+            if aa > 0.0 {
+                let outer = base + n as u32;
+                self.positions.extend((0..n).map(|i| points[i] + extrusions[i] * aa * 0.5));
+                self.coverage.extend(std::iter::repeat_n(0.0, n));
+                for i in 0..n as u32 {
+                    let j = (i + 1) % n as u32;
+                    self.push_quad([base + i, outer + i, outer + j, base + j]);
+                }
+            }
+        }
+    }
+
+    /// Stroke each subpath.
+    /// 描边每个子路径。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Path to stroke | 要描边的路径
+    /// * `style` - Width, joins and caps | 宽度、连接和端点
+    /// * `aa` - Fringe width in world units (0 = aliased) | 边缘宽度（世界单位，0 = 无抗锯齿）
+    /// * `tolerance` - Flattening tolerance for round joins and caps | 圆角连接和端点的展平容差
+    pub fn stroke(&mut self, path: &Path, style: &StrokeStyle, aa: f32, tolerance: f32) {
+        let half_width = style.width * 0.5;
+        if half_width <= 0.0 {
+            return;
+        }
+        // Lines thinner than the fringe fade instead of shrinking | 比边缘更细的线条淡出而不是变窄
+        let coverage = if aa > 0.0 { (style.width / aa).min(1.0) } else { 1.0 };
+        let core = (half_width - aa * 0.5).max(0.0);
+        let outer = half_width + aa * 0.5;
+        let round_step = 2.0 * (1.0 - (tolerance / outer).min(1.0)).acos().max(0.1);
+
+        let mut stations = Vec::new();
+        for contour in path.contours() {
+            stations.clear();
+            Self::stroke_stations(contour, style, aa, round_step, &mut stations);
+            self.push_strip(&stations, core, outer, coverage);
+        }
+    }
+
+    /// Build the cross-sections along one subpath.
+    /// 沿一个子路径构建截面。
+    fn stroke_stations(contour: &Contour, style: &StrokeStyle, aa: f32, round_step: f32, stations: &mut Vec<Station>) {
+        let points = &contour.points;
+        let n = points.len();
+        if n < 2 {
+            return;
+        }
+        let closed = contour.closed && n >= 3;
+        let half_width = style.width * 0.5;
+
+        if closed {
+            for i in 0..n {
+                Self::join_stations(points[(i + n - 1) % n], points[i], points[(i + 1) % n], style, round_step, stations);
+            }
+            let first = stations[0];
+            stations.push(first);
+            return;
+        }
+
+        let start_direction = (points[1] - points[0]).normalize_or_zero();
+        Self::cap_stations(points[0], -start_direction, style.cap, half_width, aa, round_step, stations);
+        for i in 1..n - 1 {
+            Self::join_stations(points[i - 1], points[i], points[i + 1], style, round_step, stations);
+        }
+        let end_direction = (points[n - 1] - points[n - 2]).normalize_or_zero();
+        let end = stations.len();
+        Self::cap_stations(points[n - 1], end_direction, style.cap, half_width, aa, round_step, stations);
+        // End caps are built outward; walk them back in | 终点端帽向外构建；反向排列
+        stations[end..].reverse();
+        for station in &mut stations[end..] {
+            std::mem::swap(&mut station.left, &mut station.right);
+        }
+    }
+
+    /// Cross-sections of a cap pointing along `outward` from `point`.
+    /// 从`point`沿`outward`方向的端帽截面，由外向内排列。
+    fn cap_stations(
+        point: Vec2,
+        outward: Vec2,
+        cap: LineCap,
+        half_width: f32,
+        aa: f32,
+        round_step: f32,
+        stations: &mut Vec<Station>,
+    ) {
+        // Left of the inward direction | 向内方向的左侧
+        let normal = Vec2::new(outward.y, -outward.x);
+        match cap {
+            LineCap::Round => {
+                let steps = ((std::f32::consts::FRAC_PI_2 / round_step).ceil() as usize).max(2);
+                for k in 0..=steps {
+                    let (sin, cos) = (std::f32::consts::FRAC_PI_2 * k as f32 / steps as f32).sin_cos();
+                    stations.push(Station {
+                        center: point,
+                        left: outward * cos + normal * sin,
+                        right: outward * cos - normal * sin,
+                        alpha: 1.0,
+                    });
+                }
+            }
+            LineCap::Butt | LineCap::Square => {
+                let extend = if cap == LineCap::Square { half_width } else { 0.0 };
+                let section = |distance: f32, alpha: f32| Station {
+                    center: point + outward * distance,
+                    left: normal,
+                    right: -normal,
+                    alpha,
+                };
+                if aa > 0.0 {
+                    stations.push(section(extend + aa * 0.5, 0.0));
+                    stations.push(section(extend - aa * 0.5, 1.0));
+                } else {
+                    stations.push(section(extend, 1.0));
+                }
+            }
+        }
+    }
+
+    /// Cross-sections of the corner at `point`.
+    /// `point`处拐角的截面。
+    fn join_stations(
+        previous: Vec2,
+        point: Vec2,
+        next: Vec2,
+        style: &StrokeStyle,
+        round_step: f32,
+        stations: &mut Vec<Station>,
+    ) {
+        let d0 = (point - previous).normalize_or_zero();
+        let d1 = (next - point).normalize_or_zero();
+        let n0 = d0.perp();
+        let n1 = d1.perp();
+        let turn = d0.perp_dot(d1);
+
+        let miter_vector = miter(n0, n1, f32::MAX);
+        let miter_length = miter_vector.length();
+        if (turn.abs() <= 1e-4 && d0.dot(d1) > 0.0)
+            || (style.join == LineJoin::Miter && miter_length <= style.miter_limit)
+        {
+            stations.push(Station { center: point, left: miter_vector, right: -miter_vector, alpha: 1.0 });
+            return;
+        }
+
+        // Inner side meets at the miter, kept within the shorter segment | 内侧在斜接点相交，不超过较短线段
+        let half_width = style.width * 0.5;
+        let limit = ((point - previous).length().min((next - point).length()) / half_width).max(1.0);
+        let inner = if miter_length > limit { miter_vector * (limit / miter_length) } else { miter_vector };
+
+        // Outer side from the previous normal to the next | 外侧从前一法线到后一法线
+        let (from, to) = if turn > 0.0 { (-n0, -n1) } else { (n0, n1) };
+        let angle = from.perp_dot(to).atan2(from.dot(to));
+        let steps = match style.join {
+            LineJoin::Round => ((angle.abs() / round_step).ceil() as usize).max(1),
+            _ => 1,
+        };
+        for k in 0..=steps {
+            let outer = Vec2::from_angle(angle * k as f32 / steps as f32).rotate(from);
+            let (left, right) = if turn > 0.0 { (inner, outer) } else { (outer, -inner) };
+            stations.push(Station { center: point, left, right, alpha: 1.0 });
+        }
+    }
+
+    /// Emit quads between consecutive stations: fringe, core, fringe.
+    /// 在相邻截面之间生成四边形：边缘、核心、边缘。
+    fn push_strip(&mut self, stations: &[Station], core: f32, outer: f32, coverage: f32) {
+        if stations.len() < 2 {
+            return;
+        }
+        let base = self.positions.len() as u32;
+        for station in stations {
+            let alpha = station.alpha * coverage;
+            self.positions.extend_from_slice(&[
+                station.center + station.left * outer,
+                station.center + station.left * core,
+                station.center + station.right * core,
+                station.center + station.right * outer,
+            ]);
+            self.coverage.extend_from_slice(&[0.0, alpha, alpha, 0.0]);
+        }
+        for s in 0..stations.len() as u32 - 1 {
+            let a = base + s * 4;
+            let b = a + 4;
+            for k in 0..3 {
+                self.push_quad([a + k, a + k + 1, b + k + 1, b + k]);
+            }
+        }
+    }
+
+    /// Split triangles crossing the lines where `(p - origin) · axis` equals each offset.
+    /// 拆分跨越`(p - origin) · axis`等于各偏移的直线的三角形。
+    ///
+    /// Lets per-vertex colors reproduce every stop of a linear gradient exactly.
+    /// 使逐顶点颜色能精确再现线性渐变的每个色标。
+    pub fn split_along(&mut self, origin: Vec2, axis: Vec2, offsets: &[f32]) {
+        if axis == Vec2::ZERO {
+            return;
+        }
+        for &offset in offsets {
+            let triangles = std::mem::take(&mut self.indices);
+            for triangle in triangles.chunks_exact(3) {
+                let distance: [f32; 3] =
+                    std::array::from_fn(|k| (self.positions[triangle[k] as usize] - origin).dot(axis) - offset);
+                if distance.iter().all(|&d| d >= 0.0) || distance.iter().all(|&d| d <= 0.0) {
+                    self.indices.extend_from_slice(triangle);
+                    continue;
+                }
+
+                // Clip into the two sides, then fan each | 裁剪为两侧，然后各自扇形三角化
+                let mut above: Vec<u32> = Vec::with_capacity(4);
+                let mut below: Vec<u32> = Vec::with_capacity(4);
+                for k in 0..3 {
+                    let (i, j) = (triangle[k], triangle[(k + 1) % 3]);
+                    let (di, dj) = (distance[k], distance[(k + 1) % 3]);
+                    if di >= 0.0 {
+                        above.push(i);
+                    }
+                    if di <= 0.0 {
+                        below.push(i);
+                    }
+                    if di * dj < 0.0 {
+                        let t = di / (di - dj);
+                        let vertex = self.positions.len() as u32;
+                        let (pi, pj) = (self.positions[i as usize], self.positions[j as usize]);
+                        let (ci, cj) = (self.coverage[i as usize], self.coverage[j as usize]);
+                        self.positions.push(pi + (pj - pi) * t);
+                        self.coverage.push(ci + (cj - ci) * t);
+                        above.push(vertex);
+                        below.push(vertex);
+                    }
+                }
+                for polygon in [above, below] {
+                    for k in 1..polygon.len().saturating_sub(1) {
+                        self.indices.extend_from_slice(&[polygon[0], polygon[k], polygon[k + 1]]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn push_quad(&mut self, [a, b, c, d]: [u32; 4]) {
+        self.indices.extend_from_slice(&[a, b, c, c, d, a]);
+    }
+}
+
+/// Signed polygon area (positive = counter-clockwise).
+/// 有符号多边形面积（正值 = 逆时针）。
+fn signed_area(points: &[Vec2]) -> f32 {
+    let n = points.len();
+    (0..n).map(|i| points[i].perp_dot(points[(i + 1) % n])).sum::<f32>() * 0.5
+}
+
+/// Outward normal of a counter-clockwise edge.
+/// 逆时针边的外法线。
+fn outward_normal(edge: Vec2) -> Vec2 {
+    -edge.normalize_or_zero().perp()
+}
+
+/// Offset that moves both adjacent edges by one unit along their normals.
+/// 使两条相邻边沿各自法线移动一个单位的偏移。
+fn miter(n0: Vec2, n1: Vec2, max_length: f32) -> Vec2 {
+    let denominator = 1.0 + n0.dot(n1);
+    if denominator <= 1e-4 {
+        return n1;
+    }
+    let offset = (n0 + n1) / denominator;
+    let length = offset.length();
+    if length > max_length { offset * (max_length / length) } else { offset }
+}
+
+/// Ear-clip a counter-clockwise simple polygon.
+/// 对逆时针简单多边形进行耳切三角化。
+fn triangulate(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len().saturating_sub(2));
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let ear = (0..m).find(|&k| {
+            let (a, b, c) = (remaining[(k + m - 1) % m], remaining[k], remaining[(k + 1) % m]);
+            let (pa, pb, pc) = (points[a], points[b], points[c]);
+            if (pb - pa).perp_dot(pc - pb) <= 0.0 {
+                return false;
+            }
+            remaining.iter().all(|&other| {
+                other == a || other == b || other == c || !point_in_triangle(points[other], pa, pb, pc)
+            })
+        });
+
+        match ear {
+            Some(k) => {
+                let (a, b, c) = (remaining[(k + m - 1) % m], remaining[k], remaining[(k + 1) % m]);
+                triangles.push([a, b, c]);
+                remaining.remove(k);
+            }
+            // Degenerate or self-intersecting: fan the rest | 退化或自相交：将剩余部分扇形三角化
+            None => break,
+        }
+    }
+
+    for k in 1..remaining.len().saturating_sub(1) {
+        triangles.push([remaining[0], remaining[k], remaining[k + 1]]);
+    }
+    triangles
+}
+
+fn point_in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) > 0.0 && (c - b).perp_dot(p - b) > 0.0 && (a - c).perp_dot(p - c) > 0.0
+}
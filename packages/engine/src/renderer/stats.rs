@@ -0,0 +1,240 @@
+//! Per-frame render statistics and GPU timing.
+//! 每帧渲染统计和GPU计时。
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+use web_sys::{WebGl2RenderingContext, WebGlQuery};
+
+/// Number of values in `RenderStats::to_array`.
+/// `RenderStats::to_array`中的值数量。
+pub const RENDER_STATS_STRIDE: usize = 10;
+
+/// `TIME_ELAPSED_EXT` from `EXT_disjoint_timer_query_webgl2`.
+/// 来自`EXT_disjoint_timer_query_webgl2`的`TIME_ELAPSED_EXT`。
+const TIME_ELAPSED_EXT: u32 = 0x88BF;
+
+/// `GPU_DISJOINT_EXT` from `EXT_disjoint_timer_query_webgl2`.
+/// 来自`EXT_disjoint_timer_query_webgl2`的`GPU_DISJOINT_EXT`。
+const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+
+/// Queries in flight before timing is skipped for a frame.
+/// 跳过某帧计时之前允许的进行中查询数。
+const MAX_PENDING_QUERIES: usize = 4;
+
+/// Work done by the renderer in one frame.
+/// 渲染器在一帧中完成的工作量。
+///
+/// Counts cover scene rendering (sprites, tilemaps, particles, text, shapes,
+/// meshes, lighting, blits and post-processing); the editor grid and gizmos
+/// are not counted.
+/// 统计涵盖场景渲染（精灵、瓦片地图、粒子、文本、图形、网格、光照、拷贝和后处理）；
+/// 编辑器网格和Gizmo不计入。
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Draw calls issued.
+    /// 发出的绘制调用数。
+    pub draw_calls: u32,
+    /// Sprite runs drawn (sprites sharing material and texture).
+    /// 绘制的精灵批次数（共享材质和纹理的精灵）。
+    pub batches: u32,
+    /// Sprites submitted this frame.
+    /// 本帧提交的精灵数。
+    pub sprites_submitted: u32,
+    /// Sprites skipped by view culling in the last world pass.
+    /// 最后一个世界通道中被视图剔除跳过的精灵数。
+    pub sprites_culled: u32,
+    /// Vertices written to GPU buffers.
+    /// 写入GPU缓冲区的顶点数。
+    pub vertices_uploaded: u32,
+    /// Sprite instances written to GPU buffers.
+    /// 写入GPU缓冲区的精灵实例数。
+    pub instances_uploaded: u32,
+    /// Texture binds.
+    /// 纹理绑定次数。
+    pub texture_binds: u32,
+    /// Shader program binds.
+    /// 着色器程序绑定次数。
+    pub shader_switches: u32,
+    /// Material changes.
+    /// 材质切换次数。
+    pub material_switches: u32,
+    /// GPU time of a recent frame in milliseconds, when timer queries are enabled and supported.
+    /// 最近一帧的GPU时间（毫秒），需启用且支持计时查询。
+    pub gpu_time_ms: Option<f32>,
+}
+
+impl RenderStats {
+    /// Pack as [drawCalls, batches, spritesSubmitted, spritesCulled, verticesUploaded,
+    /// instancesUploaded, textureBinds, shaderSwitches, materialSwitches, gpuTimeMs (-1 if unknown)].
+    /// 打包为数组，GPU时间未知时为-1。
+    pub fn to_array(&self) -> [f32; RENDER_STATS_STRIDE] {
+        [
+            self.draw_calls as f32,
+            self.batches as f32,
+            self.sprites_submitted as f32,
+            self.sprites_culled as f32,
+            self.vertices_uploaded as f32,
+            self.instances_uploaded as f32,
+            self.texture_binds as f32,
+            self.shader_switches as f32,
+            self.material_switches as f32,
+            self.gpu_time_ms.unwrap_or(-1.0),
+        ]
+    }
+
+    /// Take the counters of the finished frame and start a new one.
+    /// 取出已完成帧的计数器并开始新的一帧。
+    pub(crate) fn take_frame() -> Self {
+        FRAME.with(Cell::take)
+    }
+
+    /// Run `draw` without updating the counters.
+    /// 运行`draw`而不更新计数器。
+    ///
+    /// Used for the editor grid and gizmos, which are not counted.
+    /// 用于不计入统计的编辑器网格和Gizmo。
+    pub(crate) fn uncounted<R>(draw: impl FnOnce() -> R) -> R {
+        let was_paused = PAUSED.with(|paused| paused.replace(true));
+        let result = draw();
+        PAUSED.with(|paused| paused.set(was_paused));
+        result
+    }
+}
+
+thread_local! {
+    /// Counters of the frame being rendered.
+    /// 正在渲染的帧的计数器。
+    static FRAME: Cell<RenderStats> = Cell::new(RenderStats::default());
+
+    /// Whether recording is paused for editor-only drawing.
+    /// 是否因编辑器专用绘制而暂停记录。
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Update the counters of the frame being rendered.
+/// 更新正在渲染的帧的计数器。
+#[inline]
+pub(crate) fn record(update: impl FnOnce(&mut RenderStats)) {
+    if PAUSED.with(Cell::get) {
+        return;
+    }
+    FRAME.with(|frame| {
+        let mut stats = frame.get();
+        update(&mut stats);
+        frame.set(stats);
+    });
+}
+
+/// Count one draw call.
+/// 计数一次绘制调用。
+#[inline]
+pub(crate) fn record_draw_call() {
+    record(|stats| stats.draw_calls += 1);
+}
+
+/// Count vertices written to a GPU buffer.
+/// 计数写入GPU缓冲区的顶点。
+#[inline]
+pub(crate) fn record_vertices(count: usize) {
+    record(|stats| stats.vertices_uploaded += count as u32);
+}
+
+/// Measures GPU frame time with `EXT_disjoint_timer_query_webgl2`.
+/// 使用`EXT_disjoint_timer_query_webgl2`测量GPU帧时间。
+///
+/// Results arrive a few frames late; `poll` returns the most recent one.
+/// 结果会延迟几帧到达；`poll`返回最近的结果。
+pub struct GpuTimer {
+    /// Ended queries waiting for results, oldest first.
+    /// 等待结果的已结束查询，按时间先后排列。
+    pending: VecDeque<WebGlQuery>,
+
+    /// Query between `begin` and `end`.
+    /// 处于`begin`和`end`之间的查询。
+    active: Option<WebGlQuery>,
+
+    /// Queries ready for reuse.
+    /// 可复用的查询。
+    free: Vec<WebGlQuery>,
+
+    /// Latest result in milliseconds.
+    /// 最近的结果（毫秒）。
+    last_ms: Option<f32>,
+}
+
+impl GpuTimer {
+    /// Create a timer, or `None` if the extension is unavailable.
+    /// 创建计时器，扩展不可用时返回`None`。
+    pub fn new(gl: &WebGl2RenderingContext) -> Option<Self> {
+        gl.get_extension("EXT_disjoint_timer_query_webgl2").ok().flatten()?;
+        Some(Self { pending: VecDeque::new(), active: None, free: Vec::new(), last_ms: None })
+    }
+
+    /// Start timing GPU work.
+    /// 开始计时GPU工作。
+    pub fn begin(&mut self, gl: &WebGl2RenderingContext) {
+        if self.active.is_some() || self.pending.len() >= MAX_PENDING_QUERIES {
+            return;
+        }
+        let Some(query) = self.free.pop().or_else(|| gl.create_query()) else {
+            return;
+        };
+        gl.begin_query(TIME_ELAPSED_EXT, &query);
+        self.active = Some(query);
+    }
+
+    /// Stop timing GPU work.
+    /// 停止计时GPU工作。
+    pub fn end(&mut self, gl: &WebGl2RenderingContext) {
+        if let Some(query) = self.active.take() {
+            gl.end_query(TIME_ELAPSED_EXT);
+            self.pending.push_back(query);
+        }
+    }
+
+    /// Collect finished queries and return the latest GPU time in milliseconds.
+    /// 收集已完成的查询并返回最近的GPU时间（毫秒）。
+    ///
+    /// Results are dropped when the GPU reports a disjoint event (e.g. a clock change).
+    /// GPU报告不连续事件（如时钟变化）时丢弃结果。
+    pub fn poll(&mut self, gl: &WebGl2RenderingContext) -> Option<f32> {
+        let disjoint = gl
+            .get_parameter(GPU_DISJOINT_EXT)
+            .ok()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+
+        while let Some(query) = self.pending.front() {
+            let available = gl
+                .get_query_parameter(query, WebGl2RenderingContext::QUERY_RESULT_AVAILABLE)
+                .as_bool()
+                .unwrap_or(false);
+            if !available {
+                break;
+            }
+            let Some(query) = self.pending.pop_front() else {
+                break;
+            };
+            if !disjoint {
+                let nanoseconds = gl.get_query_parameter(&query, WebGl2RenderingContext::QUERY_RESULT).as_f64();
+                if let Some(nanoseconds) = nanoseconds {
+                    self.last_ms = Some((nanoseconds / 1.0e6) as f32);
+                }
+            }
+            self.free.push(query);
+        }
+        self.last_ms
+    }
+
+    /// Delete all queries.
+    /// 删除所有查询。
+    pub fn destroy(&mut self, gl: &WebGl2RenderingContext) {
+        if self.active.is_some() {
+            gl.end_query(TIME_ELAPSED_EXT);
+        }
+        for query in self.active.take().into_iter().chain(self.pending.drain(..)).chain(self.free.drain(..)) {
+            gl.delete_query(Some(&query));
+        }
+    }
+}
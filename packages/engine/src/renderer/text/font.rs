@@ -0,0 +1,271 @@
+//! BMFont font definitions.
+//! BMFont字体定义。
+
+use std::collections::HashMap;
+
+use crate::core::error::{EngineError, Result};
+
+/// Most pages a font may declare.
+/// 字体可声明的最大页面数。
+const MAX_FONT_PAGES: usize = 256;
+
+/// How glyph pixels are stored in the font pages.
+/// 字形像素在字体页面中的存储方式。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontKind {
+    /// Plain bitmap glyphs.
+    /// 普通位图字形。
+    #[default]
+    Bitmap,
+    /// Single-channel signed distance field in alpha.
+    /// alpha通道中的单通道有向距离场。
+    Sdf,
+    /// Multi-channel signed distance field in RGB.
+    /// RGB中的多通道有向距离场。
+    Msdf,
+}
+
+impl FontKind {
+    /// Create from a JavaScript code (1 = bitmap, 2 = SDF, 3 = MSDF, other = keep).
+    /// 从JavaScript代码创建（1 = 位图，2 = SDF，3 = MSDF，其他 = 保持）。
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::Bitmap),
+            2 => Some(Self::Sdf),
+            3 => Some(Self::Msdf),
+            _ => None,
+        }
+    }
+
+    /// Check whether the font uses a distance field.
+    /// 检查字体是否使用距离场。
+    #[inline]
+    pub fn is_distance_field(&self) -> bool {
+        !matches!(self, Self::Bitmap)
+    }
+}
+
+/// A glyph in a font page, in texels.
+/// 字体页面中的字形（纹素）。
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Glyph {
+    /// Left of the glyph in its page.
+    /// 字形在页面中的左边。
+    pub x: f32,
+    /// Top of the glyph in its page.
+    /// 字形在页面中的顶边。
+    pub y: f32,
+    /// Glyph width.
+    /// 字形宽度。
+    pub width: f32,
+    /// Glyph height.
+    /// 字形高度。
+    pub height: f32,
+    /// Horizontal offset from the pen position.
+    /// 相对于笔位置的水平偏移。
+    pub x_offset: f32,
+    /// Vertical offset from the line top (down).
+    /// 相对于行顶部的垂直偏移（向下）。
+    pub y_offset: f32,
+    /// Pen advance after the glyph.
+    /// 字形之后的笔前进量。
+    pub x_advance: f32,
+    /// Page index.
+    /// 页面索引。
+    pub page: u32,
+}
+
+/// A bitmap or distance-field font.
+/// 位图或距离场字体。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::text::{Font, FontKind};
+/// let fnt = r#"info face="Test" size=32
+/// common lineHeight=36 base=28 scaleW=256 scaleH=128 pages=1
+/// page id=0 file="test.png"
+/// char id=65 x=0 y=0 width=20 height=24 xoffset=1 yoffset=4 xadvance=21 page=0
+/// char id=86 x=20 y=0 width=20 height=24 xoffset=0 yoffset=4 xadvance=20 page=0
+/// kerning first=65 second=86 amount=-3
+/// distanceField fieldType=msdf distanceRange=4"#;
+/// let font = Font::parse_fnt(fnt).unwrap();
+/// assert_eq!(font.kind, FontKind::Msdf);
+/// assert_eq!(font.pages, vec!["test.png".to_string()]);
+/// assert_eq!(font.kerning('A', 'V'), -3.0);
+/// assert_eq!(font.glyph('V').unwrap().x, 20.0);
+///
+/// // Page IDs must be below the declared page count | 页面ID必须小于声明的页面数
+/// assert!(Font::parse_fnt(&fnt.replace("page id=0", "page id=4000000000")).is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Font {
+    /// Size the font was generated at, in pixels.
+    /// 字体生成时的尺寸（像素）。
+    pub size: f32,
+    /// Distance between lines.
+    /// 行间距。
+    pub line_height: f32,
+    /// Distance from the line top to the baseline.
+    /// 从行顶部到基线的距离。
+    pub base: f32,
+    /// Page width in texels.
+    /// 页面宽度（纹素）。
+    pub scale_w: f32,
+    /// Page height in texels.
+    /// 页面高度（纹素）。
+    pub scale_h: f32,
+    /// Glyph storage.
+    /// 字形存储方式。
+    pub kind: FontKind,
+    /// Distance field range in texels.
+    /// 距离场范围（纹素）。
+    pub distance_range: f32,
+    /// Page file names.
+    /// 页面文件名。
+    pub pages: Vec<String>,
+    glyphs: HashMap<char, Glyph>,
+    kernings: HashMap<(char, char), f32>,
+}
+
+impl Font {
+    /// Parse a text-format BMFont (.fnt) file.
+    /// 解析文本格式的BMFont（.fnt）文件。
+    ///
+    /// A `distanceField` line, as written by msdf-bmfont, marks SDF/MSDF fonts.
+    /// msdf-bmfont写入的`distanceField`行用于标记SDF/MSDF字体。
+    pub fn parse_fnt(source: &str) -> Result<Self> {
+        let mut font = Self { distance_range: 4.0, ..Self::default() };
+        let mut page_count = MAX_FONT_PAGES;
+
+        for line in source.lines() {
+            let (tag, attributes) = parse_line(line);
+            let number = |key: &str| -> f32 {
+                attributes
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .and_then(|(_, v)| v.parse().ok())
+                    .unwrap_or(0.0)
+            };
+
+            match tag {
+                "info" => font.size = number("size").abs(),
+                "common" => {
+                    font.line_height = number("lineHeight");
+                    font.base = number("base");
+                    font.scale_w = number("scaleW");
+                    font.scale_h = number("scaleH");
+                    let declared = number("pages") as usize;
+                    if declared > 0 {
+                        page_count = declared.min(MAX_FONT_PAGES);
+                    }
+                }
+                "page" => {
+                    let id = number("id") as usize;
+                    if id >= page_count {
+                        return Err(EngineError::InvalidFont(format!(
+                            "Page id {} is not below the page count {} | 页面ID {} 不小于页面数 {}",
+                            id, page_count, id, page_count
+                        )));
+                    }
+                    let file = attributes
+                        .iter()
+                        .find(|(k, _)| *k == "file")
+                        .map_or("", |(_, v)| v);
+                    if font.pages.len() <= id {
+                        font.pages.resize(id + 1, String::new());
+                    }
+                    font.pages[id] = file.to_string();
+                }
+                "char" => {
+                    if let Some(c) = char::from_u32(number("id") as u32) {
+                        font.glyphs.insert(c, Glyph {
+                            x: number("x"),
+                            y: number("y"),
+                            width: number("width"),
+                            height: number("height"),
+                            x_offset: number("xoffset"),
+                            y_offset: number("yoffset"),
+                            x_advance: number("xadvance"),
+                            page: number("page") as u32,
+                        });
+                    }
+                }
+                "kerning" => {
+                    let first = char::from_u32(number("first") as u32);
+                    let second = char::from_u32(number("second") as u32);
+                    if let (Some(first), Some(second)) = (first, second) {
+                        font.kernings.insert((first, second), number("amount"));
+                    }
+                }
+                "distanceField" => {
+                    let field_type = attributes
+                        .iter()
+                        .find(|(k, _)| *k == "fieldType")
+                        .map_or("sdf", |(_, v)| v);
+                    font.kind = if field_type.eq_ignore_ascii_case("msdf") { FontKind::Msdf } else { FontKind::Sdf };
+                    font.distance_range = number("distanceRange").max(1.0);
+                }
+                _ => {}
+            }
+        }
+
+        if font.glyphs.is_empty() || font.scale_w <= 0.0 || font.scale_h <= 0.0 {
+            return Err(EngineError::InvalidFont(
+                "Font has no glyphs or page size | 字体没有字形或页面尺寸".into(),
+            ));
+        }
+        if font.size <= 0.0 {
+            font.size = font.line_height;
+        }
+        Ok(font)
+    }
+
+    /// Get a glyph.
+    /// 获取字形。
+    #[inline]
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c)
+    }
+
+    /// Kerning adjustment between two characters.
+    /// 两个字符之间的字距调整。
+    #[inline]
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        self.kernings.get(&(first, second)).copied().unwrap_or(0.0)
+    }
+
+    /// Page UV rect `[u0, v0, u1, v1]` of a glyph (v0 = top).
+    /// 字形的页面UV矩形（v0 = 顶部）。
+    #[inline]
+    pub fn glyph_uv(&self, glyph: &Glyph) -> [f32; 4] {
+        [
+            glyph.x / self.scale_w,
+            glyph.y / self.scale_h,
+            (glyph.x + glyph.width) / self.scale_w,
+            (glyph.y + glyph.height) / self.scale_h,
+        ]
+    }
+}
+
+/// Split a BMFont line into its tag and `key=value` attributes.
+/// 将BMFont行拆分为标签和`key=value`属性。
+fn parse_line(line: &str) -> (&str, Vec<(&str, &str)>) {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let mut attributes = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        let Some((key, after)) = rest.split_once('=') else {
+            break;
+        };
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(char::is_whitespace).unwrap_or((after, "")),
+        };
+        attributes.push((key.trim(), value));
+        rest = remaining;
+    }
+
+    (tag, attributes)
+}
@@ -0,0 +1,292 @@
+//! Text layout: kerning, wrapping and alignment.
+//! 文本布局：字距调整、换行和对齐。
+
+use std::ops::Range;
+
+use crate::core::error::{EngineError, Result};
+use super::super::batch::SortKey;
+use super::font::Font;
+
+/// Packed text style stride
+/// (size, maxWidth, align, lineSpacing, letterSpacing, rotation, layer, orderInLayer).
+/// 打包文本样式步长。
+pub const TEXT_STYLE_STRIDE: usize = 8;
+
+/// Horizontal alignment around the text position.
+/// 围绕文本位置的水平对齐。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    /// Lines start at the position.
+    /// 行从位置开始。
+    #[default]
+    Left,
+    /// Lines are centered on the position.
+    /// 行以位置为中心。
+    Center,
+    /// Lines end at the position.
+    /// 行在位置结束。
+    Right,
+}
+
+impl TextAlign {
+    /// Create from a JavaScript code (0 = left, 1 = center, 2 = right).
+    /// 从JavaScript代码创建（0 = 左，1 = 中，2 = 右）。
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Center,
+            2 => Self::Right,
+            _ => Self::Left,
+        }
+    }
+}
+
+/// Text size, layout and placement.
+/// 文本尺寸、布局和放置。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle {
+    /// Font size in world units (0 = the font's own size).
+    /// 字体尺寸（世界单位，0 = 字体自身尺寸）。
+    pub size: f32,
+    /// Packed text color (ABGR, as for sprites).
+    /// 打包的文本颜色（ABGR，与精灵相同）。
+    pub color: u32,
+    /// Wrap width in world units (0 = no wrapping).
+    /// 换行宽度（世界单位，0 = 不换行）。
+    pub max_width: f32,
+    /// Horizontal alignment.
+    /// 水平对齐。
+    pub align: TextAlign,
+    /// Line height multiplier.
+    /// 行高乘数。
+    pub line_spacing: f32,
+    /// Extra space between characters in world units.
+    /// 字符之间的额外间距（世界单位）。
+    pub letter_spacing: f32,
+    /// Rotation around the text position in radians.
+    /// 围绕文本位置的旋转（弧度）。
+    pub rotation: f32,
+    /// Draw order relative to sprites.
+    /// 相对于精灵的绘制顺序。
+    pub sort: SortKey,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            size: 0.0,
+            color: 0xFFFF_FFFF,
+            max_width: 0.0,
+            align: TextAlign::Left,
+            line_spacing: 1.0,
+            letter_spacing: 0.0,
+            rotation: 0.0,
+            sort: SortKey::default(),
+        }
+    }
+}
+
+impl TextStyle {
+    /// Build from packed data (see `TEXT_STYLE_STRIDE`).
+    /// 从打包数据构建（参见`TEXT_STYLE_STRIDE`）。
+    pub fn from_data(data: &[f32], color: u32) -> Result<Self> {
+        if data.len() != TEXT_STYLE_STRIDE {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Text style length mismatch: expected {}, got {}",
+                TEXT_STYLE_STRIDE,
+                data.len()
+            )));
+        }
+
+        Ok(Self {
+            size: data[0].max(0.0),
+            color,
+            max_width: data[1].max(0.0),
+            align: TextAlign::from_code(data[2] as u32),
+            line_spacing: if data[3] > 0.0 { data[3] } else { 1.0 },
+            letter_spacing: data[4],
+            rotation: data[5],
+            sort: SortKey::new(data[6] as i32, data[7] as i32),
+        })
+    }
+}
+
+/// A positioned glyph quad.
+/// 已定位的字形四边形。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphQuad {
+    /// Left edge, relative to the text position.
+    /// 左边缘，相对于文本位置。
+    pub x: f32,
+    /// Top edge, downwards from the text position.
+    /// 顶边缘，从文本位置向下。
+    pub y: f32,
+    /// Quad width.
+    /// 四边形宽度。
+    pub width: f32,
+    /// Quad height.
+    /// 四边形高度。
+    pub height: f32,
+    /// Page UV rect (v0 = top).
+    /// 页面UV矩形（v0 = 顶部）。
+    pub uv: [f32; 4],
+    /// Font page index.
+    /// 字体页面索引。
+    pub page: u32,
+}
+
+/// Result of laying out a string.
+/// 字符串布局结果。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::text::{Font, TextStyle, TextAlign};
+/// let font = Font::parse_fnt("common lineHeight=10 base=8 scaleW=64 scaleH=64\n\
+///     char id=32 xadvance=5\n\
+///     char id=97 width=5 height=6 yoffset=2 xadvance=6").unwrap();
+/// let style = TextStyle { max_width: 30.0, align: TextAlign::Center, ..TextStyle::default() };
+/// let layout = font.layout("aa aa aa", &style);
+/// assert_eq!(layout.lines, 2);
+/// assert_eq!(layout.width, 29.0);
+/// assert_eq!(layout.glyphs[0].x, -14.5);
+/// assert_eq!(layout.glyphs[4].y, 12.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextLayout {
+    /// Glyph quads in string order.
+    /// 按字符串顺序的字形四边形。
+    pub glyphs: Vec<GlyphQuad>,
+    /// Width of the widest line.
+    /// 最宽行的宽度。
+    pub width: f32,
+    /// Total height of all lines.
+    /// 所有行的总高度。
+    pub height: f32,
+    /// Number of lines.
+    /// 行数。
+    pub lines: usize,
+}
+
+impl Font {
+    /// Lay out a string.
+    /// 布局字符串。
+    ///
+    /// Lines break at `\n` and, when `max_width` is set, at spaces (or inside
+    /// words longer than a line). Positions are relative to the text position
+    /// with Y pointing down.
+    /// 行在`\n`处换行，设置`max_width`时还会在空格处（或超过一行的单词内部）换行。
+    /// 位置相对于文本位置，Y轴向下。
+    pub fn layout(&self, text: &str, style: &TextStyle) -> TextLayout {
+        let scale = if style.size > 0.0 { style.size / self.size } else { 1.0 };
+        let line_advance = self.line_height * style.line_spacing * scale;
+        let mut layout = TextLayout::default();
+
+        for paragraph in text.split('\n') {
+            let chars: Vec<char> = paragraph.chars().collect();
+            for range in self.wrap(&chars, scale, style) {
+                let line = &chars[range];
+                let line_width = self.measure(line, scale, style);
+                let line_top = layout.lines as f32 * line_advance;
+                layout.width = layout.width.max(line_width);
+                layout.lines += 1;
+
+                let mut pen = match style.align {
+                    TextAlign::Left => 0.0,
+                    TextAlign::Center => -line_width * 0.5,
+                    TextAlign::Right => -line_width,
+                };
+                let mut previous = None;
+                for &c in line {
+                    let Some(glyph) = self.glyph(c).or_else(|| self.glyph('?')) else {
+                        continue;
+                    };
+                    if let Some(previous) = previous {
+                        pen += self.kerning(previous, c) * scale;
+                    }
+                    if glyph.width > 0.0 && glyph.height > 0.0 {
+                        layout.glyphs.push(GlyphQuad {
+                            x: pen + glyph.x_offset * scale,
+                            y: line_top + glyph.y_offset * scale,
+                            width: glyph.width * scale,
+                            height: glyph.height * scale,
+                            uv: self.glyph_uv(glyph),
+                            page: glyph.page,
+                        });
+                    }
+                    pen += glyph.x_advance * scale + style.letter_spacing;
+                    previous = Some(c);
+                }
+            }
+        }
+
+        if layout.lines > 0 {
+            layout.height = (layout.lines - 1) as f32 * line_advance + self.line_height * scale;
+        }
+        layout
+    }
+
+    /// Advance of a character after `previous`, including kerning.
+    /// 字符在`previous`之后的前进量，包括字距调整。
+    fn advance(&self, previous: Option<char>, c: char, scale: f32, style: &TextStyle) -> f32 {
+        let Some(glyph) = self.glyph(c).or_else(|| self.glyph('?')) else {
+            return 0.0;
+        };
+        let kerning = previous.map_or(0.0, |p| self.kerning(p, c));
+        (kerning + glyph.x_advance) * scale + style.letter_spacing
+    }
+
+    /// Width of a line without trailing spaces.
+    /// 不含末尾空格的行宽。
+    fn measure(&self, line: &[char], scale: f32, style: &TextStyle) -> f32 {
+        let end = line.iter().rposition(|&c| c != ' ').map_or(0, |i| i + 1);
+        let mut width = 0.0;
+        let mut previous = None;
+        for &c in &line[..end] {
+            width += self.advance(previous, c, scale, style);
+            previous = Some(c);
+        }
+        // No letter spacing after the last character | 最后一个字符后没有字母间距
+        if end > 0 { width - style.letter_spacing } else { 0.0 }
+    }
+
+    /// Break a paragraph into line ranges.
+    /// 将段落拆分为行范围。
+    fn wrap(&self, chars: &[char], scale: f32, style: &TextStyle) -> Vec<Range<usize>> {
+        let mut lines = Vec::new();
+        if style.max_width <= 0.0 {
+            lines.push(0..chars.len());
+            return lines;
+        }
+
+        let mut start = 0;
+        let mut width = 0.0;
+        let mut last_space = None;
+        let mut previous = None;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let advance = self.advance(previous, c, scale, style);
+            if c != ' ' && i > start && width + advance > style.max_width {
+                // Break at the last space, or inside a long word | 在最后一个空格处换行，或在长单词内部换行
+                let end = last_space.filter(|&s| s > start).unwrap_or(i);
+                lines.push(start..end);
+                start = end;
+                while start < chars.len() && chars[start] == ' ' {
+                    start += 1;
+                }
+                i = start;
+                width = 0.0;
+                last_space = None;
+                previous = None;
+                continue;
+            }
+            if c == ' ' {
+                last_space = Some(i);
+            }
+            width += advance;
+            previous = Some(c);
+            i += 1;
+        }
+        lines.push(start..chars.len());
+        lines
+    }
+}
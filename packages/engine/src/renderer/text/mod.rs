@@ -0,0 +1,10 @@
+//! Bitmap and distance-field text rendering.
+//! 位图和距离场文本渲染。
+
+mod font;
+mod layout;
+mod text_renderer;
+
+pub use font::{Font, FontKind, Glyph};
+pub use layout::{GlyphQuad, TextAlign, TextLayout, TextStyle, TEXT_STYLE_STRIDE};
+pub use text_renderer::TextRenderer;
@@ -0,0 +1,173 @@
+//! Font registry and glyph submission.
+//! 字体注册表和字形提交。
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::core::error::{EngineError, Result};
+use super::super::batch::SpriteBatchData;
+use super::font::Font;
+use super::layout::{TextLayout, TextStyle};
+
+/// A font with its page textures and material.
+/// 字体及其页面纹理和材质。
+struct LoadedFont {
+    font: Font,
+    /// Texture ID for each page.
+    /// 每个页面的纹理ID。
+    page_textures: Vec<u32>,
+    /// Material glyphs are drawn with (0 = default sprite material).
+    /// 绘制字形使用的材质（0 = 默认精灵材质）。
+    material_id: u32,
+}
+
+/// Owns loaded fonts and turns strings into sprite batch data.
+/// 持有已加载的字体，并将字符串转换为精灵批次数据。
+///
+/// Glyphs are submitted as ordinary sprites, so text sorts and batches with
+/// the rest of the scene.
+/// 字形作为普通精灵提交，因此文本与场景其余部分一起排序和批处理。
+pub struct TextRenderer {
+    /// Fonts by ID.
+    /// 按ID存储的字体。
+    fonts: HashMap<u32, LoadedFont>,
+
+    /// Next font ID.
+    /// 下一个字体ID。
+    next_id: u32,
+
+    /// Reused submission buffers.
+    /// 复用的提交缓冲区。
+    transforms: Vec<f32>,
+    texture_ids: Vec<u32>,
+    uvs: Vec<f32>,
+    colors: Vec<u32>,
+    material_ids: Vec<u32>,
+    sort_keys: Vec<f32>,
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextRenderer {
+    /// Create an empty text renderer.
+    /// 创建空的文本渲染器。
+    pub fn new() -> Self {
+        Self {
+            fonts: HashMap::new(),
+            next_id: 1,
+            transforms: Vec::new(),
+            texture_ids: Vec::new(),
+            uvs: Vec::new(),
+            colors: Vec::new(),
+            material_ids: Vec::new(),
+            sort_keys: Vec::new(),
+        }
+    }
+
+    /// Add a font, returning its ID.
+    /// 添加字体，返回其ID。
+    ///
+    /// # Arguments | 参数
+    /// * `font` - Parsed font | 已解析的字体
+    /// * `page_textures` - Texture ID for each font page | 每个字体页面的纹理ID
+    /// * `material_id` - Material to draw glyphs with | 绘制字形使用的材质
+    pub fn add_font(&mut self, font: Font, page_textures: Vec<u32>, material_id: u32) -> Result<u32> {
+        if page_textures.len() < font.pages.len().max(1) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Font needs {} page textures, got {}",
+                font.pages.len().max(1),
+                page_textures.len()
+            )));
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.fonts.insert(id, LoadedFont { font, page_textures, material_id });
+        Ok(id)
+    }
+
+    /// Remove a font, returning its material ID.
+    /// 移除字体，返回其材质ID。
+    pub fn remove_font(&mut self, id: u32) -> Option<u32> {
+        self.fonts.remove(&id).map(|f| f.material_id)
+    }
+
+    /// Get a font.
+    /// 获取字体。
+    #[inline]
+    pub fn font(&self, id: u32) -> Option<&Font> {
+        self.fonts.get(&id).map(|f| &f.font)
+    }
+
+    /// Lay out a string without submitting it.
+    /// 布局字符串但不提交。
+    pub fn measure(&self, font_id: u32, text: &str, style: &TextStyle) -> Result<TextLayout> {
+        let loaded = self.loaded(font_id)?;
+        Ok(loaded.font.layout(text, style))
+    }
+
+    /// Lay out a string and pack its glyphs as sprite batch data.
+    /// 布局字符串并将其字形打包为精灵批次数据。
+    ///
+    /// `position` is the anchor of the first line's top edge; glyph quads are
+    /// rotated around it by `style.rotation`.
+    /// `position`是第一行顶边的锚点；字形四边形围绕它旋转`style.rotation`。
+    pub fn build(&mut self, font_id: u32, text: &str, position: Vec2, style: &TextStyle) -> Result<SpriteBatchData<'_>> {
+        let loaded = self.fonts.get(&font_id).ok_or_else(|| {
+            EngineError::InvalidBatchData(format!("Font not found: {}", font_id))
+        })?;
+        let layout = loaded.font.layout(text, style);
+
+        self.transforms.clear();
+        self.texture_ids.clear();
+        self.uvs.clear();
+        self.colors.clear();
+        self.material_ids.clear();
+        self.sort_keys.clear();
+
+        let (sin, cos) = style.rotation.sin_cos();
+        let sort = [style.sort.layer as f32, style.sort.order as f32, 0.0];
+        for glyph in &layout.glyphs {
+            let Some(&texture_id) = loaded.page_textures.get(glyph.page as usize) else {
+                continue;
+            };
+            // Glyph top-left in world space (layout is Y-down) | 字形左上角的世界坐标（布局为Y向下）
+            let (lx, ly) = (glyph.x, -glyph.y);
+            self.transforms.extend_from_slice(&[
+                position.x + lx * cos - ly * sin,
+                position.y + lx * sin + ly * cos,
+                style.rotation,
+                glyph.width,
+                glyph.height,
+                0.0,
+                1.0,
+            ]);
+            self.texture_ids.push(texture_id);
+            self.uvs.extend_from_slice(&glyph.uv);
+            self.colors.push(style.color);
+            self.material_ids.push(loaded.material_id);
+            self.sort_keys.extend_from_slice(&sort);
+        }
+
+        Ok(SpriteBatchData {
+            transforms: &self.transforms,
+            texture_ids: &self.texture_ids,
+            uvs: &self.uvs,
+            colors: &self.colors,
+            material_ids: &self.material_ids,
+            sort_keys: &self.sort_keys,
+            pick_ids: &[],
+        })
+    }
+
+    fn loaded(&self, font_id: u32) -> Result<&LoadedFont> {
+        self.fonts
+            .get(&font_id)
+            .ok_or_else(|| EngineError::InvalidBatchData(format!("Font not found: {}", font_id)))
+    }
+}
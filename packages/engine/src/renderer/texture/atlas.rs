@@ -0,0 +1,290 @@
+//! Runtime texture atlas packing.
+//! 运行时纹理图集打包。
+//!
+//! The packer is pure Rust (skyline bottom-left) and does not touch WebGL,
+//! so layouts can be computed and verified without a GL context.
+//! 打包器为纯Rust实现（天际线左下算法），不依赖WebGL，
+//! 因此可以在没有GL上下文的情况下计算和验证布局。
+
+/// Rectangle allocated inside an atlas page, in texels.
+/// 图集页面中分配的矩形（纹素）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PackedRect {
+    /// Left edge.
+    /// 左边缘。
+    pub x: u32,
+    /// Top edge.
+    /// 上边缘。
+    pub y: u32,
+    /// Width.
+    /// 宽度。
+    pub width: u32,
+    /// Height.
+    /// 高度。
+    pub height: u32,
+}
+
+/// A horizontal segment of the skyline.
+/// 天际线的一个水平段。
+#[derive(Debug, Clone, Copy)]
+struct SkylineNode {
+    x: u32,
+    y: u32,
+    width: u32,
+}
+
+/// Skyline bottom-left rectangle packer.
+/// 天际线左下矩形打包器。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::AtlasPacker;
+/// let mut packer = AtlasPacker::new(64, 64, 0);
+/// let a = packer.pack(32, 32).unwrap();
+/// let b = packer.pack(32, 32).unwrap();
+/// assert_eq!((a.x, a.y), (0, 0));
+/// assert_eq!((b.x, b.y), (32, 0));
+/// assert!(packer.pack(65, 1).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AtlasPacker {
+    /// Page width in texels.
+    /// 页面宽度（纹素）。
+    width: u32,
+
+    /// Page height in texels.
+    /// 页面高度（纹素）。
+    height: u32,
+
+    /// Gap kept to the right of and below each rectangle.
+    /// 每个矩形右侧和下方保留的间隙。
+    padding: u32,
+
+    /// Skyline segments ordered by x.
+    /// 按x排序的天际线段。
+    skyline: Vec<SkylineNode>,
+
+    /// Texels covered by packed rectangles.
+    /// 已打包矩形覆盖的纹素数。
+    used_area: u64,
+}
+
+impl AtlasPacker {
+    /// Create a new packer for a page of the given size.
+    /// 为给定尺寸的页面创建新的打包器。
+    ///
+    /// # Arguments | 参数
+    /// * `width` - Page width | 页面宽度
+    /// * `height` - Page height | 页面高度
+    /// * `padding` - Gap between rectangles to avoid filtering bleed | 矩形间隙，避免过滤渗色
+    pub fn new(width: u32, height: u32, padding: u32) -> Self {
+        Self {
+            width,
+            height,
+            padding,
+            skyline: vec![SkylineNode { x: 0, y: 0, width }],
+            used_area: 0,
+        }
+    }
+
+    /// Page width.
+    /// 页面宽度。
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Page height.
+    /// 页面高度。
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Fraction of the page covered by packed rectangles (0.0-1.0).
+    /// 已打包矩形占页面的比例（0.0-1.0）。
+    pub fn occupancy(&self) -> f32 {
+        let total = self.width as u64 * self.height as u64;
+        if total == 0 {
+            return 0.0;
+        }
+        self.used_area as f32 / total as f32
+    }
+
+    /// Remove all rectangles.
+    /// 移除所有矩形。
+    pub fn reset(&mut self) {
+        self.skyline.clear();
+        self.skyline.push(SkylineNode { x: 0, y: 0, width: self.width });
+        self.used_area = 0;
+    }
+
+    /// Allocate a rectangle, returning `None` if it does not fit.
+    /// 分配矩形，放不下时返回`None`。
+    pub fn pack(&mut self, width: u32, height: u32) -> Option<PackedRect> {
+        if width == 0 || height == 0 || width > self.width || height > self.height {
+            return None;
+        }
+
+        // Padding is dropped when it would push a rectangle past the page edge.
+        // 当填充会使矩形超出页面边缘时省略填充。
+        let padded_w = (width + self.padding).min(self.width);
+        let padded_h = (height + self.padding).min(self.height);
+
+        // Pick the lowest top edge, then the narrowest segment | 选择最低的顶边，其次最窄的段
+        let mut best: Option<(usize, u32)> = None;
+        let mut best_top = u32::MAX;
+        let mut best_width = u32::MAX;
+
+        for index in 0..self.skyline.len() {
+            if let Some(y) = self.fit(index, padded_w, padded_h) {
+                let top = y + padded_h;
+                let node_width = self.skyline[index].width;
+                if top < best_top || (top == best_top && node_width < best_width) {
+                    best = Some((index, y));
+                    best_top = top;
+                    best_width = node_width;
+                }
+            }
+        }
+
+        let (index, y) = best?;
+        let x = self.skyline[index].x;
+        self.add_level(index, x, y, padded_w, padded_h);
+        self.used_area += width as u64 * height as u64;
+
+        Some(PackedRect { x, y, width, height })
+    }
+
+    /// Find the y position a rectangle would rest at when placed at a skyline node.
+    /// 计算矩形放置在某个天际线段上时的y位置。
+    fn fit(&self, index: usize, width: u32, height: u32) -> Option<u32> {
+        let x = self.skyline[index].x;
+        if x + width > self.width {
+            return None;
+        }
+
+        let mut remaining = width as i64;
+        let mut y = 0;
+        let mut i = index;
+        while remaining > 0 {
+            let node = self.skyline.get(i)?;
+            y = y.max(node.y);
+            if y + height > self.height {
+                return None;
+            }
+            remaining -= node.width as i64;
+            i += 1;
+        }
+
+        Some(y)
+    }
+
+    /// Raise the skyline over a newly placed rectangle.
+    /// 在新放置的矩形上方抬高天际线。
+    fn add_level(&mut self, index: usize, x: u32, y: u32, width: u32, height: u32) {
+        self.skyline.insert(index, SkylineNode { x, y: y + height, width });
+
+        // Trim segments now covered by the new one | 裁剪被新段覆盖的段
+        let i = index + 1;
+        while i < self.skyline.len() {
+            let prev_end = self.skyline[i - 1].x + self.skyline[i - 1].width;
+            let node = &mut self.skyline[i];
+            if node.x >= prev_end {
+                break;
+            }
+            let shrink = prev_end - node.x;
+            if node.width <= shrink {
+                self.skyline.remove(i);
+            } else {
+                node.x += shrink;
+                node.width -= shrink;
+                break;
+            }
+        }
+
+        // Merge neighbours at the same height | 合并相同高度的相邻段
+        let mut i = 0;
+        while i + 1 < self.skyline.len() {
+            if self.skyline[i].y == self.skyline[i + 1].y {
+                self.skyline[i].width += self.skyline[i + 1].width;
+                self.skyline.remove(i + 1);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Sub-texture region inside a shared atlas page.
+/// 共享图集页面中的子纹理区域。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::{AtlasRegion, PackedRect};
+/// let rect = PackedRect { x: 64, y: 0, width: 64, height: 32 };
+/// let region = AtlasRegion::new(7, rect, 256, 256);
+/// assert_eq!(region.page_texture_id, 7);
+/// assert_eq!(region.remap_uv([0.0, 0.0, 1.0, 1.0]), [0.25, 0.0, 0.5, 0.125]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasRegion {
+    /// Texture ID of the atlas page holding this region.
+    /// 包含此区域的图集页面纹理ID。
+    pub page_texture_id: u32,
+
+    /// Region rectangle in page texels.
+    /// 区域在页面中的矩形（纹素）。
+    pub rect: PackedRect,
+
+    /// Region UV rectangle [u0, v0, u1, v1] within the page.
+    /// 区域在页面中的UV矩形 [u0, v0, u1, v1]。
+    pub uv: [f32; 4],
+}
+
+impl AtlasRegion {
+    /// Create a region from a packed rectangle.
+    /// 从已打包矩形创建区域。
+    pub fn new(page_texture_id: u32, rect: PackedRect, page_width: u32, page_height: u32) -> Self {
+        let pw = page_width as f32;
+        let ph = page_height as f32;
+        Self {
+            page_texture_id,
+            rect,
+            uv: [
+                rect.x as f32 / pw,
+                rect.y as f32 / ph,
+                (rect.x + rect.width) as f32 / pw,
+                (rect.y + rect.height) as f32 / ph,
+            ],
+        }
+    }
+
+    /// Map UVs relative to the source image into page UVs.
+    /// 将相对于源图像的UV映射为页面UV。
+    #[inline]
+    pub fn remap_uv(&self, uv: [f32; 4]) -> [f32; 4] {
+        let [r0, s0, r1, s1] = self.uv;
+        let du = r1 - r0;
+        let dv = s1 - s0;
+        [
+            r0 + uv[0] * du,
+            s0 + uv[1] * dv,
+            r0 + uv[2] * du,
+            s0 + uv[3] * dv,
+        ]
+    }
+}
+
+/// Atlas page: a shared texture plus its packer.
+/// 图集页面：共享纹理及其打包器。
+#[derive(Debug, Clone)]
+pub struct AtlasPage {
+    /// Texture ID of the page.
+    /// 页面的纹理ID。
+    pub texture_id: u32,
+
+    /// Packer tracking free space.
+    /// 跟踪剩余空间的打包器。
+    pub packer: AtlasPacker,
+}
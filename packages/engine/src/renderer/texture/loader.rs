@@ -0,0 +1,268 @@
+//! Asynchronous image loading with tracked load state.
+//! 带加载状态跟踪的异步图像加载。
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlImageElement, WebGl2RenderingContext};
+
+use crate::core::error::{EngineError, Result};
+
+/// Load state of a texture loaded from a URL.
+/// 从URL加载的纹理的加载状态。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureLoadState {
+    /// Image requested; a placeholder is bound meanwhile.
+    /// 已请求图像；期间绑定占位纹理。
+    Pending,
+
+    /// Image uploaded.
+    /// 图像已上传。
+    Loaded {
+        /// Image width in pixels.
+        /// 图像宽度（像素）。
+        width: u32,
+        /// Image height in pixels.
+        /// 图像高度（像素）。
+        height: u32,
+    },
+
+    /// Image missing, blocked by CORS, undecodable or rejected by WebGL.
+    /// 图像缺失、被CORS阻止、无法解码或被WebGL拒绝。
+    Failed(String),
+}
+
+impl TextureLoadState {
+    /// Size to build UVs against, or None while only a placeholder is bound.
+    /// 用于计算UV的尺寸；仅绑定占位纹理时为None。
+    ///
+    /// Pending and failed loads show a 1x1 placeholder whose size must not be
+    /// cached. Textures not loaded from a URL (`state` is None) use their size.
+    /// 加载中和失败的纹理显示1x1占位纹理，其尺寸不应被缓存。
+    /// 并非从URL加载的纹理（`state`为None）使用其自身尺寸。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::renderer::TextureLoadState;
+    /// let placeholder = Some((1.0, 1.0));
+    /// assert_eq!(TextureLoadState::ready_size(Some(&TextureLoadState::Pending), placeholder), None);
+    /// let failed = TextureLoadState::Failed("missing".into());
+    /// assert_eq!(TextureLoadState::ready_size(Some(&failed), placeholder), None);
+    ///
+    /// let loaded = TextureLoadState::Loaded { width: 64, height: 32 };
+    /// assert_eq!(TextureLoadState::ready_size(Some(&loaded), Some((64.0, 32.0))), Some((64.0, 32.0)));
+    /// assert_eq!(TextureLoadState::ready_size(None, Some((16.0, 16.0))), Some((16.0, 16.0)));
+    /// assert_eq!(TextureLoadState::ready_size(None, Some((0.0, 0.0))), None);
+    /// ```
+    pub fn ready_size(state: Option<&Self>, size: Option<(f32, f32)>) -> Option<(f32, f32)> {
+        match state {
+            Some(TextureLoadState::Pending | TextureLoadState::Failed(_)) => None,
+            _ => size.filter(|&(w, h)| w > 0.0 && h > 0.0),
+        }
+    }
+}
+
+/// Callback receiving the loaded size or the load error.
+/// 接收加载尺寸或加载错误的回调。
+pub type TextureLoadCallback = Box<dyn FnOnce(Result<(u32, u32)>)>;
+
+/// Load of one texture ID.
+/// 单个纹理ID的加载。
+struct LoadEntry {
+    /// Identifies the latest load of the ID | 标识该ID的最近一次加载
+    token: u32,
+    state: TextureLoadState,
+    callbacks: Vec<TextureLoadCallback>,
+}
+
+/// Load states shared with image callbacks.
+/// 与图像回调共享的加载状态。
+#[derive(Clone, Default)]
+pub(crate) struct LoadTracker {
+    entries: Rc<RefCell<HashMap<u32, LoadEntry>>>,
+    next_token: Rc<Cell<u32>>,
+}
+
+impl LoadTracker {
+    /// Mark an ID pending and return the token of this load.
+    /// 将ID标记为加载中并返回本次加载的令牌。
+    ///
+    /// Callbacks waiting on an earlier load of the ID carry over.
+    /// 等待该ID较早加载的回调会保留。
+    fn begin(&self, id: u32) -> u32 {
+        let token = self.next_token.get().wrapping_add(1);
+        self.next_token.set(token);
+        let mut entries = self.entries.borrow_mut();
+        let callbacks = entries.remove(&id).map(|e| e.callbacks).unwrap_or_default();
+        entries.insert(id, LoadEntry { token, state: TextureLoadState::Pending, callbacks });
+        token
+    }
+
+    /// Check if a token is the latest load of its ID.
+    /// 检查令牌是否为其ID的最近一次加载。
+    fn is_current(&self, id: u32, token: u32) -> bool {
+        self.entries.borrow().get(&id).is_some_and(|e| e.token == token)
+    }
+
+    /// Record the outcome of a load and notify waiters.
+    /// 记录加载结果并通知等待者。
+    fn finish(&self, id: u32, token: u32, result: Result<(u32, u32)>) {
+        let callbacks = {
+            let mut entries = self.entries.borrow_mut();
+            let Some(entry) = entries.get_mut(&id).filter(|e| e.token == token) else {
+                return;
+            };
+            entry.state = match &result {
+                Ok((width, height)) => TextureLoadState::Loaded { width: *width, height: *height },
+                Err(e) => TextureLoadState::Failed(failure_message(e)),
+            };
+            std::mem::take(&mut entry.callbacks)
+        };
+        // Borrow released so callbacks may query state | 已释放借用，回调可查询状态
+        for callback in callbacks {
+            callback(clone_result(&result));
+        }
+    }
+
+    /// State of an ID, if it was loaded from a URL.
+    /// ID的状态（如果从URL加载）。
+    pub(crate) fn state(&self, id: u32) -> Option<TextureLoadState> {
+        self.entries.borrow().get(&id).map(|e| e.state.clone())
+    }
+
+    /// Call back once the ID settles; returns the callback if the ID is not tracked.
+    /// ID完成后回调；如果ID未被跟踪则返回该回调。
+    pub(crate) fn when_done(&self, id: u32, callback: TextureLoadCallback) -> Option<TextureLoadCallback> {
+        let result = {
+            let mut entries = self.entries.borrow_mut();
+            let Some(entry) = entries.get_mut(&id) else {
+                return Some(callback);
+            };
+            match &entry.state {
+                TextureLoadState::Pending => {
+                    entry.callbacks.push(callback);
+                    return None;
+                }
+                TextureLoadState::Loaded { width, height } => Ok((*width, *height)),
+                TextureLoadState::Failed(message) => Err(EngineError::TextureLoadFailed(message.clone())),
+            }
+        };
+        callback(result);
+        None
+    }
+
+    /// Sizes of loaded IDs.
+    /// 已加载ID的尺寸。
+    pub(crate) fn loaded_sizes(&self) -> Vec<(u32, u32, u32)> {
+        self.entries
+            .borrow()
+            .iter()
+            .filter_map(|(&id, e)| match e.state {
+                TextureLoadState::Loaded { width, height } => Some((id, width, height)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Forget an ID, failing its waiters.
+    /// 忘记某个ID，并使其等待者失败。
+    pub(crate) fn cancel(&self, id: u32) {
+        let entry = self.entries.borrow_mut().remove(&id);
+        for callback in entry.into_iter().flat_map(|e| e.callbacks) {
+            callback(Err(EngineError::TextureLoadFailed(format!("Texture {} removed before loading", id))));
+        }
+    }
+
+    /// Forget all IDs, failing their waiters.
+    /// 忘记所有ID，并使其等待者失败。
+    pub(crate) fn cancel_all(&self) {
+        let ids: Vec<u32> = self.entries.borrow().keys().copied().collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+}
+
+/// Message of a load error without the error prefix.
+/// 不含错误前缀的加载错误消息。
+fn failure_message(error: &EngineError) -> String {
+    match error {
+        EngineError::TextureLoadFailed(message) => message.clone(),
+        e => e.to_string(),
+    }
+}
+
+/// Copy a load result for another waiter.
+/// 为另一个等待者复制加载结果。
+fn clone_result(result: &Result<(u32, u32)>) -> Result<(u32, u32)> {
+    match result {
+        Ok(size) => Ok(*size),
+        Err(e) => Err(EngineError::TextureLoadFailed(failure_message(e))),
+    }
+}
+
+/// Fetch an image and upload it when it arrives, tracking the load under `id`.
+/// 获取图像并在到达时上传，以`id`跟踪加载。
+///
+/// The upload is skipped if the ID was reloaded or removed meanwhile. Load
+/// and upload errors are logged and reported as `TextureLoadFailed`.
+/// 如果期间ID被重新加载或移除，则跳过上传。加载和上传错误会被记录并报告为`TextureLoadFailed`。
+///
+/// # Arguments | 参数
+/// * `tracker` - Load states | 加载状态
+/// * `gl` - WebGL context | WebGL上下文
+/// * `id` - Texture ID being loaded | 正在加载的纹理ID
+/// * `url` - Image URL | 图片URL
+/// * `upload` - Uploads the decoded image | 上传解码后的图像
+pub(crate) fn load_image(
+    tracker: &LoadTracker,
+    gl: &WebGl2RenderingContext,
+    id: u32,
+    url: &str,
+    upload: impl FnOnce(&WebGl2RenderingContext, &HtmlImageElement) -> std::result::Result<(), JsValue> + 'static,
+) -> Result<()> {
+    let image = HtmlImageElement::new()
+        .map_err(|_| EngineError::TextureLoadFailed("Failed to create image element".into()))?;
+
+    // Set crossOrigin for CORS support | 设置crossOrigin以支持CORS
+    image.set_cross_origin(Some("anonymous"));
+
+    let token = tracker.begin(id);
+
+    let onload = {
+        let (tracker, gl, image, url) = (tracker.clone(), gl.clone(), image.clone(), url.to_string());
+        Closure::once_into_js(move || {
+            image.set_onerror(None);
+            if !tracker.is_current(id, token) {
+                return;
+            }
+            let size = (image.natural_width(), image.natural_height());
+            let result = upload(&gl, &image)
+                .map(|_| size)
+                .map_err(|e| EngineError::TextureLoadFailed(format!("{}: {:?}", url, e)));
+            if let Err(e) = &result {
+                log::error!("Failed to upload texture: {} | 纹理上传失败: {}", e, e);
+            }
+            tracker.finish(id, token, result);
+        })
+    };
+
+    let onerror = {
+        let (tracker, image, url) = (tracker.clone(), image.clone(), url.to_string());
+        Closure::once_into_js(move || {
+            image.set_onload(None);
+            let message = format!("{} could not be loaded (missing, blocked by CORS or not an image)", url);
+            log::error!("Failed to load texture: {} | 纹理加载失败: {}", message, url);
+            tracker.finish(id, token, Err(EngineError::TextureLoadFailed(message)));
+        })
+    };
+
+    // One-shot closures free themselves when called | 一次性闭包在调用后自行释放
+    image.set_onload(Some(onload.unchecked_ref()));
+    image.set_onerror(Some(onerror.unchecked_ref()));
+    image.set_src(url);
+
+    Ok(())
+}
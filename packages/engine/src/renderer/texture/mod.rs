@@ -1,8 +1,14 @@
 //! Texture management system.
 //! 纹理管理系统。
 
+mod atlas;
+mod loader;
+mod sampler;
 mod texture;
 mod texture_manager;
 
+pub use atlas::{AtlasPacker, AtlasPage, AtlasRegion, PackedRect};
+pub use loader::{TextureLoadCallback, TextureLoadState};
+pub use sampler::{TextureFilter, TextureLoadOptions, TextureSampler, TextureWrap};
 pub use texture::Texture;
 pub use texture_manager::TextureManager;
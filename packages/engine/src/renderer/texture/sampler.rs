@@ -0,0 +1,254 @@
+//! Texture sampling options.
+//! 纹理采样选项。
+
+use web_sys::WebGl2RenderingContext as Gl;
+
+/// `EXT_texture_filter_anisotropic` texture parameter.
+/// `EXT_texture_filter_anisotropic`纹理参数。
+const TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FE;
+
+/// `EXT_texture_filter_anisotropic` device limit.
+/// `EXT_texture_filter_anisotropic`设备上限。
+const MAX_TEXTURE_MAX_ANISOTROPY_EXT: u32 = 0x84FF;
+
+/// Texel filtering.
+/// 纹素过滤方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFilter {
+    /// Nearest texel; keeps pixel art crisp.
+    /// 最近纹素；保持像素画清晰。
+    Nearest,
+    /// Bilinear interpolation.
+    /// 双线性插值。
+    #[default]
+    Linear,
+}
+
+impl TextureFilter {
+    /// Convert from an index (0 = nearest, 1 = linear).
+    /// 从索引转换（0 = 最近，1 = 线性）。
+    pub fn from_index(index: u8) -> Self {
+        if index == 0 { TextureFilter::Nearest } else { TextureFilter::Linear }
+    }
+}
+
+/// Behavior outside the [0, 1] UV range.
+/// [0, 1] UV范围之外的行为。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureWrap {
+    /// Repeat the edge texels.
+    /// 重复边缘纹素。
+    #[default]
+    ClampToEdge,
+    /// Tile the texture.
+    /// 平铺纹理。
+    Repeat,
+    /// Tile, mirroring every other copy.
+    /// 平铺，每隔一份镜像。
+    MirroredRepeat,
+}
+
+impl TextureWrap {
+    /// Convert from an index (0 = clamp, 1 = repeat, 2 = mirrored repeat).
+    /// 从索引转换（0 = 夹取，1 = 重复，2 = 镜像重复）。
+    pub fn from_index(index: u8) -> Self {
+        match index {
+            1 => TextureWrap::Repeat,
+            2 => TextureWrap::MirroredRepeat,
+            _ => TextureWrap::ClampToEdge,
+        }
+    }
+
+    fn gl(self) -> u32 {
+        match self {
+            TextureWrap::ClampToEdge => Gl::CLAMP_TO_EDGE,
+            TextureWrap::Repeat => Gl::REPEAT,
+            TextureWrap::MirroredRepeat => Gl::MIRRORED_REPEAT,
+        }
+    }
+}
+
+/// How a texture is sampled.
+/// 纹理的采样方式。
+///
+/// The default matches the engine's historical behavior: bilinear, clamped,
+/// no mipmaps.
+/// 默认值与引擎以往行为一致：双线性、夹取、无mipmap。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureSampler {
+    /// Filter when the texture is magnified.
+    /// 纹理放大时的过滤方式。
+    pub mag_filter: TextureFilter,
+
+    /// Filter when the texture is minified.
+    /// 纹理缩小时的过滤方式。
+    pub min_filter: TextureFilter,
+
+    /// Filter between mipmap levels; None disables mipmaps.
+    /// mipmap级别之间的过滤方式；None表示禁用mipmap。
+    pub mipmap_filter: Option<TextureFilter>,
+
+    /// Horizontal wrap mode.
+    /// 水平环绕模式。
+    pub wrap_u: TextureWrap,
+
+    /// Vertical wrap mode.
+    /// 垂直环绕模式。
+    pub wrap_v: TextureWrap,
+
+    /// Maximum anisotropy; 1 disables it. Clamped to what the device supports.
+    /// 最大各向异性；1表示禁用。限制在设备支持的范围内。
+    pub anisotropy: f32,
+}
+
+impl Default for TextureSampler {
+    fn default() -> Self {
+        Self::linear()
+    }
+}
+
+impl TextureSampler {
+    /// Create a sampler using one filter throughout.
+    /// 创建全程使用同一过滤方式的采样器。
+    ///
+    /// # Arguments | 参数
+    /// * `filter` - Magnification, minification and mipmap filter | 放大、缩小和mipmap过滤方式
+    /// * `mipmaps` - Whether to use mipmaps | 是否使用mipmap
+    /// * `wrap` - Wrap mode on both axes | 两个方向的环绕模式
+    pub fn new(filter: TextureFilter, mipmaps: bool, wrap: TextureWrap) -> Self {
+        Self {
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: mipmaps.then_some(filter),
+            wrap_u: wrap,
+            wrap_v: wrap,
+            anisotropy: 1.0,
+        }
+    }
+
+    /// Bilinear filtering, clamped, no mipmaps.
+    /// 双线性过滤、夹取、无mipmap。
+    pub fn linear() -> Self {
+        Self {
+            mag_filter: TextureFilter::Linear,
+            min_filter: TextureFilter::Linear,
+            mipmap_filter: None,
+            wrap_u: TextureWrap::ClampToEdge,
+            wrap_v: TextureWrap::ClampToEdge,
+            anisotropy: 1.0,
+        }
+    }
+
+    /// Nearest filtering for crisp pixel art.
+    /// 用于清晰像素画的最近过滤。
+    pub fn pixel_art() -> Self {
+        Self { mag_filter: TextureFilter::Nearest, min_filter: TextureFilter::Nearest, ..Self::linear() }
+    }
+
+    /// Trilinear filtering with mipmaps, for zoomed-out scenes.
+    /// 带mipmap的三线性过滤，用于缩小的场景。
+    pub fn trilinear() -> Self {
+        Self { mipmap_filter: Some(TextureFilter::Linear), ..Self::linear() }
+    }
+
+    /// Set both wrap modes.
+    /// 设置两个方向的环绕模式。
+    pub fn with_wrap(mut self, wrap: TextureWrap) -> Self {
+        self.wrap_u = wrap;
+        self.wrap_v = wrap;
+        self
+    }
+
+    /// Set the maximum anisotropy.
+    /// 设置最大各向异性。
+    pub fn with_anisotropy(mut self, anisotropy: f32) -> Self {
+        self.anisotropy = anisotropy;
+        self
+    }
+
+    /// GL minification filter, combining the min and mipmap filters.
+    /// GL缩小过滤参数，组合缩小过滤和mipmap过滤方式。
+    ///
+    /// # Examples | 示例
+    /// ```rust
+    /// use es_engine::renderer::{TextureFilter, TextureSampler, TextureWrap};
+    /// use web_sys::WebGl2RenderingContext as Gl;
+    /// assert_eq!(TextureSampler::linear().gl_min_filter(), Gl::LINEAR);
+    /// assert_eq!(TextureSampler::pixel_art().gl_min_filter(), Gl::NEAREST);
+    /// assert_eq!(TextureSampler::trilinear().gl_min_filter(), Gl::LINEAR_MIPMAP_LINEAR);
+    ///
+    /// let mut sampler = TextureSampler::new(TextureFilter::Nearest, true, TextureWrap::Repeat);
+    /// assert_eq!(sampler.gl_min_filter(), Gl::NEAREST_MIPMAP_NEAREST);
+    /// sampler.mipmap_filter = Some(TextureFilter::Linear);
+    /// assert_eq!(sampler.gl_min_filter(), Gl::NEAREST_MIPMAP_LINEAR);
+    /// sampler.min_filter = TextureFilter::Linear;
+    /// sampler.mipmap_filter = Some(TextureFilter::Nearest);
+    /// assert_eq!(sampler.gl_min_filter(), Gl::LINEAR_MIPMAP_NEAREST);
+    /// ```
+    pub fn gl_min_filter(&self) -> u32 {
+        match (self.min_filter, self.mipmap_filter) {
+            (TextureFilter::Nearest, None) => Gl::NEAREST,
+            (TextureFilter::Linear, None) => Gl::LINEAR,
+            (TextureFilter::Nearest, Some(TextureFilter::Nearest)) => Gl::NEAREST_MIPMAP_NEAREST,
+            (TextureFilter::Linear, Some(TextureFilter::Nearest)) => Gl::LINEAR_MIPMAP_NEAREST,
+            (TextureFilter::Nearest, Some(TextureFilter::Linear)) => Gl::NEAREST_MIPMAP_LINEAR,
+            (TextureFilter::Linear, Some(TextureFilter::Linear)) => Gl::LINEAR_MIPMAP_LINEAR,
+        }
+    }
+
+    /// Apply to the texture bound to `TEXTURE_2D`.
+    /// 应用到绑定在`TEXTURE_2D`上的纹理。
+    ///
+    /// Mipmaps are generated when `generate_mipmaps` is set and the sampler uses
+    /// them; the texture must hold its final image by then.
+    /// 当设置了`generate_mipmaps`且采样器使用mipmap时生成mipmap；此时纹理必须已包含最终图像。
+    pub(crate) fn apply(&self, gl: &Gl, generate_mipmaps: bool) {
+        if generate_mipmaps && self.mipmap_filter.is_some() {
+            gl.generate_mipmap(Gl::TEXTURE_2D);
+        }
+
+        for (param, value) in [
+            (Gl::TEXTURE_WRAP_S, self.wrap_u.gl()),
+            (Gl::TEXTURE_WRAP_T, self.wrap_v.gl()),
+            (Gl::TEXTURE_MIN_FILTER, self.gl_min_filter()),
+            (
+                Gl::TEXTURE_MAG_FILTER,
+                if self.mag_filter == TextureFilter::Nearest { Gl::NEAREST } else { Gl::LINEAR },
+            ),
+        ] {
+            gl.tex_parameteri(Gl::TEXTURE_2D, param, value as i32);
+        }
+
+        let max = max_anisotropy(gl);
+        if max > 1.0 {
+            gl.tex_parameterf(Gl::TEXTURE_2D, TEXTURE_MAX_ANISOTROPY_EXT, self.anisotropy.clamp(1.0, max));
+        }
+    }
+}
+
+/// Options for loading a texture.
+/// 加载纹理的选项。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextureLoadOptions {
+    /// How the texture is sampled; can be changed after loading.
+    /// 纹理的采样方式；加载后可更改。
+    pub sampler: TextureSampler,
+
+    /// Multiply color by alpha on upload, for `BlendMode::PremultipliedAlpha`.
+    /// 上传时将颜色乘以alpha，用于`BlendMode::PremultipliedAlpha`。
+    pub premultiply_alpha: bool,
+}
+
+/// Largest anisotropy the device supports, or 1 without the extension.
+/// 设备支持的最大各向异性；无扩展时为1。
+fn max_anisotropy(gl: &Gl) -> f32 {
+    // Enabling an extension is idempotent | 启用扩展是幂等的
+    match gl.get_extension("EXT_texture_filter_anisotropic") {
+        Ok(Some(_)) => gl
+            .get_parameter(MAX_TEXTURE_MAX_ANISOTROPY_EXT)
+            .ok()
+            .and_then(|v| v.as_f64())
+            .map_or(1.0, |v| v as f32),
+        _ => 1.0,
+    }
+}
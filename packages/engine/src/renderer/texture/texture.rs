@@ -1,8 +1,12 @@
 //! Texture representation.
 //! 纹理表示。
 
+use std::cell::Cell;
+use std::rc::Rc;
 use web_sys::WebGlTexture;
 
+use super::TextureSampler;
+
 /// 2D texture.
 /// 2D纹理。
 pub struct Texture {
@@ -17,6 +21,22 @@ pub struct Texture {
     /// Texture height in pixels.
     /// 纹理高度（像素）。
     pub height: u32,
+
+    /// Whether rows are stored bottom-up (render targets).
+    /// 行是否自下而上存储（渲染目标）。
+    ///
+    /// Sprites sampling such textures get their V coordinates flipped so
+    /// they appear upright.
+    /// 采样此类纹理的精灵会翻转V坐标，使其正向显示。
+    pub flip_y: bool,
+
+    /// How the texture is sampled, shared with pending image uploads.
+    /// 纹理的采样方式，与待完成的图像上传共享。
+    pub(crate) sampler: Rc<Cell<TextureSampler>>,
+
+    /// Whether color channels were multiplied by alpha on upload.
+    /// 上传时颜色通道是否已乘以alpha。
+    pub(crate) premultiplied_alpha: bool,
 }
 
 impl Texture {
@@ -27,6 +47,22 @@ impl Texture {
             handle,
             width,
             height,
+            flip_y: false,
+            sampler: Rc::default(),
+            premultiplied_alpha: false,
+        }
+    }
+
+    /// Create a texture backing a render target.
+    /// 创建作为渲染目标的纹理。
+    pub fn render_target(handle: WebGlTexture, width: u32, height: u32) -> Self {
+        Self {
+            handle,
+            width,
+            height,
+            flip_y: true,
+            sampler: Rc::default(),
+            premultiplied_alpha: false,
         }
     }
 
@@ -36,4 +72,21 @@ impl Texture {
     pub fn handle(&self) -> &WebGlTexture {
         &self.handle
     }
+
+    /// Get how the texture is sampled.
+    /// 获取纹理的采样方式。
+    #[inline]
+    pub fn sampler(&self) -> TextureSampler {
+        self.sampler.get()
+    }
+
+    /// Check if color channels were multiplied by alpha on upload.
+    /// 检查上传时颜色通道是否已乘以alpha。
+    ///
+    /// Draw such textures with `BlendMode::PremultipliedAlpha`.
+    /// 此类纹理应使用`BlendMode::PremultipliedAlpha`绘制。
+    #[inline]
+    pub fn premultiplied_alpha(&self) -> bool {
+        self.premultiplied_alpha
+    }
 }
@@ -2,12 +2,22 @@
 //! 纹理加载和管理。
 
 use std::collections::HashMap;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
-use web_sys::{HtmlImageElement, WebGl2RenderingContext, WebGlTexture};
+use wasm_bindgen::JsValue;
+use web_sys::{WebGl2RenderingContext, WebGlTexture};
 
 use crate::core::error::{EngineError, Result};
-use super::Texture;
+use super::atlas::{AtlasPacker, AtlasPage, AtlasRegion, PackedRect};
+use super::loader::{load_image, LoadTracker, TextureLoadCallback, TextureLoadState};
+use super::{Texture, TextureLoadOptions, TextureSampler};
+use super::super::stats;
+
+/// Default atlas page size in texels.
+/// 默认图集页面尺寸（纹素）。
+const DEFAULT_ATLAS_PAGE_SIZE: u32 = 2048;
+
+/// Gap between packed images in texels.
+/// 打包图像之间的间隙（纹素）。
+const ATLAS_PADDING: u32 = 2;
 
 /// Texture manager for loading and caching textures.
 /// 用于加载和缓存纹理的纹理管理器。
@@ -31,6 +41,22 @@ pub struct TextureManager {
     /// Default white texture for untextured rendering.
     /// 用于无纹理渲染的默认白色纹理。
     default_texture: Option<WebGlTexture>,
+
+    /// Atlas pages shared by packed images.
+    /// 打包图像共享的图集页面。
+    atlas_pages: Vec<AtlasPage>,
+
+    /// Atlas regions, keyed by region ID (shares the texture ID space).
+    /// 图集区域，按区域ID索引（与纹理ID共用ID空间）。
+    atlas_regions: HashMap<u32, AtlasRegion>,
+
+    /// Size of newly created atlas pages.
+    /// 新建图集页面的尺寸。
+    atlas_page_size: u32,
+
+    /// Load states of images loaded from URLs.
+    /// 从URL加载的图像的加载状态。
+    loads: LoadTracker,
 }
 
 impl TextureManager {
@@ -43,6 +69,10 @@ impl TextureManager {
             path_to_id: HashMap::new(),
             next_id: 1, // Start from 1, 0 is reserved for default
             default_texture: None,
+            atlas_pages: Vec::new(),
+            atlas_regions: HashMap::new(),
+            atlas_page_size: DEFAULT_ATLAS_PAGE_SIZE,
+            loads: LoadTracker::default(),
         };
 
         // Create default white texture | 创建默认白色纹理
@@ -89,17 +119,32 @@ impl TextureManager {
     /// Load a texture from URL.
     /// 从URL加载纹理。
     ///
-    /// Note: This is an async operation. The texture will be available
-    /// after the image loads.
-    /// 注意：这是一个异步操作。纹理在图片加载后可用。
+    /// Note: This is an async operation. A gray 1x1 placeholder is bound until
+    /// the image loads; track progress with `load_state` or `when_loaded`.
+    /// 注意：这是一个异步操作。图片加载前绑定灰色1x1占位纹理；
+    /// 可通过`load_state`或`when_loaded`跟踪进度。
     pub fn load_texture(&mut self, id: u32, url: &str) -> Result<()> {
+        self.load_texture_with(id, url, TextureLoadOptions::default())
+    }
+
+    /// Load a texture from URL with sampling and upload options.
+    /// 使用采样和上传选项从URL加载纹理。
+    ///
+    /// Mipmaps, if the sampler uses them, are generated once the image arrives.
+    /// 如果采样器使用mipmap，则在图像到达后生成。
+    ///
+    /// # Arguments | 参数
+    /// * `id` - Texture ID | 纹理ID
+    /// * `url` - Image URL | 图片URL
+    /// * `options` - Sampler and premultiplied alpha | 采样器和预乘alpha
+    pub fn load_texture_with(&mut self, id: u32, url: &str, options: TextureLoadOptions) -> Result<()> {
         // Create placeholder texture | 创建占位纹理
-        let texture = self.gl
+        let handle = self.gl
             .create_texture()
             .ok_or_else(|| EngineError::TextureLoadFailed("Failed to create texture".into()))?;
 
         // Set up temporary 1x1 texture | 设置临时1x1纹理
-        self.gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+        self.gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&handle));
         let placeholder: [u8; 4] = [128, 128, 128, 255];
         let _ = self.gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
             WebGl2RenderingContext::TEXTURE_2D,
@@ -112,73 +157,98 @@ impl TextureManager {
             WebGl2RenderingContext::UNSIGNED_BYTE,
             Some(&placeholder),
         );
+        // Mipmapped samplers need a complete placeholder | mipmap采样器需要完整的占位纹理
+        options.sampler.apply(&self.gl, true);
 
-        // Clone texture handle for async loading before storing | 在存储前克隆纹理句柄用于异步加载
-        let texture_for_closure = texture.clone();
+        let mut texture = Texture::new(handle.clone(), 1, 1);
+        texture.sampler.set(options.sampler);
+        texture.premultiplied_alpha = options.premultiply_alpha;
+
+        // The sampler may change before the image arrives | 采样器可能在图像到达前被更改
+        let sampler = texture.sampler.clone();
 
         // Store texture with placeholder size | 存储带占位符尺寸的纹理
-        self.textures.insert(id, Texture::new(texture, 1, 1));
+        if let Some(previous) = self.textures.insert(id, texture) {
+            self.gl.delete_texture(Some(&previous.handle));
+        }
 
         // Load actual image asynchronously | 异步加载实际图片
-        let gl = self.gl.clone();
-
-        let image = HtmlImageElement::new()
-            .map_err(|_| EngineError::TextureLoadFailed("Failed to create image element".into()))?;
-
-        // Set crossOrigin for CORS support | 设置crossOrigin以支持CORS
-        image.set_cross_origin(Some("anonymous"));
-
-        // Clone image for use in closure | 克隆图片用于闭包
-        let image_clone = image.clone();
-
-        // Set up load callback | 设置加载回调
-        let onload = Closure::wrap(Box::new(move || {
-            gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture_for_closure));
-
-            // Use the captured image element | 使用捕获的图片元素
-            let result = gl.tex_image_2d_with_u32_and_u32_and_html_image_element(
+        load_image(&self.loads, &self.gl, id, url, move |gl, image| {
+            gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&handle));
+            if options.premultiply_alpha {
+                gl.pixel_storei(WebGl2RenderingContext::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 1);
+            }
+            let uploaded = gl.tex_image_2d_with_u32_and_u32_and_html_image_element(
                 WebGl2RenderingContext::TEXTURE_2D,
                 0,
                 WebGl2RenderingContext::RGBA as i32,
                 WebGl2RenderingContext::RGBA,
                 WebGl2RenderingContext::UNSIGNED_BYTE,
-                &image_clone,
+                image,
             );
-
-            if let Err(e) = result {
-                log::error!("Failed to upload texture: {:?} | 纹理上传失败: {:?}", e, e);
+            // Unpack state is global | 解包状态是全局的
+            if options.premultiply_alpha {
+                gl.pixel_storei(WebGl2RenderingContext::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 0);
             }
+            uploaded?;
 
-            // Set texture parameters | 设置纹理参数
-            gl.tex_parameteri(
-                WebGl2RenderingContext::TEXTURE_2D,
-                WebGl2RenderingContext::TEXTURE_WRAP_S,
-                WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
-            );
-            gl.tex_parameteri(
-                WebGl2RenderingContext::TEXTURE_2D,
-                WebGl2RenderingContext::TEXTURE_WRAP_T,
-                WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
-            );
-            gl.tex_parameteri(
-                WebGl2RenderingContext::TEXTURE_2D,
-                WebGl2RenderingContext::TEXTURE_MIN_FILTER,
-                WebGl2RenderingContext::LINEAR as i32,
-            );
-            gl.tex_parameteri(
-                WebGl2RenderingContext::TEXTURE_2D,
-                WebGl2RenderingContext::TEXTURE_MAG_FILTER,
-                WebGl2RenderingContext::LINEAR as i32,
-            );
+            sampler.get().apply(gl, true);
+            Ok(())
+        })
+    }
 
-        }) as Box<dyn Fn()>);
+    /// Change how a texture is sampled.
+    /// 更改纹理的采样方式。
+    ///
+    /// Atlas regions change their whole page. Mipmaps are regenerated when the
+    /// sampler uses them; for textures still loading the sampler takes effect
+    /// when the image arrives.
+    /// 图集区域会更改其整个页面。采样器使用mipmap时会重新生成；
+    /// 对于仍在加载的纹理，采样器在图像到达时生效。
+    pub fn set_texture_sampler(&mut self, id: u32, sampler: TextureSampler) -> Result<()> {
+        let id = self.atlas_regions.get(&id).map_or(id, |r| r.page_texture_id);
+        let texture = self.textures.get(&id).ok_or(EngineError::TextureNotFound(id))?;
+
+        texture.sampler.set(sampler);
+        self.gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture.handle));
+        sampler.apply(&self.gl, true);
+        Ok(())
+    }
 
-        image.set_onload(Some(onload.as_ref().unchecked_ref()));
-        onload.forget(); // Prevent closure from being dropped | 防止闭包被销毁
+    /// Get the load state of a texture loaded from a URL.
+    /// 获取从URL加载的纹理的加载状态。
+    ///
+    /// Returns None for IDs that were not loaded from a URL.
+    /// 对于并非从URL加载的ID返回None。
+    #[inline]
+    pub fn load_state(&self, id: u32) -> Option<TextureLoadState> {
+        self.loads.state(id)
+    }
 
-        image.set_src(url);
+    /// Call back with the image size once a texture has loaded, or with the error.
+    /// 纹理加载完成后以图像尺寸回调，失败时以错误回调。
+    ///
+    /// Settled loads call back immediately. IDs not loaded from a URL report
+    /// their current size, or `TextureNotFound`.
+    /// 已完成的加载会立即回调。并非从URL加载的ID报告其当前尺寸或`TextureNotFound`。
+    pub fn when_loaded(&self, id: u32, callback: TextureLoadCallback) {
+        if let Some(callback) = self.loads.when_done(id, callback) {
+            callback(match self.get_texture_size(id) {
+                Some((width, height)) => Ok((width as u32, height as u32)),
+                None => Err(EngineError::TextureNotFound(id)),
+            });
+        }
+    }
 
-        Ok(())
+    /// Copy the sizes of finished loads into their textures.
+    /// 将已完成加载的尺寸复制到对应纹理。
+    pub fn sync_loaded_sizes(&mut self) {
+        for (id, width, height) in self.loads.loaded_sizes() {
+            if let Some(texture) = self.textures.get_mut(&id) {
+                texture.width = width;
+                texture.height = height;
+            }
+        }
     }
 
     /// Get texture by ID.
@@ -192,15 +262,39 @@ impl TextureManager {
     /// 按ID获取纹理尺寸。
     #[inline]
     pub fn get_texture_size(&self, id: u32) -> Option<(f32, f32)> {
+        if let Some(region) = self.atlas_regions.get(&id) {
+            return Some((region.rect.width as f32, region.rect.height as f32));
+        }
+
+        // Loads finished since the last sync | 上次同步后完成的加载
+        if let Some(TextureLoadState::Loaded { width, height }) = self.loads.state(id) {
+            return Some((width as f32, height as f32));
+        }
+
         self.textures
             .get(&id)
             .map(|t| (t.width as f32, t.height as f32))
     }
 
+    /// Get texture size by ID, or None while the texture is loading or failed.
+    /// 按ID获取纹理尺寸；纹理加载中或失败时返回None。
+    ///
+    /// Use this when the size is cached, since a pending texture reports its
+    /// 1x1 placeholder size.
+    /// 缓存尺寸时应使用此方法，因为加载中的纹理报告其1x1占位尺寸。
+    #[inline]
+    pub fn get_ready_texture_size(&self, id: u32) -> Option<(f32, f32)> {
+        TextureLoadState::ready_size(self.loads.state(id).as_ref(), self.get_texture_size(id))
+    }
+
     /// Bind texture for rendering.
     /// 绑定纹理用于渲染。
     pub fn bind_texture(&self, id: u32, slot: u32) {
         self.gl.active_texture(WebGl2RenderingContext::TEXTURE0 + slot);
+        stats::record(|stats| stats.texture_binds += 1);
+
+        // Regions sample their atlas page | 区域采样其图集页面
+        let id = self.atlas_regions.get(&id).map_or(id, |r| r.page_texture_id);
 
         if let Some(texture) = self.textures.get(&id) {
             self.gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture.handle));
@@ -216,16 +310,53 @@ impl TextureManager {
         }
     }
 
+    /// Register an externally created texture, returning its new ID.
+    /// 注册外部创建的纹理，返回其新ID。
+    pub fn register_texture(&mut self, texture: Texture) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.textures.insert(id, texture);
+        id
+    }
+
+    /// Update the stored size of a texture.
+    /// 更新纹理记录的尺寸。
+    pub fn set_texture_size(&mut self, id: u32, width: u32, height: u32) {
+        if let Some(texture) = self.textures.get_mut(&id) {
+            texture.width = width;
+            texture.height = height;
+        }
+    }
+
     /// Check if texture is loaded.
     /// 检查纹理是否已加载。
     #[inline]
     pub fn has_texture(&self, id: u32) -> bool {
-        self.textures.contains_key(&id)
+        self.textures.contains_key(&id) || self.atlas_regions.contains_key(&id)
     }
 
     /// Remove texture.
     /// 移除纹理。
+    ///
+    /// Removing an atlas region frees its ID but not its space in the page.
+    /// Removing an atlas page also removes every region packed onto it.
+    /// 移除图集区域会释放其ID，但不会回收页面中的空间。
+    /// 移除图集页面也会移除打包在其上的所有区域。
     pub fn remove_texture(&mut self, id: u32) {
+        if self.atlas_pages.iter().any(|page| page.texture_id == id) {
+            self.atlas_pages.retain(|page| page.texture_id != id);
+            let regions: Vec<u32> = self.atlas_regions
+                .iter()
+                .filter(|(_, region)| region.page_texture_id == id)
+                .map(|(&region_id, _)| region_id)
+                .collect();
+            for region_id in regions {
+                self.remove_texture(region_id);
+            }
+        }
+
+        self.loads.cancel(id);
+        self.atlas_regions.remove(&id);
         if let Some(texture) = self.textures.remove(&id) {
             self.gl.delete_texture(Some(&texture.handle));
         }
@@ -307,9 +438,190 @@ impl TextureManager {
 
         // Clear path mapping | 清除路径映射
         self.path_to_id.clear();
+        self.loads.cancel_all();
+
+        // Pages were deleted with the textures above | 页面已随上面的纹理一起删除
+        self.atlas_pages.clear();
+        self.atlas_regions.clear();
 
         // Reset ID counter (1 is reserved for first texture, 0 for default)
         // 重置ID计数器（1保留给第一个纹理，0给默认纹理）
         self.next_id = 1;
     }
+
+    // ============= Texture Atlas =============
+    // ============= 纹理图集 =============
+
+    /// Set the size used for new atlas pages.
+    /// 设置新图集页面的尺寸。
+    ///
+    /// Existing pages keep their size.
+    /// 已有页面保持原尺寸。
+    pub fn set_atlas_page_size(&mut self, size: u32) {
+        self.atlas_page_size = size.max(1);
+    }
+
+    /// Get the atlas region for an ID, if the ID refers to a packed image.
+    /// 获取ID对应的图集区域（如果该ID是打包图像）。
+    #[inline]
+    pub fn get_atlas_region(&self, id: u32) -> Option<&AtlasRegion> {
+        self.atlas_regions.get(&id)
+    }
+
+    /// Resolve a texture ID and UV rect to the texture actually sampled.
+    /// 将纹理ID和UV矩形解析为实际采样的纹理。
+    ///
+    /// Atlas regions map to their page and have their UVs remapped into it.
+    /// Render targets are stored bottom-up, so their V coordinates are
+    /// flipped. Other IDs are returned unchanged.
+    /// 图集区域映射到其页面并重新映射UV。渲染目标自下而上存储，因此翻转其V坐标。
+    /// 其他ID原样返回。
+    #[inline]
+    pub fn resolve_texture(&self, id: u32, uv: [f32; 4]) -> (u32, [f32; 4]) {
+        if let Some(region) = self.atlas_regions.get(&id) {
+            return (region.page_texture_id, region.remap_uv(uv));
+        }
+
+        match self.textures.get(&id) {
+            Some(texture) if texture.flip_y => (id, [uv[0], 1.0 - uv[1], uv[2], 1.0 - uv[3]]),
+            _ => (id, uv),
+        }
+    }
+
+    /// Load an image into a shared atlas page, returning its region ID.
+    /// 将图像加载到共享图集页面，返回其区域ID。
+    ///
+    /// The image size must be known up front so the region can be packed
+    /// before the image arrives; an image of another size is not uploaded and
+    /// its load fails. Images larger than a page are loaded as standalone
+    /// textures instead. Loading the same path twice returns the existing ID.
+    /// 需要预先知道图像尺寸，以便在图像到达前完成打包；尺寸不同的图像不会被上传，其加载失败。
+    /// 大于页面的图像会作为独立纹理加载。重复加载相同路径会返回已有ID。
+    ///
+    /// # Arguments | 参数
+    /// * `path` - Image path/URL | 图片路径/URL
+    /// * `width` - Image width in pixels | 图像宽度（像素）
+    /// * `height` - Image height in pixels | 图像高度（像素）
+    pub fn load_texture_to_atlas(&mut self, path: &str, width: u32, height: u32) -> Result<u32> {
+        if let Some(&id) = self.path_to_id.get(path) {
+            return Ok(id);
+        }
+
+        if width > self.atlas_page_size || height > self.atlas_page_size {
+            log::warn!(
+                "Image {}x{} exceeds atlas page size {}, loading standalone: {} | 图像超过图集页面尺寸，单独加载: {}",
+                width, height, self.atlas_page_size, path, path
+            );
+            return self.load_texture_by_path(path);
+        }
+
+        let (page_texture_id, rect) = self.allocate_atlas_rect(width, height)?;
+        let page_size = self.textures
+            .get(&page_texture_id)
+            .map(|t| (t.width, t.height))
+            .unwrap_or((self.atlas_page_size, self.atlas_page_size));
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.atlas_regions.insert(id, AtlasRegion::new(page_texture_id, rect, page_size.0, page_size.1));
+        self.path_to_id.insert(path.to_string(), id);
+
+        // Upload into the page once the image arrives | 图像到达后上传到页面
+        let (page, sampler) = self.textures
+            .get(&page_texture_id)
+            .map(|t| (t.handle.clone(), t.sampler.clone()))
+            .ok_or(EngineError::TextureNotFound(page_texture_id))?;
+        load_image(&self.loads, &self.gl, id, path, move |gl, image| {
+            // A different size would spill into neighbouring regions | 尺寸不同会溢出到相邻区域
+            let size = (image.natural_width(), image.natural_height());
+            if size != (rect.width, rect.height) {
+                return Err(JsValue::from_str(&format!(
+                    "image is {}x{} but was packed as {}x{}",
+                    size.0, size.1, rect.width, rect.height
+                )));
+            }
+
+            gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&page));
+            gl.tex_sub_image_2d_with_u32_and_u32_and_html_image_element(
+                WebGl2RenderingContext::TEXTURE_2D,
+                0,
+                rect.x as i32,
+                rect.y as i32,
+                WebGl2RenderingContext::RGBA,
+                WebGl2RenderingContext::UNSIGNED_BYTE,
+                image,
+            )?;
+
+            // Refresh the page's mipmaps | 刷新页面的mipmap
+            if sampler.get().mipmap_filter.is_some() {
+                gl.generate_mipmap(WebGl2RenderingContext::TEXTURE_2D);
+            }
+            Ok(())
+        })?;
+
+        Ok(id)
+    }
+
+    /// Find space for a rectangle, creating a new page when all pages are full.
+    /// 为矩形寻找空间，所有页面已满时创建新页面。
+    fn allocate_atlas_rect(&mut self, width: u32, height: u32) -> Result<(u32, PackedRect)> {
+        for page in &mut self.atlas_pages {
+            if let Some(rect) = page.packer.pack(width, height) {
+                return Ok((page.texture_id, rect));
+            }
+        }
+
+        let texture_id = self.create_atlas_page()?;
+        let page = self.atlas_pages
+            .last_mut()
+            .ok_or_else(|| EngineError::TextureLoadFailed("Atlas page missing".into()))?;
+        let rect = page.packer
+            .pack(width, height)
+            .ok_or_else(|| EngineError::TextureLoadFailed(format!(
+                "Image {}x{} does not fit an empty atlas page", width, height
+            )))?;
+
+        Ok((texture_id, rect))
+    }
+
+    /// Create an empty, transparent atlas page texture.
+    /// 创建空的透明图集页面纹理。
+    fn create_atlas_page(&mut self) -> Result<u32> {
+        let size = self.atlas_page_size;
+        let texture = self.gl
+            .create_texture()
+            .ok_or_else(|| EngineError::TextureLoadFailed("Failed to create atlas page".into()))?;
+
+        self.gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                WebGl2RenderingContext::TEXTURE_2D,
+                0,
+                WebGl2RenderingContext::RGBA as i32,
+                size as i32,
+                size as i32,
+                0,
+                WebGl2RenderingContext::RGBA,
+                WebGl2RenderingContext::UNSIGNED_BYTE,
+                None,
+            )
+            .map_err(|e| EngineError::TextureLoadFailed(format!("{:?}", e)))?;
+
+        TextureSampler::default().apply(&self.gl, false);
+
+        let texture_id = self.next_id;
+        self.next_id += 1;
+        self.textures.insert(texture_id, Texture::new(texture, size, size));
+        self.atlas_pages.push(AtlasPage {
+            texture_id,
+            packer: AtlasPacker::new(size, size, ATLAS_PADDING),
+        });
+
+        log::debug!(
+            "Atlas page {} created ({}x{}) | 图集页面 {} 已创建 ({}x{})",
+            texture_id, size, size, texture_id, size, size
+        );
+
+        Ok(texture_id)
+    }
 }
@@ -0,0 +1,12 @@
+//! Chunked tilemap rendering with cached static geometry.
+//! 使用缓存静态几何体的分块瓦片地图渲染。
+
+mod tileset;
+mod tilemap_layer;
+mod tilemap_renderer;
+
+pub use tileset::{
+    Tileset, TileAnimation, TILE_FLIP_HORIZONTAL, TILE_FLIP_VERTICAL, TILE_FLIP_DIAGONAL, TILE_ID_MASK,
+};
+pub use tilemap_layer::{TilemapLayer, ChunkBuildContext, CHUNK_SIZE, CHUNK_TILES};
+pub use tilemap_renderer::TilemapRenderer;
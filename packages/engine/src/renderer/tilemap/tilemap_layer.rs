@@ -0,0 +1,484 @@
+//! Tilemap layer with chunked, cached geometry.
+//! 具有分块缓存几何体的瓦片地图层。
+
+use std::collections::HashMap;
+
+use web_sys::{WebGl2RenderingContext, WebGlBuffer, WebGlVertexArrayObject};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::{Rect, Vec2};
+use crate::resource::TextureManager;
+use super::super::batch::{SortKey, SpriteBatch, SpriteVertex};
+use super::super::stats;
+use super::tileset::{
+    Tileset, TILE_FLIP_DIAGONAL, TILE_FLIP_HORIZONTAL, TILE_FLIP_VERTICAL, TILE_ID_MASK,
+};
+
+/// Chunk edge length in tiles.
+/// 分块边长（瓦片数）。
+pub const CHUNK_SIZE: u32 = 32;
+
+/// Maximum number of tiles in a chunk.
+/// 单个分块的最大瓦片数。
+pub const CHUNK_TILES: usize = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+/// Shared state for building chunk meshes.
+/// 构建分块网格的共享状态。
+pub struct ChunkBuildContext<'a> {
+    /// Texture manager for texture sizes and atlas regions.
+    /// 用于纹理尺寸和图集区域的纹理管理器。
+    pub texture_manager: &'a TextureManager,
+    /// Registered tilesets.
+    /// 已注册的瓦片集。
+    pub tilesets: &'a HashMap<u32, Tileset>,
+    /// Animation time in seconds.
+    /// 动画时间（秒）。
+    pub time: f32,
+    /// Counter bumped whenever any animation frame changes.
+    /// 任意动画帧变化时递增的计数器。
+    pub animation_epoch: u64,
+    /// Shared quad index buffer.
+    /// 共享的四边形索引缓冲区。
+    pub index_buffer: &'a WebGlBuffer,
+}
+
+/// Tiles of one texture inside a chunk mesh.
+/// 分块网格中使用同一纹理的瓦片。
+#[derive(Clone, Copy, Debug)]
+struct TextureRange {
+    texture_id: u32,
+    start: usize,
+    count: usize,
+}
+
+/// GPU geometry of a chunk.
+/// 分块的GPU几何体。
+struct ChunkMesh {
+    vao: WebGlVertexArrayObject,
+    vbo: WebGlBuffer,
+    ranges: Vec<TextureRange>,
+}
+
+/// A square block of tiles sharing cached geometry.
+/// 共享缓存几何体的方形瓦片块。
+#[derive(Default)]
+struct Chunk {
+    /// Geometry must be rebuilt before drawing.
+    /// 绘制前需要重建几何体。
+    dirty: bool,
+    /// Contains animated tiles.
+    /// 包含动画瓦片。
+    animated: bool,
+    /// Animation epoch the geometry was built for.
+    /// 几何体构建时的动画周期。
+    epoch: u64,
+    mesh: Option<ChunkMesh>,
+}
+
+/// A grid of tiles drawn from registered tilesets.
+/// 使用已注册瓦片集绘制的瓦片网格。
+///
+/// Tile IDs follow the Tiled GID convention: 0 is empty, each tileset covers
+/// IDs from its first GID, and the top three bits flip the tile. Row 0 is the
+/// top row; `position` is the world position of the top-left corner.
+/// 瓦片ID遵循Tiled GID约定：0为空，每个瓦片集覆盖从其首个GID开始的ID，最高三位用于翻转瓦片。
+/// 第0行为顶行；`position`为左上角的世界坐标。
+///
+/// Geometry is cached per chunk of `CHUNK_SIZE`² tiles and only rebuilt for
+/// visible chunks whose tiles or animation frames changed.
+/// 几何体按`CHUNK_SIZE`²瓦片的分块缓存，仅对瓦片或动画帧发生变化的可见分块重建。
+pub struct TilemapLayer {
+    /// Width in tiles.
+    /// 宽度（瓦片数）。
+    width: u32,
+
+    /// Height in tiles.
+    /// 高度（瓦片数）。
+    height: u32,
+
+    /// Size of a tile in world units.
+    /// 瓦片的世界单位尺寸。
+    tile_size: Vec2,
+
+    /// World position of the top-left corner.
+    /// 左上角的世界坐标。
+    pub position: Vec2,
+
+    /// Draw order relative to sprites and other layers.
+    /// 相对于精灵和其他层的绘制顺序。
+    pub sort: SortKey,
+
+    /// Whether the layer is drawn.
+    /// 是否绘制该层。
+    pub visible: bool,
+
+    /// Tint color.
+    /// 着色颜色。
+    color: [f32; 4],
+
+    /// Tile GIDs, row by row from the top.
+    /// 瓦片GID，从顶行开始逐行排列。
+    tiles: Vec<u32>,
+
+    /// (first GID, tileset ID), sorted by first GID.
+    /// （首个GID，瓦片集ID），按首个GID排序。
+    tilesets: Vec<(u32, u32)>,
+
+    /// Chunk columns.
+    /// 分块列数。
+    chunks_x: u32,
+
+    /// Chunks, row by row from the top.
+    /// 分块，从顶行开始逐行排列。
+    chunks: Vec<Chunk>,
+}
+
+impl TilemapLayer {
+    /// Create an empty layer.
+    /// 创建空层。
+    ///
+    /// # Arguments | 参数
+    /// * `width` - Width in tiles | 宽度（瓦片数）
+    /// * `height` - Height in tiles | 高度（瓦片数）
+    /// * `tile_size` - Size of a tile in world units | 瓦片的世界单位尺寸
+    pub fn new(width: u32, height: u32, tile_size: Vec2) -> Self {
+        let chunks_x = width.div_ceil(CHUNK_SIZE);
+        let chunks_y = height.div_ceil(CHUNK_SIZE);
+        Self {
+            width,
+            height,
+            tile_size,
+            position: Vec2::ZERO,
+            sort: SortKey::default(),
+            visible: true,
+            color: [1.0; 4],
+            tiles: vec![0; (width * height) as usize],
+            tilesets: Vec::new(),
+            chunks_x,
+            chunks: (0..chunks_x * chunks_y).map(|_| Chunk::default()).collect(),
+        }
+    }
+
+    /// Width and height in tiles.
+    /// 宽度和高度（瓦片数）。
+    #[inline]
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Use a tileset for GIDs starting at `first_gid`.
+    /// 对从`first_gid`开始的GID使用瓦片集。
+    pub fn set_tileset(&mut self, first_gid: u32, tileset_id: u32) {
+        self.tilesets.retain(|&(gid, _)| gid != first_gid);
+        self.tilesets.push((first_gid, tileset_id));
+        self.tilesets.sort_by_key(|&(gid, _)| gid);
+        self.mark_all_dirty();
+    }
+
+    /// Check whether the layer uses a tileset.
+    /// 检查该层是否使用瓦片集。
+    pub fn uses_tileset(&self, tileset_id: u32) -> bool {
+        self.tilesets.iter().any(|&(_, id)| id == tileset_id)
+    }
+
+    /// Set the tint color.
+    /// 设置着色颜色。
+    pub fn set_color(&mut self, color: [f32; 4]) {
+        if self.color != color {
+            self.color = color;
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Replace all tiles.
+    /// 替换所有瓦片。
+    pub fn set_tiles(&mut self, tiles: &[u32]) -> Result<()> {
+        if tiles.len() != self.tiles.len() {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Tile data length mismatch: expected {}, got {}",
+                self.tiles.len(),
+                tiles.len()
+            )));
+        }
+        self.tiles.copy_from_slice(tiles);
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Replace a rectangular region of tiles.
+    /// 替换矩形区域的瓦片。
+    ///
+    /// # Arguments | 参数
+    /// * `x`, `y` - Top-left tile of the region | 区域左上角瓦片
+    /// * `width` - Region width in tiles | 区域宽度（瓦片数）
+    /// * `tiles` - Region tiles, row by row | 区域瓦片，逐行排列
+    pub fn set_region(&mut self, x: u32, y: u32, width: u32, tiles: &[u32]) -> Result<()> {
+        if width == 0 || !tiles.len().is_multiple_of(width as usize) {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Tile region length {} is not a multiple of width {}",
+                tiles.len(),
+                width
+            )));
+        }
+        let height = (tiles.len() / width as usize) as u32;
+        if x + width > self.width || y + height > self.height {
+            return Err(EngineError::InvalidBatchData(format!(
+                "Tile region {}x{} at ({}, {}) exceeds layer size {}x{}",
+                width, height, x, y, self.width, self.height
+            )));
+        }
+
+        for (row, source) in tiles.chunks_exact(width as usize).enumerate() {
+            let start = ((y + row as u32) * self.width + x) as usize;
+            self.tiles[start..start + width as usize].copy_from_slice(source);
+        }
+        for chunk_y in y / CHUNK_SIZE..=(y + height - 1) / CHUNK_SIZE {
+            for chunk_x in x / CHUNK_SIZE..=(x + width - 1) / CHUNK_SIZE {
+                self.chunks[(chunk_y * self.chunks_x + chunk_x) as usize].dirty = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a tile GID (0 when empty or out of bounds).
+    /// 获取瓦片GID（空或越界时为0）。
+    #[inline]
+    pub fn tile(&self, x: u32, y: u32) -> u32 {
+        if x < self.width && y < self.height {
+            self.tiles[(y * self.width + x) as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Mark every chunk for rebuilding.
+    /// 标记所有分块需要重建。
+    pub fn mark_all_dirty(&mut self) {
+        for chunk in &mut self.chunks {
+            chunk.dirty = true;
+        }
+    }
+
+    /// Build stale visible chunks and draw them.
+    /// 构建过期的可见分块并绘制。
+    ///
+    /// The sprite shader must be bound with the layer's projection.
+    /// 必须已绑定精灵着色器并设置该层的投影。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `ctx` - Build context | 构建上下文
+    /// * `view` - Visible world rect | 可见世界矩形
+    pub fn draw(&mut self, gl: &WebGl2RenderingContext, ctx: &ChunkBuildContext, view: &Rect) -> Result<()> {
+        let Some((x0, y0, x1, y1)) = self.visible_chunks(view) else {
+            return Ok(());
+        };
+
+        for chunk_y in y0..=y1 {
+            for chunk_x in x0..=x1 {
+                let index = (chunk_y * self.chunks_x + chunk_x) as usize;
+                let chunk = &self.chunks[index];
+                let stale = chunk.dirty || (chunk.animated && chunk.epoch != ctx.animation_epoch);
+                if stale {
+                    self.build_chunk(gl, ctx, chunk_x, chunk_y)?;
+                }
+
+                let Some(mesh) = &self.chunks[index].mesh else {
+                    continue;
+                };
+                gl.bind_vertex_array(Some(&mesh.vao));
+                for range in &mesh.ranges {
+                    ctx.texture_manager.bind_texture(range.texture_id, 0);
+                    gl.draw_elements_with_i32(
+                        WebGl2RenderingContext::TRIANGLES,
+                        (range.count * 6) as i32,
+                        WebGl2RenderingContext::UNSIGNED_INT,
+                        (range.start * 6 * 4) as i32,
+                    );
+                    stats::record_draw_call();
+                }
+            }
+        }
+
+        gl.bind_vertex_array(None);
+        Ok(())
+    }
+
+    /// Release GPU resources.
+    /// 释放GPU资源。
+    pub fn delete(self, gl: &WebGl2RenderingContext) {
+        for mesh in self.chunks.into_iter().filter_map(|c| c.mesh) {
+            gl.delete_vertex_array(Some(&mesh.vao));
+            gl.delete_buffer(Some(&mesh.vbo));
+        }
+    }
+
+    /// Inclusive chunk range overlapping a world rect.
+    /// 与世界矩形重叠的分块范围（包含两端）。
+    fn visible_chunks(&self, view: &Rect) -> Option<(u32, u32, u32, u32)> {
+        if self.chunks.is_empty() || self.tile_size.x <= 0.0 || self.tile_size.y <= 0.0 {
+            return None;
+        }
+
+        // Tile columns grow right, rows grow down from `position` | 瓦片列向右增长，行从`position`向下增长
+        let col_min = ((view.x - self.position.x) / self.tile_size.x).floor();
+        let col_max = ((view.x + view.width - self.position.x) / self.tile_size.x).floor();
+        let row_min = ((self.position.y - (view.y + view.height)) / self.tile_size.y).floor();
+        let row_max = ((self.position.y - view.y) / self.tile_size.y).floor();
+
+        if col_max < 0.0 || row_max < 0.0 || col_min >= self.width as f32 || row_min >= self.height as f32 {
+            return None;
+        }
+
+        let chunk = CHUNK_SIZE as f32;
+        let last_x = self.chunks_x - 1;
+        let last_y = (self.chunks.len() as u32 / self.chunks_x) - 1;
+        Some((
+            (col_min.max(0.0) / chunk) as u32,
+            (row_min.max(0.0) / chunk) as u32,
+            ((col_max / chunk) as u32).min(last_x),
+            ((row_max / chunk) as u32).min(last_y),
+        ))
+    }
+
+    /// Rebuild the geometry of one chunk.
+    /// 重建单个分块的几何体。
+    fn build_chunk(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        ctx: &ChunkBuildContext,
+        chunk_x: u32,
+        chunk_y: u32,
+    ) -> Result<()> {
+        let mut quads: Vec<(u32, [SpriteVertex; 4])> = Vec::new();
+        let mut animated = false;
+        let mut complete = true;
+
+        let x_end = ((chunk_x + 1) * CHUNK_SIZE).min(self.width);
+        let y_end = ((chunk_y + 1) * CHUNK_SIZE).min(self.height);
+        for y in chunk_y * CHUNK_SIZE..y_end {
+            for x in chunk_x * CHUNK_SIZE..x_end {
+                let gid = self.tiles[(y * self.width + x) as usize];
+                let id = gid & TILE_ID_MASK;
+                if id == 0 {
+                    continue;
+                }
+
+                // Last tileset starting at or before the ID | 起始于该ID或之前的最后一个瓦片集
+                let Some(&(first_gid, tileset_id)) = self.tilesets.iter().rev().find(|&&(first, _)| first <= id) else {
+                    continue;
+                };
+                let Some(tileset) = ctx.tilesets.get(&tileset_id) else {
+                    continue;
+                };
+
+                // Texture still loading or failed: retry next frame | 纹理仍在加载或失败：下一帧重试
+                let Some(texture_size) = ctx.texture_manager.get_ready_texture_size(tileset.texture_id) else {
+                    complete = false;
+                    continue;
+                };
+
+                let local = id - first_gid;
+                animated |= tileset.animation(local).is_some();
+                let frame = tileset.resolve_frame(local, ctx.time);
+                let (texture_id, uv) = ctx
+                    .texture_manager
+                    .resolve_texture(tileset.texture_id, tileset.tile_uv(frame, texture_size));
+
+                quads.push((texture_id, self.tile_vertices(x, y, gid, uv)));
+            }
+        }
+
+        // Group by texture so each texture is one draw | 按纹理分组，使每个纹理一次绘制
+        quads.sort_by_key(|&(texture_id, _)| texture_id);
+        let mut ranges: Vec<TextureRange> = Vec::new();
+        for (i, &(texture_id, _)) in quads.iter().enumerate() {
+            match ranges.last_mut() {
+                Some(range) if range.texture_id == texture_id => range.count += 1,
+                _ => ranges.push(TextureRange { texture_id, start: i, count: 1 }),
+            }
+        }
+        let vertices: Vec<SpriteVertex> = quads.iter().flat_map(|(_, v)| *v).collect();
+
+        let chunk = &mut self.chunks[(chunk_y * self.chunks_x + chunk_x) as usize];
+        chunk.dirty = !complete;
+        chunk.animated = animated;
+        chunk.epoch = ctx.animation_epoch;
+
+        if vertices.is_empty() {
+            if let Some(mesh) = chunk.mesh.take() {
+                gl.delete_vertex_array(Some(&mesh.vao));
+                gl.delete_buffer(Some(&mesh.vbo));
+            }
+            return Ok(());
+        }
+
+        let mesh = match chunk.mesh.take() {
+            Some(mesh) => mesh,
+            None => Self::create_mesh(gl, ctx.index_buffer)?,
+        };
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&mesh.vbo));
+        unsafe {
+            let data: &[f32] = bytemuck::cast_slice(&vertices);
+            let array = js_sys::Float32Array::view(data);
+            gl.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ARRAY_BUFFER,
+                &array,
+                WebGl2RenderingContext::STATIC_DRAW,
+            );
+        }
+        stats::record_vertices(vertices.len());
+        chunk.mesh = Some(ChunkMesh { ranges, ..mesh });
+        Ok(())
+    }
+
+    /// Create the vertex array and buffer of a chunk.
+    /// 创建分块的顶点数组和缓冲区。
+    fn create_mesh(gl: &WebGl2RenderingContext, index_buffer: &WebGlBuffer) -> Result<ChunkMesh> {
+        let vao = gl
+            .create_vertex_array()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let vbo = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vbo));
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+        SpriteBatch::setup_vertex_attributes(gl);
+
+        gl.bind_vertex_array(None);
+        Ok(ChunkMesh { vao, vbo, ranges: Vec::new() })
+    }
+
+    /// Vertices of a tile in layer space (top-left, top-right, bottom-right, bottom-left).
+    /// 层空间中瓦片的顶点（左上、右上、右下、左下）。
+    fn tile_vertices(&self, x: u32, y: u32, gid: u32, uv: [f32; 4]) -> [SpriteVertex; 4] {
+        let [u0, v0, u1, v1] = uv;
+        let mut tex = [[u0, v0], [u1, v0], [u1, v1], [u0, v1]];
+
+        // Diagonal first, then horizontal, then vertical (Tiled order) | 先对角，再水平，再垂直（Tiled顺序）
+        if gid & TILE_FLIP_DIAGONAL != 0 {
+            tex.swap(1, 3);
+        }
+        if gid & TILE_FLIP_HORIZONTAL != 0 {
+            tex.swap(0, 1);
+            tex.swap(2, 3);
+        }
+        if gid & TILE_FLIP_VERTICAL != 0 {
+            tex.swap(0, 3);
+            tex.swap(1, 2);
+        }
+
+        let left = x as f32 * self.tile_size.x;
+        let right = left + self.tile_size.x;
+        let top = -(y as f32) * self.tile_size.y;
+        let bottom = top - self.tile_size.y;
+        [
+            SpriteVertex::new([left, top], tex[0], self.color),
+            SpriteVertex::new([right, top], tex[1], self.color),
+            SpriteVertex::new([right, bottom], tex[2], self.color),
+            SpriteVertex::new([left, bottom], tex[3], self.color),
+        ]
+    }
+}
@@ -0,0 +1,231 @@
+//! Tilemap layer renderer.
+//! 瓦片地图层渲染器。
+
+use std::collections::HashMap;
+
+use glam::Mat3;
+use web_sys::{WebGl2RenderingContext, WebGlBuffer};
+
+use crate::core::error::{EngineError, Result};
+use crate::math::Rect;
+use crate::resource::TextureManager;
+use super::super::batch::{SortKey, SpriteBatch};
+use super::super::shader::ShaderProgram;
+use super::tilemap_layer::{ChunkBuildContext, TilemapLayer, CHUNK_TILES};
+use super::tileset::Tileset;
+
+/// Owns tilesets and tilemap layers and draws them with the sprite shader.
+/// 持有瓦片集和瓦片地图层，并使用精灵着色器绘制。
+///
+/// Unlike sprites, layers persist across frames: tiles are uploaded once and
+/// only changed or animated chunks are rebuilt.
+/// 与精灵不同，层跨帧保留：瓦片只上传一次，仅重建变化或动画的分块。
+pub struct TilemapRenderer {
+    /// Tilesets by ID.
+    /// 按ID存储的瓦片集。
+    tilesets: HashMap<u32, Tileset>,
+
+    /// Layers by ID.
+    /// 按ID存储的层。
+    layers: HashMap<u32, TilemapLayer>,
+
+    /// Next tileset ID.
+    /// 下一个瓦片集ID。
+    next_tileset_id: u32,
+
+    /// Next layer ID.
+    /// 下一个层ID。
+    next_layer_id: u32,
+
+    /// Animation time in seconds.
+    /// 动画时间（秒）。
+    time: f32,
+
+    /// Bumped whenever an animation frame changes.
+    /// 任意动画帧变化时递增。
+    animation_epoch: u64,
+
+    /// Quad indices shared by all chunks.
+    /// 所有分块共享的四边形索引。
+    index_buffer: WebGlBuffer,
+}
+
+impl TilemapRenderer {
+    /// Create a new tilemap renderer.
+    /// 创建新的瓦片地图渲染器。
+    pub fn new(gl: &WebGl2RenderingContext) -> Result<Self> {
+        let index_buffer = gl
+            .create_buffer()
+            .ok_or(EngineError::BufferCreationFailed)?;
+        gl.bind_vertex_array(None);
+        gl.bind_buffer(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER, Some(&index_buffer));
+        let indices = SpriteBatch::generate_indices(CHUNK_TILES);
+        unsafe {
+            let index_array = js_sys::Uint32Array::view(&indices);
+            gl.buffer_data_with_array_buffer_view(
+                WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+                &index_array,
+                WebGl2RenderingContext::STATIC_DRAW,
+            );
+        }
+
+        Ok(Self {
+            tilesets: HashMap::new(),
+            layers: HashMap::new(),
+            next_tileset_id: 1,
+            next_layer_id: 1,
+            time: 0.0,
+            animation_epoch: 0,
+            index_buffer,
+        })
+    }
+
+    /// Register a tileset, returning its ID.
+    /// 注册瓦片集，返回其ID。
+    pub fn add_tileset(&mut self, tileset: Tileset) -> u32 {
+        let id = self.next_tileset_id;
+        self.next_tileset_id += 1;
+        self.tilesets.insert(id, tileset);
+        id
+    }
+
+    /// Get a tileset.
+    /// 获取瓦片集。
+    #[inline]
+    pub fn tileset(&self, id: u32) -> Option<&Tileset> {
+        self.tilesets.get(&id)
+    }
+
+    /// Get a mutable tileset; layers using it are rebuilt.
+    /// 获取可变瓦片集；使用它的层会被重建。
+    pub fn tileset_mut(&mut self, id: u32) -> Option<&mut Tileset> {
+        let tileset = self.tilesets.get_mut(&id)?;
+        for layer in self.layers.values_mut().filter(|l| l.uses_tileset(id)) {
+            layer.mark_all_dirty();
+        }
+        Some(tileset)
+    }
+
+    /// Create an empty layer, returning its ID.
+    /// 创建空层，返回其ID。
+    pub fn add_layer(&mut self, layer: TilemapLayer) -> u32 {
+        let id = self.next_layer_id;
+        self.next_layer_id += 1;
+        self.layers.insert(id, layer);
+        id
+    }
+
+    /// Get a layer.
+    /// 获取层。
+    #[inline]
+    pub fn layer(&self, id: u32) -> Option<&TilemapLayer> {
+        self.layers.get(&id)
+    }
+
+    /// Get a mutable layer.
+    /// 获取可变层。
+    #[inline]
+    pub fn layer_mut(&mut self, id: u32) -> Option<&mut TilemapLayer> {
+        self.layers.get_mut(&id)
+    }
+
+    /// Remove a layer and release its geometry.
+    /// 移除层并释放其几何体。
+    pub fn remove_layer(&mut self, gl: &WebGl2RenderingContext, id: u32) -> bool {
+        match self.layers.remove(&id) {
+            Some(layer) => {
+                layer.delete(gl);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether a layer draws from a texture.
+    /// 检查层是否从某个纹理绘制。
+    pub fn layer_uses_texture(&self, id: u32, texture_id: u32) -> bool {
+        self.layers.get(&id).is_some_and(|layer| {
+            self.tilesets
+                .iter()
+                .any(|(&tileset_id, tileset)| tileset.texture_id == texture_id && layer.uses_tileset(tileset_id))
+        })
+    }
+
+    /// Check whether any layer exists.
+    /// 检查是否存在任何层。
+    #[inline]
+    pub fn has_layers(&self) -> bool {
+        !self.layers.is_empty()
+    }
+
+    /// Advance tile animations.
+    /// 推进瓦片动画。
+    pub fn update(&mut self, delta_seconds: f32) {
+        let previous = self.time;
+        self.time += delta_seconds.max(0.0);
+
+        // Only rebuild animated chunks when a frame actually changed | 仅在帧确实变化时重建动画分块
+        let changed = self.tilesets.values().any(|tileset| {
+            tileset.has_animations()
+                && tileset
+                    .animations()
+                    .any(|a| a.frame_at(previous) != a.frame_at(self.time))
+        });
+        if changed {
+            self.animation_epoch += 1;
+        }
+    }
+
+    /// IDs and sort keys of visible layers, in draw order.
+    /// 可见层的ID和排序键，按绘制顺序排列。
+    ///
+    /// Layers draw below sprites with the same layer and order in layer.
+    /// 层绘制在具有相同层和层内顺序的精灵之下。
+    pub fn draw_order(&self) -> Vec<(SortKey, u32)> {
+        let mut order: Vec<(SortKey, u32)> = self
+            .layers
+            .iter()
+            .filter(|(_, layer)| layer.visible)
+            .map(|(&id, layer)| (SortKey { depth: f32::NEG_INFINITY, ..layer.sort }, id))
+            .collect();
+        order.sort_by(|a, b| a.0.compare(&b.0).then(a.1.cmp(&b.1)));
+        order
+    }
+
+    /// Draw one layer with a bound sprite shader.
+    /// 使用已绑定的精灵着色器绘制单个层。
+    ///
+    /// # Arguments | 参数
+    /// * `gl` - WebGL2 context | WebGL2上下文
+    /// * `texture_manager` - Texture manager | 纹理管理器
+    /// * `shader` - Bound sprite shader | 已绑定的精灵着色器
+    /// * `projection` - Camera projection matrix | 相机投影矩阵
+    /// * `view` - Visible world rect used for chunk culling | 用于分块剔除的可见世界矩形
+    /// * `id` - Layer ID | 层ID
+    pub fn draw_layer(
+        &mut self,
+        gl: &WebGl2RenderingContext,
+        texture_manager: &TextureManager,
+        shader: &ShaderProgram,
+        projection: &Mat3,
+        view: &Rect,
+        id: u32,
+    ) -> Result<()> {
+        let Some(layer) = self.layers.get_mut(&id) else {
+            return Ok(());
+        };
+
+        // Geometry is in layer space; offset by the layer position | 几何体位于层空间；按层位置偏移
+        let offset = Mat3::from_translation(layer.position.to_glam());
+        shader.set_uniform_mat3(gl, "u_projection", &(*projection * offset).to_cols_array());
+
+        let ctx = ChunkBuildContext {
+            texture_manager,
+            tilesets: &self.tilesets,
+            time: self.time,
+            animation_epoch: self.animation_epoch,
+            index_buffer: &self.index_buffer,
+        };
+        layer.draw(gl, &ctx, view)
+    }
+}
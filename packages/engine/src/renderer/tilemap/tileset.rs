@@ -0,0 +1,181 @@
+//! Tilesets and animated tiles.
+//! 瓦片集和动画瓦片。
+
+use std::collections::HashMap;
+
+/// Tile ID flag: flipped horizontally (Tiled GID convention).
+/// 瓦片ID标志：水平翻转（Tiled GID约定）。
+pub const TILE_FLIP_HORIZONTAL: u32 = 0x8000_0000;
+
+/// Tile ID flag: flipped vertically.
+/// 瓦片ID标志：垂直翻转。
+pub const TILE_FLIP_VERTICAL: u32 = 0x4000_0000;
+
+/// Tile ID flag: flipped diagonally (X and Y swapped).
+/// 瓦片ID标志：对角翻转（交换X和Y）。
+pub const TILE_FLIP_DIAGONAL: u32 = 0x2000_0000;
+
+/// Mask of the tile ID bits without flip flags.
+/// 不含翻转标志的瓦片ID位掩码。
+pub const TILE_ID_MASK: u32 = !(TILE_FLIP_HORIZONTAL | TILE_FLIP_VERTICAL | TILE_FLIP_DIAGONAL);
+
+/// Animation of a single tile.
+/// 单个瓦片的动画。
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileAnimation {
+    /// Local tile indices of the frames.
+    /// 各帧的局部瓦片索引。
+    pub frames: Vec<u32>,
+
+    /// Duration of each frame in seconds.
+    /// 每帧持续时间（秒）。
+    pub frame_duration: f32,
+}
+
+impl TileAnimation {
+    /// Frame index at a time in seconds.
+    /// 指定时间（秒）的帧索引。
+    #[inline]
+    pub fn frame_at(&self, time: f32) -> usize {
+        if self.frames.is_empty() || self.frame_duration <= 0.0 {
+            return 0;
+        }
+        ((time / self.frame_duration) as usize) % self.frames.len()
+    }
+
+    /// Local tile index shown at a time in seconds.
+    /// 指定时间（秒）显示的局部瓦片索引。
+    #[inline]
+    pub fn tile_at(&self, time: f32) -> Option<u32> {
+        self.frames.get(self.frame_at(time)).copied()
+    }
+}
+
+/// Grid of equally sized tiles in one texture.
+/// 单个纹理中等尺寸瓦片的网格。
+///
+/// Tiles are numbered from the top-left, row by row, like Tiled tilesets.
+/// 瓦片从左上角开始逐行编号，与Tiled瓦片集一致。
+///
+/// # Examples | 示例
+/// ```rust
+/// use es_engine::renderer::tilemap::Tileset;
+/// // 16x16 tiles with 1 texel spacing in a 69x35 texture
+/// let tileset = Tileset::new(7, 16, 16, 1, 1);
+/// assert_eq!(tileset.columns(69.0), 4);
+/// assert_eq!(tileset.tile_uv(5, (69.0, 35.0)), [18.0 / 69.0, 18.0 / 35.0, 34.0 / 69.0, 34.0 / 35.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tileset {
+    /// Texture or atlas region ID.
+    /// 纹理或图集区域ID。
+    pub texture_id: u32,
+
+    /// Tile width in texels.
+    /// 瓦片宽度（纹素）。
+    pub tile_width: u32,
+
+    /// Tile height in texels.
+    /// 瓦片高度（纹素）。
+    pub tile_height: u32,
+
+    /// Border around the tile grid in texels.
+    /// 瓦片网格周围的边距（纹素）。
+    pub margin: u32,
+
+    /// Gap between tiles in texels.
+    /// 瓦片之间的间距（纹素）。
+    pub spacing: u32,
+
+    /// Animations by local tile index.
+    /// 按局部瓦片索引的动画。
+    animations: HashMap<u32, TileAnimation>,
+}
+
+impl Tileset {
+    /// Create a new tileset.
+    /// 创建新的瓦片集。
+    ///
+    /// # Arguments | 参数
+    /// * `texture_id` - Texture or atlas region ID | 纹理或图集区域ID
+    /// * `tile_width` - Tile width in texels | 瓦片宽度（纹素）
+    /// * `tile_height` - Tile height in texels | 瓦片高度（纹素）
+    /// * `margin` - Border around the grid in texels | 网格周围边距（纹素）
+    /// * `spacing` - Gap between tiles in texels | 瓦片间距（纹素）
+    pub fn new(texture_id: u32, tile_width: u32, tile_height: u32, margin: u32, spacing: u32) -> Self {
+        Self {
+            texture_id,
+            tile_width: tile_width.max(1),
+            tile_height: tile_height.max(1),
+            margin,
+            spacing,
+            animations: HashMap::new(),
+        }
+    }
+
+    /// Number of tile columns in a texture of the given width.
+    /// 给定宽度纹理中的瓦片列数。
+    pub fn columns(&self, texture_width: f32) -> u32 {
+        let usable = texture_width as u32 + self.spacing;
+        let usable = usable.saturating_sub(self.margin * 2);
+        (usable / (self.tile_width + self.spacing)).max(1)
+    }
+
+    /// UV rect `[u0, v0, u1, v1]` of a local tile index (v0 = top).
+    /// 局部瓦片索引的UV矩形（v0 = 顶部）。
+    pub fn tile_uv(&self, index: u32, texture_size: (f32, f32)) -> [f32; 4] {
+        let (width, height) = texture_size;
+        let columns = self.columns(width);
+        let x = self.margin + (index % columns) * (self.tile_width + self.spacing);
+        let y = self.margin + (index / columns) * (self.tile_height + self.spacing);
+        [
+            x as f32 / width,
+            y as f32 / height,
+            (x + self.tile_width) as f32 / width,
+            (y + self.tile_height) as f32 / height,
+        ]
+    }
+
+    /// Set or remove (`None`) the animation of a tile.
+    /// 设置或移除（`None`）瓦片的动画。
+    pub fn set_animation(&mut self, index: u32, animation: Option<TileAnimation>) {
+        match animation {
+            Some(animation) => {
+                self.animations.insert(index, animation);
+            }
+            None => {
+                self.animations.remove(&index);
+            }
+        }
+    }
+
+    /// Get the animation of a tile.
+    /// 获取瓦片的动画。
+    #[inline]
+    pub fn animation(&self, index: u32) -> Option<&TileAnimation> {
+        self.animations.get(&index)
+    }
+
+    /// Iterate over all tile animations.
+    /// 遍历所有瓦片动画。
+    pub fn animations(&self) -> impl Iterator<Item = &TileAnimation> {
+        self.animations.values()
+    }
+
+    /// Check whether any tile is animated.
+    /// 检查是否有动画瓦片。
+    #[inline]
+    pub fn has_animations(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    /// Local tile index shown for `index` at a time in seconds.
+    /// 指定时间（秒）`index`所显示的局部瓦片索引。
+    #[inline]
+    pub fn resolve_frame(&self, index: u32, time: f32) -> u32 {
+        self.animations
+            .get(&index)
+            .and_then(|a| a.tile_at(time))
+            .unwrap_or(index)
+    }
+}
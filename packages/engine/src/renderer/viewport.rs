@@ -6,6 +6,7 @@ use web_sys::{HtmlCanvasElement, WebGl2RenderingContext};
 use wasm_bindgen::JsCast;
 
 use super::camera::Camera2D;
+use super::post_process::PostProcessStack;
 use crate::core::error::{EngineError, Result};
 
 /// Viewport configuration and settings.
@@ -18,6 +19,10 @@ pub struct ViewportConfig {
     pub show_gizmos: bool,
     /// Clear color (RGBA).
     pub clear_color: [f32; 4],
+    /// Post-processing effects applied by the post-process pass.
+    pub post_process: PostProcessStack,
+    /// Whether the lighting pass applies submitted lights.
+    pub lighting: bool,
 }
 
 impl Default for ViewportConfig {
@@ -26,6 +31,8 @@ impl Default for ViewportConfig {
             show_grid: true,
             show_gizmos: true,
             clear_color: [0.1, 0.1, 0.12, 1.0],
+            post_process: PostProcessStack::default(),
+            lighting: false,
         }
     }
 }
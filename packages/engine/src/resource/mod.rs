@@ -4,4 +4,7 @@
 mod handle;
 
 pub use handle::{Handle, HandleId};
-pub use crate::renderer::texture::{Texture, TextureManager};
+pub use crate::renderer::texture::{
+    Texture, TextureFilter, TextureLoadCallback, TextureLoadOptions, TextureLoadState, TextureManager, TextureSampler,
+    TextureWrap,
+};